@@ -1,14 +1,3071 @@
-#include <stdint.h>
+#ifndef STARKNET_CRYPTO_H
+#define STARKNET_CRYPTO_H
+
+// Generated by cbindgen from the starknet-crypto crate sources, do not edit by hand.
+
 #include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Version of the ABI described by this header. The major version changes on breaking changes to
+// existing functions, the minor version when functions are added.
+#define ABI_MAJOR 1
+
+#define ABI_MINOR 102
+
+// starknet_crypto_abi_version must return STARKNET_CRYPTO_ABI_VERSION, otherwise the linked
+// library doesn't match the header
+#define STARKNET_CRYPTO_ABI_VERSION ((ABI_MAJOR << 16) | ABI_MINOR)
+
+// Status codes returned by every function
+#define SUCCESS 0
+
+// The bytes do not encode a felt lower than the STARK prime
+#define ERR_INVALID_FELT 1
+
+#define ERR_INVALID_MESSAGE_HASH 2
+
+#define ERR_INVALID_K 3
+
+// The coordinates do not describe a point of the STARK curve
+#define ERR_INVALID_POINT 4
+
+// The result is the point at infinity, which has no affine coordinates
+#define ERR_POINT_AT_INFINITY 5
+
+#define ERR_INVALID_R 6
+
+#define ERR_INVALID_S 7
+
+#define ERR_INVALID_V 8
+
+#define ERR_DIVISION_BY_ZERO 9
+
+// The signature is well formed but doesn't match the message and public key
+#define ERR_INVALID_SIGNATURE 10
+
+#define ERR_NOT_QUADRATIC_RESIDUE 11
+
+// The thread pool of the batch functions could not be created
+#define ERR_THREAD_POOL 12
+
+// The length of an array overflows, or a non empty array is a NULL pointer
+#define ERR_INVALID_LENGTH 13
+
+// Alias of ERR_INVALID_LENGTH returned by the _sized functions for a buffer of the wrong length
+#define ERR_BAD_LENGTH ERR_INVALID_LENGTH
+
+// The function panicked, the message can be retrieved with starknet_crypto_last_panic_message
+// or starknet_crypto_last_error
+#define ERR_PANIC 14
+
+// The hash kind is neither HASH_PEDERSEN nor HASH_POSEIDON
+#define ERR_INVALID_HASH_KIND 15
+
+// The Merkle or storage proof is well formed but doesn't lead to the root, or the VRF proof is
+// invalid
+#define ERR_INVALID_PROOF 16
+
+// The root is not one of the Patricia trie roots computed by the library in this process, or its
+// trie was evicted from the node store or cleared
+#define ERR_UNKNOWN_ROOT 17
+
+// The SNIP-12 revision is neither 0 nor 1
+#define ERR_INVALID_REVISION 18
+
+// The operating system's random number generator failed
+#define ERR_RNG 19
+
+// The string is not a well formed number or short string
+#define ERR_INVALID_STRING 20
+
+// No KZG trusted setup was loaded
+#define ERR_NO_TRUSTED_SETUP 21
+
+// A known answer test of starknet_crypto_selftest failed
+#define ERR_SELF_TEST_FAILED 22
+
+// The primitive is not one of the VECTORS_ kinds of starknet_crypto_test_vectors
+#define ERR_INVALID_PRIMITIVE 23
+
+// The allocator functions are NULL, or were set after the library allocated
+#define ERR_ALLOCATOR 24
+
+// A pointer to a fixed size argument is NULL
+#define ERR_NULL_POINTER 25
+
+// A pointer is not aligned for its type, only checked with the `check-alignment` cargo feature
+#define ERR_MISALIGNED_POINTER 26
+
+// The layout is neither LAYOUT_INTERLEAVED nor LAYOUT_COLUMNS
+#define ERR_INVALID_LAYOUT 27
+
+// A limb of a uint256 is not lower than 2^128
+#define ERR_INVALID_UINT256 28
+
+// The Pedersen commitment doesn't open to the value and blinding factor
+#define ERR_INVALID_OPENING 29
+
+// The partial sum and the doubled point of ec_op share their x coordinate at some step, so that
+// the next addition would be a doubling or reach the point at infinity
+#define ERR_EC_OP_SAME_X 30
+
+// The tag of a stark_encrypt message doesn't authenticate it under the private key of
+// stark_decrypt, because of a modified message or another recipient
+#define ERR_DECRYPTION_FAILED 31
+
+// The felt doesn't fit in the machine integer of the conversion
+#define ERR_OUT_OF_RANGE 32
+
+// The number of bytes a stark_encrypt message adds to its plaintext: the ephemeral public key and
+// the tag
+#define ENCRYPTION_OVERHEAD 64
+
+// Levels of the events received by the starknet_crypto_set_logger callback
+#define LOG_WARNING 0
+
+#define LOG_DEBUG 1
+
+// Hash kinds selecting the node hash of the Merkle functions
+#define HASH_PEDERSEN 0
+
+#define HASH_POSEIDON 1
+
+// Layouts of the operands of the batch functions: one array of the interleaved records, or one
+// array per operand
+#define LAYOUT_INTERLEAVED 0
+
+#define LAYOUT_COLUMNS 1
+
+// Primitives of the test vectors of starknet_crypto_test_vectors
+#define VECTORS_PEDERSEN 0
+
+#define VECTORS_POSEIDON 1
+
+#define VECTORS_POSEIDON_PERMUTE 2
+
+#define VECTORS_PUBLIC_KEY 3
+
+// Primitives timed by starknet_crypto_bench
+#define BENCH_PEDERSEN 0
+
+#define BENCH_POSEIDON 1
+
+#define BENCH_POSEIDON_PERMUTE 2
+
+#define BENCH_ECDSA_VERIFY 3
+
+#define BENCH_KECCAK 4
+
+#define BENCH_FELT_MUL 5
+
+// The Poseidon hash of 16 felts by starknet-crypto's poseidon_hash_many
+#define BENCH_POSEIDON_MANY 6
+
+// The Poseidon hash of the same 16 felts by the Hades permutation of the `hades` feature
+#define BENCH_HADES_POSEIDON_MANY 7
+
+// Primitives reported to the starknet_crypto_set_differential callback, with their inputs and
+// output: the felts of the arguments, and for DIFF_ECDSA_VERIFY the felt 1 for a valid signature
+// and 0 for an invalid one, for DIFF_EC_OP the height as a felt after m, for DIFF_KECCAK_F1600 the
+// 200 bytes state before and after, and for DIFF_KECCAK256 and DIFF_STARKNET_KECCAK the hashed
+// bytes and the digest
+#define DIFF_PEDERSEN 0
+
+#define DIFF_POSEIDON 1
 
-typedef uint8_t byte_t;
+#define DIFF_POSEIDON_SINGLE 2
 
-// A 252 bit prime field element (felt), represented as an array of 32 bytes.
-typedef byte_t felt_t[32];
+#define DIFF_POSEIDON_MANY 3
+
+#define DIFF_POSEIDON_PERMUTE 4
+
+#define DIFF_PEDERSEN_ON_ELEMENTS 5
+
+#define DIFF_ECDSA_VERIFY 6
+
+#define DIFF_KECCAK_F1600 7
+
+#define DIFF_KECCAK256 8
+
+#define DIFF_STARKNET_KECCAK 9
+
+#define DIFF_EC_OP 10
+
+// An incremental blake2s-256 hash
+typedef struct Blake2sContext Blake2sContext;
+
+// A deterministic generator of random felts
+typedef struct Drbg Drbg;
+
+// A felt owned by the caller between calls
+typedef struct FeltHandle FeltHandle;
+
+// A Pedersen hash chain updated one felt at a time
+typedef struct PedersenChain PedersenChain;
+
+// The partial Pedersen sum of a left operand
+typedef struct PedersenLeft PedersenLeft;
+
+// A Poseidon sponge absorbing felts one at a time
+typedef struct PoseidonContext PoseidonContext;
+
+// A Fiat-Shamir transcript
+typedef struct Transcript Transcript;
+
+// C representation of a bit array: a raw pointer to a mutable unsigned 8 bits integer.
+typedef uint8_t *Bytes;
+
+// Returns a block of `size` bytes aligned to `align`, or NULL when out of memory
+typedef uint8_t *(*AllocFn)(size_t size, size_t align);
+
+// Frees a block returned by the allocation function for the same `size` and `align`
+typedef void (*FreeFn)(uint8_t *ptr, size_t size, size_t align);
+
+// Resizes a block of `size` bytes aligned to `align` to `new_size` bytes with the same alignment,
+// returning the new block or NULL when out of memory, in which case the block is left untouched
+typedef uint8_t *(*ReallocFn)(uint8_t *ptr, size_t size, size_t align, size_t new_size);
+
+// The allocations of the library, filled by starknet_crypto_heap_stats
+typedef struct HeapStats {
+  // The number of allocations and reallocations
+  uint64_t allocations;
+  // The bytes of the blocks not freed yet
+  uint64_t live_bytes;
+  // The largest number of live bytes so far
+  uint64_t peak_bytes;
+} HeapStats;
+
+// A differential testing callback, receiving a DIFF_ primitive with its inputs and its output. The
+// felts are 32 bytes big endian and contiguous, and the buffers are only valid for the duration of
+// the call. NULL for no callback
+typedef void (*Differential)(uint32_t primitive,
+                             const uint8_t *inputs,
+                             size_t inputs_len,
+                             const uint8_t *output,
+                             size_t output_len);
+
+// A logging callback, receiving a LOG_ level and a UTF-8 message which is not NUL terminated and
+// only valid for the duration of the call. NULL for no logger
+typedef void (*Logger)(uint32_t level, const uint8_t *message, size_t len);
+
+// The counters of one primitive
+typedef struct PrimitiveStats {
+  uint64_t calls;
+  uint64_t nanoseconds;
+  uint64_t items;
+} PrimitiveStats;
+
+// The counters of the builtin primitives, filled by starknet_crypto_stats
+typedef struct Stats {
+  struct PrimitiveStats pedersen;
+  struct PrimitiveStats poseidon;
+  struct PrimitiveStats ecdsa_sign;
+  struct PrimitiveStats ecdsa_verify;
+  struct PrimitiveStats keccak;
+  struct PrimitiveStats ec_op;
+} Stats;
+
+// Returns the ABI version of the library, the major version in the high 16 bits and the minor one
+// in the low 16 bits
+uint32_t starknet_crypto_abi_version(void);
+
+// Returns whether every function of the library may be called from several threads at once,
+// which is always the case. The global state, the Pedersen tables, the table of multiples of the
+// curve generator, the thread pool, the Patricia node store, the trusted setup, the deterministic
+// generator and the hooks, is behind OnceLock, locks and atomics, and the last error and panic
+// messages are kept per thread. A context, like a transcript or a generator, must still not be
+// used by two threads at once
+bool starknet_crypto_is_threadsafe(void);
+
+// Copies at most `len` bytes of the UTF-8 description of the build into the first argument, and
+// writes its full length into the last argument. The description is made of space separated
+// `key=value` pairs: the `version` of this library, the version of its `starknet-crypto`
+// dependency, the comma separated cargo `features` and the field arithmetic `backend`
+int32_t starknet_crypto_build_info(uint8_t *buffer, size_t len, size_t *info_len);
+
+// Performs the one-time initialization up front, so that the first calls are not slower than the
+// next ones: builds the Pedersen tables of starknet_crypto_precompute_pedersen and the table of
+// the multiples of the generator of get_public_key, ecdsa_sign and ec_mul. The Poseidon round
+// constants are compiled in and starknet_crypto_set_threads spawns the threads of its pool right
+// away, so nothing else is initialized lazily. Only the first call does any work
+int32_t starknet_crypto_init(void);
+
+// Runs known answer tests of the Pedersen hash, the Poseidon hash and the ECDSA verification
+// through the exported functions, to catch a miscompiled library or one linked against the wrong
+// header before using it. Returns SUCCESS when they all pass, ERR_SELF_TEST_FAILED otherwise
+int32_t starknet_crypto_selftest(void);
+
+// Copies at most `len` bytes of the UTF-8 message of the last panic caught on the calling thread
+// into the first argument, and writes its full length (zero when nothing panicked) into the last
+// argument. Only available with the `std` cargo feature (enabled by default)
+int32_t starknet_crypto_last_panic_message(uint8_t *buffer, size_t len, size_t *message_len);
+
+// Copies at most `len` bytes of the UTF-8 message of the last error status returned on the calling
+// thread, including the payload of a panic, into the first argument, and writes its full length
+// (zero when nothing failed) into the last argument. The message is kept until the next error, a
+// successful call doesn't clear it. Only available with the `std` cargo feature (enabled by
+// default)
+int32_t starknet_crypto_last_error(uint8_t *buffer, size_t len, size_t *message_len);
 
 // Computes the poseidon hash permutation over a state of three felts
-void poseidon_permute(felt_t, felt_t, felt_t);
+int32_t poseidon_permute(Bytes first_state_felt, Bytes second_state_felt, Bytes third_state_felt);
+
+// Computes in place the poseidon hash permutation over a contiguous 96 bytes state of three felts
+int32_t poseidon_permute_state(uint8_t *state);
+
+// Computes the poseidon hash of two felts, writing the result into the third argument
+int32_t poseidon_hash(Bytes x, Bytes y, Bytes result);
+
+// Computes the poseidon hash of a single felt, writing the result into the second argument
+int32_t poseidon_hash_single(Bytes x, Bytes result);
+
+// Computes the poseidon hash of a contiguous array of `count` felts, writing the result into the
+// last argument
+int32_t poseidon_hash_many(const uint8_t *felts, size_t count, Bytes result);
+
+// Computes the poseidon hash of a domain tag felt followed by a contiguous array of `count` felts,
+// writing the result into the last argument. The result is the poseidon_hash_many of the tag and
+// the felts, with the same padding, so that hashes of different tags never collide on the same
+// felts
+int32_t poseidon_hash_many_tagged(Bytes tag, const uint8_t *felts, size_t count, Bytes result);
+
+// Computes the pedersen hash of two felts, writing the result into the third argument
+int32_t pedersen_hash(Bytes felt_1, Bytes felt_2, Bytes result);
+
+// Computes the pedersen hash of two integers, writing the result into the last argument
+int32_t pedersen_hash_u64(uint64_t a, uint64_t b, Bytes result);
+
+// Computes the pedersen hash of a felt and an integer, writing the result into the last argument
+int32_t pedersen_hash_felt_u64(Bytes a, uint64_t b, Bytes result);
+
+// Computes the pedersen hash of the accumulator and the next felt, writing the result back into
+// the accumulator, which is left untouched on error
+int32_t pedersen_hash_acc(Bytes acc, Bytes next);
+
+// Computes the length terminated pedersen hash chain H(...H(H(0, a0), a1)..., n) over a contiguous
+// array of `count` felts
+int32_t pedersen_hash_on_elements(const uint8_t *elements, size_t count, Bytes result);
+
+// Verifies a signature (r, s) of a message hash against a public key. Returns SUCCESS for a valid
+// signature, ERR_INVALID_SIGNATURE for an invalid one and another error code for malformed inputs
+int32_t verify_signature(Bytes public_key_bytes, Bytes message_bytes, Bytes r_bytes, Bytes s_bytes);
+
+// Verifies a signature (r, s) of a message hash against the x coordinate of a public key, like
+// the ECDSA builtin, which only keeps that coordinate: the signature is valid when it is valid
+// for either of the two points of that x coordinate. Returns the codes of verify_signature,
+// which follows the same semantics
+int32_t verify_signature_xonly(Bytes public_key_x,
+                               Bytes message_bytes,
+                               Bytes r_bytes,
+                               Bytes s_bytes);
+
+// Like verify_signature, and also rejects r and s that are not lower than the curve order and
+// the malleable s above half the order, since (r, n - s) is valid whenever (r, s) is. Each
+// malformed input has its own error code, ERR_INVALID_R or ERR_INVALID_S for out of range
+// components
+int32_t verify_signature_strict(Bytes public_key_bytes,
+                                Bytes message_bytes,
+                                Bytes r_bytes,
+                                Bytes s_bytes);
+
+// Like verify_signature, over the message hash of `count` contiguous felts: their hash on
+// elements with HASH_PEDERSEN and their Poseidon hash many with HASH_POSEIDON
+int32_t verify_signature_over_elements(Bytes public_key_bytes,
+                                       const uint8_t *elements,
+                                       size_t count,
+                                       Bytes r_bytes,
+                                       Bytes s_bytes,
+                                       uint32_t hash_kind);
+
+// Encodes a signature (r, s) in the 64 bytes compact format, r followed by s in big endian.
+// Returns ERR_INVALID_R or ERR_INVALID_S for a component that is zero or not lower than 2^251
+int32_t signature_to_compact(Bytes r_bytes, Bytes s_bytes, uint8_t *compact);
+
+// Decodes a signature in the 64 bytes compact format into r and s. Returns ERR_INVALID_R or
+// ERR_INVALID_S for a component that is zero or not lower than 2^251, leaving both unchanged
+int32_t signature_from_compact(const uint8_t *compact, Bytes r_bytes, Bytes s_bytes);
+
+// Derives the public key (x coordinate) of a private key, writing it into the second argument.
+// Not constant time: the scalar multiplication branches on the bits of the private key
+int32_t get_public_key(Bytes private_key_bytes, Bytes public_key_bytes);
+
+// Deterministically generates the k of a signature from a message hash, a private key and an
+// optional seed (may be NULL) as per RFC 6979, writing it into the last argument. Not guaranteed
+// to run in constant time
+int32_t rfc6979_generate_k(Bytes message_bytes,
+                           Bytes private_key_bytes,
+                           Bytes seed_bytes,
+                           Bytes k_bytes);
+
+// Signs a message hash with a private key and a k supplied by the caller, writing r and s into the
+// last two arguments. The k must be secret and never reused, ecdsa_sign_seeded derives it
+// deterministically as per RFC 6979 instead. Not constant time: the scalar multiplication and the
+// inversion of k branch on the secrets
+int32_t ecdsa_sign(Bytes private_key_bytes,
+                   Bytes message_bytes,
+                   Bytes k_bytes,
+                   Bytes r_bytes,
+                   Bytes s_bytes);
+
+// Signs a message hash with a private key and the k generated by rfc6979_generate_k with a felt
+// seed as extra entropy, writing r and s into the last two arguments. The signatures are
+// deterministic for a seed and distinct across seeds. In the rare event where the k is not a valid
+// nonce, the next seed is tried, so an invalid k only surfaces for a zero message hash signed with
+// a private key that is a multiple of the curve order, for which none is. Not constant time
+int32_t ecdsa_sign_seeded(Bytes private_key_bytes,
+                          Bytes message_bytes,
+                          Bytes seed_bytes,
+                          Bytes r_bytes,
+                          Bytes s_bytes);
+
+// Recovers the public key from a message hash and a signature (r, s, v), writing it into the last
+// argument
+int32_t recover(Bytes message_bytes,
+                Bytes r_bytes,
+                Bytes s_bytes,
+                Bytes v_bytes,
+                Bytes public_key_bytes);
+
+// Computes the address of a contract deployed by `deployer_address` from a class hash with a
+// salt and the `calldata_len` contiguous felts of its constructor calldata, writing it into the
+// last argument. The deployer address is zero for the deployments independent of the deployer
+int32_t calculate_contract_address(Bytes deployer_address,
+                                   Bytes salt,
+                                   Bytes class_hash,
+                                   const uint8_t *calldata,
+                                   size_t calldata_len,
+                                   Bytes result);
+
+// Computes the address of the storage variable named by the `name_len` bytes of `name` at the
+// `keys_len` contiguous felts of `keys`, writing it into the last argument. A variable without
+// keys lives at the starknet_keccak of its name
+int32_t get_storage_var_address(const uint8_t *name,
+                                size_t name_len,
+                                const uint8_t *keys,
+                                size_t keys_len,
+                                Bytes result);
+
+// Makes the library allocate with the embedder's functions instead of the system allocator. The
+// reallocation function may be NULL, reallocations then allocate, copy and free. Returns
+// ERR_ALLOCATOR for a NULL allocation or free function, and when called after the library
+// allocated, so it must be the first call into the library and can only be made once. Only
+// available with the `std` cargo feature (enabled by default), the `no_std` builds call
+// starknet_crypto_alloc and starknet_crypto_dealloc instead
+int32_t starknet_crypto_set_allocator(AllocFn alloc, FreeFn free, ReallocFn realloc);
+
+// Writes the number of allocations and the live and peak bytes of the library since the start into
+// the argument. Only available with the `heap-stats` cargo feature, which implies `std`
+int32_t starknet_crypto_heap_stats(struct HeapStats *stats);
+
+// Runs the batch and Merkle root functions on a pool of `threads` threads, or sequentially (the
+// default) for zero or one. Only available with the `parallel` cargo feature (enabled by default)
+int32_t starknet_crypto_set_threads(size_t threads);
+
+// Hashes `count` contiguous pairs of felts (a0, b0, a1, b1, ...) into `count` contiguous felts
+int32_t pedersen_hash_batch(const uint8_t *pairs, size_t count, uint8_t *result);
+
+// Like pedersen_hash_batch, with the pairs in the LAYOUT_ layout: `columns` points to the array of
+// the interleaved pairs, or to the arrays of the first and of the second felts of the pairs
+int32_t pedersen_hash_batch_layout(const uint8_t *const *columns,
+                                   uint32_t layout,
+                                   size_t count,
+                                   uint8_t *result);
+
+// Computes in place the poseidon hash permutation of `count` contiguous 96 bytes states of three
+// felts
+int32_t poseidon_permute_batch(uint8_t *states, size_t count);
+
+// Like poseidon_permute_batch, with the states in the LAYOUT_ layout: `columns` points to the
+// array of the interleaved states, or to the arrays of the first, second and third felts of the
+// states
+int32_t poseidon_permute_batch_layout(uint8_t *const *columns, uint32_t layout, size_t count);
+
+// Verifies `count` signatures given as contiguous arrays of public keys, messages, r and s. Sets
+// bit i (least significant bit first) of the `(count + 7) / 8` bytes bitmap when signature i is
+// valid and writes the number of valid signatures into the last argument
+int32_t verify_signature_batch(const uint8_t *public_keys,
+                               const uint8_t *messages,
+                               const uint8_t *rs,
+                               const uint8_t *ss,
+                               size_t count,
+                               uint8_t *results,
+                               size_t *valid_count);
+
+// Derives the public keys (x coordinates) of `count` contiguous private keys, writing them into
+// the contiguous array of the last argument, like get_public_key. The products of the generator go
+// through a table of its multiples built on the first call. Not constant time
+int32_t get_public_key_batch(const uint8_t *private_keys, size_t count, uint8_t *public_keys);
+
+// Signs `count` contiguous message hashes with one private key and the deterministic k of
+// rfc6979_generate_k without a seed, like ecdsa_sign_seeded, writing the r and s of each
+// signature into the contiguous arrays of the last two arguments. Returns ERR_INVALID_MESSAGE_HASH
+// without writing any signature when a message hash is out of range, and ERR_INVALID_K when one
+// is zero and the private key a multiple of the curve order. Not constant time
+int32_t ecdsa_sign_batch(Bytes private_key,
+                         const uint8_t *message_hashes,
+                         size_t count,
+                         uint8_t *rs,
+                         uint8_t *ss);
+
+// Like verify_signature_batch, with the signatures in the LAYOUT_ layout: `columns` points to the
+// array of the interleaved (public key, message, r, s) records, or to the arrays of the public
+// keys, messages, r and s
+int32_t verify_signature_batch_layout(const uint8_t *const *columns,
+                                      uint32_t layout,
+                                      size_t count,
+                                      uint8_t *results,
+                                      size_t *valid_count);
+
+// Validates `count` contiguous instances of the signature builtin, each the 128 bytes (public key,
+// message hash, r, s) record, like verify_signature. Returns SUCCESS when all of them verify, and
+// otherwise the status of the first failing instance, whose index is written into the last
+// argument. The index is `count` on success
+int32_t ecdsa_builtin_validate(const uint8_t *cells, size_t count, size_t *failing_index);
+
+// Computes the poseidon_hash_many of `span_count` spans of a shared array of `felt_count` felts
+// into `span_count` contiguous felts. The spans are contiguous (offset, length) pairs of felt
+// counts, and ERR_INVALID_LENGTH is returned when one doesn't lie in the felts
+int32_t poseidon_hash_many_spans(const uint8_t *felts,
+                                 size_t felt_count,
+                                 const uint64_t *spans,
+                                 size_t span_count,
+                                 uint8_t *results);
+
+// Computes the pedersen_hash_on_elements of `span_count` spans of a shared array of `felt_count`
+// felts into `span_count` contiguous felts. The spans are contiguous (offset, length) pairs of felt
+// counts, and ERR_INVALID_LENGTH is returned when one doesn't lie in the felts
+int32_t pedersen_hash_on_elements_spans(const uint8_t *felts,
+                                        size_t felt_count,
+                                        const uint64_t *spans,
+                                        size_t span_count,
+                                        uint8_t *results);
+
+// Runs a BENCH_* primitive `iterations` times straight in Rust and returns the mean nanoseconds
+// per run. Returns 0 for an unknown primitive or no iterations
+uint64_t starknet_crypto_bench(uint32_t op_id, uint64_t iterations);
+
+// Computes a * b^-1 mod m for big endian integers of at most 128 bytes, writing the result over
+// `out_len` bytes, at least `m_len`, into the output. Returns ERR_DIVISION_BY_ZERO when b has no
+// inverse modulo m, m being zero included, and ERR_INVALID_LENGTH for the longer integers
+int32_t bigint_div_mod(const uint8_t *a,
+                       size_t a_len,
+                       const uint8_t *b,
+                       size_t b_len,
+                       const uint8_t *m,
+                       size_t m_len,
+                       uint8_t *out,
+                       size_t out_len);
+
+// Splits a 32 bytes big endian integer into the 3 limbs of 86 bits of a BigInt3, writing them as
+// contiguous felts into the last argument, like `split` in the secp hints
+int32_t bigint3_split(const uint8_t *value, uint8_t *limbs);
+
+// Packs the 3 contiguous felt limbs of a BigInt3 modulo a 32 bytes big endian modulus, writing the
+// 32 bytes big endian result into the last argument. The limbs are signed felts, those greater
+// than (p - 1) / 2 being negative, like `pack(x, PRIME) % modulus` in the secp hints. Returns
+// ERR_DIVISION_BY_ZERO for a zero modulus
+int32_t bigint3_pack(const uint8_t *limbs, const uint8_t *modulus, Bytes result);
+
+// Packs the 5 contiguous felt limbs of a BigInt5, the unreduced product of two BigInt3, like
+// bigint3_pack
+int32_t bigint5_pack(const uint8_t *limbs, const uint8_t *modulus, Bytes result);
+
+// Applies the blake2s compression function in place over a state of 8 words, given a message block
+// of 16 words, the offset counter (t0, t1) and the finalization flags (f0, f1)
+int32_t blake2s_compress(uint32_t *state,
+                         const uint32_t *block,
+                         uint32_t t0,
+                         uint32_t t1,
+                         uint32_t f0,
+                         uint32_t f1);
+
+// Computes the blake2s-256 hash of `len` bytes, writing the 32 bytes digest into the last argument
+int32_t blake2s(const uint8_t *data, size_t len, uint8_t *result);
+
+// Allocates an empty blake2s-256 hash, writing it into the argument. It must be released with
+// blake2s_ctx_free
+int32_t blake2s_ctx_new(struct Blake2sContext **ctx);
+
+// Appends `len` bytes to the hashed stream
+int32_t blake2s_ctx_update(struct Blake2sContext *ctx, const uint8_t *data, size_t len);
+
+// Writes the 32 bytes blake2s-256 digest of the bytes appended so far into the last argument, and
+// empties the context so that it may hash another stream
+int32_t blake2s_ctx_final(struct Blake2sContext *ctx, uint8_t *result);
+
+// Releases a context allocated by blake2s_ctx_new. Does nothing for a NULL pointer
+int32_t blake2s_ctx_free(struct Blake2sContext *ctx);
+
+// Computes the hash of a Starknet block header of v0.13.4 and later, writing it into the last
+// argument. `gas_prices` points to 6 contiguous felts: the L1 gas, L1 data gas and L2 gas prices,
+// each in wei then in fri, and `protocol_version` to the `protocol_version_len` ASCII bytes of the
+// version, like "0.13.4". `l1_da_mode_blob` is whether the block publishes its data availability
+// in blobs rather than in calldata. Returns ERR_INVALID_LENGTH for a protocol version of more
+// than 31 bytes, ERR_INVALID_STRING for a non ASCII one and ERR_INVALID_FELT when the concatenated
+// counts are not lower than the STARK prime, which takes a transaction count above 2^59
+int32_t block_hash(uint64_t block_number,
+                   Bytes state_root,
+                   Bytes sequencer_address,
+                   uint64_t timestamp,
+                   uint64_t transaction_count,
+                   uint64_t event_count,
+                   uint64_t state_diff_length,
+                   bool l1_da_mode_blob,
+                   Bytes state_diff_commitment,
+                   Bytes transaction_commitment,
+                   Bytes event_commitment,
+                   Bytes receipt_commitment,
+                   const uint8_t *gas_prices,
+                   const uint8_t *protocol_version,
+                   size_t protocol_version_len,
+                   Bytes parent_block_hash,
+                   Bytes result);
+
+// Adds two integers modulo the BLS12-381 base field prime, writing the 48 bytes result into the
+// last argument
+int32_t bls12_381_add(const uint8_t *a, const uint8_t *b, uint8_t *result);
+
+// Multiplies two integers modulo the BLS12-381 base field prime, writing the 48 bytes result into
+// the last argument
+int32_t bls12_381_mul(const uint8_t *a, const uint8_t *b, uint8_t *result);
+
+// Divides the first integer by the second modulo the BLS12-381 base field prime, writing the 48
+// bytes result into the last argument. Returns ERR_DIVISION_BY_ZERO when the divisor is a multiple
+// of the prime
+int32_t bls12_381_div_mod(const uint8_t *a, const uint8_t *b, uint8_t *result);
+
+// Computes the Poseidon hash of `len` bytes packed into a Cairo `ByteArray`, the Poseidon hash of
+// many felts of its serialization: the number of 31 bytes big endian words, the words, the pending
+// word of the last `len % 31` bytes and their count. Writes it into the last argument
+int32_t poseidon_hash_bytes(const uint8_t *data, size_t len, Bytes result);
+
+// Computes the Poseidon hash of a Cairo `ByteArray` from its components, like
+// poseidon_hash_bytes over its bytes: `data_len` contiguous felts of `bytes31` words, the pending
+// word and the number of its bytes. Writes it into the last argument. Returns ERR_INVALID_LENGTH
+// for more than 30 pending bytes and ERR_INVALID_FELT for a word of more than 31 bytes or a
+// pending word of more bytes than its count
+int32_t byte_array_hash(const uint8_t *data,
+                        size_t data_len,
+                        Bytes pending_word,
+                        size_t pending_word_len,
+                        Bytes result);
+
+// Computes the compiled class hash of a CASM class, writing it into the last argument. The
+// version is a short string of at most 31 bytes such as `COMPILED_CLASS_V1`. Each list of entry
+// points is made of contiguous felts, the selector, the offset, the number of builtins and the
+// builtins of every entry point. The `segment_count` lengths of the bytecode segments add up to
+// `bytecode_len`, or there are none for a bytecode without segments
+int32_t compiled_class_hash(const uint8_t *version,
+                            size_t version_len,
+                            const uint8_t *external,
+                            size_t external_len,
+                            const uint8_t *l1_handler,
+                            size_t l1_handler_len,
+                            const uint8_t *constructor,
+                            size_t constructor_len,
+                            const uint8_t *bytecode,
+                            size_t bytecode_len,
+                            const uint64_t *segment_lengths,
+                            size_t segment_count,
+                            Bytes result);
+
+// Writes the coordinates of the second generator H of the Pedersen commitments into the arguments
+int32_t pedersen_commit_generator(Bytes x_bytes, Bytes y_bytes);
+
+// Commits to a felt value with a felt blinding factor, writing the affine coordinates of
+// value * G + blinding * H into the last two arguments. The blinding factor must be uniformly
+// random for the commitment to hide the value. Returns ERR_POINT_AT_INFINITY when both are zero
+// modulo the curve order
+int32_t pedersen_commit(Bytes value, Bytes blinding, Bytes x_bytes, Bytes y_bytes);
+
+// Returns SUCCESS when the commitment (x, y) opens to the value and blinding factor,
+// ERR_INVALID_OPENING when it doesn't and another error code for malformed inputs
+int32_t pedersen_commit_open(Bytes x_bytes, Bytes y_bytes, Bytes value, Bytes blinding);
+
+// Copies the 91 rounds of 3 Poseidon round constants, round after round, into the first argument
+int32_t poseidon_round_constants(uint8_t *buf, size_t buf_len, size_t *len);
+
+// Copies the Pedersen lookup table of the given index into the second argument: the multiples 1
+// to 15 of the point P0, P1, P2 or P3 shifted by every 4 bits window of its part of the inputs,
+// window after window. The tables of P0 and P2 have 930 points and those of P1 and P3 15. Returns
+// ERR_INVALID_LENGTH for an index above 3
+int32_t pedersen_constant_points(uint32_t table_index, uint8_t *buf, size_t buf_len, size_t *len);
+
+// Writes the coordinates of the generator of the STARK curve into the arguments
+int32_t stark_curve_generator(Bytes x_bytes, Bytes y_bytes);
+
+// Writes the order of the STARK curve, the number of its points, into the argument
+int32_t stark_curve_order(Bytes order_bytes);
+
+// Writes the alpha coefficient of the STARK curve y^2 = x^3 + alpha * x + beta into the argument
+int32_t stark_curve_alpha(Bytes alpha_bytes);
+
+// Writes the beta coefficient of the STARK curve y^2 = x^3 + alpha * x + beta into the argument
+int32_t stark_curve_beta(Bytes beta_bytes);
+
+// Returns SUCCESS when (x, y) is a point of the STARK curve, ERR_INVALID_FELT when a coordinate is
+// not a felt and ERR_INVALID_POINT otherwise
+int32_t stark_point_validate(Bytes x_bytes, Bytes y_bytes);
+
+// Returns SUCCESS when the public key is the x coordinate of a point of the STARK curve, which
+// verify_signature can recover, ERR_INVALID_FELT when it is not a felt and ERR_INVALID_POINT
+// otherwise
+int32_t stark_pubkey_validate(Bytes x_bytes);
+
+// Adds the STARK curve points (x, y) given by the first four arguments, writing the affine result
+// into the last two
+int32_t ec_add(Bytes p_x_bytes,
+               Bytes p_y_bytes,
+               Bytes q_x_bytes,
+               Bytes q_y_bytes,
+               Bytes result_x_bytes,
+               Bytes result_y_bytes);
+
+// Multiplies the STARK curve point given by the second and third arguments by a 256 bit big endian
+// scalar, writing the affine result into the last two arguments. The products of the generator
+// go through the precomputed table of get_public_key
+int32_t ec_mul(Bytes scalar_bytes,
+               Bytes p_x_bytes,
+               Bytes p_y_bytes,
+               Bytes result_x_bytes,
+               Bytes result_y_bytes);
+
+// Doubles the STARK curve point given by the first two arguments, writing the affine result into
+// the last two
+int32_t ec_double(Bytes p_x_bytes, Bytes p_y_bytes, Bytes result_x_bytes, Bytes result_y_bytes);
+
+// Negates the STARK curve point given by the first two arguments, writing (x, -y) into the last
+// two
+int32_t ec_negate(Bytes p_x_bytes, Bytes p_y_bytes, Bytes result_x_bytes, Bytes result_y_bytes);
+
+// Like ec_add over points with an infinity flag, never returning ERR_POINT_AT_INFINITY. Doubles a
+// point added to itself
+int32_t ec_add_flagged(Bytes p_x_bytes,
+                       Bytes p_y_bytes,
+                       bool p_infinity,
+                       Bytes q_x_bytes,
+                       Bytes q_y_bytes,
+                       bool q_infinity,
+                       Bytes result_x_bytes,
+                       Bytes result_y_bytes,
+                       bool *result_infinity);
+
+// Like ec_mul over points with an infinity flag, never returning ERR_POINT_AT_INFINITY
+int32_t ec_mul_flagged(Bytes scalar_bytes,
+                       Bytes p_x_bytes,
+                       Bytes p_y_bytes,
+                       bool p_infinity,
+                       Bytes result_x_bytes,
+                       Bytes result_y_bytes,
+                       bool *result_infinity);
+
+// Computes the sum of scalars[i] * points[i] for the `count` contiguous 256 bit big endian scalars
+// and points, each the 32 bytes x coordinate followed by the 32 bytes y coordinate, writing the
+// affine result into the last two arguments. Faster than the `count` ec_mul calls for more than a
+// few points
+int32_t stark_msm(const uint8_t *scalars,
+                  const uint8_t *points,
+                  size_t count,
+                  Bytes result_x_bytes,
+                  Bytes result_y_bytes);
+
+// Computes the ECDH shared secret of a private key, a felt nonzero modulo the curve order, and the
+// public point (x, y) of the peer, writing the x coordinate of private_key * peer into the last
+// argument. Returns ERR_INVALID_POINT when the peer point is not on the curve, which has a
+// cofactor of one so that every point of it is in the group of the generator. Not constant time
+int32_t stark_ecdh(Bytes private_key_bytes,
+                   Bytes peer_x_bytes,
+                   Bytes peer_y_bytes,
+                   Bytes shared_x_bytes);
+
+// Computes p + m * q like the EC_OP builtin, with a double-and-add over the `height` low bits of
+// the felt m, writing the affine result into the last two arguments. Returns ERR_INVALID_LENGTH
+// when m doesn't fit in `height` bits, at most 256, and ERR_EC_OP_SAME_X when the partial sum and
+// the doubled point share their x coordinate at some step, which the builtin rejects. An invalid
+// input point is ERR_INVALID_POINT
+int32_t ec_op(Bytes p_x_bytes,
+              Bytes p_y_bytes,
+              Bytes q_x_bytes,
+              Bytes q_y_bytes,
+              Bytes m_bytes,
+              uint32_t height,
+              Bytes result_x_bytes,
+              Bytes result_y_bytes);
+
+// Hashes `len` bytes into a point of the STARK curve, separated from the other uses by a domain
+// separation tag of `dst_len` bytes, at most 255, writing the point into the last two arguments.
+// With the prefix the length of the tag as one byte, the x coordinate is the 64 bytes
+// SHA-256(prefix || dst || msg || counter || 0x00) || SHA-256(prefix || dst || msg || counter ||
+// 0x01) modulo the STARK prime, for the first 4 bytes big endian counter 0, 1, 2... giving the x
+// coordinate of a point, and the y coordinate is the smallest of the two. Not constant time: the
+// number of attempts depends on the message
+int32_t stark_hash_to_curve(const uint8_t *msg,
+                            size_t len,
+                            const uint8_t *dst,
+                            size_t dst_len,
+                            Bytes x_bytes,
+                            Bytes y_bytes);
+
+// Recovers the y coordinate of the STARK curve point with the given x coordinate, writing the
+// smallest of the two square roots into the second argument
+int32_t ec_point_from_x(Bytes x_bytes, Bytes y_bytes);
+
+// Reports the inputs and outputs of the primitives to a callback, or stops reporting them for
+// NULL (the default). The callback must be callable from any thread
+int32_t starknet_crypto_set_differential(Differential callback);
+
+// Allocates a generator of the random felts of a seed felt, writing it into the last argument. It
+// must be released with drbg_free
+int32_t drbg_new(Bytes seed, struct Drbg **drbg);
+
+// Writes the next felt of the generator into the last argument
+int32_t drbg_next_felt(struct Drbg *drbg, Bytes felt);
+
+// Writes the next `count` felts of the generator contiguously into the buffer, the same felts as
+// `count` calls of drbg_next_felt
+int32_t drbg_fill(struct Drbg *drbg, uint8_t *buffer, size_t count);
+
+// Releases a generator allocated by drbg_new. Does nothing for a NULL pointer
+int32_t drbg_free(struct Drbg *drbg);
+
+// Encrypts `len` bytes of plaintext to a public key (x coordinate), with an ephemeral private key
+// drawn like stark_private_key_random, writing the len + ENCRYPTION_OVERHEAD bytes of the
+// ephemeral public key, ciphertext and tag into a buffer of `out_len` bytes. Returns
+// ERR_INVALID_LENGTH when the buffer is too small, ERR_INVALID_POINT when the public key is not
+// the x coordinate of a point and ERR_RNG when the random number generator fails
+int32_t stark_encrypt(Bytes recipient_public_key,
+                      const uint8_t *plaintext,
+                      size_t len,
+                      uint8_t *out,
+                      size_t out_len);
+
+// Decrypts the `len` bytes of a message of stark_encrypt with the private key of its recipient,
+// writing the len - ENCRYPTION_OVERHEAD bytes of plaintext into a buffer of `out_len` bytes.
+// Returns ERR_DECRYPTION_FAILED, without writing any plaintext, when the tag doesn't authenticate
+// the message under the private key, and ERR_INVALID_LENGTH for a message shorter than
+// ENCRYPTION_OVERHEAD or a buffer too small
+int32_t stark_decrypt(Bytes private_key_bytes,
+                      const uint8_t *message,
+                      size_t len,
+                      uint8_t *out,
+                      size_t out_len);
+
+// Returns SUCCESS when the bytes encode a felt lower than the STARK prime, ERR_INVALID_FELT
+// otherwise
+int32_t felt_check_bytes(Bytes bytes);
+
+// Reduces a big endian integer of `len` bytes, at most 64, modulo the STARK prime, writing the felt
+// into the last argument. The felts of uniformly random inputs of 48 bytes or more are uniform up
+// to a bias below 2^-128, unlike truncations of 32 bytes inputs, for hash-to-field constructions
+int32_t felt_from_bytes_wide(const uint8_t *bytes, size_t len, Bytes result_bytes);
+
+// Adds two felts, writing the result into the last argument
+int32_t felt_add(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Subtracts the second felt from the first one, writing the result into the last argument
+int32_t felt_sub(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Multiplies two felts, writing the result into the last argument
+int32_t felt_mul(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Inverts a felt, writing the result into the last argument. Returns ERR_DIVISION_BY_ZERO when
+// inverting zero
+int32_t felt_inverse(Bytes a_bytes, Bytes result_bytes);
+
+// Inverts in place `count` contiguous felts with a single inversion, by Montgomery's trick.
+// Returns ERR_DIVISION_BY_ZERO, leaving the felts untouched, when one of them is zero
+int32_t felt_batch_inverse(uint8_t *values, size_t count);
+
+// Raises the first felt to the power of a 256 bit big endian integer, writing the result into the
+// last argument
+int32_t felt_pow(Bytes base_bytes, Bytes exponent_bytes, Bytes result_bytes);
+
+// Writes the smallest square root of a felt into the last argument, or returns
+// ERR_NOT_QUADRATIC_RESIDUE
+int32_t felt_sqrt(Bytes a_bytes, Bytes result_bytes);
+
+// Writes whether the felt is a square, zero included, into the second argument
+int32_t felt_is_quadratic_residue(Bytes a_bytes, bool *result);
+
+// Splits a felt into its 128 low bits and the remaining high bits, writing both as felts into the
+// last two arguments, like the `split_felt` hint: value = high * 2^128 + low with low < 2^128
+int32_t felt_split_128(Bytes value_bytes, Bytes low_bytes, Bytes high_bytes);
+
+// Interprets a felt as a signed integer of [-(p - 1) / 2, (p - 1) / 2], like the `as_int` of the
+// hints, writing its absolute value and whether it is negative into the last two arguments. The
+// felts up to (p - 1) / 2 are nonnegative and the greater ones stand for felt - p
+int32_t felt_as_signed(Bytes value_bytes, Bytes abs_bytes, bool *negative);
+
+// Writes the felt of a signed integer given by its absolute value, at most (p - 1) / 2, and
+// whether it is negative into the last argument, the inverse of felt_as_signed. Returns
+// ERR_INVALID_FELT when the absolute value is greater than (p - 1) / 2
+int32_t felt_from_signed(Bytes abs_bytes, bool negative, Bytes result_bytes);
+
+// Writes the felt of a 64 bits integer into the last argument
+int32_t felt_from_u64(uint64_t value, Bytes result_bytes);
+
+// Writes the felt of a 128 bits integer, high * 2^64 + low, into the last argument
+int32_t felt_from_u128(uint64_t high, uint64_t low, Bytes result_bytes);
+
+// Writes the value of a felt lower than 2^64 into the last argument. Returns ERR_OUT_OF_RANGE, and
+// leaves the argument unchanged, for a greater felt
+int32_t felt_to_u64_checked(Bytes value_bytes, uint64_t *result);
+
+// Writes the value of a felt lower than 2^128 into the last two arguments, value = high * 2^64 +
+// low. Returns ERR_OUT_OF_RANGE, and leaves the arguments unchanged, for a greater felt
+int32_t felt_to_u128_checked(Bytes value_bytes, uint64_t *high, uint64_t *low);
+
+// Compares two felts as integers of [0, p), writing -1, 0 or 1 into the last argument when the
+// first one is lower than, equal to or greater than the second one
+int32_t felt_cmp(Bytes a_bytes, Bytes b_bytes, int32_t *result);
+
+// Writes whether the felt is zero into the second argument
+int32_t felt_is_zero(Bytes a_bytes, bool *result);
+
+// Writes whether the two felts are equal into the last argument, in constant time. Compares the
+// 32 bytes encodings as is, without checking that they are lower than the STARK prime
+int32_t felt_ct_eq(Bytes a_bytes, Bytes b_bytes, bool *result);
+
+// Allocates a handle holding the felt of the first argument, writing it into the last argument. It
+// must be released with felt_free
+int32_t felt_new_from_bytes(Bytes bytes, struct FeltHandle **handle);
+
+// Writes the felt of a handle into the last argument
+int32_t felt_read_bytes(const struct FeltHandle *handle, Bytes bytes);
+
+// Allocates a handle holding the sum of two handles, writing it into the last argument
+int32_t felt_op_add(const struct FeltHandle *a,
+                    const struct FeltHandle *b,
+                    struct FeltHandle **result);
+
+// Allocates a handle holding the difference of two handles, writing it into the last argument
+int32_t felt_op_sub(const struct FeltHandle *a,
+                    const struct FeltHandle *b,
+                    struct FeltHandle **result);
+
+// Allocates a handle holding the product of two handles, writing it into the last argument
+int32_t felt_op_mul(const struct FeltHandle *a,
+                    const struct FeltHandle *b,
+                    struct FeltHandle **result);
+
+// Allocates a handle holding the inverse of a handle, writing it into the last argument. Returns
+// ERR_DIVISION_BY_ZERO for zero
+int32_t felt_op_inverse(const struct FeltHandle *a, struct FeltHandle **result);
+
+// Allocates a handle holding the pedersen hash of two handles, writing it into the last argument
+int32_t felt_hash_pedersen(const struct FeltHandle *a,
+                           const struct FeltHandle *b,
+                           struct FeltHandle **result);
+
+// Allocates a handle holding the poseidon hash of two handles, writing it into the last argument
+int32_t felt_hash_poseidon(const struct FeltHandle *a,
+                           const struct FeltHandle *b,
+                           struct FeltHandle **result);
+
+// Releases a handle allocated by the felt_new_from_bytes, felt_op_ and felt_hash_ functions. Does
+// nothing for a NULL pointer
+int32_t felt_free(struct FeltHandle *handle);
+
+// Applies the Keccak-f[1600] permutation in place over a state of 25 64 bit lanes
+int32_t keccak_f1600(uint64_t *state);
+
+// Absorbs `num_blocks` contiguous blocks of 17 64 bit lanes into a zero Keccak-f[1600] state,
+// permuting after each, and writes the 4 lanes squeezed out of the state into the last argument,
+// like the keccak builtin over a whole segment. The input must be padded by the caller, so the
+// output is the keccak256 digest of the unpadded input as little endian lanes. Returns
+// ERR_INVALID_LENGTH when there is no block
+int32_t keccak_u256_blocks(const uint64_t *input, size_t num_blocks, uint64_t *output);
+
+// Computes the keccak256 hash of `len` bytes, writing the 32 bytes digest into the last argument
+int32_t keccak256(const uint8_t *data, size_t len, uint8_t *result);
+
+// Computes the keccak256 hash of `len` bytes truncated to its 250 low bits, writing it into the
+// last argument
+int32_t starknet_keccak(const uint8_t *data, size_t len, Bytes result);
+
+// Grinds the `seed_len` bytes of a seed into a Stark private key with the StarkEx algorithm,
+// writing it into the last argument. The seed of the key of an Ethereum account is the 32 bytes r
+// of its secp256k1 signature of the key derivation message
+int32_t grind_key(const uint8_t *seed, size_t seed_len, Bytes private_key);
+
+// Derives a Stark private key along the EIP-2645 path of the given layer and application, NUL
+// terminated names such as `starkex` and `starkdeployement`, 20 bytes Ethereum address and index,
+// from the `seed_len` bytes of a BIP32 seed, writing it into the last argument. Returns
+// ERR_INVALID_K in the negligible case where the path goes through an invalid BIP32 key
+int32_t eip2645_derive(const uint8_t *seed,
+                       size_t seed_len,
+                       const char *layer,
+                       const char *application,
+                       const uint8_t *eth_address,
+                       uint32_t index,
+                       Bytes private_key);
+
+// Loads the EIP-4844 trusted setup, the `g1_count` G1 points of the Lagrange basis in bit reversed
+// order followed by nothing else, and the `g2_count` G2 points of the monomial basis, replacing
+// the setup loaded before. The G1 count is 4096 and there are at least two G2 points
+int32_t kzg_load_trusted_setup(const uint8_t *g1_lagrange,
+                               size_t g1_count,
+                               const uint8_t *g2_monomial,
+                               size_t g2_count);
+
+// Computes the KZG commitment of a blob of 4096 scalars, 131072 bytes, writing the 48 bytes G1
+// point into the last argument. Returns ERR_INVALID_FELT for a scalar that is not lower than the
+// BLS12-381 scalar field modulus and ERR_NO_TRUSTED_SETUP before kzg_load_trusted_setup
+int32_t kzg_commit(const uint8_t *blob, uint8_t *commitment);
+
+// Verifies a KZG proof, a 48 bytes G1 point, that the polynomial of a commitment evaluates to `y`
+// at `z`, two 32 bytes scalars. Returns SUCCESS for a valid proof, ERR_INVALID_PROOF for an
+// invalid one and another error code for malformed inputs or before kzg_load_trusted_setup
+int32_t kzg_verify_proof(const uint8_t *commitment, Bytes z, Bytes y, const uint8_t *proof);
+
+// Evaluates the polynomial of `count` contiguous coefficients over the BLS12-381 scalar field,
+// lowest degree first, at a point, writing the value into the last argument. The coefficients and
+// the point are 32 bytes scalars lower than the field modulus
+int32_t bls_poly_eval(const uint8_t *coefficients, size_t count, Bytes point, Bytes result);
+
+// Like `poseidon_permute`, with felts as 32 bytes little endian integers
+int32_t poseidon_permute_le(Bytes first_state_felt,
+                            Bytes second_state_felt,
+                            Bytes third_state_felt);
+
+// Like `pedersen_hash`, with felts as 32 bytes little endian integers
+int32_t pedersen_hash_le(Bytes felt_1, Bytes felt_2, Bytes result);
+
+// Like `verify_signature`, with felts as 32 bytes little endian integers
+int32_t verify_signature_le(Bytes public_key_bytes,
+                            Bytes message_bytes,
+                            Bytes r_bytes,
+                            Bytes s_bytes);
+
+// Like `pedersen_hash`, with felts as four little endian 64 bit limbs holding their canonical
+// value
+int32_t pedersen_hash_limbs(const uint64_t *a, const uint64_t *b, uint64_t *result);
+
+// Like `poseidon_hash`, with felts as four little endian 64 bit limbs holding their canonical
+// value
+int32_t poseidon_hash_limbs(const uint64_t *x, const uint64_t *y, uint64_t *result);
+
+// Like `poseidon_permute`, over a state of three contiguous felts as four little endian 64 bit
+// limbs holding their canonical value (12 limbs)
+int32_t poseidon_permute_limbs(uint64_t *state);
+
+// Like `verify_signature`, with felts as four little endian 64 bit limbs holding their canonical
+// value
+int32_t verify_signature_limbs(const uint64_t *public_key,
+                               const uint64_t *message,
+                               const uint64_t *r,
+                               const uint64_t *s);
+
+// Converts `count` contiguous felts as four little endian 64 bit limbs into contiguous 32 bytes
+// big endian felts. Returns ERR_INVALID_FELT when one of them is not lower than the STARK prime,
+// after converting all of them
+int32_t felts_to_bytes_be(const uint64_t *limbs, size_t count, uint8_t *bytes);
+
+// Converts `count` contiguous 32 bytes big endian felts into contiguous felts as four little
+// endian 64 bit limbs, like felts_to_bytes_be the other way around
+int32_t felts_from_bytes_be(const uint8_t *bytes, size_t count, uint64_t *limbs);
+
+// Sends the internal events of the library to a logger, or stops logging them for NULL (the
+// default). The logger must be callable from any thread
+int32_t starknet_crypto_set_logger(Logger logger);
+
+// Computes the root of the binary Merkle tree with Pedersen nodes over `count` contiguous leaves,
+// padded with zeros up to the next power of two, writing it into the last argument
+int32_t pedersen_merkle_root(const uint8_t *leaves, size_t count, Bytes result);
+
+// Computes the root of the binary Merkle tree with Poseidon nodes over `count` contiguous leaves,
+// padded with zeros up to the next power of two, writing it into the last argument
+int32_t poseidon_merkle_root(const uint8_t *leaves, size_t count, Bytes result);
+
+// Computes the cap of height `cap_height` of the binary Merkle tree over `count` contiguous
+// leaves, padded with zeros up to the next power of two and to at least 2^cap_height leaves: the
+// 2^cap_height contiguous roots of its subtrees at depth cap_height, from the leftmost one, written
+// into the last argument. The nodes hash with HASH_PEDERSEN or HASH_POSEIDON. The cap of height 0
+// is the root, and the cap of no leaves is made of zeros
+int32_t merkle_cap(const uint8_t *leaves,
+                   size_t count,
+                   uint32_t cap_height,
+                   uint32_t hash_kind,
+                   uint8_t *cap);
+
+// Computes the commitment to a memory segment of `count` contiguous (address, value) pairs of
+// felts, writing it into the last argument: the root of the binary Merkle tree over the hashes of
+// the pairs, with HASH_PEDERSEN or HASH_POSEIDON for both the pairs and the nodes. The pairs and
+// the subtrees hash on the thread pool of the batch functions when there is one
+int32_t hash_memory_segment(const uint8_t *pairs, size_t count, uint32_t hash_kind, Bytes result);
+
+// Verifies the proof that `leaf` is the leaf at `index` of the Merkle tree with the given root,
+// from the `depth` contiguous siblings of the path starting at the leaf. The nodes hash with
+// HASH_PEDERSEN or HASH_POSEIDON. Returns SUCCESS for a valid proof, ERR_INVALID_PROOF for an
+// invalid one and another error code for malformed inputs
+int32_t merkle_verify(Bytes root,
+                      Bytes leaf,
+                      uint64_t index,
+                      const uint8_t *siblings,
+                      size_t depth,
+                      uint32_t hash_kind);
+
+// Verifies that the `count` contiguous leaves are at the given strictly increasing indices of the
+// Merkle tree of the given depth and root, from a multiproof of `proof_count` contiguous nodes. The
+// multiproof lists the nodes needed on the way up, level by level from the leaves and by
+// increasing index within a level, that are not computed from the leaves themselves. The nodes
+// hash with HASH_PEDERSEN or HASH_POSEIDON. Returns SUCCESS for a valid multiproof,
+// ERR_INVALID_PROOF for an invalid one and another error code for malformed inputs
+int32_t merkle_multiproof_verify(Bytes root,
+                                 const uint64_t *indices,
+                                 const uint8_t *leaves,
+                                 size_t count,
+                                 const uint8_t *proof,
+                                 size_t proof_count,
+                                 size_t depth,
+                                 uint32_t hash_kind);
+
+// Computes the hash of an L1 to L2 message from the 20 bytes Ethereum address `from_address` to
+// the contract `to_address`, with the selector of its L1 handler, `payload_len` contiguous felts
+// of payload and the nonce of the message, writing the 32 bytes big endian keccak256 digest into
+// the last argument
+int32_t l1_to_l2_message_hash(const uint8_t *from_address,
+                              Bytes to_address,
+                              Bytes selector,
+                              const uint8_t *payload,
+                              size_t payload_len,
+                              Bytes nonce,
+                              uint8_t *result);
+
+// Computes the pedersen hash of two felts given as Montgomery limbs (four little endian 64 bit
+// limbs with R = 2^256, the representation of the Zig Felt252), writing the result into the last
+// argument. Unreduced limbs are reported as ERR_INVALID_FELT
+int32_t pedersen_hash_mont(const uint64_t *a, const uint64_t *b, uint64_t *result);
+
+// Computes the poseidon hash permutation in place over a state of three contiguous felts given as
+// Montgomery limbs (12 limbs). Unreduced limbs are reported as ERR_INVALID_FELT
+int32_t poseidon_permute_mont(uint64_t *state);
+
+// Transforms in place the `len` contiguous felts of a power of two length, the coefficients of a
+// polynomial into its evaluations at the powers of the root of unity of the length, or back with a
+// non-zero `inverse`
+int32_t stark_ntt(uint8_t *values, size_t len, uint8_t inverse);
+
+// Computes the root of the Starknet Patricia trie of height 251 holding the `count` contiguous
+// (key, value) pairs of felts, writing it into the last argument. The keys are lower than 2^251,
+// a key given twice keeps its last value and keys with a zero value are left out
+int32_t patricia_root(const uint8_t *entries, size_t count, Bytes result);
+
+// Applies `count` contiguous (key, value) updates to the Patricia trie of a root computed by
+// patricia_root or patricia_update in this process, writing the new root into the last argument.
+// A zero value deletes the key. Only the tries built in this process are known, so this returns
+// ERR_UNKNOWN_ROOT for a root other than zero that was computed elsewhere, or whose trie was since
+// evicted or cleared
+int32_t patricia_update(Bytes prev_root, const uint8_t *updates, size_t count, Bytes result);
+
+// Empties the store of the Patricia trie nodes, after which patricia_update returns
+// ERR_UNKNOWN_ROOT for every root computed before
+int32_t patricia_clear(void);
+
+// Sets the most nodes the store of the Patricia trie nodes holds, 2^20 by default. Once an update
+// leaves more, only the trie of its new root is kept, and patricia_update returns
+// ERR_UNKNOWN_ROOT for the roots computed before it
+int32_t patricia_set_capacity(size_t max_nodes);
+
+// Verifies the storage proof that the storage of the contract at `contract_address` holds `value`
+// at `storage_key` in the state of `state_root`, a zero value proving the key is absent. The
+// contract state is its class hash, nonce and storage root, all zeros for a contract that is not
+// deployed, and `classes_root` is the root of the classes trie. The proofs are `contract_proof_len`
+// and `storage_proof_len` contiguous nodes of 3 felts, in any order: zero, the left and the right
+// child of a binary node, or the length, the path and the child of an edge node. Returns
+// ERR_INVALID_PROOF when either proof doesn't lead to its root
+int32_t verify_storage_proof(Bytes state_root,
+                             Bytes classes_root,
+                             Bytes contract_address,
+                             Bytes class_hash,
+                             Bytes nonce,
+                             Bytes storage_root,
+                             Bytes storage_key,
+                             Bytes value,
+                             const uint8_t *contract_proof,
+                             size_t contract_proof_len,
+                             const uint8_t *storage_proof,
+                             size_t storage_proof_len);
+
+// Computes the hash of the state of a contract from its class hash, the root of its storage trie
+// and its nonce, its leaf in the contracts trie, writing it into the last argument
+int32_t contract_state_hash(Bytes class_hash, Bytes storage_root, Bytes nonce, Bytes result);
+
+// Computes the global state root of a block from the roots of its contracts and classes tries,
+// the Poseidon hash of "STARKNET_STATE_V0" and both roots, or the contracts root while the
+// classes root is zero, writing it into the last argument
+int32_t global_state_root(Bytes contracts_root, Bytes classes_root, Bytes result);
+
+// Builds the lookup tables that speed up pedersen_hash_batch, taking about 1 MB of memory. Only
+// the first call builds them, and it may be made from any thread
+int32_t starknet_crypto_precompute_pedersen(void);
+
+// Precomputes the partial Pedersen sum of a left operand, writing the handle into the last
+// argument. It must be released with pedersen_left_free
+int32_t pedersen_precompute_left(Bytes a, struct PedersenLeft **left);
+
+// Computes the pedersen hash of the left operand of a handle and a felt, writing the result into
+// the last argument
+int32_t pedersen_hash_with_left(const struct PedersenLeft *left, Bytes b, Bytes result);
+
+// Releases a handle allocated by pedersen_precompute_left. Does nothing for a NULL pointer
+int32_t pedersen_left_free(struct PedersenLeft *left);
+
+// Writes the counters of the builtin primitives since the start or the last
+// starknet_crypto_stats_reset into the argument. Only available with the `profiling` cargo feature
+int32_t starknet_crypto_stats(struct Stats *stats);
+
+// Resets the counters of the builtin primitives to zero. Only available with the `profiling` cargo
+// feature
+int32_t starknet_crypto_stats_reset(void);
+
+// Computes the hash the Starknet bootloader commits to for a program, writing it into the last
+// argument, from `count` contiguous felts of the program without the bootloader version: its main
+// offset, the number of its builtins, their names as short strings and its bytecode. The hash is
+// the Pedersen hash chain of the length and then the version followed by those felts
+int32_t compute_program_hash(const uint8_t *program_felts,
+                             size_t count,
+                             uint32_t version,
+                             Bytes result);
+
+// Computes the main page hash of the public memory of a run, writing it into the last argument:
+// the hash of the (address, value) pairs of the `program_len` felts of the program from
+// `program_base`, the `pointers_len` builtin pointers from `execution_base` and the `output_len`
+// felts of output from `output_base`, flattened in that order. HASH_PEDERSEN hashes them with the
+// hash on elements, HASH_POSEIDON with the Poseidon hash many
+int32_t public_memory_hash(const uint8_t *program,
+                           size_t program_len,
+                           uint64_t program_base,
+                           const uint8_t *builtin_pointers,
+                           size_t pointers_len,
+                           uint64_t execution_base,
+                           const uint8_t *output,
+                           size_t output_len,
+                           uint64_t output_base,
+                           uint32_t hash_kind,
+                           Bytes result);
+
+// Computes the fact SHARP registers for a run of the program of `program_hash` with `output_len`
+// contiguous felts of output in a single page, the keccak256 of the program hash and of the
+// output as 32 bytes big endian words, writing the 32 bytes digest into the last argument
+int32_t program_fact(Bytes program_hash, const uint8_t *output, size_t output_len, uint8_t *result);
+
+// Makes felt_random and stark_private_key_random draw from a generator seeded with the `len`
+// bytes of the seed, the same felts and keys in the same order for the same seed, until the next
+// call. A NULL seed goes back to the operating system's random number generator. The generator is
+// shared by the threads, so their draws only repeat when they happen in the same order
+int32_t starknet_crypto_set_deterministic(const uint8_t *seed, size_t len);
+
+// Writes a uniformly random felt into the argument, or the next one of the deterministic mode of
+// starknet_crypto_set_deterministic. Returns ERR_RNG when the operating system's random number
+// generator fails
+int32_t felt_random(Bytes result);
+
+// Writes `count` contiguous uniformly random felts into the buffer, the next `count` felts of
+// felt_random in the deterministic mode. Returns ERR_INVALID_LENGTH for a NULL buffer of a non
+// zero count, and ERR_RNG when the operating system's random number generator fails, with the
+// felts drawn before the failure written
+int32_t felt_random_fill(uint8_t *buf, size_t count);
+
+// Writes a uniformly random Stark private key, between 1 and the curve order excluded, into the
+// argument, or the next one of the deterministic mode. Returns ERR_RNG when the operating system's
+// random number generator fails
+int32_t stark_private_key_random(Bytes private_key);
+
+// Signs a message with a private key and a nonce k, both felts nonzero modulo the curve order,
+// writing the Schnorr signature (r, s) into the last two arguments. k must be uniformly random and
+// never reused. Not constant time: the scalar multiplications branch on the secrets
+int32_t schnorr_sign(Bytes private_key_bytes,
+                     Bytes message_bytes,
+                     Bytes k_bytes,
+                     Bytes r_bytes,
+                     Bytes s_bytes);
+
+// Verifies a Schnorr signature (r, s) of a message by a public key. Returns SUCCESS for a valid
+// signature, ERR_INVALID_SIGNATURE for an invalid one, ERR_INVALID_S when s is not lower than the
+// curve order, ERR_INVALID_POINT when the public key is not the x coordinate of a point and
+// ERR_INVALID_FELT when an argument is not a felt
+int32_t schnorr_verify(Bytes public_key_bytes, Bytes message_bytes, Bytes r_bytes, Bytes s_bytes);
+
+// Adds the secp256k1 points (x, y) given by the first four arguments, writing the affine result
+// into the last two
+int32_t secp256k1_add(Bytes p_x_bytes,
+                      Bytes p_y_bytes,
+                      Bytes q_x_bytes,
+                      Bytes q_y_bytes,
+                      Bytes result_x_bytes,
+                      Bytes result_y_bytes);
+
+// Multiplies the secp256k1 point given by the second and third arguments by a scalar, writing the
+// affine result into the last two arguments. Constant time with respect to the scalar
+int32_t secp256k1_mul(Bytes scalar_bytes,
+                      Bytes p_x_bytes,
+                      Bytes p_y_bytes,
+                      Bytes result_x_bytes,
+                      Bytes result_y_bytes);
+
+// Recovers the y coordinate of the secp256k1 point with the given x coordinate and the same parity
+// as v
+int32_t secp256k1_get_point_from_x(Bytes x_bytes, Bytes v_bytes, Bytes y_bytes);
+
+// Writes the 33 bytes SEC1 compressed encoding of the secp256k1 point (x, y) into the last
+// argument: 0x02 for an even y or 0x03 for an odd one, followed by x
+int32_t secp256k1_point_compress(Bytes x_bytes, Bytes y_bytes, uint8_t *compressed);
+
+// Decodes a 33 bytes SEC1 compressed secp256k1 point, writing its coordinates into the last two
+// arguments. Returns ERR_INVALID_POINT for another prefix than 0x02 or 0x03, or when the x
+// coordinate is not the one of a point
+int32_t secp256k1_point_decompress(const uint8_t *compressed, Bytes x_bytes, Bytes y_bytes);
+
+// Adds two scalars modulo the secp256k1 curve order
+int32_t secp256k1_scalar_add(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Multiplies two scalars modulo the secp256k1 curve order
+int32_t secp256k1_scalar_mul(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Inverts a scalar modulo the secp256k1 curve order, writing the result into the last argument.
+// Returns ERR_DIVISION_BY_ZERO when the scalar is zero modulo the order
+int32_t secp256k1_scalar_inverse(Bytes a_bytes, Bytes result_bytes);
+
+// Divides the first scalar by the second modulo the secp256k1 curve order
+int32_t secp256k1_scalar_div(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Recovers the Ethereum address that signed a 32 bytes message hash with the signature (r, s, v),
+// writing the 20 bytes address into the last argument. v is either the recovery id (0 or 1) or its
+// Ethereum encoding (27 or 28), and s must be in the lower half of the scalars
+int32_t secp256k1_recover_eth_address(Bytes message_bytes,
+                                      Bytes r_bytes,
+                                      Bytes s_bytes,
+                                      uint32_t v,
+                                      Bytes address_bytes);
+
+// Verifies a signature (r, s) of a 32 bytes message hash against the public key (x, y) given by
+// the first two arguments. Returns SUCCESS for a valid signature, ERR_INVALID_SIGNATURE for an
+// invalid one and another error code for malformed inputs. s must be in the lower half of the
+// scalars
+int32_t secp256k1_verify(Bytes public_key_x_bytes,
+                         Bytes public_key_y_bytes,
+                         Bytes message_bytes,
+                         Bytes r_bytes,
+                         Bytes s_bytes);
+
+// Adds the secp256r1 points (x, y) given by the first four arguments, writing the affine result
+// into the last two
+int32_t secp256r1_add(Bytes p_x_bytes,
+                      Bytes p_y_bytes,
+                      Bytes q_x_bytes,
+                      Bytes q_y_bytes,
+                      Bytes result_x_bytes,
+                      Bytes result_y_bytes);
+
+// Multiplies the secp256r1 point given by the second and third arguments by a scalar, writing the
+// affine result into the last two arguments. Constant time with respect to the scalar
+int32_t secp256r1_mul(Bytes scalar_bytes,
+                      Bytes p_x_bytes,
+                      Bytes p_y_bytes,
+                      Bytes result_x_bytes,
+                      Bytes result_y_bytes);
+
+// Recovers the y coordinate of the secp256r1 point with the given x coordinate and the same parity
+// as v
+int32_t secp256r1_get_point_from_x(Bytes x_bytes, Bytes v_bytes, Bytes y_bytes);
+
+// Writes the 33 bytes SEC1 compressed encoding of the secp256r1 point (x, y) into the last
+// argument: 0x02 for an even y or 0x03 for an odd one, followed by x
+int32_t secp256r1_point_compress(Bytes x_bytes, Bytes y_bytes, uint8_t *compressed);
+
+// Decodes a 33 bytes SEC1 compressed secp256r1 point, writing its coordinates into the last two
+// arguments. Returns ERR_INVALID_POINT for another prefix than 0x02 or 0x03, or when the x
+// coordinate is not the one of a point
+int32_t secp256r1_point_decompress(const uint8_t *compressed, Bytes x_bytes, Bytes y_bytes);
+
+// Adds two scalars modulo the secp256r1 curve order
+int32_t secp256r1_scalar_add(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Multiplies two scalars modulo the secp256r1 curve order
+int32_t secp256r1_scalar_mul(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Inverts a scalar modulo the secp256r1 curve order, writing the result into the last argument.
+// Returns ERR_DIVISION_BY_ZERO when the scalar is zero modulo the order
+int32_t secp256r1_scalar_inverse(Bytes a_bytes, Bytes result_bytes);
+
+// Divides the first scalar by the second modulo the secp256r1 curve order
+int32_t secp256r1_scalar_div(Bytes a_bytes, Bytes b_bytes, Bytes result_bytes);
+
+// Overwrites `len` bytes with zeroes in a way the compiler can't optimize away, to wipe the
+// caller's own copies of private keys and nonces. The pointer may be NULL when `len` is zero
+int32_t starknet_crypto_zeroize(uint8_t *bytes, size_t len);
+
+// Applies the sha256 compression function in place over a state of 8 words, given a 64 bytes
+// message block
+int32_t sha256_compress(uint32_t *state, const uint8_t *block);
+
+// Computes the sha256 hash of `len` bytes with the standard padding, writing the 32 bytes digest
+// into the last argument
+int32_t sha256(const uint8_t *data, size_t len, uint8_t *result);
+
+// Allocates an empty Poseidon sponge, writing it into the argument. It must be released with
+// poseidon_ctx_free
+int32_t poseidon_ctx_new(struct PoseidonContext **ctx);
+
+// Absorbs a felt into the sponge
+int32_t poseidon_ctx_absorb(struct PoseidonContext *ctx, Bytes felt);
+
+// Writes the poseidon_hash_many of the felts absorbed so far into the last argument, and empties
+// the sponge so that it may hash another stream
+int32_t poseidon_ctx_squeeze(struct PoseidonContext *ctx, Bytes result);
+
+// Releases a sponge allocated by poseidon_ctx_new. Does nothing for a NULL pointer
+int32_t poseidon_ctx_free(struct PoseidonContext *ctx);
+
+// Allocates an empty Pedersen hash chain, writing it into the argument. It must be released with
+// pedersen_chain_free
+int32_t pedersen_chain_new(struct PedersenChain **ctx);
+
+// Appends a felt to the chain
+int32_t pedersen_chain_update(struct PedersenChain *ctx, Bytes felt);
+
+// Writes the pedersen_hash_on_elements of the felts appended so far, H(...H(H(0, x1), x2)..., n),
+// into the last argument, and empties the chain so that it may hash another stream
+int32_t pedersen_chain_finalize(struct PedersenChain *ctx, Bytes result);
+
+// Releases a chain allocated by pedersen_chain_new. Does nothing for a NULL pointer
+int32_t pedersen_chain_free(struct PedersenChain *ctx);
+
+// Parses the `len` bytes of a hex string, with or without a `0x` prefix, into a felt written into
+// the last argument. Leading zeros are allowed and the digits may be upper or lower case. Returns
+// ERR_INVALID_STRING for a malformed string and ERR_INVALID_FELT for a value that is not lower
+// than the STARK prime
+int32_t felt_from_hex(const uint8_t *string, size_t len, Bytes result);
+
+// Formats a felt as a NUL terminated `0x` prefixed lower case hex string without leading zeros,
+// `0x0` for zero, into a buffer of `buf_len` bytes. 66 bytes always suffice, a smaller buffer may
+// return ERR_INVALID_LENGTH
+int32_t felt_to_hex(Bytes felt, uint8_t *buf, size_t buf_len);
+
+// Parses the `len` bytes of a decimal string into a felt written into the last argument. Leading
+// zeros are allowed. Returns ERR_INVALID_STRING for a malformed string and ERR_INVALID_FELT for a
+// value that is not lower than the STARK prime
+int32_t felt_from_dec(const uint8_t *string, size_t len, Bytes result);
+
+// Formats a felt as a NUL terminated decimal string without leading zeros, `0` for zero, into a
+// buffer of `buf_len` bytes. 77 bytes always suffice, a smaller buffer may return
+// ERR_INVALID_LENGTH
+int32_t felt_to_dec(Bytes felt, uint8_t *buf, size_t buf_len);
+
+// Encodes the `len` bytes of a string of at most 31 bytes as a Cairo short string, writing the
+// felt into the last argument. With `ascii_only` the bytes must be ASCII. Returns
+// ERR_INVALID_LENGTH for a longer string and ERR_INVALID_STRING for a non ASCII byte
+int32_t short_string_to_felt(const uint8_t *string, size_t len, bool ascii_only, Bytes result);
+
+// Decodes a felt as a Cairo short string without its leading NUL bytes, writing it NUL terminated
+// into a buffer of `buf_len` bytes and its length without the terminator into the last argument.
+// 32 bytes always suffice. Returns ERR_INVALID_STRING for a felt of more than 31 bytes
+int32_t felt_to_short_string(Bytes felt, uint8_t *buf, size_t buf_len, size_t *string_len);
+
+// Computes the hash of a v1 invoke transaction with `calldata_len` contiguous felts of calldata,
+// writing it into the last argument
+int32_t invoke_v1_transaction_hash(Bytes sender_address,
+                                   const uint8_t *calldata,
+                                   size_t calldata_len,
+                                   Bytes max_fee,
+                                   Bytes chain_id,
+                                   Bytes nonce,
+                                   Bytes result);
+
+// Computes the hash of a v1 declare transaction, writing it into the last argument
+int32_t declare_v1_transaction_hash(Bytes sender_address,
+                                    Bytes class_hash,
+                                    Bytes max_fee,
+                                    Bytes chain_id,
+                                    Bytes nonce,
+                                    Bytes result);
+
+// Computes the hash of a v1 deploy_account transaction deploying a class at `contract_address`
+// with a salt and `calldata_len` contiguous felts of constructor calldata, writing it into the
+// last argument
+int32_t deploy_account_v1_transaction_hash(Bytes contract_address,
+                                           Bytes class_hash,
+                                           Bytes salt,
+                                           const uint8_t *calldata,
+                                           size_t calldata_len,
+                                           Bytes max_fee,
+                                           Bytes chain_id,
+                                           Bytes nonce,
+                                           Bytes result);
+
+// Computes the hash of a v3 invoke transaction, writing it into the last argument. The
+// resource bounds, paymaster data, account deployment data and calldata are contiguous felts
+int32_t invoke_v3_transaction_hash(Bytes sender_address,
+                                   Bytes tip,
+                                   const uint8_t *resource_bounds,
+                                   size_t resource_bounds_len,
+                                   const uint8_t *paymaster_data,
+                                   size_t paymaster_data_len,
+                                   Bytes chain_id,
+                                   Bytes nonce,
+                                   Bytes data_availability_modes,
+                                   const uint8_t *account_deployment_data,
+                                   size_t account_deployment_data_len,
+                                   const uint8_t *calldata,
+                                   size_t calldata_len,
+                                   Bytes result);
+
+// Computes the hash of a v3 declare transaction, writing it into the last argument. The resource
+// bounds, paymaster data and account deployment data are contiguous felts
+int32_t declare_v3_transaction_hash(Bytes sender_address,
+                                    Bytes tip,
+                                    const uint8_t *resource_bounds,
+                                    size_t resource_bounds_len,
+                                    const uint8_t *paymaster_data,
+                                    size_t paymaster_data_len,
+                                    Bytes chain_id,
+                                    Bytes nonce,
+                                    Bytes data_availability_modes,
+                                    const uint8_t *account_deployment_data,
+                                    size_t account_deployment_data_len,
+                                    Bytes class_hash,
+                                    Bytes compiled_class_hash,
+                                    Bytes result);
+
+// Computes the hash of a v3 deploy_account transaction deploying a class at `contract_address`
+// with a salt, writing it into the last argument. The resource bounds, paymaster data and
+// constructor calldata are contiguous felts
+int32_t deploy_account_v3_transaction_hash(Bytes contract_address,
+                                           Bytes tip,
+                                           const uint8_t *resource_bounds,
+                                           size_t resource_bounds_len,
+                                           const uint8_t *paymaster_data,
+                                           size_t paymaster_data_len,
+                                           Bytes chain_id,
+                                           Bytes nonce,
+                                           Bytes data_availability_modes,
+                                           const uint8_t *calldata,
+                                           size_t calldata_len,
+                                           Bytes class_hash,
+                                           Bytes salt,
+                                           Bytes result);
+
+// Computes the hash of `calldata_len` contiguous felts of calldata, writing it into the last
+// argument: the hash on elements of a v1 transaction with HASH_PEDERSEN, the Poseidon hash many of
+// a v3 transaction with HASH_POSEIDON
+int32_t calldata_hash(const uint8_t *calldata,
+                      size_t calldata_len,
+                      uint32_t hash_kind,
+                      Bytes result);
+
+// Computes the hash of an event emitted by `from_address` in a transaction, with `keys_len` and
+// `data_len` contiguous felts of keys and data, writing it into the last argument. HASH_POSEIDON
+// is the hash of the receipts since Starknet v0.13.2 and HASH_PEDERSEN the one before, which
+// ignores the transaction hash
+int32_t event_hash(Bytes from_address,
+                   Bytes transaction_hash,
+                   const uint8_t *keys,
+                   size_t keys_len,
+                   const uint8_t *data,
+                   size_t data_len,
+                   uint32_t hash_kind,
+                   Bytes result);
+
+// Allocates a transcript separated from the others by a domain tag felt, writing it into the last
+// argument. It must be released with transcript_free
+int32_t transcript_new(Bytes domain_tag, struct Transcript **transcript);
+
+// Absorbs `count` contiguous felts into the transcript. Nothing is absorbed when one of them is
+// not a felt
+int32_t transcript_absorb_felts(struct Transcript *transcript, const uint8_t *felts, size_t count);
+
+// Absorbs `len` bytes into the transcript, as their length followed by their 31 bytes big endian
+// chunks, the last one possibly shorter
+int32_t transcript_absorb_bytes(struct Transcript *transcript, const uint8_t *data, size_t len);
+
+// Squeezes a challenge felt out of the transcript, writing it into the last argument
+int32_t transcript_squeeze_felt(struct Transcript *transcript, Bytes challenge);
+
+// Squeezes a challenge felt out of the transcript, writing its 64 low bits into the last argument
+int32_t transcript_squeeze_u64(struct Transcript *transcript, uint64_t *challenge);
+
+// Releases a transcript allocated by transcript_new. Does nothing for a NULL pointer
+int32_t transcript_free(struct Transcript *transcript);
+
+// Computes the struct hash of typed data of the given SNIP-12 revision, 0 or 1, from the
+// `type_len` bytes of its encoded type and its `member_count` contiguous encoded members, writing
+// it into the last argument. The domain separator is the struct hash of the domain
+int32_t typed_data_struct_hash(uint32_t revision,
+                               const uint8_t *encoded_type,
+                               size_t type_len,
+                               const uint8_t *members,
+                               size_t member_count,
+                               Bytes result);
+
+// Computes the hash of a message of the given SNIP-12 revision signed by `account_address`, from
+// the domain separator and the struct hash of the message, writing it into the last argument
+int32_t typed_data_message_hash(uint32_t revision,
+                                Bytes domain_separator,
+                                Bytes account_address,
+                                Bytes struct_hash,
+                                Bytes result);
+
+// Computes the SNIP-9 message hash of an outside execution of an account on a chain, for the
+// given SNIP-12 revision, 0 for the outside executions of version 1 and 1 for those of version 2,
+// writing it into the last argument. The caller is zero when anyone may submit the execution. The
+// calls are `calls_len` contiguous felts: the contract address, the selector, the length of the
+// calldata and the calldata of every call
+int32_t outside_execution_hash(uint32_t revision,
+                               Bytes chain_id,
+                               Bytes account_address,
+                               Bytes caller,
+                               Bytes nonce,
+                               Bytes execute_after,
+                               Bytes execute_before,
+                               const uint8_t *calls,
+                               size_t calls_len,
+                               Bytes result);
+
+// Adds two uint256 modulo 2^256, writing the sum into the third argument and whether it overflowed
+// into the last one. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128
+int32_t uint256_add(const uint8_t *a, const uint8_t *b, uint8_t *result, bool *carry);
+
+// Multiplies two uint256, writing the low and the high 256 bits of the product into the last two
+// arguments. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128
+int32_t uint256_mul(const uint8_t *a, const uint8_t *b, uint8_t *low, uint8_t *high);
+
+// Divides the first uint256 by the second, writing the quotient and the remainder into the last
+// two arguments. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128 and
+// ERR_DIVISION_BY_ZERO for a zero divisor
+int32_t uint256_unsigned_div_rem(const uint8_t *a,
+                                 const uint8_t *div,
+                                 uint8_t *quotient,
+                                 uint8_t *remainder);
+
+// Computes the integer square root of a uint256, rounded down, writing it into the last argument
+// as a uint256. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128
+int32_t uint256_sqrt(const uint8_t *n, uint8_t *root);
+
+// Writes `count` test vectors of a primitive drawn from a seed into `vectors`, `buf_len` bytes
+// long. A vector of VECTORS_PEDERSEN or VECTORS_POSEIDON is two felts and their hash, of
+// VECTORS_POSEIDON_PERMUTE three felts and their permutation, and of VECTORS_PUBLIC_KEY a private
+// key and its public key. Returns ERR_INVALID_LENGTH when the vectors don't fit in the buffer
+int32_t starknet_crypto_test_vectors(uint32_t kind,
+                                     uint64_t seed,
+                                     size_t count,
+                                     uint8_t *vectors,
+                                     size_t buf_len);
+
+// Proves the evaluation of the VRF over the felt alpha with a private key, a felt nonzero modulo
+// the curve order, writing the 128 bytes proof (Gamma.x, Gamma.y, c, s) into the last argument.
+// The proofs are deterministic. Not constant time: the scalar multiplications branch on the secrets
+int32_t vrf_prove(Bytes private_key_bytes, Bytes alpha_bytes, uint8_t *proof);
+
+// Verifies a VRF proof of the felt alpha by a public key, writing the output beta of the function
+// into the last argument. Returns SUCCESS for a valid proof, ERR_INVALID_PROOF for an invalid one,
+// ERR_INVALID_POINT when the public key is not the x coordinate of a point and ERR_INVALID_FELT
+// when an argument or one of the felts of the proof is not a felt
+int32_t vrf_verify(Bytes public_key_bytes,
+                   Bytes alpha_bytes,
+                   const uint8_t *proof,
+                   Bytes beta_bytes);
+
+// Provided by the embedder when the library is built without the `std` cargo feature.
+// Returns a block of `size` bytes aligned to `align`, or NULL when out of memory
+extern uint8_t *starknet_crypto_alloc(size_t size, size_t align);
+
+// Provided by the embedder when the library is built without the `std` cargo feature.
+// Frees a block returned by `starknet_crypto_alloc` for the same `size` and `align`
+extern void starknet_crypto_dealloc(uint8_t *ptr, size_t size, size_t align);
+
+// Like poseidon_permute, with the length of each buffer after it, in elements of its type
+int32_t poseidon_permute_sized(Bytes first_state_felt,
+                               size_t first_state_felt_len,
+                               Bytes second_state_felt,
+                               size_t second_state_felt_len,
+                               Bytes third_state_felt,
+                               size_t third_state_felt_len);
+
+// Like poseidon_permute_state, with the length of each buffer after it, in elements of its type
+int32_t poseidon_permute_state_sized(uint8_t *state, size_t state_len);
+
+// Like poseidon_hash, with the length of each buffer after it, in elements of its type
+int32_t poseidon_hash_sized(Bytes x,
+                            size_t x_len,
+                            Bytes y,
+                            size_t y_len,
+                            Bytes result,
+                            size_t result_len);
+
+// Like poseidon_hash_single, with the length of each buffer after it, in elements of its type
+int32_t poseidon_hash_single_sized(Bytes x, size_t x_len, Bytes result, size_t result_len);
+
+// Like poseidon_hash_many, with the length of each buffer after it, in elements of its type
+int32_t poseidon_hash_many_sized(const uint8_t *felts,
+                                 size_t count,
+                                 Bytes result,
+                                 size_t result_len);
+
+// Like poseidon_hash_many_tagged, with the length of each buffer after it, in elements of its type
+int32_t poseidon_hash_many_tagged_sized(Bytes tag,
+                                        size_t tag_len,
+                                        const uint8_t *felts,
+                                        size_t count,
+                                        Bytes result,
+                                        size_t result_len);
+
+// Like pedersen_hash, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_sized(Bytes felt_1,
+                            size_t felt_1_len,
+                            Bytes felt_2,
+                            size_t felt_2_len,
+                            Bytes result,
+                            size_t result_len);
+
+// Like pedersen_hash_u64, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_u64_sized(uint64_t a, uint64_t b, Bytes result, size_t result_len);
+
+// Like pedersen_hash_felt_u64, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_felt_u64_sized(Bytes a,
+                                     size_t a_len,
+                                     uint64_t b,
+                                     Bytes result,
+                                     size_t result_len);
+
+// Like pedersen_hash_acc, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_acc_sized(Bytes acc, size_t acc_len, Bytes next, size_t next_len);
+
+// Like pedersen_hash_on_elements, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_on_elements_sized(const uint8_t *elements,
+                                        size_t count,
+                                        Bytes result,
+                                        size_t result_len);
+
+// Like verify_signature, with the length of each buffer after it, in elements of its type
+int32_t verify_signature_sized(Bytes public_key_bytes,
+                               size_t public_key_bytes_len,
+                               Bytes message_bytes,
+                               size_t message_bytes_len,
+                               Bytes r_bytes,
+                               size_t r_bytes_len,
+                               Bytes s_bytes,
+                               size_t s_bytes_len);
+
+// Like verify_signature_xonly, with the length of each buffer after it, in elements of its type
+int32_t verify_signature_xonly_sized(Bytes public_key_x,
+                                     size_t public_key_x_len,
+                                     Bytes message_bytes,
+                                     size_t message_bytes_len,
+                                     Bytes r_bytes,
+                                     size_t r_bytes_len,
+                                     Bytes s_bytes,
+                                     size_t s_bytes_len);
+
+// Like verify_signature_strict, with the length of each buffer after it, in elements of its type
+int32_t verify_signature_strict_sized(Bytes public_key_bytes,
+                                      size_t public_key_bytes_len,
+                                      Bytes message_bytes,
+                                      size_t message_bytes_len,
+                                      Bytes r_bytes,
+                                      size_t r_bytes_len,
+                                      Bytes s_bytes,
+                                      size_t s_bytes_len);
+
+// Like verify_signature_over_elements, with the length of each buffer after it, in elements of its type
+int32_t verify_signature_over_elements_sized(Bytes public_key_bytes,
+                                             size_t public_key_bytes_len,
+                                             const uint8_t *elements,
+                                             size_t count,
+                                             Bytes r_bytes,
+                                             size_t r_bytes_len,
+                                             Bytes s_bytes,
+                                             size_t s_bytes_len,
+                                             uint32_t hash_kind);
+
+// Like signature_to_compact, with the length of each buffer after it, in elements of its type
+int32_t signature_to_compact_sized(Bytes r_bytes,
+                                   size_t r_bytes_len,
+                                   Bytes s_bytes,
+                                   size_t s_bytes_len,
+                                   uint8_t *compact,
+                                   size_t compact_len);
+
+// Like signature_from_compact, with the length of each buffer after it, in elements of its type
+int32_t signature_from_compact_sized(const uint8_t *compact,
+                                     size_t compact_len,
+                                     Bytes r_bytes,
+                                     size_t r_bytes_len,
+                                     Bytes s_bytes,
+                                     size_t s_bytes_len);
+
+// Like get_public_key, with the length of each buffer after it, in elements of its type
+int32_t get_public_key_sized(Bytes private_key_bytes,
+                             size_t private_key_bytes_len,
+                             Bytes public_key_bytes,
+                             size_t public_key_bytes_len);
+
+// Like rfc6979_generate_k, with the length of each buffer after it, in elements of its type
+int32_t rfc6979_generate_k_sized(Bytes message_bytes,
+                                 size_t message_bytes_len,
+                                 Bytes private_key_bytes,
+                                 size_t private_key_bytes_len,
+                                 Bytes seed_bytes,
+                                 size_t seed_bytes_len,
+                                 Bytes k_bytes,
+                                 size_t k_bytes_len);
+
+// Like ecdsa_sign, with the length of each buffer after it, in elements of its type
+int32_t ecdsa_sign_sized(Bytes private_key_bytes,
+                         size_t private_key_bytes_len,
+                         Bytes message_bytes,
+                         size_t message_bytes_len,
+                         Bytes k_bytes,
+                         size_t k_bytes_len,
+                         Bytes r_bytes,
+                         size_t r_bytes_len,
+                         Bytes s_bytes,
+                         size_t s_bytes_len);
+
+// Like ecdsa_sign_seeded, with the length of each buffer after it, in elements of its type
+int32_t ecdsa_sign_seeded_sized(Bytes private_key_bytes,
+                                size_t private_key_bytes_len,
+                                Bytes message_bytes,
+                                size_t message_bytes_len,
+                                Bytes seed_bytes,
+                                size_t seed_bytes_len,
+                                Bytes r_bytes,
+                                size_t r_bytes_len,
+                                Bytes s_bytes,
+                                size_t s_bytes_len);
+
+// Like recover, with the length of each buffer after it, in elements of its type
+int32_t recover_sized(Bytes message_bytes,
+                      size_t message_bytes_len,
+                      Bytes r_bytes,
+                      size_t r_bytes_len,
+                      Bytes s_bytes,
+                      size_t s_bytes_len,
+                      Bytes v_bytes,
+                      size_t v_bytes_len,
+                      Bytes public_key_bytes,
+                      size_t public_key_bytes_len);
+
+// Like calculate_contract_address, with the length of each buffer after it, in elements of its type
+int32_t calculate_contract_address_sized(Bytes deployer_address,
+                                         size_t deployer_address_len,
+                                         Bytes salt,
+                                         size_t salt_len,
+                                         Bytes class_hash,
+                                         size_t class_hash_len,
+                                         const uint8_t *calldata,
+                                         size_t calldata_len,
+                                         Bytes result,
+                                         size_t result_len);
+
+// Like get_storage_var_address, with the length of each buffer after it, in elements of its type
+int32_t get_storage_var_address_sized(const uint8_t *name,
+                                      size_t name_len,
+                                      const uint8_t *keys,
+                                      size_t keys_len,
+                                      Bytes result,
+                                      size_t result_len);
+
+// Like ecdsa_sign_batch, with the length of each buffer after it, in elements of its type
+int32_t ecdsa_sign_batch_sized(Bytes private_key,
+                               size_t private_key_len,
+                               const uint8_t *message_hashes,
+                               size_t count,
+                               uint8_t *rs,
+                               uint8_t *ss);
+
+// Like bigint3_split, with the length of each buffer after it, in elements of its type
+int32_t bigint3_split_sized(const uint8_t *value,
+                            size_t value_len,
+                            uint8_t *limbs,
+                            size_t limbs_len);
+
+// Like bigint3_pack, with the length of each buffer after it, in elements of its type
+int32_t bigint3_pack_sized(const uint8_t *limbs,
+                           size_t limbs_len,
+                           const uint8_t *modulus,
+                           size_t modulus_len,
+                           Bytes result,
+                           size_t result_len);
+
+// Like bigint5_pack, with the length of each buffer after it, in elements of its type
+int32_t bigint5_pack_sized(const uint8_t *limbs,
+                           size_t limbs_len,
+                           const uint8_t *modulus,
+                           size_t modulus_len,
+                           Bytes result,
+                           size_t result_len);
+
+// Like blake2s_compress, with the length of each buffer after it, in elements of its type
+int32_t blake2s_compress_sized(uint32_t *state,
+                               size_t state_len,
+                               const uint32_t *block,
+                               size_t block_len,
+                               uint32_t t0,
+                               uint32_t t1,
+                               uint32_t f0,
+                               uint32_t f1);
+
+// Like blake2s, with the length of each buffer after it, in elements of its type
+int32_t blake2s_sized(const uint8_t *data, size_t len, uint8_t *result, size_t result_len);
+
+// Like blake2s_ctx_final, with the length of each buffer after it, in elements of its type
+int32_t blake2s_ctx_final_sized(struct Blake2sContext *ctx, uint8_t *result, size_t result_len);
+
+// Like block_hash, with the length of each buffer after it, in elements of its type
+int32_t block_hash_sized(uint64_t block_number,
+                         Bytes state_root,
+                         size_t state_root_len,
+                         Bytes sequencer_address,
+                         size_t sequencer_address_len,
+                         uint64_t timestamp,
+                         uint64_t transaction_count,
+                         uint64_t event_count,
+                         uint64_t state_diff_length,
+                         bool l1_da_mode_blob,
+                         Bytes state_diff_commitment,
+                         size_t state_diff_commitment_len,
+                         Bytes transaction_commitment,
+                         size_t transaction_commitment_len,
+                         Bytes event_commitment,
+                         size_t event_commitment_len,
+                         Bytes receipt_commitment,
+                         size_t receipt_commitment_len,
+                         const uint8_t *gas_prices,
+                         const uint8_t *protocol_version,
+                         size_t protocol_version_len,
+                         Bytes parent_block_hash,
+                         size_t parent_block_hash_len,
+                         Bytes result,
+                         size_t result_len);
+
+// Like bls12_381_add, with the length of each buffer after it, in elements of its type
+int32_t bls12_381_add_sized(const uint8_t *a,
+                            size_t a_len,
+                            const uint8_t *b,
+                            size_t b_len,
+                            uint8_t *result,
+                            size_t result_len);
+
+// Like bls12_381_mul, with the length of each buffer after it, in elements of its type
+int32_t bls12_381_mul_sized(const uint8_t *a,
+                            size_t a_len,
+                            const uint8_t *b,
+                            size_t b_len,
+                            uint8_t *result,
+                            size_t result_len);
+
+// Like bls12_381_div_mod, with the length of each buffer after it, in elements of its type
+int32_t bls12_381_div_mod_sized(const uint8_t *a,
+                                size_t a_len,
+                                const uint8_t *b,
+                                size_t b_len,
+                                uint8_t *result,
+                                size_t result_len);
+
+// Like poseidon_hash_bytes, with the length of each buffer after it, in elements of its type
+int32_t poseidon_hash_bytes_sized(const uint8_t *data, size_t len, Bytes result, size_t result_len);
+
+// Like byte_array_hash, with the length of each buffer after it, in elements of its type
+int32_t byte_array_hash_sized(const uint8_t *data,
+                              size_t data_len,
+                              Bytes pending_word,
+                              size_t pending_word_size,
+                              size_t pending_word_len,
+                              Bytes result,
+                              size_t result_len);
+
+// Like compiled_class_hash, with the length of each buffer after it, in elements of its type
+int32_t compiled_class_hash_sized(const uint8_t *version,
+                                  size_t version_len,
+                                  const uint8_t *external,
+                                  size_t external_len,
+                                  const uint8_t *l1_handler,
+                                  size_t l1_handler_len,
+                                  const uint8_t *constructor,
+                                  size_t constructor_len,
+                                  const uint8_t *bytecode,
+                                  size_t bytecode_len,
+                                  const uint64_t *segment_lengths,
+                                  size_t segment_count,
+                                  Bytes result,
+                                  size_t result_len);
+
+// Like pedersen_commit_generator, with the length of each buffer after it, in elements of its type
+int32_t pedersen_commit_generator_sized(Bytes x_bytes,
+                                        size_t x_bytes_len,
+                                        Bytes y_bytes,
+                                        size_t y_bytes_len);
+
+// Like pedersen_commit, with the length of each buffer after it, in elements of its type
+int32_t pedersen_commit_sized(Bytes value,
+                              size_t value_len,
+                              Bytes blinding,
+                              size_t blinding_len,
+                              Bytes x_bytes,
+                              size_t x_bytes_len,
+                              Bytes y_bytes,
+                              size_t y_bytes_len);
+
+// Like pedersen_commit_open, with the length of each buffer after it, in elements of its type
+int32_t pedersen_commit_open_sized(Bytes x_bytes,
+                                   size_t x_bytes_len,
+                                   Bytes y_bytes,
+                                   size_t y_bytes_len,
+                                   Bytes value,
+                                   size_t value_len,
+                                   Bytes blinding,
+                                   size_t blinding_len);
+
+// Like stark_curve_generator, with the length of each buffer after it, in elements of its type
+int32_t stark_curve_generator_sized(Bytes x_bytes,
+                                    size_t x_bytes_len,
+                                    Bytes y_bytes,
+                                    size_t y_bytes_len);
+
+// Like stark_curve_order, with the length of each buffer after it, in elements of its type
+int32_t stark_curve_order_sized(Bytes order_bytes, size_t order_bytes_len);
+
+// Like stark_curve_alpha, with the length of each buffer after it, in elements of its type
+int32_t stark_curve_alpha_sized(Bytes alpha_bytes, size_t alpha_bytes_len);
+
+// Like stark_curve_beta, with the length of each buffer after it, in elements of its type
+int32_t stark_curve_beta_sized(Bytes beta_bytes, size_t beta_bytes_len);
+
+// Like stark_point_validate, with the length of each buffer after it, in elements of its type
+int32_t stark_point_validate_sized(Bytes x_bytes,
+                                   size_t x_bytes_len,
+                                   Bytes y_bytes,
+                                   size_t y_bytes_len);
+
+// Like stark_pubkey_validate, with the length of each buffer after it, in elements of its type
+int32_t stark_pubkey_validate_sized(Bytes x_bytes, size_t x_bytes_len);
+
+// Like ec_add, with the length of each buffer after it, in elements of its type
+int32_t ec_add_sized(Bytes p_x_bytes,
+                     size_t p_x_bytes_len,
+                     Bytes p_y_bytes,
+                     size_t p_y_bytes_len,
+                     Bytes q_x_bytes,
+                     size_t q_x_bytes_len,
+                     Bytes q_y_bytes,
+                     size_t q_y_bytes_len,
+                     Bytes result_x_bytes,
+                     size_t result_x_bytes_len,
+                     Bytes result_y_bytes,
+                     size_t result_y_bytes_len);
+
+// Like ec_mul, with the length of each buffer after it, in elements of its type
+int32_t ec_mul_sized(Bytes scalar_bytes,
+                     size_t scalar_bytes_len,
+                     Bytes p_x_bytes,
+                     size_t p_x_bytes_len,
+                     Bytes p_y_bytes,
+                     size_t p_y_bytes_len,
+                     Bytes result_x_bytes,
+                     size_t result_x_bytes_len,
+                     Bytes result_y_bytes,
+                     size_t result_y_bytes_len);
+
+// Like ec_double, with the length of each buffer after it, in elements of its type
+int32_t ec_double_sized(Bytes p_x_bytes,
+                        size_t p_x_bytes_len,
+                        Bytes p_y_bytes,
+                        size_t p_y_bytes_len,
+                        Bytes result_x_bytes,
+                        size_t result_x_bytes_len,
+                        Bytes result_y_bytes,
+                        size_t result_y_bytes_len);
+
+// Like ec_negate, with the length of each buffer after it, in elements of its type
+int32_t ec_negate_sized(Bytes p_x_bytes,
+                        size_t p_x_bytes_len,
+                        Bytes p_y_bytes,
+                        size_t p_y_bytes_len,
+                        Bytes result_x_bytes,
+                        size_t result_x_bytes_len,
+                        Bytes result_y_bytes,
+                        size_t result_y_bytes_len);
+
+// Like ec_add_flagged, with the length of each buffer after it, in elements of its type
+int32_t ec_add_flagged_sized(Bytes p_x_bytes,
+                             size_t p_x_bytes_len,
+                             Bytes p_y_bytes,
+                             size_t p_y_bytes_len,
+                             bool p_infinity,
+                             Bytes q_x_bytes,
+                             size_t q_x_bytes_len,
+                             Bytes q_y_bytes,
+                             size_t q_y_bytes_len,
+                             bool q_infinity,
+                             Bytes result_x_bytes,
+                             size_t result_x_bytes_len,
+                             Bytes result_y_bytes,
+                             size_t result_y_bytes_len,
+                             bool *result_infinity);
+
+// Like ec_mul_flagged, with the length of each buffer after it, in elements of its type
+int32_t ec_mul_flagged_sized(Bytes scalar_bytes,
+                             size_t scalar_bytes_len,
+                             Bytes p_x_bytes,
+                             size_t p_x_bytes_len,
+                             Bytes p_y_bytes,
+                             size_t p_y_bytes_len,
+                             bool p_infinity,
+                             Bytes result_x_bytes,
+                             size_t result_x_bytes_len,
+                             Bytes result_y_bytes,
+                             size_t result_y_bytes_len,
+                             bool *result_infinity);
+
+// Like stark_msm, with the length of each buffer after it, in elements of its type
+int32_t stark_msm_sized(const uint8_t *scalars,
+                        const uint8_t *points,
+                        size_t count,
+                        Bytes result_x_bytes,
+                        size_t result_x_bytes_len,
+                        Bytes result_y_bytes,
+                        size_t result_y_bytes_len);
+
+// Like stark_ecdh, with the length of each buffer after it, in elements of its type
+int32_t stark_ecdh_sized(Bytes private_key_bytes,
+                         size_t private_key_bytes_len,
+                         Bytes peer_x_bytes,
+                         size_t peer_x_bytes_len,
+                         Bytes peer_y_bytes,
+                         size_t peer_y_bytes_len,
+                         Bytes shared_x_bytes,
+                         size_t shared_x_bytes_len);
+
+// Like ec_op, with the length of each buffer after it, in elements of its type
+int32_t ec_op_sized(Bytes p_x_bytes,
+                    size_t p_x_bytes_len,
+                    Bytes p_y_bytes,
+                    size_t p_y_bytes_len,
+                    Bytes q_x_bytes,
+                    size_t q_x_bytes_len,
+                    Bytes q_y_bytes,
+                    size_t q_y_bytes_len,
+                    Bytes m_bytes,
+                    size_t m_bytes_len,
+                    uint32_t height,
+                    Bytes result_x_bytes,
+                    size_t result_x_bytes_len,
+                    Bytes result_y_bytes,
+                    size_t result_y_bytes_len);
+
+// Like stark_hash_to_curve, with the length of each buffer after it, in elements of its type
+int32_t stark_hash_to_curve_sized(const uint8_t *msg,
+                                  size_t len,
+                                  const uint8_t *dst,
+                                  size_t dst_len,
+                                  Bytes x_bytes,
+                                  size_t x_bytes_len,
+                                  Bytes y_bytes,
+                                  size_t y_bytes_len);
+
+// Like ec_point_from_x, with the length of each buffer after it, in elements of its type
+int32_t ec_point_from_x_sized(Bytes x_bytes, size_t x_bytes_len, Bytes y_bytes, size_t y_bytes_len);
+
+// Like drbg_new, with the length of each buffer after it, in elements of its type
+int32_t drbg_new_sized(Bytes seed, size_t seed_len, struct Drbg **drbg);
+
+// Like drbg_next_felt, with the length of each buffer after it, in elements of its type
+int32_t drbg_next_felt_sized(struct Drbg *drbg, Bytes felt, size_t felt_len);
+
+// Like stark_encrypt, with the length of each buffer after it, in elements of its type
+int32_t stark_encrypt_sized(Bytes recipient_public_key,
+                            size_t recipient_public_key_len,
+                            const uint8_t *plaintext,
+                            size_t len,
+                            uint8_t *out,
+                            size_t out_len);
+
+// Like stark_decrypt, with the length of each buffer after it, in elements of its type
+int32_t stark_decrypt_sized(Bytes private_key_bytes,
+                            size_t private_key_bytes_len,
+                            const uint8_t *message,
+                            size_t len,
+                            uint8_t *out,
+                            size_t out_len);
+
+// Like felt_check_bytes, with the length of each buffer after it, in elements of its type
+int32_t felt_check_bytes_sized(Bytes bytes, size_t bytes_len);
+
+// Like felt_from_bytes_wide, with the length of each buffer after it, in elements of its type
+int32_t felt_from_bytes_wide_sized(const uint8_t *bytes,
+                                   size_t len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like felt_add, with the length of each buffer after it, in elements of its type
+int32_t felt_add_sized(Bytes a_bytes,
+                       size_t a_bytes_len,
+                       Bytes b_bytes,
+                       size_t b_bytes_len,
+                       Bytes result_bytes,
+                       size_t result_bytes_len);
+
+// Like felt_sub, with the length of each buffer after it, in elements of its type
+int32_t felt_sub_sized(Bytes a_bytes,
+                       size_t a_bytes_len,
+                       Bytes b_bytes,
+                       size_t b_bytes_len,
+                       Bytes result_bytes,
+                       size_t result_bytes_len);
+
+// Like felt_mul, with the length of each buffer after it, in elements of its type
+int32_t felt_mul_sized(Bytes a_bytes,
+                       size_t a_bytes_len,
+                       Bytes b_bytes,
+                       size_t b_bytes_len,
+                       Bytes result_bytes,
+                       size_t result_bytes_len);
+
+// Like felt_inverse, with the length of each buffer after it, in elements of its type
+int32_t felt_inverse_sized(Bytes a_bytes,
+                           size_t a_bytes_len,
+                           Bytes result_bytes,
+                           size_t result_bytes_len);
+
+// Like felt_pow, with the length of each buffer after it, in elements of its type
+int32_t felt_pow_sized(Bytes base_bytes,
+                       size_t base_bytes_len,
+                       Bytes exponent_bytes,
+                       size_t exponent_bytes_len,
+                       Bytes result_bytes,
+                       size_t result_bytes_len);
+
+// Like felt_sqrt, with the length of each buffer after it, in elements of its type
+int32_t felt_sqrt_sized(Bytes a_bytes,
+                        size_t a_bytes_len,
+                        Bytes result_bytes,
+                        size_t result_bytes_len);
+
+// Like felt_is_quadratic_residue, with the length of each buffer after it, in elements of its type
+int32_t felt_is_quadratic_residue_sized(Bytes a_bytes, size_t a_bytes_len, bool *result);
+
+// Like felt_split_128, with the length of each buffer after it, in elements of its type
+int32_t felt_split_128_sized(Bytes value_bytes,
+                             size_t value_bytes_len,
+                             Bytes low_bytes,
+                             size_t low_bytes_len,
+                             Bytes high_bytes,
+                             size_t high_bytes_len);
+
+// Like felt_as_signed, with the length of each buffer after it, in elements of its type
+int32_t felt_as_signed_sized(Bytes value_bytes,
+                             size_t value_bytes_len,
+                             Bytes abs_bytes,
+                             size_t abs_bytes_len,
+                             bool *negative);
+
+// Like felt_from_signed, with the length of each buffer after it, in elements of its type
+int32_t felt_from_signed_sized(Bytes abs_bytes,
+                               size_t abs_bytes_len,
+                               bool negative,
+                               Bytes result_bytes,
+                               size_t result_bytes_len);
+
+// Like felt_from_u64, with the length of each buffer after it, in elements of its type
+int32_t felt_from_u64_sized(uint64_t value, Bytes result_bytes, size_t result_bytes_len);
+
+// Like felt_from_u128, with the length of each buffer after it, in elements of its type
+int32_t felt_from_u128_sized(uint64_t high,
+                             uint64_t low,
+                             Bytes result_bytes,
+                             size_t result_bytes_len);
+
+// Like felt_to_u64_checked, with the length of each buffer after it, in elements of its type
+int32_t felt_to_u64_checked_sized(Bytes value_bytes, size_t value_bytes_len, uint64_t *result);
+
+// Like felt_to_u128_checked, with the length of each buffer after it, in elements of its type
+int32_t felt_to_u128_checked_sized(Bytes value_bytes,
+                                   size_t value_bytes_len,
+                                   uint64_t *high,
+                                   uint64_t *low);
+
+// Like felt_cmp, with the length of each buffer after it, in elements of its type
+int32_t felt_cmp_sized(Bytes a_bytes,
+                       size_t a_bytes_len,
+                       Bytes b_bytes,
+                       size_t b_bytes_len,
+                       int32_t *result);
+
+// Like felt_is_zero, with the length of each buffer after it, in elements of its type
+int32_t felt_is_zero_sized(Bytes a_bytes, size_t a_bytes_len, bool *result);
+
+// Like felt_ct_eq, with the length of each buffer after it, in elements of its type
+int32_t felt_ct_eq_sized(Bytes a_bytes,
+                         size_t a_bytes_len,
+                         Bytes b_bytes,
+                         size_t b_bytes_len,
+                         bool *result);
+
+// Like felt_new_from_bytes, with the length of each buffer after it, in elements of its type
+int32_t felt_new_from_bytes_sized(Bytes bytes, size_t bytes_len, struct FeltHandle **handle);
+
+// Like felt_read_bytes, with the length of each buffer after it, in elements of its type
+int32_t felt_read_bytes_sized(const struct FeltHandle *handle, Bytes bytes, size_t bytes_len);
+
+// Like keccak_f1600, with the length of each buffer after it, in elements of its type
+int32_t keccak_f1600_sized(uint64_t *state, size_t state_len);
+
+// Like keccak_u256_blocks, with the length of each buffer after it, in elements of its type
+int32_t keccak_u256_blocks_sized(const uint64_t *input,
+                                 size_t num_blocks,
+                                 uint64_t *output,
+                                 size_t output_len);
+
+// Like keccak256, with the length of each buffer after it, in elements of its type
+int32_t keccak256_sized(const uint8_t *data, size_t len, uint8_t *result, size_t result_len);
+
+// Like starknet_keccak, with the length of each buffer after it, in elements of its type
+int32_t starknet_keccak_sized(const uint8_t *data, size_t len, Bytes result, size_t result_len);
+
+// Like grind_key, with the length of each buffer after it, in elements of its type
+int32_t grind_key_sized(const uint8_t *seed,
+                        size_t seed_len,
+                        Bytes private_key,
+                        size_t private_key_len);
+
+// Like eip2645_derive, with the length of each buffer after it, in elements of its type
+int32_t eip2645_derive_sized(const uint8_t *seed,
+                             size_t seed_len,
+                             const char *layer,
+                             const char *application,
+                             const uint8_t *eth_address,
+                             size_t eth_address_len,
+                             uint32_t index,
+                             Bytes private_key,
+                             size_t private_key_len);
+
+// Like kzg_commit, with the length of each buffer after it, in elements of its type
+int32_t kzg_commit_sized(const uint8_t *blob,
+                         size_t blob_len,
+                         uint8_t *commitment,
+                         size_t commitment_len);
+
+// Like kzg_verify_proof, with the length of each buffer after it, in elements of its type
+int32_t kzg_verify_proof_sized(const uint8_t *commitment,
+                               size_t commitment_len,
+                               Bytes z,
+                               size_t z_len,
+                               Bytes y,
+                               size_t y_len,
+                               const uint8_t *proof,
+                               size_t proof_len);
+
+// Like bls_poly_eval, with the length of each buffer after it, in elements of its type
+int32_t bls_poly_eval_sized(const uint8_t *coefficients,
+                            size_t count,
+                            Bytes point,
+                            size_t point_len,
+                            Bytes result,
+                            size_t result_len);
+
+// Like poseidon_permute_le, with the length of each buffer after it, in elements of its type
+int32_t poseidon_permute_le_sized(Bytes first_state_felt,
+                                  size_t first_state_felt_len,
+                                  Bytes second_state_felt,
+                                  size_t second_state_felt_len,
+                                  Bytes third_state_felt,
+                                  size_t third_state_felt_len);
+
+// Like pedersen_hash_le, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_le_sized(Bytes felt_1,
+                               size_t felt_1_len,
+                               Bytes felt_2,
+                               size_t felt_2_len,
+                               Bytes result,
+                               size_t result_len);
+
+// Like verify_signature_le, with the length of each buffer after it, in elements of its type
+int32_t verify_signature_le_sized(Bytes public_key_bytes,
+                                  size_t public_key_bytes_len,
+                                  Bytes message_bytes,
+                                  size_t message_bytes_len,
+                                  Bytes r_bytes,
+                                  size_t r_bytes_len,
+                                  Bytes s_bytes,
+                                  size_t s_bytes_len);
+
+// Like pedersen_hash_limbs, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_limbs_sized(const uint64_t *a,
+                                  size_t a_len,
+                                  const uint64_t *b,
+                                  size_t b_len,
+                                  uint64_t *result,
+                                  size_t result_len);
+
+// Like poseidon_hash_limbs, with the length of each buffer after it, in elements of its type
+int32_t poseidon_hash_limbs_sized(const uint64_t *x,
+                                  size_t x_len,
+                                  const uint64_t *y,
+                                  size_t y_len,
+                                  uint64_t *result,
+                                  size_t result_len);
+
+// Like poseidon_permute_limbs, with the length of each buffer after it, in elements of its type
+int32_t poseidon_permute_limbs_sized(uint64_t *state, size_t state_len);
+
+// Like verify_signature_limbs, with the length of each buffer after it, in elements of its type
+int32_t verify_signature_limbs_sized(const uint64_t *public_key,
+                                     size_t public_key_len,
+                                     const uint64_t *message,
+                                     size_t message_len,
+                                     const uint64_t *r,
+                                     size_t r_len,
+                                     const uint64_t *s,
+                                     size_t s_len);
+
+// Like pedersen_merkle_root, with the length of each buffer after it, in elements of its type
+int32_t pedersen_merkle_root_sized(const uint8_t *leaves,
+                                   size_t count,
+                                   Bytes result,
+                                   size_t result_len);
+
+// Like poseidon_merkle_root, with the length of each buffer after it, in elements of its type
+int32_t poseidon_merkle_root_sized(const uint8_t *leaves,
+                                   size_t count,
+                                   Bytes result,
+                                   size_t result_len);
+
+// Like hash_memory_segment, with the length of each buffer after it, in elements of its type
+int32_t hash_memory_segment_sized(const uint8_t *pairs,
+                                  size_t count,
+                                  uint32_t hash_kind,
+                                  Bytes result,
+                                  size_t result_len);
+
+// Like merkle_verify, with the length of each buffer after it, in elements of its type
+int32_t merkle_verify_sized(Bytes root,
+                            size_t root_len,
+                            Bytes leaf,
+                            size_t leaf_len,
+                            uint64_t index,
+                            const uint8_t *siblings,
+                            size_t depth,
+                            uint32_t hash_kind);
+
+// Like merkle_multiproof_verify, with the length of each buffer after it, in elements of its type
+int32_t merkle_multiproof_verify_sized(Bytes root,
+                                       size_t root_len,
+                                       const uint64_t *indices,
+                                       const uint8_t *leaves,
+                                       size_t count,
+                                       const uint8_t *proof,
+                                       size_t proof_count,
+                                       size_t depth,
+                                       uint32_t hash_kind);
+
+// Like l1_to_l2_message_hash, with the length of each buffer after it, in elements of its type
+int32_t l1_to_l2_message_hash_sized(const uint8_t *from_address,
+                                    size_t from_address_len,
+                                    Bytes to_address,
+                                    size_t to_address_len,
+                                    Bytes selector,
+                                    size_t selector_len,
+                                    const uint8_t *payload,
+                                    size_t payload_len,
+                                    Bytes nonce,
+                                    size_t nonce_len,
+                                    uint8_t *result,
+                                    size_t result_len);
+
+// Like pedersen_hash_mont, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_mont_sized(const uint64_t *a,
+                                 size_t a_len,
+                                 const uint64_t *b,
+                                 size_t b_len,
+                                 uint64_t *result,
+                                 size_t result_len);
+
+// Like poseidon_permute_mont, with the length of each buffer after it, in elements of its type
+int32_t poseidon_permute_mont_sized(uint64_t *state, size_t state_len);
+
+// Like patricia_root, with the length of each buffer after it, in elements of its type
+int32_t patricia_root_sized(const uint8_t *entries, size_t count, Bytes result, size_t result_len);
+
+// Like patricia_update, with the length of each buffer after it, in elements of its type
+int32_t patricia_update_sized(Bytes prev_root,
+                              size_t prev_root_len,
+                              const uint8_t *updates,
+                              size_t count,
+                              Bytes result,
+                              size_t result_len);
+
+// Like verify_storage_proof, with the length of each buffer after it, in elements of its type
+int32_t verify_storage_proof_sized(Bytes state_root,
+                                   size_t state_root_len,
+                                   Bytes classes_root,
+                                   size_t classes_root_len,
+                                   Bytes contract_address,
+                                   size_t contract_address_len,
+                                   Bytes class_hash,
+                                   size_t class_hash_len,
+                                   Bytes nonce,
+                                   size_t nonce_len,
+                                   Bytes storage_root,
+                                   size_t storage_root_len,
+                                   Bytes storage_key,
+                                   size_t storage_key_len,
+                                   Bytes value,
+                                   size_t value_len,
+                                   const uint8_t *contract_proof,
+                                   size_t contract_proof_len,
+                                   const uint8_t *storage_proof,
+                                   size_t storage_proof_len);
+
+// Like contract_state_hash, with the length of each buffer after it, in elements of its type
+int32_t contract_state_hash_sized(Bytes class_hash,
+                                  size_t class_hash_len,
+                                  Bytes storage_root,
+                                  size_t storage_root_len,
+                                  Bytes nonce,
+                                  size_t nonce_len,
+                                  Bytes result,
+                                  size_t result_len);
+
+// Like global_state_root, with the length of each buffer after it, in elements of its type
+int32_t global_state_root_sized(Bytes contracts_root,
+                                size_t contracts_root_len,
+                                Bytes classes_root,
+                                size_t classes_root_len,
+                                Bytes result,
+                                size_t result_len);
+
+// Like pedersen_precompute_left, with the length of each buffer after it, in elements of its type
+int32_t pedersen_precompute_left_sized(Bytes a, size_t a_len, struct PedersenLeft **left);
+
+// Like pedersen_hash_with_left, with the length of each buffer after it, in elements of its type
+int32_t pedersen_hash_with_left_sized(const struct PedersenLeft *left,
+                                      Bytes b,
+                                      size_t b_len,
+                                      Bytes result,
+                                      size_t result_len);
+
+// Like compute_program_hash, with the length of each buffer after it, in elements of its type
+int32_t compute_program_hash_sized(const uint8_t *program_felts,
+                                   size_t count,
+                                   uint32_t version,
+                                   Bytes result,
+                                   size_t result_len);
+
+// Like public_memory_hash, with the length of each buffer after it, in elements of its type
+int32_t public_memory_hash_sized(const uint8_t *program,
+                                 size_t program_len,
+                                 uint64_t program_base,
+                                 const uint8_t *builtin_pointers,
+                                 size_t pointers_len,
+                                 uint64_t execution_base,
+                                 const uint8_t *output,
+                                 size_t output_len,
+                                 uint64_t output_base,
+                                 uint32_t hash_kind,
+                                 Bytes result,
+                                 size_t result_len);
+
+// Like program_fact, with the length of each buffer after it, in elements of its type
+int32_t program_fact_sized(Bytes program_hash,
+                           size_t program_hash_len,
+                           const uint8_t *output,
+                           size_t output_len,
+                           uint8_t *result,
+                           size_t result_len);
+
+// Like felt_random, with the length of each buffer after it, in elements of its type
+int32_t felt_random_sized(Bytes result, size_t result_len);
+
+// Like stark_private_key_random, with the length of each buffer after it, in elements of its type
+int32_t stark_private_key_random_sized(Bytes private_key, size_t private_key_len);
+
+// Like schnorr_sign, with the length of each buffer after it, in elements of its type
+int32_t schnorr_sign_sized(Bytes private_key_bytes,
+                           size_t private_key_bytes_len,
+                           Bytes message_bytes,
+                           size_t message_bytes_len,
+                           Bytes k_bytes,
+                           size_t k_bytes_len,
+                           Bytes r_bytes,
+                           size_t r_bytes_len,
+                           Bytes s_bytes,
+                           size_t s_bytes_len);
+
+// Like schnorr_verify, with the length of each buffer after it, in elements of its type
+int32_t schnorr_verify_sized(Bytes public_key_bytes,
+                             size_t public_key_bytes_len,
+                             Bytes message_bytes,
+                             size_t message_bytes_len,
+                             Bytes r_bytes,
+                             size_t r_bytes_len,
+                             Bytes s_bytes,
+                             size_t s_bytes_len);
+
+// Like secp256k1_add, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_add_sized(Bytes p_x_bytes,
+                            size_t p_x_bytes_len,
+                            Bytes p_y_bytes,
+                            size_t p_y_bytes_len,
+                            Bytes q_x_bytes,
+                            size_t q_x_bytes_len,
+                            Bytes q_y_bytes,
+                            size_t q_y_bytes_len,
+                            Bytes result_x_bytes,
+                            size_t result_x_bytes_len,
+                            Bytes result_y_bytes,
+                            size_t result_y_bytes_len);
+
+// Like secp256k1_mul, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_mul_sized(Bytes scalar_bytes,
+                            size_t scalar_bytes_len,
+                            Bytes p_x_bytes,
+                            size_t p_x_bytes_len,
+                            Bytes p_y_bytes,
+                            size_t p_y_bytes_len,
+                            Bytes result_x_bytes,
+                            size_t result_x_bytes_len,
+                            Bytes result_y_bytes,
+                            size_t result_y_bytes_len);
+
+// Like secp256k1_get_point_from_x, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_get_point_from_x_sized(Bytes x_bytes,
+                                         size_t x_bytes_len,
+                                         Bytes v_bytes,
+                                         size_t v_bytes_len,
+                                         Bytes y_bytes,
+                                         size_t y_bytes_len);
+
+// Like secp256k1_point_compress, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_point_compress_sized(Bytes x_bytes,
+                                       size_t x_bytes_len,
+                                       Bytes y_bytes,
+                                       size_t y_bytes_len,
+                                       uint8_t *compressed,
+                                       size_t compressed_len);
+
+// Like secp256k1_point_decompress, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_point_decompress_sized(const uint8_t *compressed,
+                                         size_t compressed_len,
+                                         Bytes x_bytes,
+                                         size_t x_bytes_len,
+                                         Bytes y_bytes,
+                                         size_t y_bytes_len);
+
+// Like secp256k1_scalar_add, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_scalar_add_sized(Bytes a_bytes,
+                                   size_t a_bytes_len,
+                                   Bytes b_bytes,
+                                   size_t b_bytes_len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like secp256k1_scalar_mul, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_scalar_mul_sized(Bytes a_bytes,
+                                   size_t a_bytes_len,
+                                   Bytes b_bytes,
+                                   size_t b_bytes_len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like secp256k1_scalar_inverse, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_scalar_inverse_sized(Bytes a_bytes,
+                                       size_t a_bytes_len,
+                                       Bytes result_bytes,
+                                       size_t result_bytes_len);
+
+// Like secp256k1_scalar_div, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_scalar_div_sized(Bytes a_bytes,
+                                   size_t a_bytes_len,
+                                   Bytes b_bytes,
+                                   size_t b_bytes_len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like secp256k1_recover_eth_address, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_recover_eth_address_sized(Bytes message_bytes,
+                                            size_t message_bytes_len,
+                                            Bytes r_bytes,
+                                            size_t r_bytes_len,
+                                            Bytes s_bytes,
+                                            size_t s_bytes_len,
+                                            uint32_t v,
+                                            Bytes address_bytes,
+                                            size_t address_bytes_len);
+
+// Like secp256k1_verify, with the length of each buffer after it, in elements of its type
+int32_t secp256k1_verify_sized(Bytes public_key_x_bytes,
+                               size_t public_key_x_bytes_len,
+                               Bytes public_key_y_bytes,
+                               size_t public_key_y_bytes_len,
+                               Bytes message_bytes,
+                               size_t message_bytes_len,
+                               Bytes r_bytes,
+                               size_t r_bytes_len,
+                               Bytes s_bytes,
+                               size_t s_bytes_len);
+
+// Like secp256r1_add, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_add_sized(Bytes p_x_bytes,
+                            size_t p_x_bytes_len,
+                            Bytes p_y_bytes,
+                            size_t p_y_bytes_len,
+                            Bytes q_x_bytes,
+                            size_t q_x_bytes_len,
+                            Bytes q_y_bytes,
+                            size_t q_y_bytes_len,
+                            Bytes result_x_bytes,
+                            size_t result_x_bytes_len,
+                            Bytes result_y_bytes,
+                            size_t result_y_bytes_len);
+
+// Like secp256r1_mul, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_mul_sized(Bytes scalar_bytes,
+                            size_t scalar_bytes_len,
+                            Bytes p_x_bytes,
+                            size_t p_x_bytes_len,
+                            Bytes p_y_bytes,
+                            size_t p_y_bytes_len,
+                            Bytes result_x_bytes,
+                            size_t result_x_bytes_len,
+                            Bytes result_y_bytes,
+                            size_t result_y_bytes_len);
+
+// Like secp256r1_get_point_from_x, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_get_point_from_x_sized(Bytes x_bytes,
+                                         size_t x_bytes_len,
+                                         Bytes v_bytes,
+                                         size_t v_bytes_len,
+                                         Bytes y_bytes,
+                                         size_t y_bytes_len);
+
+// Like secp256r1_point_compress, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_point_compress_sized(Bytes x_bytes,
+                                       size_t x_bytes_len,
+                                       Bytes y_bytes,
+                                       size_t y_bytes_len,
+                                       uint8_t *compressed,
+                                       size_t compressed_len);
+
+// Like secp256r1_point_decompress, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_point_decompress_sized(const uint8_t *compressed,
+                                         size_t compressed_len,
+                                         Bytes x_bytes,
+                                         size_t x_bytes_len,
+                                         Bytes y_bytes,
+                                         size_t y_bytes_len);
+
+// Like secp256r1_scalar_add, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_scalar_add_sized(Bytes a_bytes,
+                                   size_t a_bytes_len,
+                                   Bytes b_bytes,
+                                   size_t b_bytes_len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like secp256r1_scalar_mul, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_scalar_mul_sized(Bytes a_bytes,
+                                   size_t a_bytes_len,
+                                   Bytes b_bytes,
+                                   size_t b_bytes_len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like secp256r1_scalar_inverse, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_scalar_inverse_sized(Bytes a_bytes,
+                                       size_t a_bytes_len,
+                                       Bytes result_bytes,
+                                       size_t result_bytes_len);
+
+// Like secp256r1_scalar_div, with the length of each buffer after it, in elements of its type
+int32_t secp256r1_scalar_div_sized(Bytes a_bytes,
+                                   size_t a_bytes_len,
+                                   Bytes b_bytes,
+                                   size_t b_bytes_len,
+                                   Bytes result_bytes,
+                                   size_t result_bytes_len);
+
+// Like sha256_compress, with the length of each buffer after it, in elements of its type
+int32_t sha256_compress_sized(uint32_t *state,
+                              size_t state_len,
+                              const uint8_t *block,
+                              size_t block_len);
+
+// Like sha256, with the length of each buffer after it, in elements of its type
+int32_t sha256_sized(const uint8_t *data, size_t len, uint8_t *result, size_t result_len);
+
+// Like poseidon_ctx_absorb, with the length of each buffer after it, in elements of its type
+int32_t poseidon_ctx_absorb_sized(struct PoseidonContext *ctx, Bytes felt, size_t felt_len);
+
+// Like poseidon_ctx_squeeze, with the length of each buffer after it, in elements of its type
+int32_t poseidon_ctx_squeeze_sized(struct PoseidonContext *ctx, Bytes result, size_t result_len);
+
+// Like pedersen_chain_update, with the length of each buffer after it, in elements of its type
+int32_t pedersen_chain_update_sized(struct PedersenChain *ctx, Bytes felt, size_t felt_len);
+
+// Like pedersen_chain_finalize, with the length of each buffer after it, in elements of its type
+int32_t pedersen_chain_finalize_sized(struct PedersenChain *ctx, Bytes result, size_t result_len);
+
+// Like felt_from_hex, with the length of each buffer after it, in elements of its type
+int32_t felt_from_hex_sized(const uint8_t *string, size_t len, Bytes result, size_t result_len);
+
+// Like felt_to_hex, with the length of each buffer after it, in elements of its type
+int32_t felt_to_hex_sized(Bytes felt, size_t felt_len, uint8_t *buf, size_t buf_len);
+
+// Like felt_from_dec, with the length of each buffer after it, in elements of its type
+int32_t felt_from_dec_sized(const uint8_t *string, size_t len, Bytes result, size_t result_len);
+
+// Like felt_to_dec, with the length of each buffer after it, in elements of its type
+int32_t felt_to_dec_sized(Bytes felt, size_t felt_len, uint8_t *buf, size_t buf_len);
+
+// Like short_string_to_felt, with the length of each buffer after it, in elements of its type
+int32_t short_string_to_felt_sized(const uint8_t *string,
+                                   size_t len,
+                                   bool ascii_only,
+                                   Bytes result,
+                                   size_t result_len);
+
+// Like felt_to_short_string, with the length of each buffer after it, in elements of its type
+int32_t felt_to_short_string_sized(Bytes felt,
+                                   size_t felt_len,
+                                   uint8_t *buf,
+                                   size_t buf_len,
+                                   size_t *string_len);
+
+// Like invoke_v1_transaction_hash, with the length of each buffer after it, in elements of its type
+int32_t invoke_v1_transaction_hash_sized(Bytes sender_address,
+                                         size_t sender_address_len,
+                                         const uint8_t *calldata,
+                                         size_t calldata_len,
+                                         Bytes max_fee,
+                                         size_t max_fee_len,
+                                         Bytes chain_id,
+                                         size_t chain_id_len,
+                                         Bytes nonce,
+                                         size_t nonce_len,
+                                         Bytes result,
+                                         size_t result_len);
+
+// Like declare_v1_transaction_hash, with the length of each buffer after it, in elements of its type
+int32_t declare_v1_transaction_hash_sized(Bytes sender_address,
+                                          size_t sender_address_len,
+                                          Bytes class_hash,
+                                          size_t class_hash_len,
+                                          Bytes max_fee,
+                                          size_t max_fee_len,
+                                          Bytes chain_id,
+                                          size_t chain_id_len,
+                                          Bytes nonce,
+                                          size_t nonce_len,
+                                          Bytes result,
+                                          size_t result_len);
+
+// Like deploy_account_v1_transaction_hash, with the length of each buffer after it, in elements of its type
+int32_t deploy_account_v1_transaction_hash_sized(Bytes contract_address,
+                                                 size_t contract_address_len,
+                                                 Bytes class_hash,
+                                                 size_t class_hash_len,
+                                                 Bytes salt,
+                                                 size_t salt_len,
+                                                 const uint8_t *calldata,
+                                                 size_t calldata_len,
+                                                 Bytes max_fee,
+                                                 size_t max_fee_len,
+                                                 Bytes chain_id,
+                                                 size_t chain_id_len,
+                                                 Bytes nonce,
+                                                 size_t nonce_len,
+                                                 Bytes result,
+                                                 size_t result_len);
+
+// Like invoke_v3_transaction_hash, with the length of each buffer after it, in elements of its type
+int32_t invoke_v3_transaction_hash_sized(Bytes sender_address,
+                                         size_t sender_address_len,
+                                         Bytes tip,
+                                         size_t tip_len,
+                                         const uint8_t *resource_bounds,
+                                         size_t resource_bounds_len,
+                                         const uint8_t *paymaster_data,
+                                         size_t paymaster_data_len,
+                                         Bytes chain_id,
+                                         size_t chain_id_len,
+                                         Bytes nonce,
+                                         size_t nonce_len,
+                                         Bytes data_availability_modes,
+                                         size_t data_availability_modes_len,
+                                         const uint8_t *account_deployment_data,
+                                         size_t account_deployment_data_len,
+                                         const uint8_t *calldata,
+                                         size_t calldata_len,
+                                         Bytes result,
+                                         size_t result_len);
+
+// Like declare_v3_transaction_hash, with the length of each buffer after it, in elements of its type
+int32_t declare_v3_transaction_hash_sized(Bytes sender_address,
+                                          size_t sender_address_len,
+                                          Bytes tip,
+                                          size_t tip_len,
+                                          const uint8_t *resource_bounds,
+                                          size_t resource_bounds_len,
+                                          const uint8_t *paymaster_data,
+                                          size_t paymaster_data_len,
+                                          Bytes chain_id,
+                                          size_t chain_id_len,
+                                          Bytes nonce,
+                                          size_t nonce_len,
+                                          Bytes data_availability_modes,
+                                          size_t data_availability_modes_len,
+                                          const uint8_t *account_deployment_data,
+                                          size_t account_deployment_data_len,
+                                          Bytes class_hash,
+                                          size_t class_hash_len,
+                                          Bytes compiled_class_hash,
+                                          size_t compiled_class_hash_len,
+                                          Bytes result,
+                                          size_t result_len);
+
+// Like deploy_account_v3_transaction_hash, with the length of each buffer after it, in elements of its type
+int32_t deploy_account_v3_transaction_hash_sized(Bytes contract_address,
+                                                 size_t contract_address_len,
+                                                 Bytes tip,
+                                                 size_t tip_len,
+                                                 const uint8_t *resource_bounds,
+                                                 size_t resource_bounds_len,
+                                                 const uint8_t *paymaster_data,
+                                                 size_t paymaster_data_len,
+                                                 Bytes chain_id,
+                                                 size_t chain_id_len,
+                                                 Bytes nonce,
+                                                 size_t nonce_len,
+                                                 Bytes data_availability_modes,
+                                                 size_t data_availability_modes_len,
+                                                 const uint8_t *calldata,
+                                                 size_t calldata_len,
+                                                 Bytes class_hash,
+                                                 size_t class_hash_len,
+                                                 Bytes salt,
+                                                 size_t salt_len,
+                                                 Bytes result,
+                                                 size_t result_len);
+
+// Like calldata_hash, with the length of each buffer after it, in elements of its type
+int32_t calldata_hash_sized(const uint8_t *calldata,
+                            size_t calldata_len,
+                            uint32_t hash_kind,
+                            Bytes result,
+                            size_t result_len);
+
+// Like event_hash, with the length of each buffer after it, in elements of its type
+int32_t event_hash_sized(Bytes from_address,
+                         size_t from_address_len,
+                         Bytes transaction_hash,
+                         size_t transaction_hash_len,
+                         const uint8_t *keys,
+                         size_t keys_len,
+                         const uint8_t *data,
+                         size_t data_len,
+                         uint32_t hash_kind,
+                         Bytes result,
+                         size_t result_len);
+
+// Like transcript_new, with the length of each buffer after it, in elements of its type
+int32_t transcript_new_sized(Bytes domain_tag,
+                             size_t domain_tag_len,
+                             struct Transcript **transcript);
+
+// Like transcript_squeeze_felt, with the length of each buffer after it, in elements of its type
+int32_t transcript_squeeze_felt_sized(struct Transcript *transcript,
+                                      Bytes challenge,
+                                      size_t challenge_len);
+
+// Like typed_data_struct_hash, with the length of each buffer after it, in elements of its type
+int32_t typed_data_struct_hash_sized(uint32_t revision,
+                                     const uint8_t *encoded_type,
+                                     size_t type_len,
+                                     const uint8_t *members,
+                                     size_t member_count,
+                                     Bytes result,
+                                     size_t result_len);
+
+// Like typed_data_message_hash, with the length of each buffer after it, in elements of its type
+int32_t typed_data_message_hash_sized(uint32_t revision,
+                                      Bytes domain_separator,
+                                      size_t domain_separator_len,
+                                      Bytes account_address,
+                                      size_t account_address_len,
+                                      Bytes struct_hash,
+                                      size_t struct_hash_len,
+                                      Bytes result,
+                                      size_t result_len);
+
+// Like outside_execution_hash, with the length of each buffer after it, in elements of its type
+int32_t outside_execution_hash_sized(uint32_t revision,
+                                     Bytes chain_id,
+                                     size_t chain_id_len,
+                                     Bytes account_address,
+                                     size_t account_address_len,
+                                     Bytes caller,
+                                     size_t caller_len,
+                                     Bytes nonce,
+                                     size_t nonce_len,
+                                     Bytes execute_after,
+                                     size_t execute_after_len,
+                                     Bytes execute_before,
+                                     size_t execute_before_len,
+                                     const uint8_t *calls,
+                                     size_t calls_len,
+                                     Bytes result,
+                                     size_t result_len);
+
+// Like uint256_add, with the length of each buffer after it, in elements of its type
+int32_t uint256_add_sized(const uint8_t *a,
+                          size_t a_len,
+                          const uint8_t *b,
+                          size_t b_len,
+                          uint8_t *result,
+                          size_t result_len,
+                          bool *carry);
+
+// Like uint256_mul, with the length of each buffer after it, in elements of its type
+int32_t uint256_mul_sized(const uint8_t *a,
+                          size_t a_len,
+                          const uint8_t *b,
+                          size_t b_len,
+                          uint8_t *low,
+                          size_t low_len,
+                          uint8_t *high,
+                          size_t high_len);
+
+// Like uint256_unsigned_div_rem, with the length of each buffer after it, in elements of its type
+int32_t uint256_unsigned_div_rem_sized(const uint8_t *a,
+                                       size_t a_len,
+                                       const uint8_t *div,
+                                       size_t div_len,
+                                       uint8_t *quotient,
+                                       size_t quotient_len,
+                                       uint8_t *remainder,
+                                       size_t remainder_len);
+
+// Like uint256_sqrt, with the length of each buffer after it, in elements of its type
+int32_t uint256_sqrt_sized(const uint8_t *n, size_t n_len, uint8_t *root, size_t root_len);
+
+// Like vrf_prove, with the length of each buffer after it, in elements of its type
+int32_t vrf_prove_sized(Bytes private_key_bytes,
+                        size_t private_key_bytes_len,
+                        Bytes alpha_bytes,
+                        size_t alpha_bytes_len,
+                        uint8_t *proof,
+                        size_t proof_len);
 
-void pedersen_hash(felt_t, felt_t, felt_t);
+// Like vrf_verify, with the length of each buffer after it, in elements of its type
+int32_t vrf_verify_sized(Bytes public_key_bytes,
+                         size_t public_key_bytes_len,
+                         Bytes alpha_bytes,
+                         size_t alpha_bytes_len,
+                         const uint8_t *proof,
+                         size_t proof_len,
+                         Bytes beta_bytes,
+                         size_t beta_bytes_len);
 
-bool verify_signature(felt_t, felt_t, felt_t, felt_t);
\ No newline at end of file
+#endif /* STARKNET_CRYPTO_H */
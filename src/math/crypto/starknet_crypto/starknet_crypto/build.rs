@@ -0,0 +1,465 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use quote::ToTokens;
+
+// The version of the starknet-crypto dependency in the lock file, the one of the registry since
+// this crate has the same name.
+fn dependency_version(lock: &str) -> Option<&str> {
+    lock.split("[[package]]").find_map(|package| {
+        let field = |key: &str| {
+            package
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix(" = \""))
+                .map(|value| value.trim_end_matches('"'))
+        };
+        (field("name") == Some("starknet-crypto") && field("source").is_some())
+            .then(|| field("version"))
+            .flatten()
+    })
+}
+
+// The `Bytes` arguments that are not 32 bytes felts, and the other pointers to a fixed count of
+// elements of their type. The pointers to a single value, like the flags and the stats, and the
+// arrays sized by another argument have no length of their own.
+const BUFFER_LENGTHS: &[(&str, &str, usize)] = &[
+    ("bigint3_split", "value", 32),
+    ("bigint3_split", "limbs", 3 * 32),
+    ("bigint3_pack", "limbs", 3 * 32),
+    ("bigint3_pack", "modulus", 32),
+    ("bigint5_pack", "limbs", 5 * 32),
+    ("bigint5_pack", "modulus", 32),
+    ("blake2s_compress", "state", 8),
+    ("blake2s_compress", "block", 16),
+    ("blake2s", "result", 32),
+    ("blake2s_ctx_final", "result", 32),
+    ("bls12_381_add", "a", 48),
+    ("bls12_381_add", "b", 48),
+    ("bls12_381_add", "result", 48),
+    ("bls12_381_mul", "a", 48),
+    ("bls12_381_mul", "b", 48),
+    ("bls12_381_mul", "result", 48),
+    ("bls12_381_div_mod", "a", 48),
+    ("bls12_381_div_mod", "b", 48),
+    ("bls12_381_div_mod", "result", 48),
+    ("keccak_f1600", "state", 25),
+    ("keccak_u256_blocks", "output", 4),
+    ("keccak256", "result", 32),
+    ("eip2645_derive", "eth_address", 20),
+    ("kzg_commit", "blob", 32 * 4096),
+    ("kzg_commit", "commitment", 48),
+    ("kzg_verify_proof", "commitment", 48),
+    ("kzg_verify_proof", "proof", 48),
+    ("poseidon_permute_state", "state", 3 * 32),
+    ("signature_to_compact", "compact", 64),
+    ("signature_from_compact", "compact", 64),
+    ("pedersen_hash_limbs", "a", 4),
+    ("pedersen_hash_limbs", "b", 4),
+    ("pedersen_hash_limbs", "result", 4),
+    ("poseidon_hash_limbs", "x", 4),
+    ("poseidon_hash_limbs", "y", 4),
+    ("poseidon_hash_limbs", "result", 4),
+    ("poseidon_permute_limbs", "state", 3 * 4),
+    ("verify_signature_limbs", "public_key", 4),
+    ("verify_signature_limbs", "message", 4),
+    ("verify_signature_limbs", "r", 4),
+    ("verify_signature_limbs", "s", 4),
+    ("l1_to_l2_message_hash", "from_address", 20),
+    ("l1_to_l2_message_hash", "result", 32),
+    ("pedersen_hash_mont", "a", 4),
+    ("pedersen_hash_mont", "b", 4),
+    ("pedersen_hash_mont", "result", 4),
+    ("poseidon_permute_mont", "state", 3 * 4),
+    ("program_fact", "result", 32),
+    ("secp256k1_point_compress", "compressed", 33),
+    ("secp256k1_point_decompress", "compressed", 33),
+    ("secp256k1_recover_eth_address", "address_bytes", 20),
+    ("secp256r1_point_compress", "compressed", 33),
+    ("secp256r1_point_decompress", "compressed", 33),
+    ("sha256_compress", "state", 8),
+    ("sha256_compress", "block", 64),
+    ("sha256", "result", 32),
+    ("uint256_add", "a", 64),
+    ("uint256_add", "b", 64),
+    ("uint256_add", "result", 64),
+    ("uint256_mul", "a", 64),
+    ("uint256_mul", "b", 64),
+    ("uint256_mul", "low", 64),
+    ("uint256_mul", "high", 64),
+    ("uint256_unsigned_div_rem", "a", 64),
+    ("uint256_unsigned_div_rem", "div", 64),
+    ("uint256_unsigned_div_rem", "quotient", 64),
+    ("uint256_unsigned_div_rem", "remainder", 64),
+    ("uint256_sqrt", "n", 64),
+    ("uint256_sqrt", "root", 64),
+    ("vrf_prove", "proof", 4 * 32),
+    ("vrf_verify", "proof", 4 * 32),
+];
+
+// A `#[no_mangle] pub extern "C"` function of the crate, with the cfg attributes of its module and
+// its own.
+struct Export {
+    path: String,
+    cfgs: Vec<String>,
+    name: String,
+    arguments: Vec<(String, syn::Type)>,
+    returns_status: bool,
+}
+
+impl Export {
+    // The count of elements behind a buffer of fixed length, in bytes for the `Bytes` arguments.
+    fn buffer_length(&self, argument: &str) -> Option<usize> {
+        let length = BUFFER_LENGTHS
+            .iter()
+            .find(|&&(function, buffer, _)| function == self.name && buffer == argument);
+        match length {
+            Some(&(_, _, len)) => Some(len),
+            None => {
+                is_bytes(&self.arguments.iter().find(|(name, _)| name == argument)?.1).then_some(32)
+            }
+        }
+    }
+
+    fn has_buffers(&self) -> bool {
+        self.arguments
+            .iter()
+            .any(|(argument, _)| self.buffer_length(argument).is_some())
+    }
+
+    // The name of the length following a buffer in the `_sized` function.
+    fn length_name(&self, argument: &str) -> String {
+        let len = format!("{argument}_len");
+        if self.arguments.iter().any(|(other, _)| *other == len) {
+            return format!("{argument}_size");
+        }
+        len
+    }
+}
+
+// The exports, and the modules of the public types they name.
+struct Sources {
+    exports: Vec<Export>,
+    definitions: HashMap<String, String>,
+    // The function pointer types, passed as NULL by the fuzz targets
+    callbacks: HashSet<String>,
+    // The types behind the handles, released by a `_free` function
+    handles: HashSet<String>,
+}
+
+fn cfg_attributes(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .map(|attr| attr.to_token_stream().to_string())
+        .collect()
+}
+
+fn is_export(function: &syn::ItemFn) -> bool {
+    matches!(function.vis, syn::Visibility::Public(_))
+        && function
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("no_mangle"))
+        && function
+            .sig
+            .abi
+            .as_ref()
+            .and_then(|abi| abi.name.as_ref())
+            .is_some_and(|name| name.value() == "C")
+}
+
+fn type_name(ty: &syn::Type) -> String {
+    ty.to_token_stream().to_string()
+}
+
+fn is_bytes(ty: &syn::Type) -> bool {
+    type_name(ty) == "Bytes"
+}
+
+// Parses lib.rs and the files of its modules.
+fn read_sources(src: &Path) -> Sources {
+    let lib = syn::parse_file(&fs::read_to_string(src.join("lib.rs")).unwrap()).unwrap();
+    let mut modules = vec![("crate".to_string(), Vec::new(), lib.clone())];
+    for item in &lib.items {
+        if let syn::Item::Mod(module) = item {
+            if module.content.is_none() {
+                let name = module.ident.to_string();
+                let file = fs::read_to_string(src.join(format!("{name}.rs")))
+                    .or_else(|_| fs::read_to_string(src.join(name.clone()).join("mod.rs")))
+                    .unwrap();
+                let cfgs = cfg_attributes(&module.attrs);
+                modules.push((
+                    format!("crate::{name}"),
+                    cfgs,
+                    syn::parse_file(&file).unwrap(),
+                ));
+            }
+        }
+    }
+
+    let mut sources = Sources {
+        exports: Vec::new(),
+        definitions: HashMap::new(),
+        callbacks: HashSet::new(),
+        handles: HashSet::new(),
+    };
+    for (path, module_cfgs, file) in &modules {
+        for item in &file.items {
+            let (vis, ident) = match item {
+                syn::Item::Struct(item) => (&item.vis, &item.ident),
+                syn::Item::Enum(item) => (&item.vis, &item.ident),
+                syn::Item::Type(item) => {
+                    if type_name(&item.ty).starts_with("Option < extern \"C\" fn") {
+                        sources.callbacks.insert(item.ident.to_string());
+                    }
+                    (&item.vis, &item.ident)
+                }
+                syn::Item::Fn(function) if is_export(function) => {
+                    let arguments = function.sig.inputs.iter().map(|input| match input {
+                        syn::FnArg::Typed(typed) => {
+                            (typed.pat.to_token_stream().to_string(), (*typed.ty).clone())
+                        }
+                        syn::FnArg::Receiver(_) => unreachable!("an export takes no self"),
+                    });
+                    let cfgs = cfg_attributes(&function.attrs);
+                    sources.exports.push(Export {
+                        path: path.clone(),
+                        cfgs: module_cfgs.iter().cloned().chain(cfgs).collect(),
+                        name: function.sig.ident.to_string(),
+                        arguments: arguments.collect(),
+                        returns_status: function.sig.output.to_token_stream().to_string()
+                            == "-> i32",
+                    });
+                    continue;
+                }
+                _ => continue,
+            };
+            if matches!(vis, syn::Visibility::Public(_)) {
+                sources.definitions.insert(ident.to_string(), path.clone());
+            }
+        }
+    }
+    for export in &sources.exports {
+        if let (true, [(_, syn::Type::Ptr(pointer))]) =
+            (export.name.ends_with("_free"), export.arguments.as_slice())
+        {
+            sources.handles.insert(type_name(&pointer.elem));
+        }
+    }
+    for &(function, buffer, _) in BUFFER_LENGTHS {
+        let argument = sources
+            .exports
+            .iter()
+            .find(|export| export.name == function)
+            .and_then(|export| export.arguments.iter().find(|(name, _)| name == buffer));
+        assert!(
+            matches!(argument, Some((_, syn::Type::Ptr(_))))
+                || argument.is_some_and(|(_, ty)| is_bytes(ty)),
+            "the length of {function}'s `{buffer}` is not the length of a pointer argument"
+        );
+    }
+    sources
+}
+
+// Qualifies the types named by the signatures of the exports with the module defining them.
+fn qualify(ty: &mut syn::Type, definitions: &HashMap<String, String>) {
+    match ty {
+        syn::Type::Ptr(pointer) => qualify(&mut pointer.elem, definitions),
+        syn::Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            let name = path.path.segments[0].ident.to_string();
+            let module = match name.as_str() {
+                "c_char" => "core::ffi",
+                name => match definitions.get(name) {
+                    Some(module) => module,
+                    None => return,
+                },
+            };
+            *path = syn::parse_str(&format!("{module}::{name}")).unwrap();
+        }
+        _ => {}
+    }
+}
+
+fn write_cfgs(code: &mut String, export: &Export, indent: &str) {
+    for cfg in &export.cfgs {
+        writeln!(code, "{indent}{cfg}").unwrap();
+    }
+}
+
+// Generates the `_sized` counterpart of every export taking a buffer of fixed length, with the
+// length of each such buffer after it, into the file included by src/sized.rs.
+fn write_sized_functions(sources: &Sources, out: &Path) {
+    let mut code = String::new();
+    for export in &sources.exports {
+        if !export.has_buffers() {
+            continue;
+        }
+        let (mut parameters, mut checks, mut forwarded) = (Vec::new(), Vec::new(), Vec::new());
+        for (argument, ty) in &export.arguments {
+            let mut qualified = ty.clone();
+            qualify(&mut qualified, &sources.definitions);
+            parameters.push(format!("{argument}: {}", qualified.to_token_stream()));
+            forwarded.push(argument.clone());
+            if let Some(expected) = export.buffer_length(argument) {
+                let len = export.length_name(argument);
+                parameters.push(format!("{len}: usize"));
+                checks.push(format!("({len}, {expected})"));
+            }
+        }
+        write_cfgs(&mut code, export, "");
+        writeln!(
+            code,
+            "/// Like {name}, with the length of each buffer after it, in elements of its type\n\
+             #[no_mangle]\n\
+             #[allow(clippy::too_many_arguments)]\n\
+             pub extern \"C\" fn {name}_sized({}) -> i32 {{\n    \
+             crate::sized::with_lengths(&[{}], || {}::{name}({}))\n}}\n",
+            parameters.join(", "),
+            checks.join(", "),
+            export.path,
+            forwarded.join(", "),
+            name = export.name,
+        )
+        .unwrap();
+    }
+    fs::write(out, code).unwrap();
+}
+
+// The expression of src/fuzzing.rs drawing an argument of type `ty` of the export from `args`.
+fn fuzzing_argument(sources: &Sources, export: &Export, argument: &str, ty: &syn::Type) -> String {
+    if let (Some(len), true) = (export.buffer_length(argument), is_bytes(ty)) {
+        return format!("args.bytes({len})");
+    }
+    match ty {
+        syn::Type::Ptr(pointer) => match &*pointer.elem {
+            syn::Type::Ptr(inner) if sources.handles.contains(&type_name(&inner.elem)) => {
+                "args.handle_slot()".into()
+            }
+            syn::Type::Ptr(_) => "args.pointers().cast()".into(),
+            elem if sources.handles.contains(&type_name(elem)) => {
+                if export.name.ends_with("_free") {
+                    "args.free_handle()".into()
+                } else {
+                    "args.handle()".into()
+                }
+            }
+            _ => "args.array()".into(),
+        },
+        _ => match type_name(ty).as_str() {
+            "usize" => "args.length()".into(),
+            "u64" => "args.integer()".into(),
+            name @ ("u8" | "u16" | "u32" | "i32") => format!("args.integer() as {name}"),
+            "bool" => "args.integer() % 2 == 1".into(),
+            name if sources.callbacks.contains(name) => "None".into(),
+            name => panic!(
+                "no fuzzing argument for `{argument}: {name}` of {}",
+                export.name
+            ),
+        },
+    }
+}
+
+// Generates the table of every export and `_sized` function, called with the arguments of the
+// fuzz targets, into the file included by src/fuzzing.rs.
+fn write_fuzzing_table(sources: &Sources, out: &Path) {
+    let mut code = String::from(
+        "/// Every exported function, the `_sized` ones included, in the order of the sources\n\
+         pub fn exports<A: Arguments>() -> Vec<Export<A>> {\n    let mut exports = Vec::new();\n",
+    );
+    let mut push = |export: &Export, name: &str, statements: &str, call: String| {
+        write_cfgs(&mut code, export, "    ");
+        let body = if export.returns_status {
+            format!("{statements}Some({call})")
+        } else {
+            format!("{statements}{call};\n            None")
+        };
+        let args = if body.contains("args.") {
+            "args: &mut A"
+        } else {
+            "_"
+        };
+        writeln!(
+            code,
+            "    exports.push(Export {{\n        name: \"{name}\",\n        \
+             call: |{args}| {{\n            {body}\n        }},\n    }});"
+        )
+        .unwrap();
+    };
+    for export in &sources.exports {
+        let arguments: Vec<String> = export
+            .arguments
+            .iter()
+            .map(|(argument, ty)| fuzzing_argument(sources, export, argument, ty))
+            .collect();
+        let call = format!("{}::{}({})", export.path, export.name, arguments.join(", "));
+        push(export, &export.name, "", call);
+    }
+    for export in &sources.exports {
+        if !export.has_buffers() {
+            continue;
+        }
+        let (mut statements, mut arguments) = (String::new(), Vec::new());
+        for (argument, ty) in &export.arguments {
+            match export.buffer_length(argument) {
+                Some(len) => {
+                    let draw = if is_bytes(ty) {
+                        "sized_bytes"
+                    } else {
+                        "sized_array"
+                    };
+                    write!(
+                        statements,
+                        "let {argument} = args.{draw}({len});\n            "
+                    )
+                    .unwrap();
+                    arguments.push(format!("{argument}.0, {argument}.1"));
+                }
+                None => arguments.push(fuzzing_argument(sources, export, argument, ty)),
+            }
+        }
+        let name = format!("{}_sized", export.name);
+        let call = format!("crate::sized::{name}({})", arguments.join(", "));
+        push(export, &name, &statements, call);
+    }
+    code.push_str("    exports\n}\n");
+    fs::write(out, code).unwrap();
+}
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    // Reported by starknet_crypto_build_info. Without the lock file next to the manifest, as a
+    // dependency of another workspace, the version is unknown
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = fs::read_to_string(crate_dir.join("Cargo.lock")).unwrap_or_default();
+    println!(
+        "cargo:rustc-env=STARKNET_CRYPTO_RS_VERSION={}",
+        dependency_version(&lock).unwrap_or("unknown")
+    );
+
+    println!("cargo:rerun-if-changed=src");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let sources = read_sources(&crate_dir.join("src"));
+    let sized = out_dir.join("sized.rs");
+    write_sized_functions(&sources, &sized);
+    write_fuzzing_table(&sources, &out_dir.join("fuzzing.rs"));
+
+    // The header lives next to the staticlib the Makefile copies out of the target directory
+    let header = crate_dir.join("../starknet_crypto.h");
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    // Parse the sources rather than the package, since the starknet-crypto dependency shares its
+    // name. The generated `_sized` functions are not reachable from lib.rs through a `mod`
+    cbindgen::Builder::new()
+        .with_src(crate_dir.join("src/lib.rs"))
+        .with_src(sized)
+        .with_config(cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap())
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(header);
+}
@@ -0,0 +1,685 @@
+// The bodies of the fuzz targets, over the exported functions as the Zig VM calls them. Run with
+// `cargo fuzz run <felt|hashes|signatures|curve|exports>` from the crate directory.
+//
+// The inputs are decoded into buffers, fuzzed or one of the boundary values around the STARK prime,
+// so the canonical limit is hit more often than by chance, and sometimes shorter than a felt. The
+// targets call the `_sized` functions with the length of each buffer, and every buffer is its own
+// allocation of that length, so that `cargo miri test` catches an out of bounds access of the
+// library over the boundary inputs of the tests. The targets check that no call panics, that a
+// buffer of the wrong length is rejected without being written, and that the statuses agree with
+// the canonicity of the inputs and with each other.
+//
+// The `exports` target goes through the table of the `fuzzing` feature instead, generated from the
+// sources of the library, to call every exported function and `_sized` variant.
+use std::any::TypeId;
+
+use starknet_crypto::{
+    fuzzing::{self, Arguments, Export},
+    sized::{
+        ec_add_sized, ec_mul_sized, ec_point_from_x_sized, felt_add_sized, felt_check_bytes_sized,
+        felt_inverse_sized, felt_mul_sized, felt_new_from_bytes_sized, felt_pow_sized,
+        felt_read_bytes_sized, felt_sqrt_sized, felt_sub_sized, get_public_key_sized,
+        pedersen_hash_on_elements_sized, pedersen_hash_sized, poseidon_hash_many_sized,
+        poseidon_hash_single_sized, poseidon_hash_sized, poseidon_permute_sized, recover_sized,
+        signature_from_compact_sized, signature_to_compact_sized, stark_point_validate_sized,
+        stark_pubkey_validate_sized, verify_signature_sized, verify_signature_strict_sized,
+    },
+    Bytes, ERR_BAD_LENGTH, ERR_INVALID_FELT, ERR_PANIC, SUCCESS,
+};
+
+// The handles are freed by a function without buffers, so without a `_sized` counterpart.
+pub enum FeltHandle {}
+
+extern "C" {
+    fn felt_free(handle: *mut FeltHandle) -> i32;
+}
+
+// The STARK prime 2^251 + 17 * 2^192 + 1 in big endian.
+const PRIME: [u8; 32] = {
+    let mut prime = [0u8; 32];
+    prime[0] = 0x08;
+    prime[7] = 0x11;
+    prime[31] = 0x01;
+    prime
+};
+
+type Buffer = Box<[u8]>;
+
+fn boundary(kind: u8) -> [u8; 32] {
+    let mut felt = PRIME;
+    match kind % 7 {
+        0 => felt = [0; 32],
+        1 => felt = [0xff; 32],
+        2 => felt[31] = 0,
+        3 => felt[31] = 2,
+        4 => {}
+        5 => {
+            felt = [0; 32];
+            felt[31] = 1;
+        }
+        _ => {
+            felt = [0; 32];
+            felt[0] = 0x08;
+        }
+    }
+    felt
+}
+
+fn zeroed() -> Buffer {
+    Box::new([0; 32])
+}
+
+// Reads the fuzzed buffers: a selector byte below 32 picks a boundary value, one below 40 the next
+// 24 to 31 bytes, otherwise the next 32 bytes, padded with zeroes, are the felt.
+pub struct Input<'a>(&'a [u8]);
+
+impl Input<'_> {
+    fn byte(&mut self) -> u8 {
+        let Some((&byte, rest)) = self.0.split_first() else {
+            return 0;
+        };
+        self.0 = rest;
+        byte
+    }
+
+    fn felt(&mut self) -> Buffer {
+        let Some((&selector, rest)) = self.0.split_first() else {
+            return zeroed();
+        };
+        if selector < 32 {
+            self.0 = rest;
+            return Box::new(boundary(selector));
+        }
+        let size = if selector < 40 {
+            selector as usize - 8
+        } else {
+            32
+        };
+        let len = rest.len().min(size);
+        let mut felt = vec![0u8; size];
+        felt[..len].copy_from_slice(&rest[..len]);
+        self.0 = &rest[len..];
+        felt.into()
+    }
+
+    fn felts(&mut self, count: usize) -> Vec<u8> {
+        let mut felts = Vec::with_capacity(count * 32);
+        for _ in 0..count {
+            let felt = self.felt();
+            felts.extend_from_slice(&felt);
+            felts.resize(felts.len().next_multiple_of(32), 0);
+        }
+        felts
+    }
+}
+
+fn is_felt(bytes: &[u8]) -> bool {
+    bytes < &PRIME[..]
+}
+
+#[track_caller]
+fn check(status: i32) -> i32 {
+    assert_ne!(status, ERR_PANIC, "the call panicked");
+    status
+}
+
+// The status of a `_sized` call: ERR_BAD_LENGTH, with the outputs still zeroed, when one of the
+// buffers isn't 32 bytes long, otherwise that of the function it wraps.
+#[track_caller]
+fn sized(status: i32, inputs: &[&[u8]], outputs: &[&[u8]]) -> Option<i32> {
+    let status = check(status);
+    if inputs
+        .iter()
+        .chain(outputs)
+        .all(|buffer| buffer.len() == 32)
+    {
+        return Some(status);
+    }
+    assert_eq!(status, ERR_BAD_LENGTH);
+    assert!(outputs
+        .iter()
+        .all(|output| output.iter().all(|&byte| byte == 0)));
+    None
+}
+
+// The status of a function of felts: SUCCESS exactly when all of them are felts.
+#[track_caller]
+fn check_felts(status: Option<i32>, felts: &[&[u8]]) {
+    let Some(status) = status else {
+        return;
+    };
+    let expected = if felts.iter().all(|felt| is_felt(felt)) {
+        SUCCESS
+    } else {
+        ERR_INVALID_FELT
+    };
+    assert_eq!(status, expected);
+}
+
+pub fn felt(data: &[u8]) {
+    let mut input = Input(data);
+    let (mut a, mut b, mut result) = (input.felt(), input.felt(), input.felt());
+    result.fill(0);
+    let status = felt_check_bytes_sized(a.as_mut_ptr(), a.len());
+    check_felts(sized(status, &[&a], &[]), &[&a]);
+    for op in [felt_add_sized, felt_sub_sized, felt_mul_sized] {
+        let status = op(
+            a.as_mut_ptr(),
+            a.len(),
+            b.as_mut_ptr(),
+            b.len(),
+            result.as_mut_ptr(),
+            result.len(),
+        );
+        check_felts(sized(status, &[&a, &b], &[&result]), &[&a, &b]);
+        result.fill(0);
+    }
+    let status = felt_inverse_sized(a.as_mut_ptr(), a.len(), result.as_mut_ptr(), result.len());
+    sized(status, &[&a], &[&result]);
+    result.fill(0);
+    let status = felt_pow_sized(
+        a.as_mut_ptr(),
+        a.len(),
+        b.as_mut_ptr(),
+        b.len(),
+        result.as_mut_ptr(),
+        result.len(),
+    );
+    sized(status, &[&a, &b], &[&result]);
+    result.fill(0);
+    let status = felt_sqrt_sized(a.as_mut_ptr(), a.len(), result.as_mut_ptr(), result.len());
+    if sized(status, &[&a], &[&result]) == Some(SUCCESS) {
+        let mut square = zeroed();
+        let root = result.as_mut_ptr();
+        assert_eq!(
+            felt_mul_sized(root, 32, root, 32, square.as_mut_ptr(), 32),
+            SUCCESS
+        );
+        assert_eq!(square, a);
+    }
+    result.fill(0);
+
+    // The bytes of a felt come back unchanged, whether through a handle or an addition of zero
+    let mut handle = core::ptr::null_mut();
+    let status = felt_new_from_bytes_sized(a.as_mut_ptr(), a.len(), &mut handle);
+    check_felts(sized(status, &[&a], &[]), &[&a]);
+    if !handle.is_null() {
+        let status = felt_read_bytes_sized(handle, result.as_mut_ptr(), result.len());
+        if sized(status, &[], &[&result]).is_some() {
+            assert_eq!(status, SUCCESS);
+            assert_eq!(result, a);
+        }
+        assert_eq!(unsafe { felt_free(handle.cast()) }, SUCCESS);
+
+        let (mut zero, mut sum) = (zeroed(), zeroed());
+        assert_eq!(
+            felt_add_sized(
+                a.as_mut_ptr(),
+                32,
+                zero.as_mut_ptr(),
+                32,
+                sum.as_mut_ptr(),
+                32
+            ),
+            SUCCESS
+        );
+        assert_eq!(sum, a);
+    }
+}
+
+pub fn hashes(data: &[u8]) {
+    let mut input = Input(data);
+    let (mut a, mut b, mut c) = (input.felt(), input.felt(), input.felt());
+    let mut result = input.felt();
+    result.fill(0);
+    let inputs = [a.clone(), b.clone(), c.clone()];
+    let pair: [&[u8]; 2] = [&inputs[0], &inputs[1]];
+    for hash in [pedersen_hash_sized, poseidon_hash_sized] {
+        let status = hash(
+            a.as_mut_ptr(),
+            a.len(),
+            b.as_mut_ptr(),
+            b.len(),
+            result.as_mut_ptr(),
+            result.len(),
+        );
+        check_felts(sized(status, &pair, &[&result]), &pair);
+        result.fill(0);
+    }
+    let status =
+        poseidon_hash_single_sized(a.as_mut_ptr(), a.len(), result.as_mut_ptr(), result.len());
+    check_felts(sized(status, &pair[..1], &[&result]), &pair[..1]);
+    result.fill(0);
+    // The state is permuted in place, so only a rejected length leaves the inputs as they were
+    let status = poseidon_permute_sized(
+        a.as_mut_ptr(),
+        a.len(),
+        b.as_mut_ptr(),
+        b.len(),
+        c.as_mut_ptr(),
+        c.len(),
+    );
+    let state: [&[u8]; 3] = [&inputs[0], &inputs[1], &inputs[2]];
+    check_felts(sized(status, &state, &[]), &state);
+
+    let count = data.first().map_or(0, |&byte| byte as usize % 8);
+    let felts = input.felts(count);
+    let chunks: Vec<&[u8]> = felts.chunks(32).collect();
+    for hash in [poseidon_hash_many_sized, pedersen_hash_on_elements_sized] {
+        let status = hash(felts.as_ptr(), count, result.as_mut_ptr(), result.len());
+        check_felts(sized(status, &[], &[&result]), &chunks);
+        result.fill(0);
+    }
+}
+
+pub fn signatures(data: &[u8]) {
+    let mut input = Input(data);
+    let (mut public_key, mut message) = (input.felt(), input.felt());
+    let (mut r, mut s, mut v) = (input.felt(), input.felt(), input.felt());
+    let mut result = input.felt();
+    result.fill(0);
+
+    let mut verify = |strict: bool| {
+        let verify = if strict {
+            verify_signature_strict_sized
+        } else {
+            verify_signature_sized
+        };
+        let status = verify(
+            public_key.as_mut_ptr(),
+            public_key.len(),
+            message.as_mut_ptr(),
+            message.len(),
+            r.as_mut_ptr(),
+            r.len(),
+            s.as_mut_ptr(),
+            s.len(),
+        );
+        sized(status, &[&public_key, &message, &r, &s], &[])
+    };
+    // A signature passing the strict checks passes the plain ones
+    if verify(true) == Some(SUCCESS) {
+        assert_eq!(verify(false), Some(SUCCESS));
+    } else {
+        verify(false);
+    }
+    let status = recover_sized(
+        message.as_mut_ptr(),
+        message.len(),
+        r.as_mut_ptr(),
+        r.len(),
+        s.as_mut_ptr(),
+        s.len(),
+        v.as_mut_ptr(),
+        v.len(),
+        result.as_mut_ptr(),
+        result.len(),
+    );
+    sized(status, &[&message, &r, &s, &v], &[&result]);
+    result.fill(0);
+    let status = get_public_key_sized(
+        public_key.as_mut_ptr(),
+        public_key.len(),
+        result.as_mut_ptr(),
+        result.len(),
+    );
+    check_felts(sized(status, &[&public_key], &[&result]), &[&public_key]);
+
+    // The compact encoding round trips
+    let mut compact = Box::new([0u8; 64]);
+    let status = signature_to_compact_sized(
+        r.as_mut_ptr(),
+        r.len(),
+        s.as_mut_ptr(),
+        s.len(),
+        compact.as_mut_ptr(),
+        compact.len(),
+    );
+    if sized(status, &[&r, &s], &[]) == Some(SUCCESS) {
+        let (mut decoded_r, mut decoded_s) = (zeroed(), zeroed());
+        assert_eq!(
+            signature_from_compact_sized(
+                compact.as_ptr(),
+                compact.len(),
+                decoded_r.as_mut_ptr(),
+                32,
+                decoded_s.as_mut_ptr(),
+                32
+            ),
+            SUCCESS
+        );
+        assert_eq!((decoded_r, decoded_s), (r, s));
+    }
+}
+
+pub fn curve(data: &[u8]) {
+    let mut input = Input(data);
+    let (mut p_x, mut q_x, mut scalar) = (input.felt(), input.felt(), input.felt());
+    let (mut p_y, mut q_y) = (zeroed(), zeroed());
+    let (mut x, mut y) = (zeroed(), zeroed());
+    let status = stark_point_validate_sized(
+        p_x.as_mut_ptr(),
+        p_x.len(),
+        scalar.as_mut_ptr(),
+        scalar.len(),
+    );
+    sized(status, &[&p_x, &scalar], &[]);
+    let status = ec_point_from_x_sized(p_x.as_mut_ptr(), p_x.len(), p_y.as_mut_ptr(), 32);
+    let p_status = sized(status, &[&p_x], &[&p_y]);
+    let status = ec_point_from_x_sized(q_x.as_mut_ptr(), q_x.len(), q_y.as_mut_ptr(), 32);
+    let q_status = sized(status, &[&q_x], &[&q_y]);
+    // The x coordinates of the points are exactly the valid public keys
+    let status = stark_pubkey_validate_sized(p_x.as_mut_ptr(), p_x.len());
+    assert_eq!(
+        sized(status, &[&p_x], &[]) == Some(SUCCESS),
+        p_status == Some(SUCCESS)
+    );
+    if p_status != Some(SUCCESS) {
+        return;
+    }
+    assert_eq!(
+        stark_point_validate_sized(p_x.as_mut_ptr(), 32, p_y.as_mut_ptr(), 32),
+        SUCCESS
+    );
+    let status = ec_mul_sized(
+        scalar.as_mut_ptr(),
+        scalar.len(),
+        p_x.as_mut_ptr(),
+        32,
+        p_y.as_mut_ptr(),
+        32,
+        x.as_mut_ptr(),
+        32,
+        y.as_mut_ptr(),
+        32,
+    );
+    if sized(status, &[&scalar], &[&x, &y]) == Some(SUCCESS) {
+        assert_eq!(
+            stark_point_validate_sized(x.as_mut_ptr(), 32, y.as_mut_ptr(), 32),
+            SUCCESS
+        );
+    }
+    if q_status == Some(SUCCESS)
+        && check(ec_add_sized(
+            p_x.as_mut_ptr(),
+            32,
+            p_y.as_mut_ptr(),
+            32,
+            q_x.as_mut_ptr(),
+            32,
+            q_y.as_mut_ptr(),
+            32,
+            x.as_mut_ptr(),
+            32,
+            y.as_mut_ptr(),
+            32,
+        )) == SUCCESS
+    {
+        assert_eq!(
+            stark_point_validate_sized(x.as_mut_ptr(), 32, y.as_mut_ptr(), 32),
+            SUCCESS
+        );
+    }
+}
+
+// The bytes of the arrays, enough for the largest lengths drawn, times the 136 bytes of the
+// Keccak blocks of keccak_u256_blocks.
+const ARRAY_LEN: usize = 32 * 1024;
+// The columns behind the pointers of the batch layouts.
+const COLUMNS: usize = 16;
+
+// A buffer or an array of a call, with its content before it.
+struct Argument<T> {
+    contents: Box<[T]>,
+    before: Box<[T]>,
+}
+
+impl<T: Clone> Argument<T> {
+    fn new(mut contents: Box<[T]>) -> (Self, *mut T) {
+        // The allocation of the box stays where it is when the box moves
+        let ptr = contents.as_mut_ptr();
+        let before = contents.clone();
+        (Argument { contents, before }, ptr)
+    }
+}
+
+// Draws the arguments of the calls of the table, and owns their buffers and the handles they
+// allocate.
+pub struct Calls<'a> {
+    input: Input<'a>,
+    buffers: Vec<Argument<u8>>,
+    // The arrays, of `u64`s so that they are aligned for any element
+    arrays: Vec<Argument<u64>>,
+    slots: Vec<(TypeId, Box<*mut ()>)>,
+    handles: Vec<(TypeId, *mut ())>,
+    bad_length: bool,
+    freed: bool,
+}
+
+impl<'a> Calls<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Calls {
+            input: Input(data),
+            buffers: Vec::new(),
+            arrays: Vec::new(),
+            slots: Vec::new(),
+            handles: Vec::new(),
+            bad_length: false,
+            freed: false,
+        }
+    }
+
+    fn buffer(&mut self, bytes: Vec<u8>) -> Bytes {
+        let (buffer, ptr) = Argument::new(bytes.into());
+        self.buffers.push(buffer);
+        ptr
+    }
+
+    fn words(&mut self, words: Box<[u64]>) -> *mut u64 {
+        let (array, ptr) = Argument::new(words);
+        self.arrays.push(array);
+        ptr
+    }
+
+    fn find_handle<T: 'static>(&mut self) -> Option<usize> {
+        let matching: Vec<_> = (0..self.handles.len())
+            .filter(|&index| self.handles[index].0 == TypeId::of::<T>())
+            .collect();
+        let choice = self.input.byte() as usize;
+        (!matching.is_empty()).then(|| matching[choice % matching.len()])
+    }
+
+    // Makes the call, then checks that it didn't panic and that a `_sized` function given a buffer
+    // of the wrong length touched none of them.
+    fn call(&mut self, export: &Export<Self>) {
+        self.bad_length = false;
+        let status = (export.call)(self);
+        assert_ne!(status, Some(ERR_PANIC), "{} panicked", export.name);
+        if self.bad_length {
+            assert_eq!(status, Some(ERR_BAD_LENGTH), "{}", export.name);
+            for buffer in &self.buffers {
+                assert_eq!(
+                    buffer.contents, buffer.before,
+                    "{} wrote a buffer",
+                    export.name
+                );
+            }
+            for array in &self.arrays {
+                assert_eq!(
+                    array.contents, array.before,
+                    "{} wrote an array",
+                    export.name
+                );
+            }
+        }
+        for (type_id, slot) in self.slots.drain(..) {
+            if !slot.is_null() {
+                self.handles.push((type_id, *slot));
+            }
+        }
+        self.buffers.clear();
+        self.arrays.clear();
+    }
+
+    // Releases the handles left with the `_free` functions.
+    fn free_handles(&mut self, exports: &[Export<Self>]) {
+        for export in exports
+            .iter()
+            .filter(|export| export.name.ends_with("_free"))
+        {
+            loop {
+                self.freed = false;
+                self.call(export);
+                if !self.freed {
+                    break;
+                }
+            }
+        }
+        assert!(self.handles.is_empty(), "a handle has no _free function");
+    }
+}
+
+impl Arguments for Calls<'_> {
+    fn bytes(&mut self, len: usize) -> Bytes {
+        let mut felt = self.input.felt().into_vec();
+        felt.resize(len, 0);
+        self.buffer(felt)
+    }
+
+    // The felts of the input are the expected length, the shorter buffers are kept as they are.
+    fn sized_bytes(&mut self, len: usize) -> (Bytes, usize) {
+        let mut felt = self.input.felt().into_vec();
+        if felt.len() == 32 {
+            felt.resize(len, 0);
+        }
+        self.bad_length |= felt.len() != len;
+        let actual = felt.len();
+        (self.buffer(felt), actual)
+    }
+
+    // Up to 7 felts of the input, then zeroes, which also end the C strings.
+    fn array<T>(&mut self) -> *mut T {
+        assert!(core::mem::align_of::<T>() <= core::mem::align_of::<u64>());
+        let mut bytes = vec![0u8; ARRAY_LEN];
+        let count = self.input.byte() % 8;
+        for chunk in bytes.chunks_mut(32).take(count as usize) {
+            let felt = self.input.felt();
+            chunk[..felt.len()].copy_from_slice(&felt);
+        }
+        let words = bytes
+            .chunks(8)
+            .map(|word| u64::from_ne_bytes(word.try_into().unwrap()))
+            .collect();
+        self.words(words).cast()
+    }
+
+    // The expected count of elements, or one short after a short felt of the input. The array ends
+    // at the end of its allocation, so reading past it is caught.
+    fn sized_array<T>(&mut self, len: usize) -> (*mut T, usize) {
+        assert!(core::mem::align_of::<T>() <= core::mem::align_of::<u64>());
+        let felt = self.input.felt();
+        let count = if felt.len() == 32 { len } else { len - 1 };
+        self.bad_length |= count != len;
+        let size = count * core::mem::size_of::<T>();
+        let mut bytes = vec![0u8; size.next_multiple_of(8)];
+        for (byte, value) in bytes.iter_mut().zip(felt.iter().cycle()) {
+            *byte = *value;
+        }
+        let words: Box<[u64]> = bytes
+            .chunks(8)
+            .map(|word| u64::from_ne_bytes(word.try_into().unwrap()))
+            .collect();
+        let end = words.len() * 8;
+        let ptr = self.words(words).cast::<u8>().wrapping_add(end - size);
+        (ptr.cast(), count)
+    }
+
+    fn pointers(&mut self) -> *mut *mut u8 {
+        let columns = (0..COLUMNS)
+            .map(|_| self.array::<u8>() as usize as u64)
+            .collect();
+        self.words(columns).cast()
+    }
+
+    fn length(&mut self) -> usize {
+        self.input.byte() as usize % 128
+    }
+
+    fn integer(&mut self) -> u64 {
+        self.input.byte() as u64 % 8
+    }
+
+    fn handle<T: 'static>(&mut self) -> *mut T {
+        match self.find_handle::<T>() {
+            Some(index) => self.handles[index].1.cast(),
+            None => core::ptr::null_mut(),
+        }
+    }
+
+    fn handle_slot<T: 'static>(&mut self) -> *mut *mut T {
+        let mut slot = Box::new(core::ptr::null_mut());
+        let ptr: *mut *mut () = &mut *slot;
+        self.slots.push((TypeId::of::<T>(), slot));
+        ptr.cast()
+    }
+
+    fn free_handle<T: 'static>(&mut self) -> *mut T {
+        let Some(index) = self.find_handle::<T>() else {
+            return core::ptr::null_mut();
+        };
+        self.freed = true;
+        self.handles.swap_remove(index).1.cast()
+    }
+}
+
+// Calls the exported functions picked by the input, each picked by two bytes, with the arguments
+// that follow, then releases the handles they allocated.
+pub fn exports(data: &[u8]) {
+    let exports = fuzzing::exports::<Calls>();
+    let mut calls = Calls::new(data);
+    while calls.input.0.len() >= 2 {
+        let index = u16::from_le_bytes([calls.input.byte(), calls.input.byte()]) as usize;
+        calls.call(&exports[index % exports.len()]);
+    }
+    calls.free_handles(&exports);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every pair of boundary values through every target, then with the second buffer short of a
+    // felt, also run by `cargo miri test`.
+    #[test]
+    fn test_boundary_inputs() {
+        for first in 0..7 {
+            for second in 0..7 {
+                for data in [
+                    [first, second, first, second, 5, 6],
+                    [first, 33 + second, first, second, 5, 6],
+                ] {
+                    felt(&data);
+                    hashes(&data);
+                    signatures(&data);
+                    curve(&data);
+                }
+            }
+        }
+    }
+
+    // Every exported function over the boundary values, then with the handles allocated by the
+    // earlier calls.
+    #[test]
+    fn test_every_export() {
+        for kind in 0..7 {
+            let data = [kind, 33 + kind, 3, 1, kind, 2, 5, 6, kind, 0, 4];
+            let exports = fuzzing::exports::<Calls>();
+            let mut calls = Calls::new(&[]);
+            for export in &exports {
+                calls.input = Input(&data);
+                calls.call(export);
+            }
+            calls.free_handles(&exports);
+        }
+    }
+}
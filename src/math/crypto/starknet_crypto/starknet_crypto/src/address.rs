@@ -0,0 +1,250 @@
+// Starknet contract addresses and storage addresses.
+//
+// A contract address is the Pedersen hash on elements of the `STARKNET_CONTRACT_ADDRESS` prefix,
+// the deployer address, the salt, the class hash and the hash on elements of the constructor
+// calldata, reduced modulo 2^251 - 256 so that the addresses stay out of the range of the storage
+// keys.
+// The address of a storage variable chains its keys with the Pedersen hash, starting from the
+// starknet_keccak of its name, and is reduced modulo the same bound.
+use starknet_crypto_rs::pedersen_hash;
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes, hash_on_elements,
+    keccak::sn_keccak, slice_from_ptr, try_field_element_from_bytes, Bytes, ERR_INVALID_FELT,
+    SUCCESS,
+};
+
+// 2^251 - 256
+const ADDRESS_BOUND: Felt =
+    Felt::from_hex_unchecked("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00");
+
+// Reduces a felt modulo ADDRESS_BOUND, which is larger than half the STARK prime.
+fn address_from_felt(felt: Felt) -> Felt {
+    if felt < ADDRESS_BOUND {
+        felt
+    } else {
+        felt - ADDRESS_BOUND
+    }
+}
+
+/// Computes the address of a contract deployed by `deployer_address` from a class hash with a
+/// salt and the `calldata_len` contiguous felts of its constructor calldata, writing it into the
+/// last argument. The deployer address is zero for the deployments independent of the deployer
+#[no_mangle]
+pub extern "C" fn calculate_contract_address(
+    deployer_address: Bytes,
+    salt: Bytes,
+    class_hash: Bytes,
+    calldata: *const u8,
+    calldata_len: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(deployer_address, salt, class_hash, result);
+        let (Some(deployer_address), Some(salt), Some(class_hash)) = (
+            try_field_element_from_bytes(deployer_address),
+            try_field_element_from_bytes(salt),
+            try_field_element_from_bytes(class_hash),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let calldata = match field_elements_from_bytes(calldata, calldata_len) {
+            Ok(calldata) => calldata,
+            Err(status) => return status,
+        };
+
+        let prefix = Felt::from_bytes_be_slice(b"STARKNET_CONTRACT_ADDRESS");
+        let hash = hash_on_elements(&[
+            prefix,
+            deployer_address,
+            salt,
+            class_hash,
+            hash_on_elements(&calldata),
+        ]);
+        bytes_from_field_element(address_from_felt(hash), result);
+        SUCCESS
+    })
+}
+
+/// Computes the address of the storage variable named by the `name_len` bytes of `name` at the
+/// `keys_len` contiguous felts of `keys`, writing it into the last argument. A variable without
+/// keys lives at the starknet_keccak of its name
+#[no_mangle]
+pub extern "C" fn get_storage_var_address(
+    name: *const u8,
+    name_len: usize,
+    keys: *const u8,
+    keys_len: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let (name, keys) = match (
+            slice_from_ptr(name, name_len),
+            field_elements_from_bytes(keys, keys_len),
+        ) {
+            (Ok(name), Ok(keys)) => (name, keys),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+
+        let selector = Felt::from_bytes_be(&sn_keccak(name));
+        let hash = keys
+            .iter()
+            .fold(selector, |acc, key| pedersen_hash(&acc, key));
+        bytes_from_field_element(address_from_felt(hash), result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_bound() {
+        let bound =
+            Felt::from_hex("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00")
+                .unwrap();
+        assert_eq!(ADDRESS_BOUND, bound);
+        assert_eq!(address_from_felt(bound - Felt::ONE), bound - Felt::ONE);
+        assert_eq!(address_from_felt(bound), Felt::ZERO);
+        assert_eq!(address_from_felt(-Felt::ONE), -Felt::ONE - bound);
+    }
+
+    #[test]
+    fn test_calculate_contract_address() {
+        let felt = |hex: &str| Felt::from_hex(hex).unwrap();
+        let deployer = felt("0x1");
+        let salt = felt("0x2");
+        let class_hash = felt("0x3");
+        let calldata = [felt("0x4"), felt("0x5")];
+        let calldata_bytes: Vec<u8> = calldata.iter().flat_map(|f| f.to_bytes_be()).collect();
+
+        let mut result = [0u8; 32];
+        assert_eq!(
+            calculate_contract_address(
+                deployer.to_bytes_be().as_mut_ptr(),
+                salt.to_bytes_be().as_mut_ptr(),
+                class_hash.to_bytes_be().as_mut_ptr(),
+                calldata_bytes.as_ptr(),
+                calldata.len(),
+                result.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        let expected = hash_on_elements(&[
+            felt("0x535441524b4e45545f434f4e54524143545f41444452455353"),
+            deployer,
+            salt,
+            class_hash,
+            hash_on_elements(&calldata),
+        ]);
+        assert_eq!(result, address_from_felt(expected).to_bytes_be());
+
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            calculate_contract_address(
+                not_a_felt.as_mut_ptr(),
+                salt.to_bytes_be().as_mut_ptr(),
+                class_hash.to_bytes_be().as_mut_ptr(),
+                core::ptr::null(),
+                0,
+                result.as_mut_ptr(),
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_get_storage_var_address() {
+        let mut result = [0u8; 32];
+        let name = b"balance";
+        assert_eq!(
+            get_storage_var_address(
+                name.as_ptr(),
+                name.len(),
+                core::ptr::null(),
+                0,
+                result.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        let selector =
+            Felt::from_hex("0x206f38f7e4f15e87567361213c28f235cccdaa1d7fd34c9db1dfe9489c6a091")
+                .unwrap();
+        assert_eq!(result, selector.to_bytes_be());
+
+        let keys = [Felt::from(7u8), Felt::from(9u8)];
+        let keys_bytes: Vec<u8> = keys.iter().flat_map(|key| key.to_bytes_be()).collect();
+        assert_eq!(
+            get_storage_var_address(
+                name.as_ptr(),
+                name.len(),
+                keys_bytes.as_ptr(),
+                keys.len(),
+                result.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        let expected = pedersen_hash(&pedersen_hash(&selector, &keys[0]), &keys[1]);
+        assert_eq!(result, address_from_felt(expected).to_bytes_be());
+    }
+
+    // The accounts deployed by the v1 and v3 deploy_account transactions of Starknet mainnet block
+    // 636864, from the transaction_hash.json fixtures of starknet_api. Both deploy the same class
+    // with their salt as the calldata, and the v1 transaction hashes to its mainnet hash only with
+    // the right address.
+    #[test]
+    fn test_mainnet_contract_address() {
+        let felt = |hex: &str| Felt::from_hex(hex).unwrap();
+        let class_hash = felt("0x13bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6");
+        let address = |salt: Felt| {
+            let mut result = [0u8; 32];
+            assert_eq!(
+                calculate_contract_address(
+                    [0u8; 32].as_mut_ptr(),
+                    salt.to_bytes_be().as_mut_ptr(),
+                    class_hash.to_bytes_be().as_mut_ptr(),
+                    salt.to_bytes_be().as_ptr(),
+                    1,
+                    result.as_mut_ptr(),
+                ),
+                SUCCESS
+            );
+            Felt::from_bytes_be(&result)
+        };
+
+        let v1_salt = felt("0x54c617a2e91df5344958e0eb2c30c58a1134b3f8e59e88deba60a24f95c0a2c");
+        let v1_address = address(v1_salt);
+        assert_eq!(
+            v1_address,
+            felt("0x2aaedc4cc67a5ad0134868689f49b9c8838db640a5c84a13e6cccc828c21aa")
+        );
+        let v3_salt = felt("0x1f218cfa725d1679ff028c53d094a765dfd1de632787cab37ffe10f3915dcc7");
+        assert_eq!(
+            address(v3_salt),
+            felt("0x2ef67bd3f91cb3c6f5bdaf8468752a40fb3bdeb880779d36ec3c43736002158")
+        );
+
+        let mut result = [0u8; 32];
+        assert_eq!(
+            crate::transaction::deploy_account_v1_transaction_hash(
+                v1_address.to_bytes_be().as_mut_ptr(),
+                class_hash.to_bytes_be().as_mut_ptr(),
+                v1_salt.to_bytes_be().as_mut_ptr(),
+                v1_salt.to_bytes_be().as_ptr(),
+                1,
+                felt("0x3a23c71d8b9").to_bytes_be().as_mut_ptr(),
+                felt("0x534e5f4d41494e").to_bytes_be().as_mut_ptr(),
+                [0u8; 32].as_mut_ptr(),
+                result.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            felt("0x40e7ac7efc374f3d1241c6f991de2ea534d84e8be307420658353527226c5e4").to_bytes_be()
+        );
+    }
+}
@@ -0,0 +1,242 @@
+// The global allocator of the `std` builds, forwarding to the allocation functions of the embedder
+// once they are set with starknet_crypto_set_allocator, and to the system allocator until then.
+//
+// A block must be freed by the allocator that returned it, so the embedder's functions can only be
+// set before the library allocates anything, which any function but starknet_crypto_abi_version
+// may do.
+//
+// With the `heap-stats` cargo feature, the allocator also counts the allocations and the live and
+// peak bytes of the library's blocks, whichever allocator they come from, for the embedders that
+// budget their memory. A reallocation counts as an allocation.
+#[cfg(feature = "heap-stats")]
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use crate::{ffi_guard, ERR_ALLOCATOR, SUCCESS};
+
+/// Returns a block of `size` bytes aligned to `align`, or NULL when out of memory
+pub type AllocFn = Option<extern "C" fn(size: usize, align: usize) -> *mut u8>;
+/// Frees a block returned by the allocation function for the same `size` and `align`
+pub type FreeFn = Option<extern "C" fn(ptr: *mut u8, size: usize, align: usize)>;
+/// Resizes a block of `size` bytes aligned to `align` to `new_size` bytes with the same alignment,
+/// returning the new block or NULL when out of memory, in which case the block is left untouched
+pub type ReallocFn =
+    Option<extern "C" fn(ptr: *mut u8, size: usize, align: usize, new_size: usize) -> *mut u8>;
+
+struct Hooks {
+    alloc: extern "C" fn(usize, usize) -> *mut u8,
+    free: extern "C" fn(*mut u8, usize, usize),
+    realloc: ReallocFn,
+}
+
+static HOOKS: OnceLock<Hooks> = OnceLock::new();
+// Whether the system allocator returned a block, after which the hooks can't be set anymore.
+static SYSTEM_ALLOCATED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "heap-stats")]
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "heap-stats")]
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "heap-stats")]
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The allocations of the library, filled by starknet_crypto_heap_stats
+#[cfg(feature = "heap-stats")]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// The number of allocations and reallocations
+    pub allocations: u64,
+    /// The bytes of the blocks not freed yet
+    pub live_bytes: u64,
+    /// The largest number of live bytes so far
+    pub peak_bytes: u64,
+}
+
+// Records a block of `size` bytes replacing one of `freed` bytes, when the allocation succeeded.
+#[cfg(feature = "heap-stats")]
+fn record_allocation(ptr: *mut u8, size: usize, freed: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    LIVE_BYTES.fetch_sub(freed, Ordering::Relaxed);
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+#[cfg(feature = "heap-stats")]
+fn record_free(size: usize) {
+    LIVE_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+struct EmbedderAllocator;
+
+impl EmbedderAllocator {
+    fn system(&self) -> &System {
+        if !SYSTEM_ALLOCATED.load(Ordering::Relaxed) {
+            SYSTEM_ALLOCATED.store(true, Ordering::Relaxed);
+        }
+        &System
+    }
+}
+
+unsafe impl GlobalAlloc for EmbedderAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = match HOOKS.get() {
+            Some(hooks) => (hooks.alloc)(layout.size(), layout.align()),
+            None => self.system().alloc(layout),
+        };
+        #[cfg(feature = "heap-stats")]
+        record_allocation(ptr, layout.size(), 0);
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        #[cfg(feature = "heap-stats")]
+        record_free(layout.size());
+        match HOOKS.get() {
+            Some(hooks) => (hooks.free)(ptr, layout.size(), layout.align()),
+            None => System.dealloc(ptr, layout),
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        match HOOKS.get() {
+            Some(Hooks {
+                realloc: Some(realloc),
+                ..
+            }) => {
+                let new_ptr = realloc(ptr, layout.size(), layout.align(), new_size);
+                #[cfg(feature = "heap-stats")]
+                record_allocation(new_ptr, new_size, layout.size());
+                new_ptr
+            }
+            // Without a reallocation function, allocate, copy and free, which record the blocks
+            Some(_) => {
+                let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+                let new_ptr = self.alloc(new_layout);
+                if !new_ptr.is_null() {
+                    core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                    self.dealloc(ptr, layout);
+                }
+                new_ptr
+            }
+            None => {
+                let new_ptr = self.system().realloc(ptr, layout, new_size);
+                #[cfg(feature = "heap-stats")]
+                record_allocation(new_ptr, new_size, layout.size());
+                new_ptr
+            }
+        }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: EmbedderAllocator = EmbedderAllocator;
+
+/// Makes the library allocate with the embedder's functions instead of the system allocator. The
+/// reallocation function may be NULL, reallocations then allocate, copy and free. Returns
+/// ERR_ALLOCATOR for a NULL allocation or free function, and when called after the library
+/// allocated, so it must be the first call into the library and can only be made once. Only
+/// available with the `std` cargo feature (enabled by default), the `no_std` builds call
+/// starknet_crypto_alloc and starknet_crypto_dealloc instead
+#[no_mangle]
+pub extern "C" fn starknet_crypto_set_allocator(
+    alloc: AllocFn,
+    free: FreeFn,
+    realloc: ReallocFn,
+) -> i32 {
+    ffi_guard(|| {
+        let (Some(alloc), Some(free)) = (alloc, free) else {
+            return ERR_ALLOCATOR;
+        };
+        if SYSTEM_ALLOCATED.load(Ordering::Relaxed) {
+            return ERR_ALLOCATOR;
+        }
+        match HOOKS.set(Hooks {
+            alloc,
+            free,
+            realloc,
+        }) {
+            Ok(()) => SUCCESS,
+            Err(_) => ERR_ALLOCATOR,
+        }
+    })
+}
+
+/// Writes the number of allocations and the live and peak bytes of the library since the start into
+/// the argument. Only available with the `heap-stats` cargo feature, which implies `std`
+#[cfg(feature = "heap-stats")]
+#[no_mangle]
+pub extern "C" fn starknet_crypto_heap_stats(stats: *mut HeapStats) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(stats);
+        unsafe {
+            *stats = HeapStats {
+                allocations: ALLOCATIONS.load(Ordering::Relaxed),
+                live_bytes: LIVE_BYTES.load(Ordering::Relaxed) as u64,
+                peak_bytes: PEAK_BYTES.load(Ordering::Relaxed) as u64,
+            }
+        };
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn alloc(_size: usize, _align: usize) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    extern "C" fn free(_ptr: *mut u8, _size: usize, _align: usize) {}
+
+    #[test]
+    fn test_set_allocator_after_allocating() {
+        // The test harness and this vector allocated with the system allocator
+        let _ = Vec::<u8>::with_capacity(1);
+        assert!(SYSTEM_ALLOCATED.load(Ordering::Relaxed));
+        assert_eq!(
+            starknet_crypto_set_allocator(Some(alloc), Some(free), None),
+            ERR_ALLOCATOR
+        );
+        assert_eq!(
+            starknet_crypto_set_allocator(None, Some(free), None),
+            ERR_ALLOCATOR
+        );
+        assert!(HOOKS.get().is_none());
+    }
+
+    #[cfg(feature = "heap-stats")]
+    #[test]
+    fn test_heap_stats() {
+        let stats = || {
+            let mut stats = HeapStats::default();
+            assert_eq!(starknet_crypto_heap_stats(&mut stats), SUCCESS);
+            stats
+        };
+        // Other tests allocate concurrently, so the counters only give bounds
+        let before = stats();
+        let mut block = Vec::<u8>::with_capacity(1 << 24);
+        let during = stats();
+        assert!(during.allocations > before.allocations);
+        assert!(during.peak_bytes >= 1 << 24);
+        block.reserve_exact(2 << 24);
+        assert!(stats().peak_bytes >= 3 << 24);
+        drop(block);
+        assert!(stats().live_bytes < 3 << 24);
+        assert!(stats().peak_bytes >= stats().live_bytes);
+        assert_eq!(
+            starknet_crypto_heap_stats(core::ptr::null_mut()),
+            crate::ERR_NULL_POINTER
+        );
+    }
+}
@@ -0,0 +1,98 @@
+// The `backend-lambdaworks` backend, on top of lambdaworks-math.
+use alloc::vec::Vec;
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::{
+        curves::stark_curve::StarkCurve, point::ShortWeierstrassProjectivePoint,
+        traits::IsShortWeierstrass,
+    },
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    msm::pippenger::msm,
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+
+pub(crate) type Felt = FieldElement<Stark252PrimeField>;
+pub(crate) type Point = ShortWeierstrassProjectivePoint<StarkCurve>;
+
+pub(crate) fn felt_from_bytes(bytes: &[u8; 32]) -> Option<Felt> {
+    let value = U256::from_bytes_be(bytes).ok()?;
+    // The conversion reduces modulo the STARK prime, felts are the values it leaves unchanged
+    let felt = Felt::from(&value);
+    (felt.representative() == value).then_some(felt)
+}
+
+pub(crate) fn bytes_from_felt(felt: &Felt) -> [u8; 32] {
+    // The limbs are stored most significant first
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(felt.representative().limbs) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+pub(crate) fn felt_add(a: &Felt, b: &Felt) -> Felt {
+    a + b
+}
+
+pub(crate) fn felt_sub(a: &Felt, b: &Felt) -> Felt {
+    a - b
+}
+
+pub(crate) fn felt_mul(a: &Felt, b: &Felt) -> Felt {
+    a * b
+}
+
+pub(crate) fn felt_inverse(a: &Felt) -> Option<Felt> {
+    a.inv().ok()
+}
+
+pub(crate) fn felt_pow(base: &Felt, exponent: &[u8; 32]) -> Felt {
+    base.pow(U256::from_bytes_be(exponent).unwrap())
+}
+
+// Returns the smallest of the two square roots.
+pub(crate) fn felt_sqrt(a: &Felt) -> Option<Felt> {
+    let (root, opposite) = a.sqrt()?;
+    if root.representative() <= opposite.representative() {
+        Some(root)
+    } else {
+        Some(opposite)
+    }
+}
+
+pub(crate) fn point_from_coordinates(x: &Felt, y: &Felt) -> Option<Point> {
+    Point::new([*x, *y, Felt::one()]).ok()
+}
+
+// Returns the smallest y coordinate of the points with the given x coordinate.
+pub(crate) fn point_y_from_x(x: &Felt) -> Option<Felt> {
+    felt_sqrt(&(x * x * x + StarkCurve::a() * x + StarkCurve::b()))
+}
+
+fn coordinates_from_point(point: &Point) -> Option<(Felt, Felt)> {
+    if point.is_neutral_element() {
+        return None;
+    }
+    let point = point.to_affine();
+    Some((*point.x(), *point.y()))
+}
+
+pub(crate) fn point_add(p: &Point, q: &Point) -> Option<(Felt, Felt)> {
+    coordinates_from_point(&p.operate_with(q))
+}
+
+pub(crate) fn point_mul(p: &Point, scalar: &[u8; 32]) -> Option<(Felt, Felt)> {
+    coordinates_from_point(&p.operate_with_self(U256::from_bytes_be(scalar).unwrap()))
+}
+
+pub(crate) fn point_msm(points: &[Point], scalars: &[[u8; 32]]) -> Option<(Felt, Felt)> {
+    let scalars: Vec<U256> = scalars
+        .iter()
+        .map(|scalar| U256::from_bytes_be(scalar).unwrap())
+        .collect();
+    coordinates_from_point(&msm(&scalars, points).unwrap())
+}
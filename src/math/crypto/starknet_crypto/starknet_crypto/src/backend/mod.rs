@@ -0,0 +1,16 @@
+// The field and curve arithmetic behind the felt and curve functions, from starknet-types-core and
+// starknet-curve by default or from lambdaworks-math with the `backend-lambdaworks` feature. Both
+// backends expose the same functions over their own `Felt` and `Point` types, so the C ABI doesn't
+// depend on the backend. The hashes and signatures always use starknet-crypto.
+//
+// Felts and scalars are 32 bytes big endian integers, and points are never the point at infinity:
+// the functions producing one return None instead.
+#[cfg(feature = "backend-lambdaworks")]
+mod lambdaworks;
+#[cfg(not(feature = "backend-lambdaworks"))]
+mod starknet;
+
+#[cfg(feature = "backend-lambdaworks")]
+pub(crate) use lambdaworks::*;
+#[cfg(not(feature = "backend-lambdaworks"))]
+pub(crate) use starknet::*;
@@ -0,0 +1,112 @@
+// The default backend, on top of starknet-types-core and starknet-curve.
+use alloc::vec;
+
+use starknet_curve::curve_params::{ALPHA, BETA};
+use starknet_types_core::curve::ProjectivePoint;
+pub(crate) use starknet_types_core::felt::Felt;
+
+pub(crate) type Point = ProjectivePoint;
+
+pub(crate) fn felt_from_bytes(bytes: &[u8; 32]) -> Option<Felt> {
+    crate::try_felt_from_array(bytes)
+}
+
+pub(crate) fn bytes_from_felt(felt: &Felt) -> [u8; 32] {
+    felt.to_bytes_be()
+}
+
+pub(crate) fn felt_add(a: &Felt, b: &Felt) -> Felt {
+    *a + *b
+}
+
+pub(crate) fn felt_sub(a: &Felt, b: &Felt) -> Felt {
+    *a - *b
+}
+
+pub(crate) fn felt_mul(a: &Felt, b: &Felt) -> Felt {
+    *a * *b
+}
+
+pub(crate) fn felt_inverse(a: &Felt) -> Option<Felt> {
+    a.inverse()
+}
+
+pub(crate) fn felt_pow(base: &Felt, exponent: &[u8; 32]) -> Felt {
+    // Left to right square and multiply
+    let mut power = Felt::ONE;
+    for byte in exponent {
+        for i in (0..8).rev() {
+            power = power * power;
+            if (byte >> i) & 1 == 1 {
+                power *= *base;
+            }
+        }
+    }
+    power
+}
+
+// Returns the smallest of the two square roots.
+pub(crate) fn felt_sqrt(a: &Felt) -> Option<Felt> {
+    let root = a.sqrt()?;
+    Some(root.min(-root))
+}
+
+pub(crate) fn point_from_coordinates(x: &Felt, y: &Felt) -> Option<Point> {
+    ProjectivePoint::from_affine(*x, *y).ok()
+}
+
+// Returns the smallest y coordinate of the points with the given x coordinate.
+pub(crate) fn point_y_from_x(x: &Felt) -> Option<Felt> {
+    felt_sqrt(&(*x * *x * *x + ALPHA * *x + BETA))
+}
+
+fn coordinates_from_point(point: &Point) -> Option<(Felt, Felt)> {
+    let point = point.to_affine().ok()?;
+    Some((point.x(), point.y()))
+}
+
+pub(crate) fn point_add(p: &Point, q: &Point) -> Option<(Felt, Felt)> {
+    coordinates_from_point(&(p + q))
+}
+
+pub(crate) fn point_mul(p: &Point, scalar: &[u8; 32]) -> Option<(Felt, Felt)> {
+    coordinates_from_point(&(p * Felt::from_bytes_be(scalar)))
+}
+
+// The `width` bits of a big endian scalar starting at bit `start`.
+fn window(scalar: &[u8; 32], start: usize, width: usize) -> usize {
+    (start..(start + width).min(256))
+        .rev()
+        .fold(0, |digit, bit| {
+            (digit << 1) | ((scalar[31 - bit / 8] >> (bit % 8)) & 1) as usize
+        })
+}
+
+// Pippenger's bucket method, with windows of about log2(n) bits.
+pub(crate) fn point_msm(points: &[Point], scalars: &[[u8; 32]]) -> Option<(Felt, Felt)> {
+    let width = match points.len() {
+        0..=31 => 3,
+        n => n.ilog2() as usize - 2,
+    };
+    let mut total = ProjectivePoint::identity();
+    for start in (0..256).step_by(width).rev() {
+        for _ in 0..width {
+            total = total.double();
+        }
+        let mut buckets = vec![ProjectivePoint::identity(); (1 << width) - 1];
+        for (point, scalar) in points.iter().zip(scalars) {
+            let digit = window(scalar, start, width);
+            if digit != 0 {
+                buckets[digit - 1] += point;
+            }
+        }
+        // The sum of digit * bucket, as the sum of the running sums from the highest digit down
+        let (mut running, mut sum) = (ProjectivePoint::identity(), ProjectivePoint::identity());
+        for bucket in buckets.iter().rev() {
+            running += bucket;
+            sum += &running;
+        }
+        total += &sum;
+    }
+    coordinates_from_point(&total)
+}
@@ -0,0 +1,960 @@
+// Batched variants of the builtin hashes and of the signature verification and signing, amortizing
+// the FFI overhead over the long runs of cells processed by the builtin runners. The `_layout`
+// variants also read their operands from one array per operand, like the memory columns of the
+// builtins, instead of one array of interleaved records.
+//
+// The batches run sequentially unless a thread pool was set up with
+// `starknet_crypto_set_threads`, which requires the `parallel` feature. The Pedersen hashes use the
+// tables built by `starknet_crypto_precompute_pedersen` when there are some.
+use alloc::vec::Vec;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use starknet_crypto_rs::pedersen_hash;
+use starknet_types_core::felt::Felt;
+
+#[cfg(feature = "std")]
+use crate::generator;
+#[cfg(feature = "signing")]
+use crate::sign_rfc6979;
+use crate::{
+    bytes_from_field_element, differential,
+    ecdsa::verify,
+    ffi_guard, field_elements_from_bytes,
+    hades::{poseidon_hash_many, poseidon_permute_comp},
+    hash_on_elements,
+    logger::log,
+    profiling::{Primitive, Profile},
+    slice_from_ptr, status_from_verification, try_felt_from_array, DIFF_PEDERSEN,
+    DIFF_PEDERSEN_ON_ELEMENTS, DIFF_POSEIDON_MANY, DIFF_POSEIDON_PERMUTE, ERR_INVALID_FELT,
+    ERR_INVALID_LAYOUT, ERR_INVALID_LENGTH, ERR_INVALID_SIGNATURE, LAYOUT_COLUMNS,
+    LAYOUT_INTERLEAVED, LOG_DEBUG, SUCCESS,
+};
+#[cfg(any(feature = "std", feature = "signing"))]
+use crate::{secret::try_secret_from_bytes, Bytes};
+#[cfg(feature = "parallel")]
+use crate::{ERR_THREAD_POOL, LOG_WARNING};
+
+#[cfg(feature = "parallel")]
+static THREAD_POOL: std::sync::RwLock<Option<rayon::ThreadPool>> = std::sync::RwLock::new(None);
+
+/// Runs the batch and Merkle root functions on a pool of `threads` threads, or sequentially (the
+/// default) for zero or one. Only available with the `parallel` cargo feature (enabled by default)
+#[cfg(feature = "parallel")]
+#[no_mangle]
+pub extern "C" fn starknet_crypto_set_threads(threads: usize) -> i32 {
+    ffi_guard(|| {
+        let pool = if threads > 1 {
+            match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+                Ok(pool) => Some(pool),
+                Err(error) => {
+                    log(
+                        LOG_WARNING,
+                        format_args!("could not create a pool of {threads} threads: {error}"),
+                    );
+                    return ERR_THREAD_POOL;
+                }
+            }
+        } else {
+            None
+        };
+        log(
+            LOG_DEBUG,
+            format_args!("running the batches on {} threads", threads.max(1)),
+        );
+        *THREAD_POOL.write().unwrap() = pool;
+        SUCCESS
+    })
+}
+
+// The number of threads the batches run on.
+pub(crate) fn thread_count() -> usize {
+    #[cfg(feature = "parallel")]
+    if let Some(pool) = THREAD_POOL.read().unwrap().as_ref() {
+        return pool.current_num_threads();
+    }
+    1
+}
+
+// Computes `f(0), ..., f(count - 1)`, in parallel when a thread pool is set.
+pub(crate) fn map_batch<T: Send>(count: usize, f: impl Fn(usize) -> T + Sync + Send) -> Vec<T> {
+    #[cfg(feature = "parallel")]
+    if let Some(pool) = THREAD_POOL.read().unwrap().as_ref() {
+        log(
+            LOG_DEBUG,
+            format_args!(
+                "running a batch of {count} on {} threads",
+                pool.current_num_threads()
+            ),
+        );
+        return pool.install(|| (0..count).into_par_iter().map(f).collect());
+    }
+    log(
+        LOG_DEBUG,
+        format_args!("running a batch of {count} sequentially"),
+    );
+    (0..count).map(f).collect()
+}
+
+// Hashes a pair with the precomputed tables when they were built.
+pub(crate) fn pedersen_hash_pair(a: &Felt, b: &Felt) -> Felt {
+    #[cfg(feature = "std")]
+    if let Some(tables) = crate::pedersen::TABLES.get() {
+        return tables.hash(a, b);
+    }
+    pedersen_hash(a, b)
+}
+
+// Reads the `count` records of N felts of a batch, from the single array of the interleaved records
+// or from the N arrays of the columns.
+fn records_from_layout<const N: usize>(
+    columns: *const *const u8,
+    layout: u32,
+    count: usize,
+) -> Result<Vec<[Felt; N]>, i32> {
+    match layout {
+        LAYOUT_INTERLEAVED => {
+            let felt_count = count.checked_mul(N).ok_or(ERR_INVALID_LENGTH)?;
+            let felts = field_elements_from_bytes(slice_from_ptr(columns, 1)?[0], felt_count)?;
+            Ok(felts
+                .chunks_exact(N)
+                .map(|record| record.try_into().unwrap())
+                .collect())
+        }
+        LAYOUT_COLUMNS => {
+            let columns = slice_from_ptr(columns, N)?
+                .iter()
+                .map(|&column| field_elements_from_bytes(column, count))
+                .collect::<Result<Vec<_>, i32>>()?;
+            Ok((0..count)
+                .map(|i| core::array::from_fn(|j| columns[j][i]))
+                .collect())
+        }
+        _ => Err(ERR_INVALID_LAYOUT),
+    }
+}
+
+// Writes the records of a batch back in the layout they were read from.
+fn records_to_layout<const N: usize>(records: &[[Felt; N]], columns: *const *mut u8, layout: u32) {
+    for (i, record) in records.iter().enumerate() {
+        for (j, felt) in record.iter().enumerate() {
+            let bytes = match layout {
+                LAYOUT_INTERLEAVED => unsafe { (*columns).add(32 * (N * i + j)) },
+                _ => unsafe { (*columns.add(j)).add(32 * i) },
+            };
+            bytes_from_field_element(*felt, bytes);
+        }
+    }
+}
+
+fn pedersen_hashes(columns: *const *const u8, layout: u32, count: usize, result: *mut u8) -> i32 {
+    if result.is_null() && count > 0 {
+        return ERR_INVALID_LENGTH;
+    }
+    let records = match records_from_layout::<2>(columns, layout, count) {
+        Ok(records) => records,
+        Err(status) => return status,
+    };
+    #[cfg(feature = "std")]
+    if crate::pedersen::TABLES.get().is_none() {
+        log(
+            LOG_DEBUG,
+            format_args!("hashing {count} pairs without the precomputed Pedersen tables"),
+        );
+    }
+    let hashes = map_batch(count, |i| {
+        pedersen_hash_pair(&records[i][0], &records[i][1])
+    });
+    for (i, hash) in hashes.into_iter().enumerate() {
+        differential::report(DIFF_PEDERSEN, &records[i], &[hash]);
+        bytes_from_field_element(hash, unsafe { result.add(32 * i) });
+    }
+    SUCCESS
+}
+
+fn poseidon_permutations(columns: *const *mut u8, layout: u32, count: usize) -> i32 {
+    let records = match records_from_layout::<3>(columns as *const *const u8, layout, count) {
+        Ok(records) => records,
+        Err(status) => return status,
+    };
+    let permuted = map_batch(count, |i| {
+        let mut state_array = records[i];
+        poseidon_permute_comp(&mut state_array);
+        state_array
+    });
+    for (record, state) in records.iter().zip(&permuted) {
+        differential::report(DIFF_POSEIDON_PERMUTE, record, state);
+    }
+    records_to_layout(&permuted, columns, layout);
+    SUCCESS
+}
+
+fn signature_verifications(
+    columns: *const *const u8,
+    layout: u32,
+    count: usize,
+    results: *mut u8,
+    valid_count: *mut usize,
+) -> i32 {
+    if results.is_null() && count > 0 {
+        return ERR_INVALID_LENGTH;
+    }
+    let records = match records_from_layout::<4>(columns, layout, count) {
+        Ok(records) => records,
+        Err(status) => return status,
+    };
+    // Malformed signatures, like an out of range r or s, count as invalid
+    let verified = map_batch(count, |i| {
+        let [public_key, message, r, s] = &records[i];
+        verify(public_key, message, r, s).unwrap_or_default()
+    });
+    for (record, &valid) in records.iter().zip(&verified) {
+        let status = if valid {
+            SUCCESS
+        } else {
+            ERR_INVALID_SIGNATURE
+        };
+        differential::report_verification(record, status);
+    }
+
+    // Bit i of the `(count + 7) / 8` bytes bitmap, least significant bit first, tells whether
+    // signature i verifies
+    for (i, chunk) in verified.chunks(8).enumerate() {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |bits, (j, &valid)| bits | (valid as u8) << j);
+        unsafe { *results.add(i) = bits };
+    }
+    unsafe { *valid_count = verified.into_iter().filter(|&valid| valid).count() };
+    SUCCESS
+}
+
+/// Hashes `count` contiguous pairs of felts (a0, b0, a1, b1, ...) into `count` contiguous felts
+#[no_mangle]
+pub extern "C" fn pedersen_hash_batch(pairs: *const u8, count: usize, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        let _profile = Profile::start(Primitive::Pedersen, count);
+        pedersen_hashes(&pairs, LAYOUT_INTERLEAVED, count, result)
+    })
+}
+
+/// Like pedersen_hash_batch, with the pairs in the LAYOUT_ layout: `columns` points to the array of
+/// the interleaved pairs, or to the arrays of the first and of the second felts of the pairs
+#[no_mangle]
+pub extern "C" fn pedersen_hash_batch_layout(
+    columns: *const *const u8,
+    layout: u32,
+    count: usize,
+    result: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(columns);
+        let _profile = Profile::start(Primitive::Pedersen, count);
+        pedersen_hashes(columns, layout, count, result)
+    })
+}
+
+/// Computes in place the poseidon hash permutation of `count` contiguous 96 bytes states of three
+/// felts
+#[no_mangle]
+pub extern "C" fn poseidon_permute_batch(states: *mut u8, count: usize) -> i32 {
+    ffi_guard(|| {
+        let _profile = Profile::start(Primitive::Poseidon, count);
+        poseidon_permutations(&states, LAYOUT_INTERLEAVED, count)
+    })
+}
+
+/// Like poseidon_permute_batch, with the states in the LAYOUT_ layout: `columns` points to the
+/// array of the interleaved states, or to the arrays of the first, second and third felts of the
+/// states
+#[no_mangle]
+pub extern "C" fn poseidon_permute_batch_layout(
+    columns: *const *mut u8,
+    layout: u32,
+    count: usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(columns);
+        let _profile = Profile::start(Primitive::Poseidon, count);
+        poseidon_permutations(columns, layout, count)
+    })
+}
+
+/// Verifies `count` signatures given as contiguous arrays of public keys, messages, r and s. Sets
+/// bit i (least significant bit first) of the `(count + 7) / 8` bytes bitmap when signature i is
+/// valid and writes the number of valid signatures into the last argument
+#[no_mangle]
+pub extern "C" fn verify_signature_batch(
+    public_keys: *const u8,
+    messages: *const u8,
+    rs: *const u8,
+    ss: *const u8,
+    count: usize,
+    results: *mut u8,
+    valid_count: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(valid_count);
+        let _profile = Profile::start(Primitive::EcdsaVerify, count);
+        let columns = [public_keys, messages, rs, ss];
+        signature_verifications(
+            columns.as_ptr(),
+            LAYOUT_COLUMNS,
+            count,
+            results,
+            valid_count,
+        )
+    })
+}
+
+/// Derives the public keys (x coordinates) of `count` contiguous private keys, writing them into
+/// the contiguous array of the last argument, like get_public_key. The products of the generator go
+/// through a table of its multiples built on the first call. Not constant time
+#[cfg(feature = "std")]
+#[no_mangle]
+pub extern "C" fn get_public_key_batch(
+    private_keys: *const u8,
+    count: usize,
+    public_keys: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        if public_keys.is_null() && count > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let private_keys = match count
+            .checked_mul(32)
+            .ok_or(ERR_INVALID_LENGTH)
+            .and_then(|len| slice_from_ptr(private_keys, len))
+        {
+            Ok(private_keys) => private_keys,
+            Err(status) => return status,
+        };
+        let Some(private_keys) = private_keys
+            .chunks_exact(32)
+            .map(|key| try_secret_from_bytes(key.as_ptr() as Bytes))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let derived = map_batch(count, |i| generator::public_key(&private_keys[i]));
+        for (i, public_key) in derived.into_iter().enumerate() {
+            bytes_from_field_element(public_key, unsafe { public_keys.add(32 * i) });
+        }
+        SUCCESS
+    })
+}
+
+/// Signs `count` contiguous message hashes with one private key and the deterministic k of
+/// rfc6979_generate_k without a seed, like ecdsa_sign_seeded, writing the r and s of each
+/// signature into the contiguous arrays of the last two arguments. Returns ERR_INVALID_MESSAGE_HASH
+/// without writing any signature when a message hash is out of range, and ERR_INVALID_K when one
+/// is zero and the private key a multiple of the curve order. Not constant time
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn ecdsa_sign_batch(
+    private_key: Bytes,
+    message_hashes: *const u8,
+    count: usize,
+    rs: *mut u8,
+    ss: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key);
+        if (rs.is_null() || ss.is_null()) && count > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let _profile = Profile::start(Primitive::EcdsaSign, count);
+        let Some(private_key) = try_secret_from_bytes(private_key) else {
+            return ERR_INVALID_FELT;
+        };
+        let messages = match field_elements_from_bytes(message_hashes, count) {
+            Ok(messages) => messages,
+            Err(status) => return status,
+        };
+        let signatures = map_batch(count, |i| sign_rfc6979(&private_key, &messages[i], None));
+        let signatures = match signatures.into_iter().collect::<Result<Vec<_>, i32>>() {
+            Ok(signatures) => signatures,
+            Err(status) => return status,
+        };
+        for (i, (r, s)) in signatures.into_iter().enumerate() {
+            bytes_from_field_element(r, unsafe { rs.add(32 * i) });
+            bytes_from_field_element(s, unsafe { ss.add(32 * i) });
+        }
+        SUCCESS
+    })
+}
+
+/// Like verify_signature_batch, with the signatures in the LAYOUT_ layout: `columns` points to the
+/// array of the interleaved (public key, message, r, s) records, or to the arrays of the public
+/// keys, messages, r and s
+#[no_mangle]
+pub extern "C" fn verify_signature_batch_layout(
+    columns: *const *const u8,
+    layout: u32,
+    count: usize,
+    results: *mut u8,
+    valid_count: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(columns, valid_count);
+        let _profile = Profile::start(Primitive::EcdsaVerify, count);
+        signature_verifications(columns, layout, count, results, valid_count)
+    })
+}
+
+/// Validates `count` contiguous instances of the signature builtin, each the 128 bytes (public key,
+/// message hash, r, s) record, like verify_signature. Returns SUCCESS when all of them verify, and
+/// otherwise the status of the first failing instance, whose index is written into the last
+/// argument. The index is `count` on success
+#[no_mangle]
+pub extern "C" fn ecdsa_builtin_validate(
+    cells: *const u8,
+    count: usize,
+    failing_index: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(failing_index);
+        let _profile = Profile::start(Primitive::EcdsaVerify, count);
+        let cells = match count.checked_mul(128).ok_or(ERR_INVALID_LENGTH) {
+            Ok(len) => match slice_from_ptr(cells, len) {
+                Ok(cells) => cells,
+                Err(status) => return status,
+            },
+            Err(status) => return status,
+        };
+        let statuses = map_batch(count, |i| {
+            let record: Option<Vec<Felt>> = cells[128 * i..128 * (i + 1)]
+                .chunks_exact(32)
+                .map(|felt| try_felt_from_array(felt.try_into().unwrap()))
+                .collect();
+            let Some(record) = record else {
+                return ERR_INVALID_FELT;
+            };
+            let [public_key, message, r, s]: [Felt; 4] = record.try_into().unwrap();
+            let status = status_from_verification(verify(&public_key, &message, &r, &s));
+            differential::report_verification(&[public_key, message, r, s], status);
+            status
+        });
+
+        let first_failing = statuses.iter().position(|&status| status != SUCCESS);
+        unsafe { *failing_index = first_failing.unwrap_or(count) };
+        first_failing.map_or(SUCCESS, |i| statuses[i])
+    })
+}
+
+// Hashes the (offset, length) spans of a shared array of felts into contiguous felts.
+fn span_hashes(
+    felts: *const u8,
+    felt_count: usize,
+    spans: *const u64,
+    span_count: usize,
+    results: *mut u8,
+    primitive: u32,
+    hash: impl Fn(&[Felt]) -> Felt + Sync + Send,
+) -> i32 {
+    if results.is_null() && span_count > 0 {
+        return ERR_INVALID_LENGTH;
+    }
+    let Some(descriptor_count) = span_count.checked_mul(2) else {
+        return ERR_INVALID_LENGTH;
+    };
+    let (felts, descriptors) = match (
+        field_elements_from_bytes(felts, felt_count),
+        slice_from_ptr(spans, descriptor_count),
+    ) {
+        (Ok(felts), Ok(descriptors)) => (felts, descriptors),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    // Each span must lie in the felts
+    let spans: Option<Vec<&[Felt]>> = descriptors
+        .chunks_exact(2)
+        .map(|span| {
+            let offset = usize::try_from(span[0]).ok()?;
+            let end = offset.checked_add(usize::try_from(span[1]).ok()?)?;
+            felts.get(offset..end)
+        })
+        .collect();
+    let Some(spans) = spans else {
+        return ERR_INVALID_LENGTH;
+    };
+    let hashes = map_batch(span_count, |i| hash(spans[i]));
+    for (i, hash) in hashes.into_iter().enumerate() {
+        differential::report(primitive, spans[i], &[hash]);
+        bytes_from_field_element(hash, unsafe { results.add(32 * i) });
+    }
+    SUCCESS
+}
+
+/// Computes the poseidon_hash_many of `span_count` spans of a shared array of `felt_count` felts
+/// into `span_count` contiguous felts. The spans are contiguous (offset, length) pairs of felt
+/// counts, and ERR_INVALID_LENGTH is returned when one doesn't lie in the felts
+#[no_mangle]
+pub extern "C" fn poseidon_hash_many_spans(
+    felts: *const u8,
+    felt_count: usize,
+    spans: *const u64,
+    span_count: usize,
+    results: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        let _profile = Profile::start(Primitive::Poseidon, span_count);
+        span_hashes(
+            felts,
+            felt_count,
+            spans,
+            span_count,
+            results,
+            DIFF_POSEIDON_MANY,
+            |felts| poseidon_hash_many(felts),
+        )
+    })
+}
+
+/// Computes the pedersen_hash_on_elements of `span_count` spans of a shared array of `felt_count`
+/// felts into `span_count` contiguous felts. The spans are contiguous (offset, length) pairs of felt
+/// counts, and ERR_INVALID_LENGTH is returned when one doesn't lie in the felts
+#[no_mangle]
+pub extern "C" fn pedersen_hash_on_elements_spans(
+    felts: *const u8,
+    felt_count: usize,
+    spans: *const u64,
+    span_count: usize,
+    results: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        let _profile = Profile::start(Primitive::Pedersen, span_count);
+        span_hashes(
+            felts,
+            felt_count,
+            spans,
+            span_count,
+            results,
+            DIFF_PEDERSEN_ON_ELEMENTS,
+            hash_on_elements,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt_bytes(hex: &str) -> [u8; 32] {
+        Felt::from_hex(hex).unwrap().to_bytes_be()
+    }
+
+    #[test]
+    fn test_pedersen_hash_batch() {
+        let a = felt_bytes("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb");
+        let b = felt_bytes("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a");
+        let pairs = [a, b, b, a].concat();
+        let mut result = [0u8; 64];
+        assert_eq!(
+            pedersen_hash_batch(pairs.as_ptr(), 2, result.as_mut_ptr()),
+            SUCCESS
+        );
+
+        assert_eq!(
+            result[..32],
+            felt_bytes("0x30e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662")
+        );
+        let reversed = pedersen_hash(&Felt::from_bytes_be(&b), &Felt::from_bytes_be(&a));
+        assert_eq!(result[32..], reversed.to_bytes_be());
+    }
+
+    #[test]
+    fn test_pedersen_hash_batch_empty() {
+        assert_eq!(
+            pedersen_hash_batch(core::ptr::null(), 0, core::ptr::null_mut()),
+            SUCCESS
+        );
+    }
+
+    #[test]
+    fn test_poseidon_permute_batch() {
+        let mut first = [Felt::ZERO, Felt::ONE, Felt::TWO];
+        let mut second = [Felt::THREE, Felt::TWO, Felt::ONE];
+        let mut states: Vec<u8> = first
+            .iter()
+            .chain(second.iter())
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        assert_eq!(poseidon_permute_batch(states.as_mut_ptr(), 2), SUCCESS);
+
+        poseidon_permute_comp(&mut first);
+        poseidon_permute_comp(&mut second);
+        let expected: Vec<u8> = first
+            .iter()
+            .chain(second.iter())
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        assert_eq!(states, expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_get_public_key_batch() {
+        let private_keys: Vec<Felt> = [1u64, 2, 0xdead_beef, 1 << 60]
+            .into_iter()
+            .map(Felt::from)
+            .collect();
+        let bytes: Vec<u8> = private_keys
+            .iter()
+            .flat_map(|key| key.to_bytes_be())
+            .collect();
+        let mut public_keys = vec![0u8; 32 * 4];
+        assert_eq!(
+            get_public_key_batch(bytes.as_ptr(), 4, public_keys.as_mut_ptr()),
+            SUCCESS
+        );
+        for (key, public_key) in private_keys.iter().zip(public_keys.chunks_exact(32)) {
+            assert_eq!(
+                public_key,
+                starknet_crypto_rs::get_public_key(key).to_bytes_be()
+            );
+        }
+
+        let mut bytes = bytes;
+        bytes[32] = 0xff;
+        assert_eq!(
+            get_public_key_batch(bytes.as_ptr(), 4, public_keys.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(
+            get_public_key_batch(bytes.as_ptr(), 4, core::ptr::null_mut()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_ecdsa_sign_batch() {
+        use crate::{ecdsa_sign_seeded, ERR_INVALID_MESSAGE_HASH};
+        use starknet_crypto_rs::{get_public_key, rfc6979_generate_k, sign};
+
+        let private_key = Felt::from(0x1234u64);
+        let messages: Vec<Felt> = (1..=5u64).map(Felt::from).collect();
+        let bytes: Vec<u8> = messages
+            .iter()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        let (mut rs, mut ss) = (vec![0u8; 32 * 5], vec![0u8; 32 * 5]);
+        assert_eq!(
+            ecdsa_sign_batch(
+                private_key.to_bytes_be().as_mut_ptr(),
+                bytes.as_ptr(),
+                5,
+                rs.as_mut_ptr(),
+                ss.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        let public_key = get_public_key(&private_key);
+        for (i, message) in messages.iter().enumerate() {
+            let k = rfc6979_generate_k(message, &private_key, None);
+            let expected = sign(&private_key, message, &k).unwrap();
+            let r = Felt::from_bytes_be(rs[32 * i..32 * (i + 1)].try_into().unwrap());
+            let s = Felt::from_bytes_be(ss[32 * i..32 * (i + 1)].try_into().unwrap());
+            assert_eq!((r, s), (expected.r, expected.s));
+            assert!(verify(&public_key, message, &expected.r, &expected.s).unwrap());
+
+            // The nonces without a seed are not the ones of a seed
+            let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+            ecdsa_sign_seeded(
+                private_key.to_bytes_be().as_mut_ptr(),
+                message.to_bytes_be().as_mut_ptr(),
+                Felt::ONE.to_bytes_be().as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            );
+            assert_ne!(r, rs[32 * i..32 * (i + 1)]);
+        }
+
+        // A message hash out of range fails the whole batch
+        let mut bytes = bytes;
+        bytes[32 * 3] = 0x08;
+        let mut untouched = vec![0u8; 32 * 5];
+        assert_eq!(
+            ecdsa_sign_batch(
+                private_key.to_bytes_be().as_mut_ptr(),
+                bytes.as_ptr(),
+                5,
+                untouched.as_mut_ptr(),
+                ss.as_mut_ptr()
+            ),
+            ERR_INVALID_MESSAGE_HASH
+        );
+        assert_eq!(untouched, vec![0u8; 32 * 5]);
+        assert_eq!(
+            ecdsa_sign_batch(
+                private_key.to_bytes_be().as_mut_ptr(),
+                bytes.as_ptr(),
+                5,
+                core::ptr::null_mut(),
+                ss.as_mut_ptr()
+            ),
+            ERR_INVALID_LENGTH
+        );
+
+        // No k is valid for a zero message hash and a zero private key
+        let zeros = [0u8; 32 * 2];
+        assert_eq!(
+            ecdsa_sign_batch(
+                [0u8; 32].as_mut_ptr(),
+                zeros.as_ptr(),
+                2,
+                untouched.as_mut_ptr(),
+                ss.as_mut_ptr()
+            ),
+            crate::ERR_INVALID_K
+        );
+        assert_eq!(untouched, vec![0u8; 32 * 5]);
+    }
+
+    #[test]
+    fn test_verify_signature_batch() {
+        let private_key =
+            Felt::from_hex("0x1e2feb89414c343c1027c4d1c386bbc4cd613e30d8f16adf91b7584a2265b1f")
+                .unwrap();
+        let public_key = starknet_crypto_rs::get_public_key(&private_key);
+        let messages: Vec<Felt> = (1..=9u8).map(Felt::from).collect();
+        let mut signatures: Vec<_> = messages
+            .iter()
+            .map(|message| starknet_crypto_rs::sign(&private_key, message, &Felt::THREE).unwrap())
+            .collect();
+        // Break the signatures of the second and the ninth messages
+        signatures[1].s = signatures[0].s;
+        signatures[8].r = Felt::ONE;
+
+        let to_bytes = |felts: Vec<Felt>| -> Vec<u8> {
+            felts.iter().flat_map(|felt| felt.to_bytes_be()).collect()
+        };
+        let public_keys = to_bytes(vec![public_key; 9]);
+        let rs = to_bytes(signatures.iter().map(|signature| signature.r).collect());
+        let ss = to_bytes(signatures.iter().map(|signature| signature.s).collect());
+        let messages = to_bytes(messages);
+        let mut results = [0xffu8; 2];
+        let mut valid_count = 0;
+        assert_eq!(
+            verify_signature_batch(
+                public_keys.as_ptr(),
+                messages.as_ptr(),
+                rs.as_ptr(),
+                ss.as_ptr(),
+                9,
+                results.as_mut_ptr(),
+                &mut valid_count,
+            ),
+            SUCCESS
+        );
+        assert_eq!(valid_count, 7);
+        assert_eq!(results, [0b1111_1101, 0b0000_0000]);
+    }
+
+    #[test]
+    fn test_batch_layouts() {
+        let felts = |values: &[u64]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|&value| Felt::from(value).to_bytes_be())
+                .collect()
+        };
+        let pairs = felts(&[1, 2, 3, 4]);
+        let mut interleaved = [0u8; 64];
+        assert_eq!(
+            pedersen_hash_batch(pairs.as_ptr(), 2, interleaved.as_mut_ptr()),
+            SUCCESS
+        );
+        let (first, second) = (felts(&[1, 3]), felts(&[2, 4]));
+        let mut columns = [0u8; 64];
+        assert_eq!(
+            pedersen_hash_batch_layout(
+                [first.as_ptr(), second.as_ptr()].as_ptr(),
+                LAYOUT_COLUMNS,
+                2,
+                columns.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(columns, interleaved);
+
+        // The states are permuted in place in their columns
+        let mut states = felts(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(poseidon_permute_batch(states.as_mut_ptr(), 2), SUCCESS);
+        let mut first = felts(&[1, 4]);
+        let mut second = felts(&[2, 5]);
+        let mut third = felts(&[3, 6]);
+        assert_eq!(
+            poseidon_permute_batch_layout(
+                [first.as_mut_ptr(), second.as_mut_ptr(), third.as_mut_ptr()].as_ptr(),
+                LAYOUT_COLUMNS,
+                2
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            [&first[..32], &second[..32], &third[..32]].concat(),
+            states[..96]
+        );
+        assert_eq!(
+            [&first[32..], &second[32..], &third[32..]].concat(),
+            states[96..]
+        );
+
+        let mut result = [0u8; 32];
+        assert_eq!(
+            pedersen_hash_batch_layout([pairs.as_ptr()].as_ptr(), 2, 1, result.as_mut_ptr()),
+            ERR_INVALID_LAYOUT
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_batch_interleaved() {
+        let private_key =
+            Felt::from_hex("0x1e2feb89414c343c1027c4d1c386bbc4cd613e30d8f16adf91b7584a2265b1f")
+                .unwrap();
+        let public_key = starknet_crypto_rs::get_public_key(&private_key);
+        let signature = starknet_crypto_rs::sign(&private_key, &Felt::TWO, &Felt::THREE).unwrap();
+        let records: Vec<u8> = [
+            [public_key, Felt::TWO, signature.r, signature.s],
+            [public_key, Felt::ONE, signature.r, signature.s],
+        ]
+        .iter()
+        .flatten()
+        .flat_map(|felt| felt.to_bytes_be())
+        .collect();
+        let mut results = [0u8; 1];
+        let mut valid_count = 0;
+        assert_eq!(
+            verify_signature_batch_layout(
+                [records.as_ptr()].as_ptr(),
+                LAYOUT_INTERLEAVED,
+                2,
+                results.as_mut_ptr(),
+                &mut valid_count
+            ),
+            SUCCESS
+        );
+        assert_eq!(results, [0b01]);
+        assert_eq!(valid_count, 1);
+    }
+
+    #[test]
+    fn test_ecdsa_builtin_validate() {
+        let private_key =
+            Felt::from_hex("0x1e2feb89414c343c1027c4d1c386bbc4cd613e30d8f16adf91b7584a2265b1f")
+                .unwrap();
+        let public_key = starknet_crypto_rs::get_public_key(&private_key);
+        let mut records: Vec<[Felt; 4]> = (1..=4u8)
+            .map(|message| {
+                let message = Felt::from(message);
+                let signature =
+                    starknet_crypto_rs::sign(&private_key, &message, &Felt::THREE).unwrap();
+                [public_key, message, signature.r, signature.s]
+            })
+            .collect();
+        let validate = |records: &[[Felt; 4]]| {
+            let cells: Vec<u8> = records
+                .iter()
+                .flatten()
+                .flat_map(|felt| felt.to_bytes_be())
+                .collect();
+            let mut failing_index = usize::MAX;
+            let status = ecdsa_builtin_validate(cells.as_ptr(), records.len(), &mut failing_index);
+            (status, failing_index)
+        };
+        assert_eq!(validate(&records), (SUCCESS, 4));
+
+        // The first of the failing instances is reported
+        records[3][1] = Felt::TWO;
+        records[2][1] = Felt::ONE;
+        assert_eq!(validate(&records), (ERR_INVALID_SIGNATURE, 2));
+        let mut cells: Vec<u8> = records[..2]
+            .iter()
+            .flatten()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        cells[128 + 64..128 + 96].fill(0xff);
+        let mut failing_index = 0;
+        assert_eq!(
+            ecdsa_builtin_validate(cells.as_ptr(), 2, &mut failing_index),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(failing_index, 1);
+        assert_eq!(validate(&[]), (SUCCESS, 0));
+    }
+
+    #[test]
+    fn test_hash_spans() {
+        let elements: Vec<Felt> = (1..=5u8).map(Felt::from).collect();
+        let felts: Vec<u8> = elements
+            .iter()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        // Spans may be empty or overlap
+        let spans = [0u64, 2, 1, 4, 5, 0];
+        let mut results = [0u8; 96];
+        assert_eq!(
+            poseidon_hash_many_spans(felts.as_ptr(), 5, spans.as_ptr(), 3, results.as_mut_ptr()),
+            SUCCESS
+        );
+        let expected: Vec<u8> = [&elements[0..2], &elements[1..5], &elements[5..5]]
+            .iter()
+            .flat_map(|span| poseidon_hash_many(*span).to_bytes_be())
+            .collect();
+        assert_eq!(results[..], expected);
+
+        assert_eq!(
+            pedersen_hash_on_elements_spans(
+                felts.as_ptr(),
+                5,
+                spans.as_ptr(),
+                3,
+                results.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        let expected: Vec<u8> = [&elements[0..2], &elements[1..5], &elements[5..5]]
+            .iter()
+            .flat_map(|span| hash_on_elements(span).to_bytes_be())
+            .collect();
+        assert_eq!(results[..], expected);
+
+        // A span past the end of the felts
+        let spans = [3u64, 3];
+        assert_eq!(
+            poseidon_hash_many_spans(felts.as_ptr(), 5, spans.as_ptr(), 1, results.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+        let spans = [u64::MAX, 2];
+        assert_eq!(
+            poseidon_hash_many_spans(felts.as_ptr(), 5, spans.as_ptr(), 1, results.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_batch() {
+        let elements: Vec<Felt> = (0..64u8).map(Felt::from).collect();
+        let pairs: Vec<u8> = elements
+            .iter()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        let mut sequential = [0u8; 32 * 32];
+        assert_eq!(
+            pedersen_hash_batch(pairs.as_ptr(), 32, sequential.as_mut_ptr()),
+            SUCCESS
+        );
+
+        assert_eq!(starknet_crypto_set_threads(4), SUCCESS);
+        let mut parallel = [0u8; 32 * 32];
+        assert_eq!(
+            pedersen_hash_batch(pairs.as_ptr(), 32, parallel.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(starknet_crypto_set_threads(1), SUCCESS);
+
+        assert_eq!(sequential, parallel);
+    }
+}
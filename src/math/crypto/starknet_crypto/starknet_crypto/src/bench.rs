@@ -0,0 +1,105 @@
+// Timing of the primitives from inside the library, for the benchmark step of the Zig build.
+//
+// Each primitive runs over fixed inputs through the Rust functions behind its exported function,
+// without crossing the FFI, so comparing the time of a Zig loop over the exported function with
+// starknet_crypto_bench gives the cost of the calls themselves.
+use core::hint::black_box;
+use std::time::Instant;
+
+use starknet_crypto_rs::{pedersen_hash, poseidon_hash_many};
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    backend,
+    ecdsa::verify,
+    hades::{self, poseidon_hash, poseidon_permute_comp},
+    keccak::sn_keccak,
+    BENCH_ECDSA_VERIFY, BENCH_FELT_MUL, BENCH_HADES_POSEIDON_MANY, BENCH_KECCAK, BENCH_PEDERSEN,
+    BENCH_POSEIDON, BENCH_POSEIDON_MANY, BENCH_POSEIDON_PERMUTE,
+};
+
+// The valid signature of the message 2 checked by starknet_crypto_selftest.
+const PUBLIC_KEY: &str = "0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca";
+const R: &str = "0x411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20";
+const S: &str = "0x405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b";
+
+fn felt(hex: &str) -> Felt {
+    Felt::from_hex(hex).unwrap()
+}
+
+fn time(iterations: u64, mut run: impl FnMut()) -> u64 {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        run();
+    }
+    (start.elapsed().as_nanos() / iterations as u128) as u64
+}
+
+/// Runs a BENCH_* primitive `iterations` times straight in Rust and returns the mean nanoseconds
+/// per run. Returns 0 for an unknown primitive or no iterations
+#[no_mangle]
+pub extern "C" fn starknet_crypto_bench(op_id: u32, iterations: u64) -> u64 {
+    if iterations == 0 {
+        return 0;
+    }
+    let (a, b) = (felt("0x1234"), felt("0x5678"));
+    match op_id {
+        BENCH_PEDERSEN => time(iterations, || {
+            black_box(pedersen_hash(black_box(&a), black_box(&b)));
+        }),
+        BENCH_POSEIDON => time(iterations, || {
+            black_box(poseidon_hash(black_box(a), black_box(b)));
+        }),
+        BENCH_POSEIDON_PERMUTE => {
+            let mut state = [a, b, Felt::TWO];
+            time(iterations, || poseidon_permute_comp(black_box(&mut state)))
+        }
+        BENCH_ECDSA_VERIFY => {
+            let (public_key, r, s) = (felt(PUBLIC_KEY), felt(R), felt(S));
+            time(iterations, || {
+                let valid = verify(&public_key, black_box(&Felt::TWO), &r, &s);
+                black_box(valid).ok();
+            })
+        }
+        BENCH_KECCAK => time(iterations, || {
+            black_box(sn_keccak(black_box(&[0xab; 64])));
+        }),
+        BENCH_FELT_MUL => {
+            let a = backend::felt_from_bytes(&a.to_bytes_be()).unwrap();
+            let b = backend::felt_from_bytes(&b.to_bytes_be()).unwrap();
+            time(iterations, || {
+                black_box(backend::felt_mul(black_box(&a), black_box(&b)));
+            })
+        }
+        BENCH_POSEIDON_MANY | BENCH_HADES_POSEIDON_MANY => {
+            // Both are timed in every build, whichever one the `hades` feature picks for the library
+            let hash_many = if op_id == BENCH_POSEIDON_MANY {
+                poseidon_hash_many
+            } else {
+                hades::hash_many
+            };
+            let felts: Vec<Felt> = (0..16u64).map(|i| a + Felt::from(i)).collect();
+            time(iterations, || {
+                black_box(hash_many(black_box(&felts)));
+            })
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starknet_crypto_bench() {
+        assert!(verify(&felt(PUBLIC_KEY), &Felt::TWO, &felt(R), &felt(S)).unwrap());
+        for op_id in BENCH_PEDERSEN..=BENCH_HADES_POSEIDON_MANY {
+            // Every primitive takes some time, except maybe the felt multiplication
+            let nanoseconds = starknet_crypto_bench(op_id, 3);
+            assert!(nanoseconds > 0 || op_id == BENCH_FELT_MUL);
+        }
+        assert_eq!(starknet_crypto_bench(BENCH_HADES_POSEIDON_MANY + 1, 3), 0);
+        assert_eq!(starknet_crypto_bench(BENCH_PEDERSEN, 0), 0);
+    }
+}
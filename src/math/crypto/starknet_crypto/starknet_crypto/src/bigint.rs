@@ -0,0 +1,251 @@
+// Modular arithmetic over big integers for the hints whose moduli are not the STARK prime, like the
+// `div_mod` hints of the secp and BLS12-381 primes and orders.
+//
+// The integers are big endian of at most 128 bytes, computed over 1024 bits integers. The BigInt3
+// and BigInt5 of the secp hints are their 3 or 5 limbs of 86 bits as felts, from the lowest one,
+// which the hints read as signed felts and the multiplications leave unreduced.
+use elliptic_curve::bigint::{Encoding, NonZero, U1024};
+
+use crate::{
+    array_from_bytes, bytes_from_array,
+    felt::{HALF_MODULUS_BYTES, MODULUS_BYTES},
+    ffi_guard, slice_from_ptr, Bytes, ERR_DIVISION_BY_ZERO, ERR_INVALID_FELT, ERR_INVALID_LENGTH,
+    SUCCESS,
+};
+
+// The greatest length in bytes of the integers.
+const MAX_LEN: usize = 128;
+// The number of bits of the limbs of BigInt3 and BigInt5.
+const LIMB_BITS: usize = 86;
+
+// Reads a big endian integer of `len` bytes from a C array.
+fn uint_from_ptr(ptr: *const u8, len: usize) -> Result<U1024, i32> {
+    let bytes = slice_from_ptr(ptr, len)?;
+    if bytes.len() > MAX_LEN {
+        return Err(ERR_INVALID_LENGTH);
+    }
+    let mut padded = [0u8; MAX_LEN];
+    padded[MAX_LEN - bytes.len()..].copy_from_slice(bytes);
+    Ok(U1024::from_be_bytes(padded))
+}
+
+/// Computes a * b^-1 mod m for big endian integers of at most 128 bytes, writing the result over
+/// `out_len` bytes, at least `m_len`, into the output. Returns ERR_DIVISION_BY_ZERO when b has no
+/// inverse modulo m, m being zero included, and ERR_INVALID_LENGTH for the longer integers
+#[no_mangle]
+pub extern "C" fn bigint_div_mod(
+    a: *const u8,
+    a_len: usize,
+    b: *const u8,
+    b_len: usize,
+    m: *const u8,
+    m_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        let (a, b, m) = match (
+            uint_from_ptr(a, a_len),
+            uint_from_ptr(b, b_len),
+            uint_from_ptr(m, m_len),
+        ) {
+            (Ok(a), Ok(b), Ok(m)) => (a, b, m),
+            (Err(status), _, _) | (_, Err(status), _) | (_, _, Err(status)) => return status,
+        };
+        if out_len < m_len || out.is_null() {
+            return ERR_INVALID_LENGTH;
+        }
+        let Some(modulus) = Option::<NonZero<U1024>>::from(NonZero::new(m)) else {
+            return ERR_DIVISION_BY_ZERO;
+        };
+
+        // Every integer is zero modulo 1, the invertible zero included
+        let result = if m == U1024::ONE {
+            U1024::ZERO
+        } else {
+            let (b_inverse, invertible) = b.rem(&modulus).inv_mod(&m);
+            if !bool::from(invertible) {
+                return ERR_DIVISION_BY_ZERO;
+            }
+            let product = a.rem(&modulus).mul_wide(&b_inverse);
+            U1024::const_rem_wide(product, &m).0
+        };
+
+        // The result is lower than m, so the bytes beyond the output are zeroes
+        let out = unsafe { core::slice::from_raw_parts_mut(out, out_len) };
+        let bytes = result.to_be_bytes();
+        let len = out_len.min(MAX_LEN);
+        out[..out_len - len].fill(0);
+        out[out_len - len..].copy_from_slice(&bytes[MAX_LEN - len..]);
+        SUCCESS
+    })
+}
+
+fn uint_from_array(bytes: &[u8; 32]) -> U1024 {
+    let mut padded = [0u8; MAX_LEN];
+    padded[MAX_LEN - 32..].copy_from_slice(bytes);
+    U1024::from_be_bytes(padded)
+}
+
+// Packs `count` contiguous limbs modulo a 32 bytes modulus, like `pack(x, PRIME) % modulus` in the
+// hints: the limbs greater than (p - 1) / 2 stand for the negative integers limb - p.
+fn pack(limbs: *const u8, count: usize, modulus: *const u8, result: Bytes) -> i32 {
+    let Some(modulus) =
+        Option::<NonZero<U1024>>::from(NonZero::new(uint_from_array(&array_from_bytes(modulus))))
+    else {
+        return ERR_DIVISION_BY_ZERO;
+    };
+    let (mut positive, mut negative) = (U1024::ZERO, U1024::ZERO);
+    for i in 0..count {
+        let limb = array_from_bytes(limbs.wrapping_add(32 * i));
+        if limb >= MODULUS_BYTES {
+            return ERR_INVALID_FELT;
+        }
+        // The sums stay below 2^600, far from overflowing
+        if limb > HALF_MODULUS_BYTES {
+            let abs = uint_from_array(&MODULUS_BYTES).wrapping_sub(&uint_from_array(&limb));
+            negative = negative.wrapping_add(&abs.shl_vartime(LIMB_BITS * i));
+        } else {
+            positive = positive.wrapping_add(&uint_from_array(&limb).shl_vartime(LIMB_BITS * i));
+        }
+    }
+    let value = positive
+        .rem(&modulus)
+        .sub_mod(&negative.rem(&modulus), &modulus);
+    bytes_from_array(&value.to_be_bytes()[MAX_LEN - 32..], result);
+    SUCCESS
+}
+
+/// Splits a 32 bytes big endian integer into the 3 limbs of 86 bits of a BigInt3, writing them as
+/// contiguous felts into the last argument, like `split` in the secp hints
+#[no_mangle]
+pub extern "C" fn bigint3_split(value: *const u8, limbs: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(value, limbs);
+        let value = uint_from_array(&array_from_bytes(value));
+        let mask = U1024::ONE.shl_vartime(LIMB_BITS).wrapping_sub(&U1024::ONE);
+        for i in 0..3 {
+            let limb = value.shr_vartime(LIMB_BITS * i).bitand(&mask).to_be_bytes();
+            bytes_from_array(&limb[MAX_LEN - 32..], limbs.wrapping_add(32 * i));
+        }
+        SUCCESS
+    })
+}
+
+/// Packs the 3 contiguous felt limbs of a BigInt3 modulo a 32 bytes big endian modulus, writing the
+/// 32 bytes big endian result into the last argument. The limbs are signed felts, those greater
+/// than (p - 1) / 2 being negative, like `pack(x, PRIME) % modulus` in the secp hints. Returns
+/// ERR_DIVISION_BY_ZERO for a zero modulus
+#[no_mangle]
+pub extern "C" fn bigint3_pack(limbs: *const u8, modulus: *const u8, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(limbs, modulus, result);
+        pack(limbs, 3, modulus, result)
+    })
+}
+
+/// Packs the 5 contiguous felt limbs of a BigInt5, the unreduced product of two BigInt3, like
+/// bigint3_pack
+#[no_mangle]
+pub extern "C" fn bigint5_pack(limbs: *const u8, modulus: *const u8, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(limbs, modulus, result);
+        pack(limbs, 5, modulus, result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex_bytes;
+
+    fn div_mod(a: &[u8], b: &[u8], m: &[u8], out_len: usize) -> (i32, Vec<u8>) {
+        let mut out = vec![0xaau8; out_len];
+        let status = bigint_div_mod(
+            a.as_ptr(),
+            a.len(),
+            b.as_ptr(),
+            b.len(),
+            m.as_ptr(),
+            m.len(),
+            out.as_mut_ptr(),
+            out_len,
+        );
+        (status, out)
+    }
+
+    #[test]
+    fn test_bigint_div_mod() {
+        assert_eq!(div_mod(&[1], &[2], &[7], 1), (SUCCESS, vec![4]));
+        // The even moduli too: 5 is its own inverse modulo 8
+        assert_eq!(div_mod(&[3], &[5], &[8], 2), (SUCCESS, vec![0, 7]));
+        assert_eq!(div_mod(&[3], &[5], &[1], 1), (SUCCESS, vec![0]));
+
+        // Over the BLS12-381 prime, with an operand longer than the modulus and a wider output
+        let p = hex_bytes(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        );
+        let a = [0x5cu8; 96];
+        let (status, quotient) = div_mod(&a, &[3], &p, 64);
+        assert_eq!(status, SUCCESS);
+        assert_eq!(quotient[..16], [0u8; 16]);
+        // quotient * 3 = a mod p
+        let (_, reduced) = div_mod(&a, &[1], &p, 48);
+        let tripled = U1024::const_rem_wide(
+            uint_from_ptr(quotient.as_ptr(), 64)
+                .unwrap()
+                .mul_wide(&U1024::from_u8(3)),
+            &uint_from_ptr(p.as_ptr(), p.len()).unwrap(),
+        )
+        .0;
+        assert_eq!(tripled.to_be_bytes()[80..], reduced[..]);
+
+        assert_eq!(div_mod(&[1], &[2], &[8], 1).0, ERR_DIVISION_BY_ZERO);
+        assert_eq!(div_mod(&[1], &[7], &[7], 1).0, ERR_DIVISION_BY_ZERO);
+        assert_eq!(div_mod(&[1], &[2], &[0, 0], 2).0, ERR_DIVISION_BY_ZERO);
+        assert_eq!(div_mod(&[1], &[2], &[1, 7], 1).0, ERR_INVALID_LENGTH);
+        assert_eq!(div_mod(&[1; 129], &[2], &[7], 1).0, ERR_INVALID_LENGTH);
+    }
+
+    #[test]
+    fn test_bigint_pack() {
+        use starknet_types_core::felt::Felt;
+
+        let secp_p = hex_bytes("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+        let value = hex_bytes("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let mut limbs = [0u8; 96];
+        assert_eq!(bigint3_split(value.as_ptr(), limbs.as_mut_ptr()), SUCCESS);
+        let low = Felt::from_hex("0xe28d959f2815b16f81798").unwrap();
+        assert_eq!(limbs[..32], low.to_bytes_be());
+        let mut result = [0u8; 32];
+        assert_eq!(
+            bigint3_pack(limbs.as_ptr(), secp_p.as_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result[..], value[..]);
+
+        // The limbs (-1, 1, 0, 0, 2) with a negative one, (-1 + 2^86 + 2 * 2^344) % 101 == 63
+        let mut wide = [0u8; 160];
+        wide[..32].copy_from_slice(&(-Felt::ONE).to_bytes_be());
+        wide[63] = 1;
+        wide[159] = 2;
+        let mut modulus = [0u8; 32];
+        modulus[31] = 101;
+        assert_eq!(
+            bigint5_pack(wide.as_ptr(), modulus.as_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result[..31], [0u8; 31]);
+        assert_eq!(result[31], 63);
+
+        assert_eq!(
+            bigint3_pack(limbs.as_ptr(), [0u8; 32].as_ptr(), result.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        limbs[..32].copy_from_slice(&MODULUS_BYTES);
+        assert_eq!(
+            bigint3_pack(limbs.as_ptr(), secp_p.as_ptr(), result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+}
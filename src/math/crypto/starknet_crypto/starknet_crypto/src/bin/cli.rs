@@ -0,0 +1,178 @@
+// A command line front end to the exported functions, to reproduce the answer of the library for
+// the inputs of the Zig VM in one shell command:
+//
+//     starknet-crypto-cli pedersen 0x1 0x2
+//     starknet-crypto-cli pedersen-many 0x1 0x2 0x3
+//     starknet-crypto-cli poseidon 0x1 0x2
+//     starknet-crypto-cli poseidon-many 0x1 0x2 0x3
+//     starknet-crypto-cli verify --pubkey 0x... --msg 0x... --r 0x... --s 0x...
+//
+// The felts are hexadecimal with a 0x prefix or decimal, and the results are printed in
+// hexadecimal. The errors of the library are printed with their starknet_crypto_last_error message.
+use std::process::ExitCode;
+
+use starknet_crypto::{
+    pedersen_hash, pedersen_hash_on_elements, poseidon_hash, poseidon_hash_many,
+    starknet_crypto_last_error, verify_signature, ERR_INVALID_SIGNATURE, SUCCESS,
+};
+use starknet_types_core::felt::Felt;
+
+const USAGE: &str = "usage:
+    starknet-crypto-cli pedersen <a> <b>
+    starknet-crypto-cli pedersen-many <felt>...
+    starknet-crypto-cli poseidon <a> <b>
+    starknet-crypto-cli poseidon-many <felt>...
+    starknet-crypto-cli verify --pubkey <felt> --msg <felt> --r <felt> --s <felt>";
+
+fn parse_felt(arg: &str) -> Result<[u8; 32], String> {
+    let felt = match arg.strip_prefix("0x") {
+        Some(hex) => Felt::from_hex(hex),
+        None => Felt::from_dec_str(arg),
+    };
+    felt.map(|felt| felt.to_bytes_be())
+        .map_err(|_| format!("not a felt: {arg}"))
+}
+
+fn parse_felts(args: &[String]) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(32 * args.len());
+    for arg in args {
+        bytes.extend(parse_felt(arg)?);
+    }
+    Ok(bytes)
+}
+
+// The value following each of the flags, in any order.
+fn parse_flags<const N: usize>(args: &[String], flags: [&str; N]) -> Result<[[u8; 32]; N], String> {
+    let mut values = [None; N];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(i) = flags.iter().position(|flag| flag == arg) else {
+            return Err(format!("unexpected argument: {arg}"));
+        };
+        let value = args.next().ok_or(format!("missing value of {arg}"))?;
+        values[i] = Some(parse_felt(value)?);
+    }
+    let mut felts = [[0u8; 32]; N];
+    for (i, value) in values.into_iter().enumerate() {
+        felts[i] = value.ok_or(format!("missing {}", flags[i]))?;
+    }
+    Ok(felts)
+}
+
+fn hex(felt: &[u8; 32]) -> String {
+    format!("{:#x}", Felt::from_bytes_be(felt))
+}
+
+fn last_error() -> String {
+    let mut message = [0u8; 256];
+    let mut message_len = 0;
+    starknet_crypto_last_error(message.as_mut_ptr(), message.len(), &mut message_len);
+    String::from_utf8_lossy(&message[..message_len.min(message.len())]).into_owned()
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, args) = args.split_first().ok_or(USAGE)?;
+    let mut result = [0u8; 32];
+    let status = match (command.as_str(), args) {
+        ("pedersen", [a, b]) => {
+            let (mut a, mut b) = (parse_felt(a)?, parse_felt(b)?);
+            pedersen_hash(a.as_mut_ptr(), b.as_mut_ptr(), result.as_mut_ptr())
+        }
+        ("poseidon", [a, b]) => {
+            let (mut a, mut b) = (parse_felt(a)?, parse_felt(b)?);
+            poseidon_hash(a.as_mut_ptr(), b.as_mut_ptr(), result.as_mut_ptr())
+        }
+        ("pedersen-many", felts) => {
+            let bytes = parse_felts(felts)?;
+            pedersen_hash_on_elements(bytes.as_ptr(), felts.len(), result.as_mut_ptr())
+        }
+        ("poseidon-many", felts) => {
+            let bytes = parse_felts(felts)?;
+            poseidon_hash_many(bytes.as_ptr(), felts.len(), result.as_mut_ptr())
+        }
+        ("verify", flags) => {
+            let [mut public_key, mut message, mut r, mut s] =
+                parse_flags(flags, ["--pubkey", "--msg", "--r", "--s"])?;
+            let status = verify_signature(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            );
+            return match status {
+                SUCCESS => Ok("valid".into()),
+                ERR_INVALID_SIGNATURE => Err("invalid".into()),
+                _ => Err(last_error()),
+            };
+        }
+        _ => return Err(USAGE.into()),
+    };
+    if status != SUCCESS {
+        return Err(last_error());
+    }
+    Ok(hex(&result))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_args(args: &[&str]) -> Result<String, String> {
+        run(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_cli() {
+        let a = "0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb";
+        let b = "0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a";
+        assert_eq!(
+            run_args(&["pedersen", a, b]).unwrap(),
+            "0x30e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662"
+        );
+        let expected = starknet_crypto_rs::poseidon_hash_many(&[1u8, 2, 3].map(Felt::from));
+        assert_eq!(
+            run_args(&["poseidon-many", "0x1", "2", "0x3"]).unwrap(),
+            format!("{expected:#x}")
+        );
+
+        let private_key = Felt::ONE;
+        let message = Felt::TWO;
+        let signature = starknet_crypto_rs::sign(&private_key, &message, &Felt::THREE).unwrap();
+        let public_key = starknet_crypto_rs::get_public_key(&private_key);
+        let [public_key, message, r, s] =
+            [public_key, message, signature.r, signature.s].map(|felt| format!("{felt:#x}"));
+        let verify = |s: &str| {
+            run_args(&[
+                "verify",
+                "--msg",
+                &message,
+                "--r",
+                &r,
+                "--s",
+                s,
+                "--pubkey",
+                &public_key,
+            ])
+        };
+        assert_eq!(verify(&s).unwrap(), "valid");
+        assert_eq!(verify("0x1").unwrap_err(), "invalid");
+
+        assert!(run_args(&["pedersen", "0x1"]).is_err());
+        assert!(run_args(&["pedersen", "0x1", "nope"]).is_err());
+        assert!(run_args(&["verify", "--msg", "0x1"]).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+// Blake2s primitives used by the blake2s hint family.
+//
+// The hashes are blake2s-256 without a key: the parameter block sets the digest length to 32 bytes
+// and the fanout and depth to 1. Like the stream contexts, the blake2s contexts are opaque to C and
+// released with blake2s_ctx_free.
+use alloc::boxed::Box;
+
+use crate::{bytes_from_array, ffi_guard, slice_from_ptr, SUCCESS};
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+fn mix(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
+
+// The blake2s compression function F, as described in RFC 7693, updating `h` in place.
+fn compress(h: &mut [u32; 8], m: &[u32; 16], t0: u32, t1: u32, f0: u32, f1: u32) {
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= t0;
+    v[13] ^= t1;
+    v[14] ^= f0;
+    v[15] ^= f1;
+
+    for s in SIGMA.iter() {
+        mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Applies the blake2s compression function in place over a state of 8 words, given a message block
+/// of 16 words, the offset counter (t0, t1) and the finalization flags (f0, f1)
+#[no_mangle]
+pub extern "C" fn blake2s_compress(
+    state: *mut u32,
+    block: *const u32,
+    t0: u32,
+    t1: u32,
+    f0: u32,
+    f1: u32,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(state, block);
+        // The state is 8 words updated in place, the block is 16 words
+        let state = unsafe { &mut *(state as *mut [u32; 8]) };
+        let block = unsafe { &*(block as *const [u32; 16]) };
+        compress(state, block, t0, t1, f0, f1);
+        SUCCESS
+    })
+}
+
+/// An incremental blake2s-256 hash
+pub struct Blake2sContext {
+    h: [u32; 8],
+    // The last block is only compressed on finalization, with the finalization flag
+    buffer: [u8; 64],
+    buffered: usize,
+    // The number of bytes compressed so far
+    counter: u64,
+}
+
+impl Default for Blake2sContext {
+    fn default() -> Self {
+        let mut h = IV;
+        h[0] ^= 0x01010020;
+        Self {
+            h,
+            buffer: [0; 64],
+            buffered: 0,
+            counter: 0,
+        }
+    }
+}
+
+impl Blake2sContext {
+    fn compress_buffer(&mut self, last: bool) {
+        self.counter += self.buffered as u64;
+        let mut block = [0u32; 16];
+        for (word, bytes) in block.iter_mut().zip(self.buffer.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        let f0 = if last { u32::MAX } else { 0 };
+        compress(
+            &mut self.h,
+            &block,
+            self.counter as u32,
+            (self.counter >> 32) as u32,
+            f0,
+            0,
+        );
+        self.buffered = 0;
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.buffered == 64 {
+                self.compress_buffer(false);
+            }
+            let len = data.len().min(64 - self.buffered);
+            self.buffer[self.buffered..self.buffered + len].copy_from_slice(&data[..len]);
+            self.buffered += len;
+            data = &data[len..];
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        self.buffer[self.buffered..].fill(0);
+        self.compress_buffer(true);
+        let mut digest = [0u8; 32];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.h) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+/// Computes the blake2s-256 hash of `len` bytes, writing the 32 bytes digest into the last argument
+#[no_mangle]
+pub extern "C" fn blake2s(data: *const u8, len: usize, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let mut ctx = Blake2sContext::default();
+        ctx.update(data);
+        bytes_from_array(&ctx.finalize(), result);
+        SUCCESS
+    })
+}
+
+/// Allocates an empty blake2s-256 hash, writing it into the argument. It must be released with
+/// blake2s_ctx_free
+#[no_mangle]
+pub extern "C" fn blake2s_ctx_new(ctx: *mut *mut Blake2sContext) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx);
+        unsafe { *ctx = Box::into_raw(Box::default()) };
+        SUCCESS
+    })
+}
+
+/// Appends `len` bytes to the hashed stream
+#[no_mangle]
+pub extern "C" fn blake2s_ctx_update(ctx: *mut Blake2sContext, data: *const u8, len: usize) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        unsafe { (*ctx).update(data) };
+        SUCCESS
+    })
+}
+
+/// Writes the 32 bytes blake2s-256 digest of the bytes appended so far into the last argument, and
+/// empties the context so that it may hash another stream
+#[no_mangle]
+pub extern "C" fn blake2s_ctx_final(ctx: *mut Blake2sContext, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx, result);
+        let ctx = core::mem::take(unsafe { &mut *ctx });
+        bytes_from_array(&ctx.finalize(), result);
+        SUCCESS
+    })
+}
+
+/// Releases a context allocated by blake2s_ctx_new. Does nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn blake2s_ctx_free(ctx: *mut Blake2sContext) -> i32 {
+    ffi_guard(|| {
+        if !ctx.is_null() {
+            drop(unsafe { Box::from_raw(ctx) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex_bytes;
+
+    #[test]
+    fn test_blake2s_compress_abc() {
+        // Single block blake2s-256 hash of "abc": the parameter block sets the digest length
+        // to 32 bytes and the fanout and depth to 1.
+        let mut state = IV;
+        state[0] ^= 0x01010020;
+        let mut block = [0u32; 16];
+        block[0] = u32::from_le_bytes([b'a', b'b', b'c', 0]);
+
+        assert_eq!(
+            blake2s_compress(state.as_mut_ptr(), block.as_ptr(), 3, 0, u32::MAX, 0),
+            SUCCESS
+        );
+
+        let digest: Vec<u8> = state.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let expected: Vec<u8> = [
+            0x508c5e8c, 0x327c14e2, 0xe1a72ba3, 0x4eeb452f, 0x37458b20, 0x9ed63a29, 0x4d999b4c,
+            0x86675982,
+        ]
+        .iter()
+        .flat_map(|word: &u32| word.to_be_bytes())
+        .collect();
+        assert_eq!(digest, expected);
+    }
+
+    #[test]
+    fn test_blake2s() {
+        let mut result = [0u8; 32];
+        assert_eq!(blake2s(core::ptr::null(), 0, result.as_mut_ptr()), SUCCESS);
+        assert_eq!(
+            result.to_vec(),
+            hex_bytes("69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9")
+        );
+        assert_eq!(blake2s(b"abc".as_ptr(), 3, result.as_mut_ptr()), SUCCESS);
+        assert_eq!(
+            result.to_vec(),
+            hex_bytes("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+        );
+    }
+
+    #[test]
+    fn test_blake2s_ctx() {
+        let data: Vec<u8> = (0..200).map(|i| i as u8).collect();
+        let mut ctx = core::ptr::null_mut();
+        assert_eq!(blake2s_ctx_new(&mut ctx), SUCCESS);
+        // Lengths around the block size, appended in uneven pieces, the later streams reusing the
+        // finalized context
+        for len in [0, 1, 63, 64, 65, 128, 200] {
+            for piece in data[..len].chunks(7) {
+                assert_eq!(
+                    blake2s_ctx_update(ctx, piece.as_ptr(), piece.len()),
+                    SUCCESS
+                );
+            }
+            let (mut streamed, mut expected) = ([0u8; 32], [0u8; 32]);
+            assert_eq!(blake2s_ctx_final(ctx, streamed.as_mut_ptr()), SUCCESS);
+            assert_eq!(blake2s(data.as_ptr(), len, expected.as_mut_ptr()), SUCCESS);
+            assert_eq!(streamed, expected);
+        }
+        assert_eq!(blake2s_ctx_free(ctx), SUCCESS);
+        assert_eq!(blake2s_ctx_free(core::ptr::null_mut()), SUCCESS);
+    }
+}
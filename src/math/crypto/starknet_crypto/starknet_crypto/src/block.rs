@@ -0,0 +1,282 @@
+// Hashes of the Starknet block headers, the Poseidon based scheme of Starknet v0.13.4.
+//
+// A block hash is the Poseidon hash of many felts of "STARKNET_BLOCK_HASH1", the block number,
+// the global state root, the sequencer address, the timestamp, the concatenated counts, the state
+// diff, transaction, event and receipt commitments, the hash of the gas prices, the protocol
+// version, a zero and the parent block hash. The concatenated counts are the felt of the 64 bits
+// big endian transaction count, event count and state diff length followed by a byte of 0x80 for
+// the blob data availability mode and 0 for calldata, and seven zero bytes. The gas prices hash is
+// the Poseidon hash of many felts of "STARKNET_GAS_PRICES0" and the L1 gas, L1 data gas and L2
+// gas prices, each in wei then in fri, and the protocol version the short string of its ASCII
+// name, like "0.13.4".
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes, hades::poseidon_hash_many,
+    slice_from_ptr, transaction::try_field_elements_from_ptrs, try_felt_from_array, Bytes,
+    ERR_INVALID_FELT, ERR_INVALID_LENGTH, ERR_INVALID_STRING, SUCCESS,
+};
+
+// The number of gas prices: L1 gas, L1 data gas and L2 gas, in wei and in fri.
+const GAS_PRICES: usize = 6;
+
+fn short_string(string: &[u8]) -> Felt {
+    Felt::from_bytes_be_slice(string)
+}
+
+// The felt of the counts, None when the transaction count is too large for the STARK prime.
+fn concatenated_counts(
+    transaction_count: u64,
+    event_count: u64,
+    state_diff_length: u64,
+    l1_da_mode_blob: bool,
+) -> Option<Felt> {
+    let mut counts = [0u8; 32];
+    counts[..8].copy_from_slice(&transaction_count.to_be_bytes());
+    counts[8..16].copy_from_slice(&event_count.to_be_bytes());
+    counts[16..24].copy_from_slice(&state_diff_length.to_be_bytes());
+    counts[24] = if l1_da_mode_blob { 0x80 } else { 0 };
+    try_felt_from_array(&counts)
+}
+
+fn gas_prices_hash(gas_prices: &[Felt]) -> Felt {
+    let mut felts = [Felt::ZERO; GAS_PRICES + 1];
+    felts[0] = short_string(b"STARKNET_GAS_PRICES0");
+    felts[1..].copy_from_slice(gas_prices);
+    poseidon_hash_many(&felts)
+}
+
+/// Computes the hash of a Starknet block header of v0.13.4 and later, writing it into the last
+/// argument. `gas_prices` points to 6 contiguous felts: the L1 gas, L1 data gas and L2 gas prices,
+/// each in wei then in fri, and `protocol_version` to the `protocol_version_len` ASCII bytes of the
+/// version, like "0.13.4". `l1_da_mode_blob` is whether the block publishes its data availability
+/// in blobs rather than in calldata. Returns ERR_INVALID_LENGTH for a protocol version of more
+/// than 31 bytes, ERR_INVALID_STRING for a non ASCII one and ERR_INVALID_FELT when the concatenated
+/// counts are not lower than the STARK prime, which takes a transaction count above 2^59
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn block_hash(
+    block_number: u64,
+    state_root: Bytes,
+    sequencer_address: Bytes,
+    timestamp: u64,
+    transaction_count: u64,
+    event_count: u64,
+    state_diff_length: u64,
+    l1_da_mode_blob: bool,
+    state_diff_commitment: Bytes,
+    transaction_commitment: Bytes,
+    event_commitment: Bytes,
+    receipt_commitment: Bytes,
+    gas_prices: *const u8,
+    protocol_version: *const u8,
+    protocol_version_len: usize,
+    parent_block_hash: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            state_root,
+            sequencer_address,
+            state_diff_commitment,
+            transaction_commitment,
+            event_commitment,
+            receipt_commitment,
+            gas_prices,
+            parent_block_hash,
+            result
+        );
+        let (
+            Some([state_root, sequencer_address, parent_block_hash]),
+            Some([state_diff, transactions, events, receipts]),
+        ) = (
+            try_field_elements_from_ptrs([state_root, sequencer_address, parent_block_hash]),
+            try_field_elements_from_ptrs([
+                state_diff_commitment,
+                transaction_commitment,
+                event_commitment,
+                receipt_commitment,
+            ]),
+        )
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let gas_prices = match field_elements_from_bytes(gas_prices, GAS_PRICES) {
+            Ok(gas_prices) => gas_prices,
+            Err(status) => return status,
+        };
+        let protocol_version = match slice_from_ptr(protocol_version, protocol_version_len) {
+            Ok(version) if version.len() > 31 => return ERR_INVALID_LENGTH,
+            Ok(version) if !version.is_ascii() => return ERR_INVALID_STRING,
+            Ok(version) => short_string(version),
+            Err(status) => return status,
+        };
+        let Some(counts) = concatenated_counts(
+            transaction_count,
+            event_count,
+            state_diff_length,
+            l1_da_mode_blob,
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        let hash = poseidon_hash_many(&[
+            short_string(b"STARKNET_BLOCK_HASH1"),
+            Felt::from(block_number),
+            state_root,
+            sequencer_address,
+            Felt::from(timestamp),
+            counts,
+            state_diff,
+            transactions,
+            events,
+            receipts,
+            gas_prices_hash(&gas_prices),
+            protocol_version,
+            Felt::ZERO,
+            parent_block_hash,
+        ]);
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Header {
+        transaction_count: u64,
+        l1_da_mode_blob: bool,
+        gas_prices: [Felt; GAS_PRICES],
+        protocol_version: &'static [u8],
+    }
+
+    const HEADER: Header = Header {
+        transaction_count: 3,
+        l1_da_mode_blob: true,
+        gas_prices: [Felt::ONE; GAS_PRICES],
+        protocol_version: b"0.13.4",
+    };
+
+    // The header of a block of number 7 whose other felts are 10, 11, 12...
+    fn hash(header: &Header) -> (i32, Felt) {
+        let mut felts = [0u8; 32 * 7];
+        for (i, felt) in felts.chunks_exact_mut(32).enumerate() {
+            felt[31] = 10 + i as u8;
+        }
+        let felt = |i: usize| felts[32 * i..].as_ptr().cast_mut();
+        let gas_prices: Vec<u8> = header
+            .gas_prices
+            .iter()
+            .flat_map(|price| price.to_bytes_be())
+            .collect();
+        let mut result = [0u8; 32];
+        let status = block_hash(
+            7,
+            felt(0),
+            felt(1),
+            1_700_000_000,
+            header.transaction_count,
+            5,
+            2,
+            header.l1_da_mode_blob,
+            felt(2),
+            felt(3),
+            felt(4),
+            felt(5),
+            gas_prices.as_ptr(),
+            header.protocol_version.as_ptr(),
+            header.protocol_version.len(),
+            felt(6),
+            result.as_mut_ptr(),
+        );
+        (status, Felt::from_bytes_be(&result))
+    }
+
+    #[test]
+    fn test_block_hash() {
+        let felt = |value: u64| Felt::from(value);
+        let counts =
+            Felt::from_hex("0x0000000000000003000000000000000500000000000000028000000000000000")
+                .unwrap();
+        let gas_prices_hash = poseidon_hash_many(&[
+            short_string(b"STARKNET_GAS_PRICES0"),
+            felt(1),
+            felt(1),
+            felt(1),
+            felt(1),
+            felt(1),
+            felt(1),
+        ]);
+        let expected = poseidon_hash_many(&[
+            short_string(b"STARKNET_BLOCK_HASH1"),
+            felt(7),
+            felt(10),
+            felt(11),
+            felt(1_700_000_000),
+            counts,
+            felt(12),
+            felt(13),
+            felt(14),
+            felt(15),
+            gas_prices_hash,
+            Felt::from_hex("0x302e31332e34").unwrap(),
+            Felt::ZERO,
+            felt(16),
+        ]);
+        assert_eq!(hash(&HEADER), (SUCCESS, expected));
+
+        // The data availability mode and every gas price count
+        let calldata = hash(&Header {
+            l1_da_mode_blob: false,
+            ..HEADER
+        });
+        assert_eq!(calldata.0, SUCCESS);
+        assert_ne!(calldata.1, expected);
+        let mut gas_prices = HEADER.gas_prices;
+        gas_prices[5] = felt(2);
+        assert_ne!(
+            hash(&Header {
+                gas_prices,
+                ..HEADER
+            })
+            .1,
+            expected
+        );
+
+        assert_eq!(
+            hash(&Header {
+                transaction_count: u64::MAX,
+                ..HEADER
+            })
+            .0,
+            ERR_INVALID_FELT
+        );
+        assert_eq!(
+            hash(&Header {
+                protocol_version: &[b'0'; 32],
+                ..HEADER
+            })
+            .0,
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            hash(&Header {
+                protocol_version: "0.13.4é".as_bytes(),
+                ..HEADER
+            })
+            .0,
+            ERR_INVALID_STRING
+        );
+        gas_prices[0] = Felt::MAX;
+        assert_eq!(
+            hash(&Header {
+                gas_prices,
+                ..HEADER
+            })
+            .0,
+            SUCCESS
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Arithmetic over the 381 bits base field of the BLS12-381 curve, used by the `bls_field` hints of
+// the Starknet OS.
+//
+// Operands are 48 bytes big endian integers, reduced modulo the field prime so that the unreduced
+// u384 values of the hints can be passed as they are. The packing of these values into felts is
+// left to the caller.
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::curves::bls12_381::field_extension::{
+        BLS12381FieldModulus, BLS12381PrimeField,
+    },
+    field::{element::FieldElement, fields::montgomery_backed_prime_fields::IsModulus},
+    traits::ByteConversion,
+    unsigned_integer::element::U384,
+};
+
+use crate::{bytes_from_array, ffi_guard, ERR_DIVISION_BY_ZERO, SUCCESS};
+
+type BaseFieldElement = FieldElement<BLS12381PrimeField>;
+
+// Reads a 384 bits integer and reduces it modulo the field prime, which is above 2^380.
+fn element_from_ptr(ptr: *const u8) -> BaseFieldElement {
+    let bytes = unsafe { core::slice::from_raw_parts(ptr, 48) };
+    let mut value = U384::from_bytes_be(bytes).unwrap();
+    while value >= BLS12381FieldModulus::MODULUS {
+        value = value - BLS12381FieldModulus::MODULUS;
+    }
+    BaseFieldElement::new(value)
+}
+
+fn element_to_ptr(element: &BaseFieldElement, ptr: *mut u8) {
+    bytes_from_array(&element.to_bytes_be(), ptr);
+}
+
+/// Adds two integers modulo the BLS12-381 base field prime, writing the 48 bytes result into the
+/// last argument
+#[no_mangle]
+pub extern "C" fn bls12_381_add(a: *const u8, b: *const u8, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result);
+        element_to_ptr(&(element_from_ptr(a) + element_from_ptr(b)), result);
+        SUCCESS
+    })
+}
+
+/// Multiplies two integers modulo the BLS12-381 base field prime, writing the 48 bytes result into
+/// the last argument
+#[no_mangle]
+pub extern "C" fn bls12_381_mul(a: *const u8, b: *const u8, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result);
+        element_to_ptr(&(element_from_ptr(a) * element_from_ptr(b)), result);
+        SUCCESS
+    })
+}
+
+/// Divides the first integer by the second modulo the BLS12-381 base field prime, writing the 48
+/// bytes result into the last argument. Returns ERR_DIVISION_BY_ZERO when the divisor is a multiple
+/// of the prime
+#[no_mangle]
+pub extern "C" fn bls12_381_div_mod(a: *const u8, b: *const u8, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result);
+        let Ok(inverse) = element_from_ptr(b).inv() else {
+            return ERR_DIVISION_BY_ZERO;
+        };
+        element_to_ptr(&(element_from_ptr(a) * inverse), result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(value: &U384) -> [u8; 48] {
+        value.to_bytes_be().try_into().unwrap()
+    }
+
+    fn op(f: extern "C" fn(*const u8, *const u8, *mut u8) -> i32, a: &U384, b: &U384) -> [u8; 48] {
+        let mut result = [0u8; 48];
+        assert_eq!(
+            f(bytes(a).as_ptr(), bytes(b).as_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        result
+    }
+
+    #[test]
+    fn test_bls12_381_arithmetic() {
+        let p = BLS12381FieldModulus::MODULUS;
+        let p_minus_one = p - U384::from_u64(1);
+        let two = U384::from_u64(2);
+
+        assert_eq!(
+            op(bls12_381_add, &p_minus_one, &two),
+            bytes(&U384::from_u64(1))
+        );
+        // (p - 1)^2 = 1
+        assert_eq!(
+            op(bls12_381_mul, &p_minus_one, &p_minus_one),
+            bytes(&U384::from_u64(1))
+        );
+        // 1 / 2 = (p + 1) / 2
+        let half = (p + U384::from_u64(1)) >> 1;
+        assert_eq!(
+            op(bls12_381_div_mod, &U384::from_u64(1), &two),
+            bytes(&half)
+        );
+        // Unreduced operands, 2^384 - 1 is 9 p plus a remainder
+        let max = U384::from_hex_unchecked(&"f".repeat(96));
+        let remainder = (0..9).fold(max, |value, _| value - p);
+        assert_eq!(
+            op(bls12_381_add, &max, &U384::from_u64(0)),
+            bytes(&remainder)
+        );
+    }
+
+    #[test]
+    fn test_bls12_381_div_mod_by_zero() {
+        let mut result = [0u8; 48];
+        let one = bytes(&U384::from_u64(1));
+        let p = bytes(&BLS12381FieldModulus::MODULUS);
+        assert_eq!(
+            bls12_381_div_mod(one.as_ptr(), p.as_ptr(), result.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+    }
+}
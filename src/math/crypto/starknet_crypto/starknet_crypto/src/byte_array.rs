@@ -0,0 +1,208 @@
+// Poseidon hashes of byte strings packed like the Cairo `ByteArray` of the corelib.
+//
+// A `ByteArray` packs its bytes into big endian words of 31 bytes, the `bytes31` of its data, and
+// keeps the last `len % 31` bytes in a pending word, a felt of their big endian value, with their
+// count. It serializes as the number of words, the words, the pending word and the pending count,
+// and the bytes hash as the Poseidon hash of many felts of that serialization, like
+// `poseidon_hash_span` over the serialized array in a contract. The pending count is what tells
+// apart pending bytes that only differ by leading zeros, which their value alone loses.
+use alloc::vec::Vec;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    array_from_bytes, bytes_from_field_element, ffi_guard, hades::poseidon_hash_many,
+    slice_from_ptr, Bytes, ERR_INVALID_FELT, ERR_INVALID_LENGTH, SUCCESS,
+};
+
+// The number of bytes of a `bytes31` word.
+const WORD_LEN: usize = 31;
+
+// The hash of the serialization of a `ByteArray`.
+fn hash(
+    words: impl ExactSizeIterator<Item = Felt>,
+    pending_word: Felt,
+    pending_len: usize,
+) -> Felt {
+    let mut felts = Vec::with_capacity(words.len() + 3);
+    felts.push(Felt::from(words.len()));
+    felts.extend(words);
+    felts.extend([pending_word, Felt::from(pending_len)]);
+    poseidon_hash_many(&felts)
+}
+
+/// Computes the Poseidon hash of `len` bytes packed into a Cairo `ByteArray`, the Poseidon hash of
+/// many felts of its serialization: the number of 31 bytes big endian words, the words, the pending
+/// word of the last `len % 31` bytes and their count. Writes it into the last argument
+#[no_mangle]
+pub extern "C" fn poseidon_hash_bytes(data: *const u8, len: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let words = data.chunks_exact(WORD_LEN);
+        let pending = words.remainder();
+        let hash = hash(
+            words.map(Felt::from_bytes_be_slice),
+            Felt::from_bytes_be_slice(pending),
+            pending.len(),
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the Poseidon hash of a Cairo `ByteArray` from its components, like
+/// poseidon_hash_bytes over its bytes: `data_len` contiguous felts of `bytes31` words, the pending
+/// word and the number of its bytes. Writes it into the last argument. Returns ERR_INVALID_LENGTH
+/// for more than 30 pending bytes and ERR_INVALID_FELT for a word of more than 31 bytes or a
+/// pending word of more bytes than its count
+#[no_mangle]
+pub extern "C" fn byte_array_hash(
+    data: *const u8,
+    data_len: usize,
+    pending_word: Bytes,
+    pending_word_len: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(pending_word, result);
+        if pending_word_len >= WORD_LEN {
+            return ERR_INVALID_LENGTH;
+        }
+        let words = match data_len
+            .checked_mul(32)
+            .ok_or(ERR_INVALID_LENGTH)
+            .and_then(|len| slice_from_ptr(data, len))
+        {
+            Ok(words) => words,
+            Err(status) => return status,
+        };
+        // The words and pending word are felts only if their leading bytes are zeros
+        let pending_word = array_from_bytes(pending_word);
+        if words.chunks_exact(32).any(|word| word[0] != 0)
+            || pending_word[..32 - pending_word_len]
+                .iter()
+                .any(|&byte| byte != 0)
+        {
+            return ERR_INVALID_FELT;
+        }
+        let hash = hash(
+            words.chunks_exact(32).map(Felt::from_bytes_be_slice),
+            Felt::from_bytes_be_slice(&pending_word),
+            pending_word_len,
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_bytes(data: &[u8]) -> Felt {
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_bytes(data.as_ptr(), data.len(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        Felt::from_bytes_be(&result)
+    }
+
+    #[test]
+    fn test_poseidon_hash_bytes() {
+        let felt = Felt::from;
+        let abc = Felt::from_hex("0x616263").unwrap();
+        assert_eq!(
+            hash_bytes(b"abc"),
+            poseidon_hash_many(&[felt(0u8), abc, felt(3u8)])
+        );
+        assert_eq!(
+            hash_bytes(&[]),
+            poseidon_hash_many(&[felt(0u8), felt(0u8), felt(0u8)])
+        );
+
+        // 31 bytes fill a word and leave an empty pending word, the 32nd byte goes to the next one
+        let data: Vec<u8> = (1..=32).collect();
+        let word = Felt::from_bytes_be_slice(&data[..31]);
+        assert_eq!(
+            hash_bytes(&data[..31]),
+            poseidon_hash_many(&[felt(1u8), word, felt(0u8), felt(0u8)])
+        );
+        assert_eq!(
+            hash_bytes(&data),
+            poseidon_hash_many(&[felt(1u8), word, felt(32u8), felt(1u8)])
+        );
+
+        // Trailing and leading zero bytes change the hash
+        assert_ne!(hash_bytes(&[0]), hash_bytes(&[]));
+        assert_ne!(hash_bytes(&[0, 1]), hash_bytes(&[1]));
+
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_bytes(core::ptr::null(), 0, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            poseidon_hash_bytes(core::ptr::null(), 1, result.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_byte_array_hash() {
+        // 40 bytes: one word and 9 pending bytes
+        let data: Vec<u8> = (1..=40).collect();
+        let mut words = [0u8; 32];
+        words[1..].copy_from_slice(&data[..31]);
+        let mut pending_word = [0u8; 32];
+        pending_word[23..].copy_from_slice(&data[31..]);
+        let byte_array = |words: &[u8], pending_word: &mut [u8; 32], pending_word_len| {
+            let mut result = [0u8; 32];
+            let status = byte_array_hash(
+                words.as_ptr(),
+                words.len() / 32,
+                pending_word.as_mut_ptr(),
+                pending_word_len,
+                result.as_mut_ptr(),
+            );
+            (status, Felt::from_bytes_be(&result))
+        };
+        assert_eq!(
+            byte_array(&words, &mut pending_word, 9),
+            (SUCCESS, hash_bytes(&data))
+        );
+        assert_eq!(
+            byte_array(&words, &mut pending_word, 10),
+            (
+                SUCCESS,
+                hash_bytes(&[&data[..31], &[0], &data[31..]].concat())
+            )
+        );
+        assert_eq!(
+            byte_array(&[], &mut [0u8; 32], 0),
+            (SUCCESS, hash_bytes(&[]))
+        );
+
+        assert_eq!(byte_array(&words, &mut pending_word, 8).0, ERR_INVALID_FELT);
+        assert_eq!(
+            byte_array(&words, &mut pending_word, 31).0,
+            ERR_INVALID_LENGTH
+        );
+        words[0] = 1;
+        assert_eq!(byte_array(&words, &mut pending_word, 9).0, ERR_INVALID_FELT);
+        assert_eq!(
+            byte_array_hash(
+                core::ptr::null(),
+                1,
+                pending_word.as_mut_ptr(),
+                9,
+                words.as_mut_ptr()
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+}
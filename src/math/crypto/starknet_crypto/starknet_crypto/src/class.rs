@@ -0,0 +1,316 @@
+// Hashes of Starknet classes.
+//
+// The compiled class hash of a CASM class is the Poseidon hash of its version as a short string,
+// the hashes of its external, L1 handler and constructor entry points, and the hash of its
+// bytecode. Each list of entry points hashes as the Poseidon hash of the selector, the offset and
+// the Poseidon hash of the builtins of every entry point. Without segments the bytecode hashes as
+// its Poseidon hash, and split into segments as 1 + the Poseidon hash of the length followed by the
+// Poseidon hash of every segment.
+use alloc::vec::Vec;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes, hades::poseidon_hash_many,
+    slice_from_ptr, Bytes, ERR_INVALID_LENGTH, SUCCESS,
+};
+
+// Hashes a list of entry points, each encoded as its selector, its offset, its number of builtins
+// and the builtins.
+fn entry_points_hash(mut encoded: &[Felt]) -> Result<Felt, i32> {
+    let mut elements = Vec::new();
+    while let [selector, offset, builtin_count, rest @ ..] = encoded {
+        let builtin_count = u64::try_from(*builtin_count)
+            .ok()
+            .and_then(|count| usize::try_from(count).ok())
+            .filter(|&count| count <= rest.len())
+            .ok_or(ERR_INVALID_LENGTH)?;
+        let (builtins, rest) = rest.split_at(builtin_count);
+        elements.extend([*selector, *offset, poseidon_hash_many(builtins)]);
+        encoded = rest;
+    }
+    if !encoded.is_empty() {
+        return Err(ERR_INVALID_LENGTH);
+    }
+    Ok(poseidon_hash_many(&elements))
+}
+
+fn bytecode_hash(bytecode: &[Felt], segment_lengths: &[u64]) -> Result<Felt, i32> {
+    if segment_lengths.is_empty() {
+        return Ok(poseidon_hash_many(bytecode));
+    }
+    let mut elements = Vec::with_capacity(2 * segment_lengths.len());
+    let mut rest = bytecode;
+    for &length in segment_lengths {
+        let length = usize::try_from(length)
+            .ok()
+            .filter(|&length| length <= rest.len())
+            .ok_or(ERR_INVALID_LENGTH)?;
+        let (segment, tail) = rest.split_at(length);
+        elements.extend([Felt::from(length), poseidon_hash_many(segment)]);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        return Err(ERR_INVALID_LENGTH);
+    }
+    Ok(Felt::ONE + poseidon_hash_many(&elements))
+}
+
+/// Computes the compiled class hash of a CASM class, writing it into the last argument. The
+/// version is a short string of at most 31 bytes such as `COMPILED_CLASS_V1`. Each list of entry
+/// points is made of contiguous felts, the selector, the offset, the number of builtins and the
+/// builtins of every entry point. The `segment_count` lengths of the bytecode segments add up to
+/// `bytecode_len`, or there are none for a bytecode without segments
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn compiled_class_hash(
+    version: *const u8,
+    version_len: usize,
+    external: *const u8,
+    external_len: usize,
+    l1_handler: *const u8,
+    l1_handler_len: usize,
+    constructor: *const u8,
+    constructor_len: usize,
+    bytecode: *const u8,
+    bytecode_len: usize,
+    segment_lengths: *const u64,
+    segment_count: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let version = match slice_from_ptr(version, version_len) {
+            Ok(version) if version.len() <= 31 => Felt::from_bytes_be_slice(version),
+            Ok(_) => return ERR_INVALID_LENGTH,
+            Err(status) => return status,
+        };
+        let hashes = [
+            (external, external_len),
+            (l1_handler, l1_handler_len),
+            (constructor, constructor_len),
+        ]
+        .map(|(entry_points, len)| {
+            entry_points_hash(&field_elements_from_bytes(entry_points, len)?)
+        });
+        let [external, l1_handler, constructor] = match hashes {
+            [Ok(external), Ok(l1_handler), Ok(constructor)] => [external, l1_handler, constructor],
+            [Err(status), ..] | [_, Err(status), _] | [.., Err(status)] => return status,
+        };
+        let bytecode = match (
+            field_elements_from_bytes(bytecode, bytecode_len),
+            slice_from_ptr(segment_lengths, segment_count),
+        ) {
+            (Ok(bytecode), Ok(segment_lengths)) => bytecode_hash(&bytecode, segment_lengths),
+            (Err(status), _) | (_, Err(status)) => Err(status),
+        };
+        let bytecode = match bytecode {
+            Ok(bytecode) => bytecode,
+            Err(status) => return status,
+        };
+
+        let hash = poseidon_hash_many(&[version, external, l1_handler, constructor, bytecode]);
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felts_bytes(felts: &[u64]) -> Vec<u8> {
+        felts
+            .iter()
+            .flat_map(|&felt| Felt::from(felt).to_bytes_be())
+            .collect()
+    }
+
+    fn hash(external: &[u64], bytecode: &[u64], segment_lengths: &[u64]) -> Result<Felt, i32> {
+        let version = b"COMPILED_CLASS_V1";
+        let (external_bytes, bytecode_bytes) = (felts_bytes(external), felts_bytes(bytecode));
+        let mut result = [0u8; 32];
+        match compiled_class_hash(
+            version.as_ptr(),
+            version.len(),
+            external_bytes.as_ptr(),
+            external.len(),
+            core::ptr::null(),
+            0,
+            core::ptr::null(),
+            0,
+            bytecode_bytes.as_ptr(),
+            bytecode.len(),
+            segment_lengths.as_ptr(),
+            segment_lengths.len(),
+            result.as_mut_ptr(),
+        ) {
+            SUCCESS => Ok(Felt::from_bytes_be(&result)),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_compiled_class_hash() {
+        let felt = |value: u64| Felt::from(value);
+        let felts = |values: &[u64]| -> Vec<Felt> { values.iter().map(|&v| felt(v)).collect() };
+        let version = Felt::from_bytes_be_slice(b"COMPILED_CLASS_V1");
+        // Two external entry points, the second one with two builtins
+        let external = [10, 0, 0, 11, 4, 2, 20, 21];
+        let external_hash = poseidon_hash_many(&[
+            felt(10),
+            felt(0),
+            poseidon_hash_many(&[]),
+            felt(11),
+            felt(4),
+            poseidon_hash_many(&felts(&[20, 21])),
+        ]);
+        let empty = poseidon_hash_many(&[]);
+        let bytecode = [1, 2, 3, 4, 5];
+
+        let expected = poseidon_hash_many(&[
+            version,
+            external_hash,
+            empty,
+            empty,
+            poseidon_hash_many(&felts(&bytecode)),
+        ]);
+        assert_eq!(hash(&external, &bytecode, &[]), Ok(expected));
+
+        let segments_hash = Felt::ONE
+            + poseidon_hash_many(&[
+                felt(2),
+                poseidon_hash_many(&felts(&[1, 2])),
+                felt(3),
+                poseidon_hash_many(&felts(&[3, 4, 5])),
+            ]);
+        let expected = poseidon_hash_many(&[version, external_hash, empty, empty, segments_hash]);
+        assert_eq!(hash(&external, &bytecode, &[2, 3]), Ok(expected));
+    }
+
+    #[test]
+    fn test_compiled_class_hash_invalid_length() {
+        let bytecode = [1, 2, 3];
+        // The segments don't cover the bytecode
+        assert_eq!(hash(&[], &bytecode, &[2]), Err(ERR_INVALID_LENGTH));
+        assert_eq!(hash(&[], &bytecode, &[2, 2]), Err(ERR_INVALID_LENGTH));
+        // An entry point with more builtins than felts left, and a truncated entry point
+        assert_eq!(
+            hash(&[10, 0, 2, 20], &bytecode, &[]),
+            Err(ERR_INVALID_LENGTH)
+        );
+        assert_eq!(hash(&[10, 0], &bytecode, &[]), Err(ERR_INVALID_LENGTH));
+    }
+
+    // The minimal_contract CASM class of the test data of the Cairo compiler 2.21.0, whose
+    // compiled_class_hash_test_data gives its Poseidon compiled class hash as the legacy one, the
+    // hash of the Starknet versions before v0.14.1.
+    #[test]
+    fn test_compiled_class_hash_minimal_contract() {
+        let hex = |hex: &str| Felt::from_hex(hex).unwrap();
+        let bytecode: Vec<u8> = [
+            "0xa0680017fff8000",
+            "0x7",
+            "0x482680017ffa8000",
+            "0x100000000000000000000000000000000",
+            "0x400280007ff97fff",
+            "0x10780017fff7fff",
+            "0x21",
+            "0x4825800180007ffa",
+            "0x0",
+            "0x400280007ff97fff",
+            "0x48297ffc80007ffd",
+            "0x20680017fff7fff",
+            "0x4",
+            "0x10780017fff7fff",
+            "0xd",
+            "0x1104800180018000",
+            "0x22",
+            "0x482680017ff98000",
+            "0x1",
+            "0x48127ff57fff8000",
+            "0x480a7ffb7fff8000",
+            "0x480680017fff8000",
+            "0x1",
+            "0x48127ffa7fff8000",
+            "0x48127ffa7fff8000",
+            "0x208b7fff7fff7ffe",
+            "0x40780017fff7fff",
+            "0x1",
+            "0x482680017ff98000",
+            "0x1",
+            "0x482480017ffc8000",
+            "0x21b6",
+            "0x480a7ffb7fff8000",
+            "0x480680017fff8000",
+            "0x0",
+            "0x48127ffb7fff8000",
+            "0x48127ffa7fff8000",
+            "0x208b7fff7fff7ffe",
+            "0x1104800180018000",
+            "0x10",
+            "0x482680017ff98000",
+            "0x1",
+            "0x480a7ffa7fff8000",
+            "0x480a7ffb7fff8000",
+            "0x480680017fff8000",
+            "0x1",
+            "0x48127ffa7fff8000",
+            "0x48127ffa7fff8000",
+            "0x208b7fff7fff7ffe",
+            "0x480680017fff8000",
+            "0x496e70757420746f6f206c6f6e6720666f7220617267756d656e7473",
+            "0x1104800180018000",
+            "0x8",
+            "0x208b7fff7fff7ffe",
+            "0x480680017fff8000",
+            "0x4f7574206f6620676173",
+            "0x1104800180018000",
+            "0x3",
+            "0x208b7fff7fff7ffe",
+            "0x40780017fff7fff",
+            "0x1",
+            "0x400180007fff7ffd",
+            "0x48127fff7fff8000",
+            "0x482480017ffe8000",
+            "0x1",
+            "0x208b7fff7fff7ffe",
+        ]
+        .iter()
+        .flat_map(|felt| hex(felt).to_bytes_be())
+        .collect();
+        let external = [
+            hex("0x1fc3f77ebc090777f567969ad9823cf6334ab888acb385ca72668ec5adbde80"),
+            Felt::ZERO,
+            Felt::ONE,
+            Felt::from_bytes_be_slice(b"range_check"),
+        ]
+        .map(|felt| felt.to_bytes_be())
+        .concat();
+        let version = b"COMPILED_CLASS_V1";
+        let segment_lengths = [49, 5, 5, 7];
+        let mut result = [0u8; 32];
+        assert_eq!(
+            compiled_class_hash(
+                version.as_ptr(),
+                version.len(),
+                external.as_ptr(),
+                4,
+                core::ptr::null(),
+                0,
+                core::ptr::null(),
+                0,
+                bytecode.as_ptr(),
+                bytecode.len() / 32,
+                segment_lengths.as_ptr(),
+                segment_lengths.len(),
+                result.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            hex("0x452dae26bbf8d0e0cbe1bc6234c003f0b4d14e186b21eda81c237e2b9eb16ac").to_bytes_be()
+        );
+    }
+}
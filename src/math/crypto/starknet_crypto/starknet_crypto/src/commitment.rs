@@ -0,0 +1,226 @@
+// Pedersen commitments value * G + blinding * H over the STARK curve, binding as long as the
+// discrete logarithm of H in base G is unknown and hiding for a uniformly random blinding factor.
+//
+// G is the generator of the curve and H the first point whose x coordinate is
+// poseidon_hash(PEDERSEN_COMMIT_TAG, counter) for the counters 0, 1, 2... with the smallest of its
+// two y coordinates, so that nobody knows its discrete logarithm. The curve has a prime order, every
+// such point generates it. The values and blinding factors are felts, taken modulo the curve order.
+use elliptic_curve::bigint::{Encoding, NonZero, U256};
+use starknet_curve::curve_params::{EC_ORDER, GENERATOR};
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    array_from_bytes,
+    backend::{self, Point},
+    curve::{bytes_from_coordinates, point_from_bytes},
+    felt::bytes_from_felt,
+    ffi_guard,
+    hades::poseidon_hash,
+    try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_OPENING, SUCCESS,
+};
+
+// The short string of the domain tag of the derivation of H.
+const PEDERSEN_COMMIT_TAG: &[u8] = b"STARKNET_CRYPTO_PEDERSEN_COMMIT";
+
+fn felt_from_field_element(felt: &Felt) -> backend::Felt {
+    backend::felt_from_bytes(&felt.to_bytes_be()).unwrap()
+}
+
+// The coordinates of H. About one x coordinate out of two is on the curve.
+fn second_generator() -> (backend::Felt, backend::Felt) {
+    let tag = Felt::from_bytes_be_slice(PEDERSEN_COMMIT_TAG);
+    let mut counter = Felt::ZERO;
+    loop {
+        let x = felt_from_field_element(&poseidon_hash(tag, counter));
+        if let Some(y) = backend::point_y_from_x(&x) {
+            return (x, y);
+        }
+        counter += Felt::ONE;
+    }
+}
+
+fn generators() -> [Point; 2] {
+    let (h_x, h_y) = second_generator();
+    let (g_x, g_y) = (
+        felt_from_field_element(&GENERATOR.x()),
+        felt_from_field_element(&GENERATOR.y()),
+    );
+    [
+        backend::point_from_coordinates(&g_x, &g_y).unwrap(),
+        backend::point_from_coordinates(&h_x, &h_y).unwrap(),
+    ]
+}
+
+// The commitment to a value and a blinding factor, None for the point at infinity.
+fn commit(value: Bytes, blinding: Bytes) -> Result<Option<(backend::Felt, backend::Felt)>, i32> {
+    let (Some(_), Some(_)) = (
+        try_field_element_from_bytes(value),
+        try_field_element_from_bytes(blinding),
+    ) else {
+        return Err(ERR_INVALID_FELT);
+    };
+    let order = NonZero::new(U256::from_be_bytes(EC_ORDER.to_bytes_be())).unwrap();
+    let scalars = [value, blinding].map(|bytes| {
+        U256::from_be_bytes(array_from_bytes(bytes))
+            .rem(&order)
+            .to_be_bytes()
+    });
+    Ok(backend::point_msm(&generators(), &scalars))
+}
+
+/// Writes the coordinates of the second generator H of the Pedersen commitments into the arguments
+#[no_mangle]
+pub extern "C" fn pedersen_commit_generator(x_bytes: Bytes, y_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes);
+        let (x, y) = second_generator();
+        bytes_from_felt(&x, x_bytes);
+        bytes_from_felt(&y, y_bytes);
+        SUCCESS
+    })
+}
+
+/// Commits to a felt value with a felt blinding factor, writing the affine coordinates of
+/// value * G + blinding * H into the last two arguments. The blinding factor must be uniformly
+/// random for the commitment to hide the value. Returns ERR_POINT_AT_INFINITY when both are zero
+/// modulo the curve order
+#[no_mangle]
+pub extern "C" fn pedersen_commit(
+    value: Bytes,
+    blinding: Bytes,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(value, blinding, x_bytes, y_bytes);
+        match commit(value, blinding) {
+            Ok(commitment) => bytes_from_coordinates(commitment, x_bytes, y_bytes),
+            Err(status) => status,
+        }
+    })
+}
+
+/// Returns SUCCESS when the commitment (x, y) opens to the value and blinding factor,
+/// ERR_INVALID_OPENING when it doesn't and another error code for malformed inputs
+#[no_mangle]
+pub extern "C" fn pedersen_commit_open(
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+    value: Bytes,
+    blinding: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes, value, blinding);
+        if let Err(status) = point_from_bytes(x_bytes, y_bytes) {
+            return status;
+        }
+        let commitment = match commit(value, blinding) {
+            Ok(commitment) => commitment,
+            Err(status) => return status,
+        };
+        let point = (array_from_bytes(x_bytes), array_from_bytes(y_bytes));
+        match commitment {
+            Some((x, y))
+                if (backend::bytes_from_felt(&x), backend::bytes_from_felt(&y)) == point =>
+            {
+                SUCCESS
+            }
+            _ => ERR_INVALID_OPENING,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::{ec_add, ec_mul, stark_curve_generator, stark_point_validate},
+        ERR_POINT_AT_INFINITY,
+    };
+
+    fn felt(value: u64) -> [u8; 32] {
+        Felt::from(value).to_bytes_be()
+    }
+
+    #[test]
+    fn test_pedersen_commit() {
+        let (mut h_x, mut h_y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            pedersen_commit_generator(h_x.as_mut_ptr(), h_y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            stark_point_validate(h_x.as_mut_ptr(), h_y.as_mut_ptr()),
+            SUCCESS
+        );
+        let (mut g_x, mut g_y) = ([0u8; 32], [0u8; 32]);
+        stark_curve_generator(g_x.as_mut_ptr(), g_y.as_mut_ptr());
+        assert_ne!(h_x, g_x);
+        // The x coordinate of H is the hash of the tag and the first counter on the curve
+        let tag = Felt::from_bytes_be_slice(PEDERSEN_COMMIT_TAG);
+        assert!((0..64u64).any(|counter| poseidon_hash(tag, counter.into()).to_bytes_be() == h_x));
+
+        // 5 * G + 7 * H
+        let (mut value, mut blinding) = (felt(5), felt(7));
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            pedersen_commit(
+                value.as_mut_ptr(),
+                blinding.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        let mut terms = [[0u8; 32]; 4];
+        let [a_x, a_y, b_x, b_y] = &mut terms;
+        ec_mul(
+            value.as_mut_ptr(),
+            g_x.as_mut_ptr(),
+            g_y.as_mut_ptr(),
+            a_x.as_mut_ptr(),
+            a_y.as_mut_ptr(),
+        );
+        ec_mul(
+            blinding.as_mut_ptr(),
+            h_x.as_mut_ptr(),
+            h_y.as_mut_ptr(),
+            b_x.as_mut_ptr(),
+            b_y.as_mut_ptr(),
+        );
+        let (mut sum_x, mut sum_y) = ([0u8; 32], [0u8; 32]);
+        ec_add(
+            a_x.as_mut_ptr(),
+            a_y.as_mut_ptr(),
+            b_x.as_mut_ptr(),
+            b_y.as_mut_ptr(),
+            sum_x.as_mut_ptr(),
+            sum_y.as_mut_ptr(),
+        );
+        assert_eq!((x, y), (sum_x, sum_y));
+
+        let mut open = |value: &mut [u8; 32], blinding: &mut [u8; 32]| {
+            pedersen_commit_open(
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+                value.as_mut_ptr(),
+                blinding.as_mut_ptr(),
+            )
+        };
+        assert_eq!(open(&mut value, &mut blinding), SUCCESS);
+        assert_eq!(open(&mut felt(6), &mut blinding), ERR_INVALID_OPENING);
+        assert_eq!(open(&mut blinding.clone(), &mut value), ERR_INVALID_OPENING);
+        assert_eq!(open(&mut [0xff; 32], &mut blinding), ERR_INVALID_FELT);
+
+        let (mut zero, mut other_zero) = (felt(0), felt(0));
+        assert_eq!(
+            pedersen_commit(
+                zero.as_mut_ptr(),
+                other_zero.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr()
+            ),
+            ERR_POINT_AT_INFINITY
+        );
+    }
+}
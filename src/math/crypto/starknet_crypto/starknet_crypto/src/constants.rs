@@ -0,0 +1,132 @@
+// The Poseidon round constants and the Pedersen lookup tables, generated by the same macros as the
+// private ones of starknet-crypto, for implementations checked against this library.
+//
+// The felts are 32 bytes big endian and the points their x coordinate followed by their y
+// coordinate. Like starknet_crypto_last_panic_message, the functions copy at most `buf_len` bytes
+// and write the full length of the constants into the last argument.
+use starknet_types_core::curve::AffinePoint;
+use starknet_types_core::felt::Felt;
+
+use crate::{bytes_from_array, ffi_guard, ERR_INVALID_LENGTH, SUCCESS};
+
+#[allow(dead_code)]
+pub(crate) mod generated {
+    use super::Felt;
+
+    starknet_crypto_codegen::poseidon_consts!();
+    starknet_crypto_codegen::lookup_table!(4);
+}
+
+fn copy_constants(bytes: impl Iterator<Item = u8>, buf: *mut u8, buf_len: usize, len: *mut usize) {
+    let mut full_len = 0;
+    for byte in bytes {
+        if full_len < buf_len {
+            bytes_from_array(&[byte], buf.wrapping_add(full_len));
+        }
+        full_len += 1;
+    }
+    unsafe { *len = full_len };
+}
+
+/// Copies the 91 rounds of 3 Poseidon round constants, round after round, into the first argument
+#[no_mangle]
+pub extern "C" fn poseidon_round_constants(buf: *mut u8, buf_len: usize, len: *mut usize) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(len);
+        if buf.is_null() && buf_len > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let bytes = generated::POSEIDON_CONSTS
+            .iter()
+            .flat_map(Felt::to_bytes_be);
+        copy_constants(bytes, buf, buf_len, len);
+        SUCCESS
+    })
+}
+
+/// Copies the Pedersen lookup table of the given index into the second argument: the multiples 1
+/// to 15 of the point P0, P1, P2 or P3 shifted by every 4 bits window of its part of the inputs,
+/// window after window. The tables of P0 and P2 have 930 points and those of P1 and P3 15. Returns
+/// ERR_INVALID_LENGTH for an index above 3
+#[no_mangle]
+pub extern "C" fn pedersen_constant_points(
+    table_index: u32,
+    buf: *mut u8,
+    buf_len: usize,
+    len: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(len);
+        if buf.is_null() && buf_len > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let table: &[AffinePoint] = match table_index {
+            0 => &generated::CURVE_CONSTS_P0,
+            1 => &generated::CURVE_CONSTS_P1,
+            2 => &generated::CURVE_CONSTS_P2,
+            3 => &generated::CURVE_CONSTS_P3,
+            _ => return ERR_INVALID_LENGTH,
+        };
+        let bytes = table
+            .iter()
+            .flat_map(|point| [point.x().to_bytes_be(), point.y().to_bytes_be()])
+            .flatten();
+        copy_constants(bytes, buf, buf_len, len);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_curve::curve_params::{PEDERSEN_P0, PEDERSEN_P1};
+
+    #[test]
+    fn test_poseidon_round_constants() {
+        let mut len = 0;
+        assert_eq!(
+            poseidon_round_constants(core::ptr::null_mut(), 0, &mut len),
+            SUCCESS
+        );
+        assert_eq!(len, 91 * 3 * 32);
+        let mut constants = vec![0u8; len];
+        assert_eq!(
+            poseidon_round_constants(constants.as_mut_ptr(), len, &mut len),
+            SUCCESS
+        );
+        // The first constant of poseidon3.txt
+        let first = Felt::from_dec_str(
+            "2950795762459345168613727575620414179244544320470208355568817838579231751791",
+        )
+        .unwrap();
+        assert_eq!(constants[..32], first.to_bytes_be());
+    }
+
+    #[test]
+    fn test_pedersen_constant_points() {
+        let mut len = 0;
+        let mut point = [0u8; 64];
+        assert_eq!(
+            pedersen_constant_points(0, point.as_mut_ptr(), 64, &mut len),
+            SUCCESS
+        );
+        assert_eq!(len, 930 * 64);
+        // The first point of a table is its base point
+        assert_eq!(point[..32], PEDERSEN_P0.x().to_bytes_be());
+        assert_eq!(point[32..], PEDERSEN_P0.y().to_bytes_be());
+
+        let mut table = vec![0u8; 15 * 64];
+        assert_eq!(
+            pedersen_constant_points(1, table.as_mut_ptr(), table.len(), &mut len),
+            SUCCESS
+        );
+        assert_eq!(len, 15 * 64);
+        let second = PEDERSEN_P1 + PEDERSEN_P1;
+        assert_eq!(table[64..96], second.x().to_bytes_be());
+
+        assert_eq!(
+            pedersen_constant_points(4, table.as_mut_ptr(), table.len(), &mut len),
+            ERR_INVALID_LENGTH
+        );
+    }
+}
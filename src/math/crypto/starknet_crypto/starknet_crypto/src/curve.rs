@@ -0,0 +1,1245 @@
+// Arithmetic over the STARK curve: y^2 = x^3 + alpha * x + beta.
+//
+// The affine functions report a point at infinity result as ERR_POINT_AT_INFINITY. The `_flagged`
+// functions instead take and return points with an infinity flag, so that every step of the EC_OP
+// and signature builtins can go through them: the coordinates of an input with the flag set are
+// ignored, and the point at infinity is written as zero coordinates with the flag set.
+use alloc::vec::Vec;
+
+use elliptic_curve::bigint::{Encoding, NonZero, U256};
+use sha2::{Digest, Sha256};
+use starknet_curve::curve_params::{ALPHA, BETA, EC_ORDER, GENERATOR};
+
+use crate::{
+    array_from_bytes,
+    backend::{self, Felt, Point},
+    bytes_from_array, differential,
+    felt::{bytes_from_felt, reduce_wide, try_felt_from_bytes},
+    ffi_guard,
+    profiling::{Primitive, Profile},
+    slice_from_ptr, Bytes, DIFF_EC_OP, ERR_EC_OP_SAME_X, ERR_INVALID_FELT, ERR_INVALID_LENGTH,
+    ERR_INVALID_POINT, ERR_POINT_AT_INFINITY, SUCCESS,
+};
+#[cfg(feature = "std")]
+use crate::{generator, schnorr::felt_from_field_element};
+#[cfg(feature = "signing")]
+use crate::{schnorr::reduce, secret::try_secret_from_bytes};
+#[cfg(feature = "signing")]
+use zeroize::Zeroizing;
+
+// Reads an affine point from its C representation, checking that it lies on the curve.
+pub(crate) fn point_from_bytes(x_bytes: Bytes, y_bytes: Bytes) -> Result<Point, i32> {
+    let (Some(x), Some(y)) = (try_felt_from_bytes(x_bytes), try_felt_from_bytes(y_bytes)) else {
+        return Err(ERR_INVALID_FELT);
+    };
+    backend::point_from_coordinates(&x, &y).ok_or(ERR_INVALID_POINT)
+}
+
+// Writes the affine coordinates of a point into their C representation. The backends have no
+// coordinates for the point at infinity, which is reported as an error instead.
+pub(crate) fn bytes_from_coordinates(
+    coordinates: Option<(Felt, Felt)>,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    let Some((x, y)) = coordinates else {
+        return ERR_POINT_AT_INFINITY;
+    };
+    bytes_from_felt(&x, x_bytes);
+    bytes_from_felt(&y, y_bytes);
+    SUCCESS
+}
+
+// A point along its coordinates, since the backends don't expose them.
+pub(crate) type PointWithCoordinates = (Point, (Felt, Felt));
+
+// Reads a point with an infinity flag, None for the point at infinity.
+fn flagged_point_from_bytes(
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+    infinity: bool,
+) -> Result<Option<PointWithCoordinates>, i32> {
+    if infinity {
+        return Ok(None);
+    }
+    let point = point_from_bytes(x_bytes, y_bytes)?;
+    let coordinates = (
+        try_felt_from_bytes(x_bytes).unwrap(),
+        try_felt_from_bytes(y_bytes).unwrap(),
+    );
+    Ok(Some((point, coordinates)))
+}
+
+// Writes a point with an infinity flag, the point at infinity as zero coordinates.
+fn bytes_from_flagged(
+    coordinates: Option<(Felt, Felt)>,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+    infinity: *mut bool,
+) -> i32 {
+    unsafe { *infinity = coordinates.is_none() };
+    let Some((x, y)) = coordinates else {
+        bytes_from_array(&[0; 32], x_bytes);
+        bytes_from_array(&[0; 32], y_bytes);
+        return SUCCESS;
+    };
+    bytes_from_felt(&x, x_bytes);
+    bytes_from_felt(&y, y_bytes);
+    SUCCESS
+}
+
+// Reads a 256 bits scalar reduced modulo the curve order, for the backends' scalar multiplication.
+fn scalar_from_bytes(scalar_bytes: Bytes) -> [u8; 32] {
+    // Reducing the scalar modulo the (prime) curve order gives the same product and guarantees the
+    // double-and-add never adds a point to its opposite.
+    let order = NonZero::new(U256::from_be_bytes(EC_ORDER.to_bytes_be())).unwrap();
+    U256::from_be_bytes(array_from_bytes(scalar_bytes))
+        .rem(&order)
+        .to_be_bytes()
+}
+
+/// Writes the coordinates of the generator of the STARK curve into the arguments
+#[no_mangle]
+pub extern "C" fn stark_curve_generator(x_bytes: Bytes, y_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes);
+        bytes_from_array(&GENERATOR.x().to_bytes_be(), x_bytes);
+        bytes_from_array(&GENERATOR.y().to_bytes_be(), y_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes the order of the STARK curve, the number of its points, into the argument
+#[no_mangle]
+pub extern "C" fn stark_curve_order(order_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(order_bytes);
+        bytes_from_array(&EC_ORDER.to_bytes_be(), order_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes the alpha coefficient of the STARK curve y^2 = x^3 + alpha * x + beta into the argument
+#[no_mangle]
+pub extern "C" fn stark_curve_alpha(alpha_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(alpha_bytes);
+        bytes_from_array(&ALPHA.to_bytes_be(), alpha_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes the beta coefficient of the STARK curve y^2 = x^3 + alpha * x + beta into the argument
+#[no_mangle]
+pub extern "C" fn stark_curve_beta(beta_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(beta_bytes);
+        bytes_from_array(&BETA.to_bytes_be(), beta_bytes);
+        SUCCESS
+    })
+}
+
+/// Returns SUCCESS when (x, y) is a point of the STARK curve, ERR_INVALID_FELT when a coordinate is
+/// not a felt and ERR_INVALID_POINT otherwise
+#[no_mangle]
+pub extern "C" fn stark_point_validate(x_bytes: Bytes, y_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes);
+        match point_from_bytes(x_bytes, y_bytes) {
+            Ok(_) => SUCCESS,
+            Err(status) => status,
+        }
+    })
+}
+
+/// Returns SUCCESS when the public key is the x coordinate of a point of the STARK curve, which
+/// verify_signature can recover, ERR_INVALID_FELT when it is not a felt and ERR_INVALID_POINT
+/// otherwise
+#[no_mangle]
+pub extern "C" fn stark_pubkey_validate(x_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes);
+        let Some(x) = try_felt_from_bytes(x_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        match backend::point_y_from_x(&x) {
+            Some(_) => SUCCESS,
+            None => ERR_INVALID_POINT,
+        }
+    })
+}
+
+/// Adds the STARK curve points (x, y) given by the first four arguments, writing the affine result
+/// into the last two
+#[no_mangle]
+pub extern "C" fn ec_add(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    q_x_bytes: Bytes,
+    q_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        let p = match point_from_bytes(p_x_bytes, p_y_bytes) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+        let q = match point_from_bytes(q_x_bytes, q_y_bytes) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+
+        bytes_from_coordinates(backend::point_add(&p, &q), result_x_bytes, result_y_bytes)
+    })
+}
+
+/// Multiplies the STARK curve point given by the second and third arguments by a 256 bit big endian
+/// scalar, writing the affine result into the last two arguments. The products of the generator
+/// go through the precomputed table of get_public_key
+#[no_mangle]
+pub extern "C" fn ec_mul(
+    scalar_bytes: Bytes,
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            scalar_bytes,
+            p_x_bytes,
+            p_y_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        let p = match point_from_bytes(p_x_bytes, p_y_bytes) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+
+        #[cfg(feature = "std")]
+        if array_from_bytes(p_x_bytes) == GENERATOR.x().to_bytes_be()
+            && array_from_bytes(p_y_bytes) == GENERATOR.y().to_bytes_be()
+        {
+            // The reduced scalars are below 2^252, which the table covers
+            let product = generator::mul(&scalar_from_bytes(scalar_bytes)).map(|point| {
+                (
+                    felt_from_field_element(&point.x()),
+                    felt_from_field_element(&point.y()),
+                )
+            });
+            return bytes_from_coordinates(product, result_x_bytes, result_y_bytes);
+        }
+        let product = backend::point_mul(&p, &scalar_from_bytes(scalar_bytes));
+        bytes_from_coordinates(product, result_x_bytes, result_y_bytes)
+    })
+}
+
+/// Doubles the STARK curve point given by the first two arguments, writing the affine result into
+/// the last two
+#[no_mangle]
+pub extern "C" fn ec_double(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(p_x_bytes, p_y_bytes, result_x_bytes, result_y_bytes);
+        let p = match point_from_bytes(p_x_bytes, p_y_bytes) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+        bytes_from_coordinates(backend::point_add(&p, &p), result_x_bytes, result_y_bytes)
+    })
+}
+
+/// Negates the STARK curve point given by the first two arguments, writing (x, -y) into the last
+/// two
+#[no_mangle]
+pub extern "C" fn ec_negate(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(p_x_bytes, p_y_bytes, result_x_bytes, result_y_bytes);
+        if let Err(status) = point_from_bytes(p_x_bytes, p_y_bytes) {
+            return status;
+        }
+        let (x, y) = (
+            try_felt_from_bytes(p_x_bytes).unwrap(),
+            try_felt_from_bytes(p_y_bytes).unwrap(),
+        );
+        let zero = backend::felt_from_bytes(&[0; 32]).unwrap();
+        bytes_from_coordinates(
+            Some((x, backend::felt_sub(&zero, &y))),
+            result_x_bytes,
+            result_y_bytes,
+        )
+    })
+}
+
+/// Like ec_add over points with an infinity flag, never returning ERR_POINT_AT_INFINITY. Doubles a
+/// point added to itself
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn ec_add_flagged(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    p_infinity: bool,
+    q_x_bytes: Bytes,
+    q_y_bytes: Bytes,
+    q_infinity: bool,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+    result_infinity: *mut bool,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            result_x_bytes,
+            result_y_bytes,
+            result_infinity
+        );
+        let p = match flagged_point_from_bytes(p_x_bytes, p_y_bytes, p_infinity) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+        let q = match flagged_point_from_bytes(q_x_bytes, q_y_bytes, q_infinity) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+        let sum = match (p, q) {
+            (Some((p, _)), Some((q, _))) => backend::point_add(&p, &q),
+            (Some((_, coordinates)), None) | (None, Some((_, coordinates))) => Some(coordinates),
+            (None, None) => None,
+        };
+        bytes_from_flagged(sum, result_x_bytes, result_y_bytes, result_infinity)
+    })
+}
+
+/// Like ec_mul over points with an infinity flag, never returning ERR_POINT_AT_INFINITY
+#[no_mangle]
+pub extern "C" fn ec_mul_flagged(
+    scalar_bytes: Bytes,
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    p_infinity: bool,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+    result_infinity: *mut bool,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            scalar_bytes,
+            p_x_bytes,
+            p_y_bytes,
+            result_x_bytes,
+            result_y_bytes,
+            result_infinity
+        );
+        let p = match flagged_point_from_bytes(p_x_bytes, p_y_bytes, p_infinity) {
+            Ok(point) => point,
+            Err(status) => return status,
+        };
+        let product = p.and_then(|(p, _)| backend::point_mul(&p, &scalar_from_bytes(scalar_bytes)));
+        bytes_from_flagged(product, result_x_bytes, result_y_bytes, result_infinity)
+    })
+}
+
+/// Computes the sum of scalars[i] * points[i] for the `count` contiguous 256 bit big endian scalars
+/// and points, each the 32 bytes x coordinate followed by the 32 bytes y coordinate, writing the
+/// affine result into the last two arguments. Faster than the `count` ec_mul calls for more than a
+/// few points
+#[no_mangle]
+pub extern "C" fn stark_msm(
+    scalars: *const u8,
+    points: *const u8,
+    count: usize,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result_x_bytes, result_y_bytes);
+        let (scalars, points) = match count.checked_mul(64).ok_or(ERR_INVALID_LENGTH) {
+            Ok(len) => match (
+                slice_from_ptr(scalars, len / 2),
+                slice_from_ptr(points, len),
+            ) {
+                (Ok(scalars), Ok(points)) => (scalars, points),
+                (Err(status), _) | (_, Err(status)) => return status,
+            },
+            Err(status) => return status,
+        };
+        let points: Result<Vec<Point>, i32> = points
+            .chunks_exact(64)
+            .map(|point| {
+                let (x, y) = point.split_at(32);
+                let (Some(x), Some(y)) = (
+                    backend::felt_from_bytes(x.try_into().unwrap()),
+                    backend::felt_from_bytes(y.try_into().unwrap()),
+                ) else {
+                    return Err(ERR_INVALID_FELT);
+                };
+                backend::point_from_coordinates(&x, &y).ok_or(ERR_INVALID_POINT)
+            })
+            .collect();
+        let points = match points {
+            Ok(points) => points,
+            Err(status) => return status,
+        };
+        // Like ec_mul, reduce the scalars modulo the curve order
+        let order = NonZero::new(U256::from_be_bytes(EC_ORDER.to_bytes_be())).unwrap();
+        let scalars: Vec<[u8; 32]> = scalars
+            .chunks_exact(32)
+            .map(|scalar| U256::from_be_slice(scalar).rem(&order).to_be_bytes())
+            .collect();
+
+        let sum = backend::point_msm(&points, &scalars);
+        bytes_from_coordinates(sum, result_x_bytes, result_y_bytes)
+    })
+}
+
+/// Computes the ECDH shared secret of a private key, a felt nonzero modulo the curve order, and the
+/// public point (x, y) of the peer, writing the x coordinate of private_key * peer into the last
+/// argument. Returns ERR_INVALID_POINT when the peer point is not on the curve, which has a
+/// cofactor of one so that every point of it is in the group of the generator. Not constant time
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn stark_ecdh(
+    private_key_bytes: Bytes,
+    peer_x_bytes: Bytes,
+    peer_y_bytes: Bytes,
+    shared_x_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            private_key_bytes,
+            peer_x_bytes,
+            peer_y_bytes,
+            shared_x_bytes
+        );
+        let Some(private_key) = try_secret_from_bytes(private_key_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        let peer = match point_from_bytes(peer_x_bytes, peer_y_bytes) {
+            Ok(peer) => peer,
+            Err(status) => return status,
+        };
+        let scalar = Zeroizing::new(reduce(&private_key).to_be_bytes());
+        if *scalar == [0u8; 32] {
+            return ERR_INVALID_FELT;
+        }
+        match backend::point_mul(&peer, &scalar) {
+            Some((x, _)) => {
+                bytes_from_felt(&x, shared_x_bytes);
+                SUCCESS
+            }
+            None => ERR_POINT_AT_INFINITY,
+        }
+    })
+}
+
+/// Computes p + m * q like the EC_OP builtin, with a double-and-add over the `height` low bits of
+/// the felt m, writing the affine result into the last two arguments. Returns ERR_INVALID_LENGTH
+/// when m doesn't fit in `height` bits, at most 256, and ERR_EC_OP_SAME_X when the partial sum and
+/// the doubled point share their x coordinate at some step, which the builtin rejects. An invalid
+/// input point is ERR_INVALID_POINT
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn ec_op(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    q_x_bytes: Bytes,
+    q_y_bytes: Bytes,
+    m_bytes: Bytes,
+    height: u32,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            m_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        let _profile = Profile::start(Primitive::EcOp, 1);
+        let (Some(p_x), Some(p_y), Some(q_x), Some(q_y), Some(_)) = (
+            try_felt_from_bytes(p_x_bytes),
+            try_felt_from_bytes(p_y_bytes),
+            try_felt_from_bytes(q_x_bytes),
+            try_felt_from_bytes(q_y_bytes),
+            try_felt_from_bytes(m_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let (Some(mut p), Some(mut q)) = (
+            backend::point_from_coordinates(&p_x, &p_y),
+            backend::point_from_coordinates(&q_x, &q_y),
+        ) else {
+            return ERR_INVALID_POINT;
+        };
+        let m = U256::from_be_bytes(array_from_bytes(m_bytes));
+        if height > 256 || m.bits() > height as usize {
+            return ERR_INVALID_LENGTH;
+        }
+
+        // The coordinates are kept along the points, since the backends don't expose them
+        let (mut partial_sum, mut doubled_point) = ((p_x, p_y), (q_x, q_y));
+        // Like cairo-vm's `ec_op_impl`, compare the x coordinates at every step whether the bit is
+        // set or not, so that the same inputs fail in both.
+        for bit in 0..height as usize {
+            if partial_sum.0 == doubled_point.0 {
+                return ERR_EC_OP_SAME_X;
+            }
+            if m.bit_vartime(bit) {
+                // Distinct x coordinates never add up to the point at infinity
+                let sum = backend::point_add(&p, &q).unwrap();
+                p = backend::point_from_coordinates(&sum.0, &sum.1).unwrap();
+                partial_sum = sum;
+            }
+            // There is no point of order 2 to double into the point at infinity
+            let double = backend::point_add(&q, &q).unwrap();
+            q = backend::point_from_coordinates(&double.0, &double.1).unwrap();
+            doubled_point = double;
+        }
+        let status = bytes_from_coordinates(Some(partial_sum), result_x_bytes, result_y_bytes);
+        if status == SUCCESS && differential::enabled() {
+            let mut inputs = Vec::with_capacity(6 * 32);
+            for bytes in [p_x_bytes, p_y_bytes, q_x_bytes, q_y_bytes, m_bytes] {
+                inputs.extend(array_from_bytes(bytes));
+            }
+            // The height as a felt, after the five felts of the operands
+            inputs.extend([0u8; 28]);
+            inputs.extend(height.to_be_bytes());
+            let output = [result_x_bytes, result_y_bytes].map(|bytes| array_from_bytes(bytes));
+            differential::report_bytes(DIFF_EC_OP, &inputs, output.as_flattened());
+        }
+        status
+    })
+}
+
+/// Hashes `len` bytes into a point of the STARK curve, separated from the other uses by a domain
+/// separation tag of `dst_len` bytes, at most 255, writing the point into the last two arguments.
+/// With the prefix the length of the tag as one byte, the x coordinate is the 64 bytes
+/// SHA-256(prefix || dst || msg || counter || 0x00) || SHA-256(prefix || dst || msg || counter ||
+/// 0x01) modulo the STARK prime, for the first 4 bytes big endian counter 0, 1, 2... giving the x
+/// coordinate of a point, and the y coordinate is the smallest of the two. Not constant time: the
+/// number of attempts depends on the message
+#[no_mangle]
+pub extern "C" fn stark_hash_to_curve(
+    msg: *const u8,
+    len: usize,
+    dst: *const u8,
+    dst_len: usize,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes);
+        let Ok(prefix) = u8::try_from(dst_len) else {
+            return ERR_INVALID_LENGTH;
+        };
+        let (msg, dst) = match (slice_from_ptr(msg, len), slice_from_ptr(dst, dst_len)) {
+            (Ok(msg), Ok(dst)) => (msg, dst),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+        let (_, (x, y)) = hash_to_curve(msg, prefix, dst);
+        bytes_from_felt(&x, x_bytes);
+        bytes_from_felt(&y, y_bytes);
+        SUCCESS
+    })
+}
+
+// The point of stark_hash_to_curve, with the prefix the length of the tag.
+pub(crate) fn hash_to_curve(msg: &[u8], prefix: u8, dst: &[u8]) -> PointWithCoordinates {
+    let hasher = Sha256::new()
+        .chain_update([prefix])
+        .chain_update(dst)
+        .chain_update(msg);
+    // About one x coordinate out of two is on the curve
+    for counter in 0u32.. {
+        let hasher = hasher.clone().chain_update(counter.to_be_bytes());
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(&hasher.clone().chain_update([0]).finalize());
+        wide[32..].copy_from_slice(&hasher.chain_update([1]).finalize());
+        let x = backend::felt_from_bytes(&reduce_wide(&wide)).unwrap();
+        if let Some(y) = backend::point_y_from_x(&x) {
+            let point = backend::point_from_coordinates(&x, &y).unwrap();
+            return (point, (x, y));
+        }
+    }
+    unreachable!("no counter gives a point of the curve")
+}
+
+/// Recovers the y coordinate of the STARK curve point with the given x coordinate, writing the
+/// smallest of the two square roots into the second argument
+#[no_mangle]
+pub extern "C" fn ec_point_from_x(x_bytes: Bytes, y_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes);
+        let Some(x) = try_felt_from_bytes(x_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        // Both y and -y are valid. Like cairo-lang's `recover_y`, the backend picks the smallest
+        // square root.
+        let Some(y) = backend::point_y_from_x(&x) else {
+            return ERR_INVALID_POINT;
+        };
+        bytes_from_felt(&y, y_bytes);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_types_core::felt::Felt;
+
+    #[test]
+    fn test_ec_add() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            ec_add(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        // 2G
+        assert_eq!(
+            result_x,
+            felt_bytes("0x759ca09377679ecd535a81e83039658bf40959283187c654c5416f439403cf5")
+        );
+        assert_eq!(
+            result_y,
+            felt_bytes("0x6f524a3400e7708d5c01a28598ad272e7455aa88778b19f93b562d7a9646c41")
+        );
+    }
+
+    #[test]
+    fn test_ec_add_opposite_points() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut minus_g_y = (-GENERATOR.y()).to_bytes_be();
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            ec_add(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                minus_g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            ERR_POINT_AT_INFINITY
+        );
+    }
+
+    #[test]
+    fn test_ec_double_and_negate() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            ec_double(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        // 2G
+        assert_eq!(
+            x,
+            felt_bytes("0x759ca09377679ecd535a81e83039658bf40959283187c654c5416f439403cf5")
+        );
+
+        assert_eq!(
+            ec_negate(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!((x, y), (g_x, (-GENERATOR.y()).to_bytes_be()));
+        let mut one = Felt::ONE.to_bytes_be();
+        assert_eq!(
+            ec_negate(
+                g_x.as_mut_ptr(),
+                one.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr()
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_ec_flagged() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut minus_g_y = (-GENERATOR.y()).to_bytes_be();
+        // Garbage coordinates, ignored along a set infinity flag
+        let mut garbage = [0xffu8; 32];
+        let (mut x, mut y, mut infinity) = ([1u8; 32], [1u8; 32], false);
+        let mut add = |p: (&mut [u8; 32], &mut [u8; 32], bool),
+                       q: (&mut [u8; 32], &mut [u8; 32], bool)| {
+            let status = ec_add_flagged(
+                p.0.as_mut_ptr(),
+                p.1.as_mut_ptr(),
+                p.2,
+                q.0.as_mut_ptr(),
+                q.1.as_mut_ptr(),
+                q.2,
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+                &mut infinity,
+            );
+            (status, x, y, infinity)
+        };
+
+        // G - G, 0 + 0, G + 0 and 0 + G
+        assert_eq!(
+            add(
+                (&mut g_x.clone(), &mut g_y, false),
+                (&mut g_x, &mut minus_g_y, false)
+            ),
+            (SUCCESS, [0; 32], [0; 32], true)
+        );
+        assert_eq!(
+            add(
+                (&mut garbage.clone(), &mut garbage.clone(), true),
+                (&mut garbage, &mut [0; 32], true)
+            ),
+            (SUCCESS, [0; 32], [0; 32], true)
+        );
+        let g = (GENERATOR.x().to_bytes_be(), GENERATOR.y().to_bytes_be());
+        assert_eq!(
+            add(
+                (&mut g_x.clone(), &mut g_y.clone(), false),
+                (&mut garbage.clone(), &mut garbage.clone(), true)
+            ),
+            (SUCCESS, g.0, g.1, false)
+        );
+        assert_eq!(
+            add(
+                (&mut garbage.clone(), &mut garbage.clone(), true),
+                (&mut g_x.clone(), &mut g_y.clone(), false)
+            ),
+            (SUCCESS, g.0, g.1, false)
+        );
+        // The flag doesn't excuse invalid coordinates otherwise
+        assert_eq!(
+            add(
+                (&mut garbage.clone(), &mut garbage.clone(), false),
+                (&mut g_x, &mut g_y, false)
+            )
+            .0,
+            ERR_INVALID_FELT
+        );
+
+        // n * G and k * 0 are the point at infinity
+        let mut order = EC_ORDER.to_bytes_be();
+        let mut mul =
+            |scalar: &mut [u8; 32], p_x: &mut [u8; 32], p_y: &mut [u8; 32], p_infinity| {
+                let status = ec_mul_flagged(
+                    scalar.as_mut_ptr(),
+                    p_x.as_mut_ptr(),
+                    p_y.as_mut_ptr(),
+                    p_infinity,
+                    x.as_mut_ptr(),
+                    y.as_mut_ptr(),
+                    &mut infinity,
+                );
+                (status, x, y, infinity)
+            };
+        let (mut g_x, mut g_y) = g;
+        assert_eq!(
+            mul(&mut order, &mut g_x, &mut g_y, false),
+            (SUCCESS, [0; 32], [0; 32], true)
+        );
+        assert_eq!(
+            mul(
+                &mut Felt::TWO.to_bytes_be(),
+                &mut garbage.clone(),
+                &mut garbage,
+                true
+            ),
+            (SUCCESS, [0; 32], [0; 32], true)
+        );
+        let (status, x, _, infinity) = mul(&mut Felt::TWO.to_bytes_be(), &mut g_x, &mut g_y, false);
+        assert_eq!((status, infinity), (SUCCESS, false));
+        assert_eq!(
+            x,
+            felt_bytes("0x759ca09377679ecd535a81e83039658bf40959283187c654c5416f439403cf5")
+        );
+    }
+
+    #[test]
+    fn test_ec_add_invalid_point() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut one = Felt::ONE.to_bytes_be();
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            ec_add(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                one.as_mut_ptr(),
+                one.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    fn felt_bytes(hex: &str) -> [u8; 32] {
+        Felt::from_hex(hex).unwrap().to_bytes_be()
+    }
+
+    #[test]
+    fn test_ec_mul() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut scalar =
+            felt_bytes("0x03c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc");
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            ec_mul(
+                scalar.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        // The x coordinate of private_key * G is the public key
+        assert_eq!(
+            result_x,
+            felt_bytes("0x077a3b314db07c45076d11f62b6f9e748a39790441823307743cf00d6597ea43")
+        );
+
+        // The generator's table gives the products of the double-and-add, the point at infinity
+        // included
+        let generator = point_from_bytes(g_x.as_mut_ptr(), g_y.as_mut_ptr()).unwrap();
+        for mut scalar in [scalar, [0xff; 32], EC_ORDER.to_bytes_be(), [0u8; 32]] {
+            let expected = backend::point_mul(&generator, &scalar_from_bytes(scalar.as_mut_ptr()));
+            let status = ec_mul(
+                scalar.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            );
+            match expected {
+                Some((x, y)) => {
+                    assert_eq!(status, SUCCESS);
+                    assert_eq!(
+                        (result_x, result_y),
+                        (backend::bytes_from_felt(&x), backend::bytes_from_felt(&y))
+                    );
+                }
+                None => assert_eq!(status, ERR_POINT_AT_INFINITY),
+            }
+        }
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_stark_ecdh() {
+        use starknet_crypto_rs::get_public_key;
+
+        let (alice, bob) = (Felt::from(0xa11ceu64), Felt::from(0xb0bu64));
+        let public_point = |private_key: &Felt| {
+            let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+            let status = ec_mul(
+                private_key.to_bytes_be().as_mut_ptr(),
+                GENERATOR.x().to_bytes_be().as_mut_ptr(),
+                GENERATOR.y().to_bytes_be().as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+            );
+            assert_eq!(status, SUCCESS);
+            (x, y)
+        };
+        let shared = |private_key: &Felt, (mut x, mut y): ([u8; 32], [u8; 32])| {
+            let mut shared_x = [0u8; 32];
+            let status = stark_ecdh(
+                private_key.to_bytes_be().as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+                shared_x.as_mut_ptr(),
+            );
+            (status, shared_x)
+        };
+        let (status, alice_shared) = shared(&alice, public_point(&bob));
+        assert_eq!(status, SUCCESS);
+        assert_eq!(shared(&bob, public_point(&alice)), (SUCCESS, alice_shared));
+        // The shared x is the public key of the product of the private keys
+        assert_eq!(alice_shared, get_public_key(&(alice * bob)).to_bytes_be());
+
+        let (x, mut y) = public_point(&bob);
+        y[31] ^= 1;
+        assert_eq!(shared(&alice, (x, y)).0, ERR_INVALID_POINT);
+        assert_eq!(shared(&EC_ORDER, public_point(&bob)).0, ERR_INVALID_FELT);
+    }
+
+    #[test]
+    fn test_ec_mul_reduces_scalar() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+
+        // n * G is the point at infinity
+        let mut order = EC_ORDER.to_bytes_be();
+        assert_eq!(
+            ec_mul(
+                order.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            ERR_POINT_AT_INFINITY
+        );
+
+        // (n + 2) * G is 2G
+        let mut order_plus_two = (EC_ORDER + Felt::TWO).to_bytes_be();
+        assert_eq!(
+            ec_mul(
+                order_plus_two.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            result_x,
+            felt_bytes("0x759ca09377679ecd535a81e83039658bf40959283187c654c5416f439403cf5")
+        );
+
+        // Scalars don't need to be felts
+        let mut max_scalar = [0xffu8; 32];
+        assert_eq!(
+            ec_mul(
+                max_scalar.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+    }
+
+    // Returns the coordinates of k * G.
+    fn multiple_of_generator(k: u8) -> ([u8; 32], [u8; 32]) {
+        let mut scalar = Felt::from(k).to_bytes_be();
+        let (mut g_x, mut g_y) = (GENERATOR.x().to_bytes_be(), GENERATOR.y().to_bytes_be());
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            ec_mul(
+                scalar.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        (x, y)
+    }
+
+    fn msm(scalars: &[u64], points: &[([u8; 32], [u8; 32])]) -> Result<([u8; 32], [u8; 32]), i32> {
+        let scalars: Vec<u8> = scalars
+            .iter()
+            .flat_map(|&scalar| Felt::from(scalar).to_bytes_be())
+            .collect();
+        let points: Vec<u8> = points
+            .iter()
+            .flat_map(|(x, y)| x.iter().chain(y))
+            .copied()
+            .collect();
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        match stark_msm(
+            scalars.as_ptr(),
+            points.as_ptr(),
+            points.len() / 64,
+            x.as_mut_ptr(),
+            y.as_mut_ptr(),
+        ) {
+            SUCCESS => Ok((x, y)),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_stark_msm() {
+        let points: Vec<_> = (1..=3).map(multiple_of_generator).collect();
+        // G + 2 * 2G + 3 * 3G
+        assert_eq!(msm(&[1, 2, 3], &points), Ok(multiple_of_generator(14)));
+
+        // Enough points for wider windows, 1 * G + 2 * 2G + ... + 100 * 100G = 338350 G
+        let points: Vec<_> = (1..=100).map(multiple_of_generator).collect();
+        let scalars: Vec<u64> = (1..=100).collect();
+        let mut scalar = Felt::from(338350u32).to_bytes_be();
+        let (mut g_x, mut g_y) = (GENERATOR.x().to_bytes_be(), GENERATOR.y().to_bytes_be());
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            ec_mul(
+                scalar.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(msm(&scalars, &points), Ok((x, y)));
+    }
+
+    #[test]
+    fn test_stark_msm_at_infinity() {
+        let g = multiple_of_generator(1);
+        let minus_g = (g.0, (-Felt::from_bytes_be(&g.1)).to_bytes_be());
+        // The buckets of 5 * G and 5 * -G cancel out
+        assert_eq!(msm(&[5, 5], &[g, minus_g]), Err(ERR_POINT_AT_INFINITY));
+        assert_eq!(msm(&[5, 4], &[g, minus_g]), Ok(g));
+        assert_eq!(msm(&[], &[]), Err(ERR_POINT_AT_INFINITY));
+        assert_eq!(msm(&[1], &[(g.0, g.0)]), Err(ERR_INVALID_POINT));
+    }
+
+    fn op(p: u8, q: u8, m: u8, height: u32) -> Result<([u8; 32], [u8; 32]), i32> {
+        let (mut p_x, mut p_y) = multiple_of_generator(p);
+        let (mut q_x, mut q_y) = multiple_of_generator(q);
+        let mut m = Felt::from(m).to_bytes_be();
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        match ec_op(
+            p_x.as_mut_ptr(),
+            p_y.as_mut_ptr(),
+            q_x.as_mut_ptr(),
+            q_y.as_mut_ptr(),
+            m.as_mut_ptr(),
+            height,
+            x.as_mut_ptr(),
+            y.as_mut_ptr(),
+        ) {
+            SUCCESS => Ok((x, y)),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_ec_op() {
+        // 2G + 3 * G
+        assert_eq!(op(2, 1, 3, 256), Ok(multiple_of_generator(5)));
+        assert_eq!(op(2, 1, 3, 2), Ok(multiple_of_generator(5)));
+        assert_eq!(op(2, 1, 0, 1), Ok(multiple_of_generator(2)));
+        // 4 doesn't fit in 2 bits
+        assert_eq!(op(2, 1, 4, 2), Err(ERR_INVALID_LENGTH));
+        assert_eq!(op(2, 1, 3, 257), Err(ERR_INVALID_LENGTH));
+    }
+
+    #[test]
+    fn test_ec_op_same_x_coordinate() {
+        assert_eq!(op(1, 1, 1, 256), Err(ERR_EC_OP_SAME_X));
+        // 3G + G = 4G meets the doubled point 4G at the third step, whose bit is not set
+        assert_eq!(op(3, 1, 1, 2), Ok(multiple_of_generator(4)));
+        assert_eq!(op(3, 1, 1, 3), Err(ERR_EC_OP_SAME_X));
+    }
+
+    #[test]
+    fn test_stark_curve_parameters() {
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            stark_curve_generator(x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            x,
+            felt_bytes("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca")
+        );
+        assert_eq!(
+            y,
+            felt_bytes("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f")
+        );
+        assert_eq!(
+            stark_point_validate(x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+
+        let mut order = [0u8; 32];
+        assert_eq!(stark_curve_order(order.as_mut_ptr()), SUCCESS);
+        assert_eq!(
+            order,
+            felt_bytes("0x800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f")
+        );
+
+        let (mut alpha, mut beta) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(stark_curve_alpha(alpha.as_mut_ptr()), SUCCESS);
+        assert_eq!(stark_curve_beta(beta.as_mut_ptr()), SUCCESS);
+        assert_eq!(alpha, felt_bytes("0x1"));
+        assert_eq!(
+            beta,
+            felt_bytes("0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89")
+        );
+    }
+
+    #[test]
+    fn test_stark_point_validate() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut g_y = GENERATOR.y().to_bytes_be();
+        let mut one = Felt::ONE.to_bytes_be();
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            stark_point_validate(g_x.as_mut_ptr(), g_y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            stark_point_validate(g_x.as_mut_ptr(), one.as_mut_ptr()),
+            ERR_INVALID_POINT
+        );
+        assert_eq!(
+            stark_point_validate(g_x.as_mut_ptr(), not_a_felt.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_stark_pubkey_validate() {
+        let mut public_key =
+            felt_bytes("0x077a3b314db07c45076d11f62b6f9e748a39790441823307743cf00d6597ea43");
+        assert_eq!(stark_pubkey_validate(public_key.as_mut_ptr()), SUCCESS);
+        // x^3 + x + beta is not a square for x = 5
+        let mut x = Felt::from(5u8).to_bytes_be();
+        assert_eq!(stark_pubkey_validate(x.as_mut_ptr()), ERR_INVALID_POINT);
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            stark_pubkey_validate(not_a_felt.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_stark_hash_to_curve() {
+        let hash = |msg: &[u8], dst: &[u8]| {
+            let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+            let status = stark_hash_to_curve(
+                msg.as_ptr(),
+                msg.len(),
+                dst.as_ptr(),
+                dst.len(),
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+            );
+            assert_eq!(status, SUCCESS);
+            (x, y)
+        };
+        let (mut x, mut y) = hash(b"message", b"DST");
+        assert_eq!(
+            stark_point_validate(x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(hash(b"message", b"DST"), (x, y));
+        assert_ne!(hash(b"message", b"DST2").0, x);
+        // The length prefix of the tag keeps the splits of the same bytes apart
+        assert_ne!(hash(b"Tmessage", b"DS").0, x);
+        hash(b"", b"");
+
+        // The first attempt, when it is on the curve, is the two hashes modulo the prime
+        let attempt = |counter: u32| {
+            let hasher = Sha256::new()
+                .chain_update([3])
+                .chain_update(b"DST")
+                .chain_update(b"message")
+                .chain_update(counter.to_be_bytes());
+            let wide = [
+                hasher.clone().chain_update([0]).finalize(),
+                hasher.chain_update([1]).finalize(),
+            ]
+            .concat();
+            reduce_wide(&wide)
+        };
+        let mut counter = 0;
+        while backend::point_y_from_x(&backend::felt_from_bytes(&attempt(counter)).unwrap())
+            .is_none()
+        {
+            counter += 1;
+        }
+        assert_eq!(x, attempt(counter));
+
+        let dst = [0u8; 256];
+        assert_eq!(
+            stark_hash_to_curve(
+                dst.as_ptr(),
+                0,
+                dst.as_ptr(),
+                256,
+                x.as_mut_ptr(),
+                y.as_mut_ptr()
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_ec_point_from_x() {
+        let mut g_x = GENERATOR.x().to_bytes_be();
+        let mut y = [0u8; 32];
+        assert_eq!(ec_point_from_x(g_x.as_mut_ptr(), y.as_mut_ptr()), SUCCESS);
+
+        let y = Felt::from_bytes_be(&y);
+        assert!(y == GENERATOR.y() || y == -GENERATOR.y());
+        assert!(y <= -y);
+    }
+
+    #[test]
+    fn test_ec_point_from_x_not_on_curve() {
+        // x^3 + x + beta is not a square for x = 5
+        let mut x = Felt::from(5u8).to_bytes_be();
+        let mut y = [0u8; 32];
+        assert_eq!(
+            ec_point_from_x(x.as_mut_ptr(), y.as_mut_ptr()),
+            ERR_INVALID_POINT
+        );
+    }
+}
@@ -0,0 +1,153 @@
+// A differential testing callback set by the embedder, receiving the inputs and the output of each
+// primitive computed by the library, so that a native implementation of the embedder can be checked
+// against it call by call while the primitives move over one at a time.
+//
+// The callback is called after each successful call of the pedersen, poseidon, ECDSA verification,
+// keccak and EC_OP functions, and once per record of their batch functions, from whichever thread
+// computed it. Like the logger, the reports are only built when a callback is set, so they cost an
+// atomic load otherwise.
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use starknet_types_core::felt::Felt;
+
+use crate::{ffi_guard, DIFF_ECDSA_VERIFY, ERR_INVALID_SIGNATURE, SUCCESS};
+
+/// A differential testing callback, receiving a DIFF_ primitive with its inputs and its output. The
+/// felts are 32 bytes big endian and contiguous, and the buffers are only valid for the duration of
+/// the call. NULL for no callback
+pub type Differential = Option<
+    extern "C" fn(
+        primitive: u32,
+        inputs: *const u8,
+        inputs_len: usize,
+        output: *const u8,
+        output_len: usize,
+    ),
+>;
+
+type Callback = extern "C" fn(u32, *const u8, usize, *const u8, usize);
+
+static DIFFERENTIAL: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Reports the inputs and outputs of the primitives to a callback, or stops reporting them for
+/// NULL (the default). The callback must be callable from any thread
+#[no_mangle]
+pub extern "C" fn starknet_crypto_set_differential(callback: Differential) -> i32 {
+    ffi_guard(|| {
+        let callback = callback.map_or(core::ptr::null_mut(), |callback| callback as *mut ());
+        DIFFERENTIAL.store(callback, Ordering::Release);
+        SUCCESS
+    })
+}
+
+fn callback() -> Option<Callback> {
+    let callback = DIFFERENTIAL.load(Ordering::Acquire);
+    // Only starknet_crypto_set_differential stores into DIFFERENTIAL, and always a non NULL callback
+    (!callback.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), Callback>(callback) })
+}
+
+// Whether a callback is set, for the primitives that must save their inputs before computing.
+pub(crate) fn enabled() -> bool {
+    !DIFFERENTIAL.load(Ordering::Acquire).is_null()
+}
+
+fn felt_bytes(felts: &[Felt]) -> Vec<u8> {
+    felts.iter().flat_map(|felt| felt.to_bytes_be()).collect()
+}
+
+// Reports a primitive of felts when a callback is set.
+pub(crate) fn report(primitive: u32, inputs: &[Felt], output: &[Felt]) {
+    if let Some(callback) = callback() {
+        let (inputs, output) = (felt_bytes(inputs), felt_bytes(output));
+        callback(
+            primitive,
+            inputs.as_ptr(),
+            inputs.len(),
+            output.as_ptr(),
+            output.len(),
+        );
+    }
+}
+
+// Reports a primitive of bytes when a callback is set.
+pub(crate) fn report_bytes(primitive: u32, inputs: &[u8], output: &[u8]) {
+    if let Some(callback) = callback() {
+        callback(
+            primitive,
+            inputs.as_ptr(),
+            inputs.len(),
+            output.as_ptr(),
+            output.len(),
+        );
+    }
+}
+
+// Reports a signature verification, whose output is the felt 1 for a valid signature and 0 for an
+// invalid one.
+pub(crate) fn report_verification(inputs: &[Felt], status: i32) {
+    match status {
+        SUCCESS => report(DIFF_ECDSA_VERIFY, inputs, &[Felt::ONE]),
+        ERR_INVALID_SIGNATURE => report(DIFF_ECDSA_VERIFY, inputs, &[Felt::ZERO]),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pedersen_hash, poseidon_hash_many, DIFF_PEDERSEN, DIFF_POSEIDON_MANY};
+    use std::sync::Mutex;
+
+    // The primitive, inputs and output of each report.
+    type Report = (u32, Vec<u8>, Vec<u8>);
+
+    static REPORTS: Mutex<Vec<Report>> = Mutex::new(Vec::new());
+
+    extern "C" fn record(
+        primitive: u32,
+        inputs: *const u8,
+        inputs_len: usize,
+        output: *const u8,
+        output_len: usize,
+    ) {
+        let inputs = unsafe { core::slice::from_raw_parts(inputs, inputs_len) };
+        let output = unsafe { core::slice::from_raw_parts(output, output_len) };
+        REPORTS
+            .lock()
+            .unwrap()
+            .push((primitive, inputs.to_vec(), output.to_vec()));
+    }
+
+    #[test]
+    fn test_differential() {
+        let mut a = Felt::ONE.to_bytes_be();
+        let mut b = Felt::TWO.to_bytes_be();
+        let mut result = [0u8; 32];
+        assert_eq!(starknet_crypto_set_differential(Some(record)), SUCCESS);
+        assert_eq!(
+            pedersen_hash(a.as_mut_ptr(), b.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        let felts = [a, b].concat();
+        assert_eq!(
+            poseidon_hash_many(felts.as_ptr(), 2, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(starknet_crypto_set_differential(None), SUCCESS);
+
+        // Other tests may run primitives meanwhile, only the reports of these inputs are checked
+        let reports = REPORTS.lock().unwrap();
+        let pedersen = reports
+            .iter()
+            .find(|(primitive, inputs, _)| *primitive == DIFF_PEDERSEN && *inputs == felts)
+            .unwrap();
+        let expected = starknet_crypto_rs::pedersen_hash(&Felt::ONE, &Felt::TWO);
+        assert_eq!(pedersen.2, expected.to_bytes_be());
+        let poseidon = reports
+            .iter()
+            .find(|(primitive, inputs, _)| *primitive == DIFF_POSEIDON_MANY && *inputs == felts)
+            .unwrap();
+        assert_eq!(poseidon.2, result);
+    }
+}
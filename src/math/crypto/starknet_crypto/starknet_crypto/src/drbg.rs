@@ -0,0 +1,158 @@
+// A deterministic generator of random felts over the Poseidon sponge, so that the fuzzing and
+// property tests of the VM draw the same felts from a seed in every implementation.
+//
+// The state of the sponge starts as (seed, 0, "STARKNET_CRYPTO_DRBG"), the capacity being the tag
+// as a short string. Each permutation of the state, starting with the initial one, yields the two
+// felts of its rate. The capacity is never output, so the state can't be recovered from the felts.
+// Like the transcripts, generators are opaque to C and released with drbg_free.
+use alloc::boxed::Box;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, hades::poseidon_permute_comp,
+    try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_LENGTH, SUCCESS,
+};
+
+/// A deterministic generator of random felts
+pub struct Drbg {
+    state: [Felt; 3],
+    // The number of felts of the rate already output since the last permutation
+    squeezed: usize,
+}
+
+impl Drbg {
+    pub(crate) fn new(seed: Felt) -> Self {
+        let tag = Felt::from_bytes_be_slice(b"STARKNET_CRYPTO_DRBG");
+        Drbg {
+            state: [seed, Felt::ZERO, tag],
+            squeezed: 2,
+        }
+    }
+
+    pub(crate) fn next_felt(&mut self) -> Felt {
+        if self.squeezed == 2 {
+            poseidon_permute_comp(&mut self.state);
+            self.squeezed = 0;
+        }
+        self.squeezed += 1;
+        self.state[self.squeezed - 1]
+    }
+}
+
+/// Allocates a generator of the random felts of a seed felt, writing it into the last argument. It
+/// must be released with drbg_free
+#[no_mangle]
+pub extern "C" fn drbg_new(seed: Bytes, drbg: *mut *mut Drbg) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(seed, drbg);
+        let Some(seed) = try_field_element_from_bytes(seed) else {
+            return ERR_INVALID_FELT;
+        };
+        unsafe { *drbg = Box::into_raw(Box::new(Drbg::new(seed))) };
+        SUCCESS
+    })
+}
+
+/// Writes the next felt of the generator into the last argument
+#[no_mangle]
+pub extern "C" fn drbg_next_felt(drbg: *mut Drbg, felt: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(drbg, felt);
+        let next = unsafe { (*drbg).next_felt() };
+        bytes_from_field_element(next, felt);
+        SUCCESS
+    })
+}
+
+/// Writes the next `count` felts of the generator contiguously into the buffer, the same felts as
+/// `count` calls of drbg_next_felt
+#[no_mangle]
+pub extern "C" fn drbg_fill(drbg: *mut Drbg, buffer: *mut u8, count: usize) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(drbg);
+        if count == 0 {
+            return SUCCESS;
+        }
+        let Some(len) = count
+            .checked_mul(32)
+            .filter(|&len| len <= isize::MAX as usize)
+        else {
+            return ERR_INVALID_LENGTH;
+        };
+        if buffer.is_null() {
+            return ERR_INVALID_LENGTH;
+        }
+        let drbg = unsafe { &mut *drbg };
+        let buffer = unsafe { core::slice::from_raw_parts_mut(buffer, len) };
+        for chunk in buffer.chunks_exact_mut(32) {
+            chunk.copy_from_slice(&drbg.next_felt().to_bytes_be());
+        }
+        SUCCESS
+    })
+}
+
+/// Releases a generator allocated by drbg_new. Does nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn drbg_free(drbg: *mut Drbg) -> i32 {
+    ffi_guard(|| {
+        if !drbg.is_null() {
+            drop(unsafe { Box::from_raw(drbg) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_drbg(seed: u64) -> *mut Drbg {
+        let mut drbg = core::ptr::null_mut();
+        assert_eq!(
+            drbg_new(Felt::from(seed).to_bytes_be().as_mut_ptr(), &mut drbg),
+            SUCCESS
+        );
+        drbg
+    }
+
+    fn next_felt(drbg: *mut Drbg) -> [u8; 32] {
+        let mut felt = [0u8; 32];
+        assert_eq!(drbg_next_felt(drbg, felt.as_mut_ptr()), SUCCESS);
+        felt
+    }
+
+    #[test]
+    fn test_drbg() {
+        let drbg = new_drbg(42);
+        let felts: Vec<[u8; 32]> = (0..5).map(|_| next_felt(drbg)).collect();
+        assert_eq!(drbg_free(drbg), SUCCESS);
+
+        // The first two felts are the rate of the permutation of the initial state
+        let tag = Felt::from_bytes_be_slice(b"STARKNET_CRYPTO_DRBG");
+        let mut state = [Felt::from(42u64), Felt::ZERO, tag];
+        poseidon_permute_comp(&mut state);
+        assert_eq!(felts[0], state[0].to_bytes_be());
+        assert_eq!(felts[1], state[1].to_bytes_be());
+        poseidon_permute_comp(&mut state);
+        assert_eq!(felts[2], state[0].to_bytes_be());
+
+        // Filling draws the same stream, whatever the split of the calls
+        let drbg = new_drbg(42);
+        let mut buffer = [0u8; 5 * 32];
+        assert_eq!(drbg_fill(drbg, buffer.as_mut_ptr(), 1), SUCCESS);
+        assert_eq!(drbg_fill(drbg, core::ptr::null_mut(), 0), SUCCESS);
+        assert_eq!(drbg_fill(drbg, buffer[32..].as_mut_ptr(), 4), SUCCESS);
+        assert_eq!(buffer[..], felts.concat()[..]);
+        assert_eq!(
+            drbg_fill(drbg, core::ptr::null_mut(), 1),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(drbg_free(drbg), SUCCESS);
+
+        let other = new_drbg(43);
+        assert_ne!(next_felt(other), felts[0]);
+        assert_eq!(drbg_free(other), SUCCESS);
+        assert_eq!(drbg_free(core::ptr::null_mut()), SUCCESS);
+    }
+}
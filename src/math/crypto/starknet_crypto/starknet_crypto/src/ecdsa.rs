@@ -0,0 +1,220 @@
+// The ECDSA functions of starknet-crypto that can end on the point at infinity, which
+// starknet-crypto unwraps: the public key of a multiple of the curve order, the signature of a k
+// equal to the order, and the signatures whose verification or recovery sums to the point at
+// infinity, which the callers can reach by choosing the public key. Otherwise the results are
+// those of starknet-crypto.
+#[cfg(all(feature = "signing", any(not(feature = "std"), test)))]
+use starknet_crypto_rs::{ExtendedSignature, SignError};
+use starknet_crypto_rs::{RecoverError, VerifyError};
+use starknet_curve::curve_params::{ALPHA, BETA, EC_ORDER, GENERATOR};
+use starknet_types_core::{
+    curve::{AffinePoint, ProjectivePoint},
+    felt::{Felt, NonZeroFelt},
+};
+
+const ORDER: NonZeroFelt = NonZeroFelt::from_felt_unchecked(EC_ORDER);
+
+fn generator() -> ProjectivePoint {
+    ProjectivePoint::from_affine_unchecked(GENERATOR.x(), GENERATOR.y())
+}
+
+// Like get_public_key of starknet-crypto, zero for the multiples of the order as in
+// generator::public_key.
+#[cfg(any(not(feature = "std"), test))]
+pub(crate) fn get_public_key(private_key: &Felt) -> Felt {
+    if *private_key == Felt::ZERO || *private_key == EC_ORDER {
+        return Felt::ZERO;
+    }
+    starknet_crypto_rs::get_public_key(private_key)
+}
+
+// Like sign of starknet-crypto, InvalidK for a k equal to the order as for zero.
+#[cfg(all(feature = "signing", any(not(feature = "std"), test)))]
+pub(crate) fn sign(
+    private_key: &Felt,
+    message: &Felt,
+    k: &Felt,
+) -> Result<ExtendedSignature, SignError> {
+    if *k == EC_ORDER && *message < Felt::ELEMENT_UPPER_BOUND {
+        return Err(SignError::InvalidK);
+    }
+    starknet_crypto_rs::sign(private_key, message, k)
+}
+
+// Like verify of starknet-crypto, false when one of the two candidates for r is the point at
+// infinity.
+pub(crate) fn verify(
+    public_key: &Felt,
+    message: &Felt,
+    r: &Felt,
+    s: &Felt,
+) -> Result<bool, VerifyError> {
+    if *message >= Felt::ELEMENT_UPPER_BOUND {
+        return Err(VerifyError::InvalidMessageHash);
+    }
+    if *r == Felt::ZERO || *r >= Felt::ELEMENT_UPPER_BOUND {
+        return Err(VerifyError::InvalidR);
+    }
+    if *s == Felt::ZERO || *s >= Felt::ELEMENT_UPPER_BOUND {
+        return Err(VerifyError::InvalidS);
+    }
+    let y = (public_key * public_key * public_key + ALPHA * public_key + BETA)
+        .sqrt()
+        .ok_or(VerifyError::InvalidPublicKey)?;
+    let public_key = ProjectivePoint::from_affine_unchecked(*public_key, y);
+
+    let w = s.mod_inverse(&ORDER).ok_or(VerifyError::InvalidS)?;
+    if w == Felt::ZERO || w >= Felt::ELEMENT_UPPER_BOUND {
+        return Err(VerifyError::InvalidS);
+    }
+    let zw_g = &generator() * message.mul_mod(&w, &ORDER);
+    let rw_q = &public_key * r.mul_mod(&w, &ORDER);
+    let has_x_r = |point: ProjectivePoint| point.to_affine().is_ok_and(|point| point.x() == *r);
+    Ok(has_x_r(&zw_g + &rw_q) || has_x_r(&zw_g - &rw_q))
+}
+
+// Like recover of starknet-crypto, InvalidS when the recovered key would be the point at
+// infinity, that is when s * R = z * G.
+pub(crate) fn recover(message: &Felt, r: &Felt, s: &Felt, v: &Felt) -> Result<Felt, RecoverError> {
+    if *message >= Felt::ELEMENT_UPPER_BOUND {
+        return Err(RecoverError::InvalidMessageHash);
+    }
+    if *r == Felt::ZERO || *r >= Felt::ELEMENT_UPPER_BOUND {
+        return Err(RecoverError::InvalidR);
+    }
+    if *s == Felt::ZERO || *s >= EC_ORDER {
+        return Err(RecoverError::InvalidS);
+    }
+    if *v > Felt::ONE {
+        return Err(RecoverError::InvalidV);
+    }
+    // The y coordinate of R has the parity v
+    let full_r = AffinePoint::new_from_x(r, *v == Felt::ONE).ok_or(RecoverError::InvalidR)?;
+    let full_r = ProjectivePoint::from_affine_unchecked(full_r.x(), full_r.y());
+
+    let r_inverse = r.mod_inverse(&ORDER).ok_or(RecoverError::InvalidR)?;
+    let rs_zg = &(&full_r * *s) - &(&generator() * *message);
+    let public_key = &rs_zg * r_inverse;
+    public_key
+        .to_affine()
+        .map(|point| point.x())
+        .map_err(|_| RecoverError::InvalidS)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use super::*;
+
+    #[test]
+    fn test_get_public_key() {
+        for private_key in [Felt::ONE, Felt::TWO, EC_ORDER - Felt::ONE] {
+            assert_eq!(
+                get_public_key(&private_key),
+                starknet_crypto_rs::get_public_key(&private_key)
+            );
+        }
+        for private_key in [Felt::ZERO, EC_ORDER] {
+            assert!(catch_unwind(|| starknet_crypto_rs::get_public_key(&private_key)).is_err());
+            assert_eq!(get_public_key(&private_key), Felt::ZERO);
+        }
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_sign() {
+        let private_key = Felt::from(0x1234u64);
+        assert_eq!(
+            sign(&private_key, &Felt::TWO, &Felt::THREE).unwrap().r,
+            starknet_crypto_rs::sign(&private_key, &Felt::TWO, &Felt::THREE)
+                .unwrap()
+                .r
+        );
+        assert!(
+            catch_unwind(|| starknet_crypto_rs::sign(&private_key, &Felt::TWO, &EC_ORDER)).is_err()
+        );
+        assert!(matches!(
+            sign(&private_key, &Felt::TWO, &EC_ORDER),
+            Err(SignError::InvalidK)
+        ));
+        assert!(matches!(
+            sign(&private_key, &Felt::ELEMENT_UPPER_BOUND, &EC_ORDER),
+            Err(SignError::InvalidMessageHash)
+        ));
+    }
+
+    #[test]
+    fn test_verify() {
+        let private_key = Felt::from(0x1234u64);
+        let public_key = starknet_crypto_rs::get_public_key(&private_key);
+        let signature = starknet_crypto_rs::sign(&private_key, &Felt::TWO, &Felt::THREE).unwrap();
+        let bound = Felt::ELEMENT_UPPER_BOUND;
+        for (public_key, message, r, s) in [
+            (public_key, Felt::TWO, signature.r, signature.s),
+            (public_key, Felt::THREE, signature.r, signature.s),
+            (public_key, Felt::TWO, signature.r, Felt::ONE),
+            (public_key, bound, signature.r, signature.s),
+            (public_key, Felt::TWO, Felt::ZERO, signature.s),
+            (public_key, Felt::TWO, signature.r, bound),
+            (Felt::ZERO, Felt::TWO, signature.r, signature.s),
+        ] {
+            assert_eq!(
+                format!("{:?}", verify(&public_key, &message, &r, &s)),
+                format!(
+                    "{:?}",
+                    starknet_crypto_rs::verify(&public_key, &message, &r, &s)
+                )
+            );
+        }
+
+        // With the generator as public key, z = n - r sums z * w * G and r * w * G to the point
+        // at infinity
+        let r = Felt::from_hex_unchecked(
+            "0x400000000000000000000000000000000000000000000000000000000000000",
+        );
+        let (public_key, message) = (GENERATOR.x(), EC_ORDER - r);
+        let expected = catch_unwind(|| starknet_crypto_rs::verify(&public_key, &message, &r, &r));
+        assert!(expected.is_err());
+        assert!(matches!(verify(&public_key, &message, &r, &r), Ok(false)));
+    }
+
+    #[test]
+    fn test_recover() {
+        let private_key = Felt::from(0x1234u64);
+        let signature = starknet_crypto_rs::sign(&private_key, &Felt::TWO, &Felt::THREE).unwrap();
+        for (message, r, s, v) in [
+            (Felt::TWO, signature.r, signature.s, signature.v),
+            (Felt::TWO, signature.r, signature.s, Felt::ONE - signature.v),
+            (
+                Felt::ELEMENT_UPPER_BOUND,
+                signature.r,
+                signature.s,
+                signature.v,
+            ),
+            (Felt::TWO, Felt::ZERO, signature.s, signature.v),
+            (Felt::TWO, signature.r, EC_ORDER, signature.v),
+            (Felt::TWO, signature.r, signature.s, Felt::TWO),
+        ] {
+            assert_eq!(
+                format!("{:?}", recover(&message, &r, &s, &v)),
+                format!("{:?}", starknet_crypto_rs::recover(&message, &r, &s, &v))
+            );
+        }
+        assert_eq!(
+            recover(&Felt::TWO, &signature.r, &signature.s, &signature.v).unwrap(),
+            starknet_crypto_rs::get_public_key(&private_key)
+        );
+
+        // R = k * G for the k of the signature, so s * R = z * G for s = z / k
+        let k_inverse = Felt::THREE.mod_inverse(&ORDER).unwrap();
+        let s = Felt::TWO.mul_mod(&k_inverse, &ORDER);
+        let r = signature.r;
+        let v = signature.v;
+        assert!(catch_unwind(|| starknet_crypto_rs::recover(&Felt::TWO, &r, &s, &v)).is_err());
+        assert!(matches!(
+            recover(&Felt::TWO, &r, &s, &v),
+            Err(RecoverError::InvalidS)
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+// Encryption to a Stark public key, ECIES style over the STARK curve.
+//
+// The sender draws an ephemeral private key e and derives the shared secret S, the x coordinate of
+// e * P for the recipient's public key P, which the recipient finds again as d * E for its private
+// key d and the ephemeral public key E. The x coordinates are enough, since the points of opposite
+// y share the x coordinate of their products. The keys are the Poseidon hashes of a tag, S and E.x,
+// the keystream the big endian low 31 bytes of poseidon_hash(encryption key, i) for the blocks i,
+// XORed with the plaintext, and the tag the Poseidon hash of many felts of the authentication key,
+// the length and the 31 bytes big endian words of the ciphertext. A message encrypts as E.x, the
+// ciphertext and the tag, ENCRYPTION_OVERHEAD bytes longer than the plaintext.
+use alloc::vec::Vec;
+
+use elliptic_curve::bigint::Encoding;
+use starknet_types_core::felt::Felt;
+use zeroize::Zeroizing;
+
+use crate::{
+    backend::{self, Point},
+    ffi_guard,
+    hades::{poseidon_hash, poseidon_hash_many},
+    schnorr::{felt_from_field_element, reduce},
+    secret::{try_secret_from_bytes, SecretFelt},
+    slice_from_ptr, try_felt_from_array, Bytes, ENCRYPTION_OVERHEAD, ERR_DECRYPTION_FAILED,
+    ERR_INVALID_FELT, ERR_INVALID_LENGTH, ERR_INVALID_POINT, SUCCESS,
+};
+#[cfg(feature = "random")]
+use crate::{random::draw_private_key, schnorr::generator, try_field_element_from_bytes};
+
+// The length of a block of the keystream and of a word of the tag.
+const BLOCK_LEN: usize = 31;
+
+// The point of x coordinate x, with either y.
+fn point_from_x(x: &Felt) -> Result<Point, i32> {
+    let x = felt_from_field_element(x);
+    let y = backend::point_y_from_x(&x).ok_or(ERR_INVALID_POINT)?;
+    backend::point_from_coordinates(&x, &y).ok_or(ERR_INVALID_POINT)
+}
+
+// The x coordinate of scalar * point, for a scalar nonzero modulo the curve order.
+fn mul_x(point: &Point, scalar: &Felt) -> Zeroizing<SecretFelt> {
+    let scalar = Zeroizing::new(reduce(scalar).to_be_bytes());
+    let (x, _) = backend::point_mul(point, &scalar).unwrap();
+    let x = Felt::from_bytes_be(&backend::bytes_from_felt(&x));
+    Zeroizing::new(SecretFelt(x))
+}
+
+// The encryption and authentication keys of a shared secret and an ephemeral public key.
+fn keys(shared: &Felt, ephemeral: Felt) -> [Zeroizing<SecretFelt>; 2] {
+    [b"STARK_ECIES_ENC".as_slice(), b"STARK_ECIES_MAC"].map(|tag| {
+        let tag = Felt::from_bytes_be_slice(tag);
+        let key = poseidon_hash_many(&[tag, *shared, ephemeral]);
+        Zeroizing::new(SecretFelt(key))
+    })
+}
+
+// XORs the keystream of the encryption key into the data.
+fn apply_keystream(key: &Felt, data: &mut [u8]) {
+    for (i, block) in data.chunks_mut(BLOCK_LEN).enumerate() {
+        let keystream = Zeroizing::new(poseidon_hash(*key, Felt::from(i)).to_bytes_be());
+        for (byte, key_byte) in block.iter_mut().zip(&keystream[32 - BLOCK_LEN..]) {
+            *byte ^= key_byte;
+        }
+    }
+}
+
+fn tag(key: &Felt, ciphertext: &[u8]) -> [u8; 32] {
+    let mut felts = Vec::with_capacity(ciphertext.len() / BLOCK_LEN + 3);
+    felts.extend([*key, Felt::from(ciphertext.len())]);
+    felts.extend(ciphertext.chunks(BLOCK_LEN).map(Felt::from_bytes_be_slice));
+    poseidon_hash_many(&felts).to_bytes_be()
+}
+
+/// Encrypts `len` bytes of plaintext to a public key (x coordinate), with an ephemeral private key
+/// drawn like stark_private_key_random, writing the len + ENCRYPTION_OVERHEAD bytes of the
+/// ephemeral public key, ciphertext and tag into a buffer of `out_len` bytes. Returns
+/// ERR_INVALID_LENGTH when the buffer is too small, ERR_INVALID_POINT when the public key is not
+/// the x coordinate of a point and ERR_RNG when the random number generator fails
+#[cfg(feature = "random")]
+#[no_mangle]
+pub extern "C" fn stark_encrypt(
+    recipient_public_key: Bytes,
+    plaintext: *const u8,
+    len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(recipient_public_key);
+        let Some(recipient) = try_field_element_from_bytes(recipient_public_key) else {
+            return ERR_INVALID_FELT;
+        };
+        let plaintext = match slice_from_ptr(plaintext, len) {
+            Ok(plaintext) => plaintext,
+            Err(status) => return status,
+        };
+        if out.is_null()
+            || len
+                .checked_add(ENCRYPTION_OVERHEAD)
+                .is_none_or(|len| out_len < len)
+        {
+            return ERR_INVALID_LENGTH;
+        }
+        let recipient = match point_from_x(&recipient) {
+            Ok(recipient) => recipient,
+            Err(status) => return status,
+        };
+        let ephemeral_key = match draw_private_key() {
+            Ok(key) => key,
+            Err(status) => return status,
+        };
+        let ephemeral = mul_x(&generator(), &ephemeral_key);
+        let shared = mul_x(&recipient, &ephemeral_key);
+        let [encryption_key, authentication_key] = keys(&shared, **ephemeral);
+
+        let out = unsafe { core::slice::from_raw_parts_mut(out, len + ENCRYPTION_OVERHEAD) };
+        let (ephemeral_bytes, rest) = out.split_at_mut(32);
+        let (ciphertext, tag_bytes) = rest.split_at_mut(len);
+        ephemeral_bytes.copy_from_slice(&ephemeral.to_bytes_be());
+        ciphertext.copy_from_slice(plaintext);
+        apply_keystream(&encryption_key, ciphertext);
+        tag_bytes.copy_from_slice(&tag(&authentication_key, ciphertext));
+        SUCCESS
+    })
+}
+
+/// Decrypts the `len` bytes of a message of stark_encrypt with the private key of its recipient,
+/// writing the len - ENCRYPTION_OVERHEAD bytes of plaintext into a buffer of `out_len` bytes.
+/// Returns ERR_DECRYPTION_FAILED, without writing any plaintext, when the tag doesn't authenticate
+/// the message under the private key, and ERR_INVALID_LENGTH for a message shorter than
+/// ENCRYPTION_OVERHEAD or a buffer too small
+#[no_mangle]
+pub extern "C" fn stark_decrypt(
+    private_key_bytes: Bytes,
+    message: *const u8,
+    len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key_bytes);
+        let Some(private_key) = try_secret_from_bytes(private_key_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        if reduce(&private_key).to_be_bytes() == [0u8; 32] {
+            return ERR_INVALID_FELT;
+        }
+        let message = match slice_from_ptr(message, len) {
+            Ok(message) if len >= ENCRYPTION_OVERHEAD => message,
+            Ok(_) => return ERR_INVALID_LENGTH,
+            Err(status) => return status,
+        };
+        let plaintext_len = len - ENCRYPTION_OVERHEAD;
+        if (out.is_null() && plaintext_len > 0) || out_len < plaintext_len {
+            return ERR_INVALID_LENGTH;
+        }
+        let (ephemeral, rest) = message.split_at(32);
+        let (ciphertext, tag_bytes) = rest.split_at(plaintext_len);
+        let Some(ephemeral) = try_felt_from_array(ephemeral.try_into().unwrap()) else {
+            return ERR_DECRYPTION_FAILED;
+        };
+        let ephemeral_point = match point_from_x(&ephemeral) {
+            Ok(point) => point,
+            Err(_) => return ERR_DECRYPTION_FAILED,
+        };
+        let shared = mul_x(&ephemeral_point, &private_key);
+        let [encryption_key, authentication_key] = keys(&shared, ephemeral);
+        // The tag is public, but the comparison still doesn't stop at the first difference
+        let expected = tag(&authentication_key, ciphertext);
+        let difference = expected
+            .iter()
+            .zip(tag_bytes)
+            .fold(0, |difference, (a, b)| difference | (a ^ b));
+        if difference != 0 {
+            return ERR_DECRYPTION_FAILED;
+        }
+
+        if plaintext_len > 0 {
+            let plaintext = unsafe { core::slice::from_raw_parts_mut(out, plaintext_len) };
+            plaintext.copy_from_slice(ciphertext);
+            apply_keystream(&encryption_key, plaintext);
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(all(test, feature = "random"))]
+mod tests {
+    use std::sync::PoisonError;
+
+    use starknet_crypto_rs::get_public_key;
+
+    use super::*;
+    use crate::{random::tests::GENERATOR, schnorr::ORDER};
+
+    fn encrypt(public_key: &Felt, plaintext: &[u8]) -> Vec<u8> {
+        let mut message = vec![0u8; plaintext.len() + ENCRYPTION_OVERHEAD];
+        let status = stark_encrypt(
+            public_key.to_bytes_be().as_mut_ptr(),
+            plaintext.as_ptr(),
+            plaintext.len(),
+            message.as_mut_ptr(),
+            message.len(),
+        );
+        assert_eq!(status, SUCCESS);
+        message
+    }
+
+    fn decrypt(private_key: &Felt, message: &[u8]) -> Result<Vec<u8>, i32> {
+        let mut plaintext = vec![0u8; message.len().saturating_sub(ENCRYPTION_OVERHEAD)];
+        match stark_decrypt(
+            private_key.to_bytes_be().as_mut_ptr(),
+            message.as_ptr(),
+            message.len(),
+            plaintext.as_mut_ptr(),
+            plaintext.len(),
+        ) {
+            SUCCESS => Ok(plaintext),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_stark_encrypt() {
+        let _generator = GENERATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let private_key = Felt::from(0xb0bu64);
+        let public_key = get_public_key(&private_key);
+        // Messages of no, part of one and several keystream blocks
+        for len in [0, 1, 31, 32, 100] {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let message = encrypt(&public_key, &plaintext);
+            let ephemeral = Felt::from_bytes_be_slice(&message[..32]);
+            assert!(point_from_x(&ephemeral).is_ok());
+            assert_eq!(decrypt(&private_key, &message), Ok(plaintext.clone()));
+            // The ephemeral key is fresh for every message
+            assert_ne!(encrypt(&public_key, &plaintext), message);
+        }
+
+        let plaintext = b"attack at dawn";
+        let message = encrypt(&public_key, plaintext);
+        assert_ne!(&message[32..32 + plaintext.len()], plaintext);
+        for i in [0, 32, message.len() - 1] {
+            let mut tampered = message.clone();
+            tampered[i] ^= 1;
+            assert_eq!(decrypt(&private_key, &tampered), Err(ERR_DECRYPTION_FAILED));
+        }
+        assert_eq!(
+            decrypt(&Felt::from(0xa11ceu64), &message),
+            Err(ERR_DECRYPTION_FAILED)
+        );
+        // The key is reduced modulo the curve order like the signing keys
+        let order = Felt::from_bytes_be(&ORDER.to_be_bytes());
+        assert_eq!(
+            decrypt(&(private_key + order), &message),
+            Ok(plaintext.to_vec())
+        );
+        assert_eq!(decrypt(&Felt::ZERO, &message), Err(ERR_INVALID_FELT));
+        assert_eq!(
+            decrypt(&private_key, &message[..ENCRYPTION_OVERHEAD - 1]),
+            Err(ERR_INVALID_LENGTH)
+        );
+    }
+
+    #[test]
+    fn test_stark_encrypt_lengths() {
+        let private_key = Felt::from(0xb0bu64);
+        let public_key = get_public_key(&private_key);
+        let mut message = [0u8; ENCRYPTION_OVERHEAD + 4];
+        let encrypt_into = |public_key: &Felt, message: &mut [u8], len| {
+            stark_encrypt(
+                public_key.to_bytes_be().as_mut_ptr(),
+                b"ping".as_ptr(),
+                4,
+                message.as_mut_ptr(),
+                len,
+            )
+        };
+        let len = message.len();
+        assert_eq!(
+            encrypt_into(&public_key, &mut message, len - 1),
+            ERR_INVALID_LENGTH
+        );
+        // An x coordinate without a point
+        let mut x = Felt::ONE;
+        while backend::point_y_from_x(&felt_from_field_element(&x)).is_some() {
+            x += Felt::ONE;
+        }
+        assert_eq!(encrypt_into(&x, &mut message, len), ERR_INVALID_POINT);
+
+        assert_eq!(encrypt_into(&public_key, &mut message, len), SUCCESS);
+        let mut plaintext = [0u8; 4];
+        let mut decrypt_into = |plaintext_len| {
+            stark_decrypt(
+                private_key.to_bytes_be().as_mut_ptr(),
+                message.as_ptr(),
+                len,
+                plaintext.as_mut_ptr(),
+                plaintext_len,
+            )
+        };
+        assert_eq!(decrypt_into(3), ERR_INVALID_LENGTH);
+        assert_eq!(decrypt_into(4), SUCCESS);
+        assert_eq!(&plaintext, b"ping");
+    }
+}
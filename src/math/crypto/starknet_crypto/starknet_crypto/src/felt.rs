@@ -0,0 +1,803 @@
+// Arithmetic over the STARK prime field.
+//
+// Only felt_ct_eq runs in constant time, the arithmetic of the backends branches on its operands.
+use alloc::vec::Vec;
+
+use elliptic_curve::{
+    bigint::{Encoding, NonZero, U512},
+    subtle::ConstantTimeEq,
+};
+
+use crate::{
+    array_from_bytes,
+    backend::{self, Felt},
+    bytes_from_array, ffi_guard, slice_from_ptr, Bytes, ERR_DIVISION_BY_ZERO, ERR_INVALID_FELT,
+    ERR_INVALID_LENGTH, ERR_NOT_QUADRATIC_RESIDUE, ERR_OUT_OF_RANGE, SUCCESS,
+};
+
+// The STARK prime 2^251 + 17 * 2^192 + 1, in big endian.
+pub(crate) const MODULUS_BYTES: [u8; 32] = [
+    0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+// (p - 1) / 2, the greatest felt standing for a nonnegative integer, in big endian.
+pub(crate) const HALF_MODULUS_BYTES: [u8; 32] = [
+    0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+// Reads a felt of the backend from its C representation.
+pub(crate) fn try_felt_from_bytes(bytes: Bytes) -> Option<Felt> {
+    backend::felt_from_bytes(&array_from_bytes(bytes))
+}
+
+// Writes a felt of the backend into its C representation.
+pub(crate) fn bytes_from_felt(felt: &Felt, bytes: Bytes) {
+    bytes_from_array(&backend::bytes_from_felt(felt), bytes);
+}
+
+// Reduces a big endian integer of at most 64 bytes modulo the STARK prime.
+pub(crate) fn reduce_wide(bytes: &[u8]) -> [u8; 32] {
+    let (mut wide, mut modulus) = ([0u8; 64], [0u8; 64]);
+    wide[64 - bytes.len()..].copy_from_slice(bytes);
+    modulus[32..].copy_from_slice(&MODULUS_BYTES);
+    let modulus = NonZero::new(U512::from_be_bytes(modulus)).unwrap();
+    let felt = U512::from_be_bytes(wide).rem(&modulus).to_be_bytes();
+    felt[32..].try_into().unwrap()
+}
+
+fn felt_zero() -> Felt {
+    backend::felt_from_bytes(&[0u8; 32]).unwrap()
+}
+
+fn felt_one() -> Felt {
+    let mut bytes = [0u8; 32];
+    bytes[31] = 1;
+    backend::felt_from_bytes(&bytes).unwrap()
+}
+
+// Reads the two operands of a binary operation.
+fn operands_from_bytes(a_bytes: Bytes, b_bytes: Bytes) -> Option<(Felt, Felt)> {
+    Some((try_felt_from_bytes(a_bytes)?, try_felt_from_bytes(b_bytes)?))
+}
+
+/// Returns SUCCESS when the bytes encode a felt lower than the STARK prime, ERR_INVALID_FELT
+/// otherwise
+#[no_mangle]
+pub extern "C" fn felt_check_bytes(bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(bytes);
+        // Big endian byte arrays compare like the integers they encode
+        if array_from_bytes(bytes) < MODULUS_BYTES {
+            SUCCESS
+        } else {
+            ERR_INVALID_FELT
+        }
+    })
+}
+
+/// Reduces a big endian integer of `len` bytes, at most 64, modulo the STARK prime, writing the felt
+/// into the last argument. The felts of uniformly random inputs of 48 bytes or more are uniform up
+/// to a bias below 2^-128, unlike truncations of 32 bytes inputs, for hash-to-field constructions
+#[no_mangle]
+pub extern "C" fn felt_from_bytes_wide(bytes: *const u8, len: usize, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result_bytes);
+        if len > 64 {
+            return ERR_INVALID_LENGTH;
+        }
+        let bytes = match slice_from_ptr(bytes, len) {
+            Ok(bytes) => bytes,
+            Err(status) => return status,
+        };
+        bytes_from_array(&reduce_wide(bytes), result_bytes);
+        SUCCESS
+    })
+}
+
+/// Adds two felts, writing the result into the last argument
+#[no_mangle]
+pub extern "C" fn felt_add(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        let Some((a, b)) = operands_from_bytes(a_bytes, b_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        bytes_from_felt(&backend::felt_add(&a, &b), result_bytes);
+        SUCCESS
+    })
+}
+
+/// Subtracts the second felt from the first one, writing the result into the last argument
+#[no_mangle]
+pub extern "C" fn felt_sub(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        let Some((a, b)) = operands_from_bytes(a_bytes, b_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        bytes_from_felt(&backend::felt_sub(&a, &b), result_bytes);
+        SUCCESS
+    })
+}
+
+/// Multiplies two felts, writing the result into the last argument
+#[no_mangle]
+pub extern "C" fn felt_mul(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        let Some((a, b)) = operands_from_bytes(a_bytes, b_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        bytes_from_felt(&backend::felt_mul(&a, &b), result_bytes);
+        SUCCESS
+    })
+}
+
+/// Inverts a felt, writing the result into the last argument. Returns ERR_DIVISION_BY_ZERO when
+/// inverting zero
+#[no_mangle]
+pub extern "C" fn felt_inverse(a_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, result_bytes);
+        let Some(a) = try_felt_from_bytes(a_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        let Some(inverse) = backend::felt_inverse(&a) else {
+            return ERR_DIVISION_BY_ZERO;
+        };
+        bytes_from_felt(&inverse, result_bytes);
+        SUCCESS
+    })
+}
+
+/// Inverts in place `count` contiguous felts with a single inversion, by Montgomery's trick.
+/// Returns ERR_DIVISION_BY_ZERO, leaving the felts untouched, when one of them is zero
+#[no_mangle]
+pub extern "C" fn felt_batch_inverse(values: *mut u8, count: usize) -> i32 {
+    ffi_guard(|| {
+        let Some(len) = count.checked_mul(32) else {
+            return ERR_INVALID_LENGTH;
+        };
+        if count == 0 {
+            return SUCCESS;
+        }
+        if values.is_null() || len > isize::MAX as usize {
+            return ERR_INVALID_LENGTH;
+        }
+        let bytes = unsafe { core::slice::from_raw_parts_mut(values, len) };
+        let felts: Option<Vec<Felt>> = bytes
+            .chunks_exact(32)
+            .map(|felt| backend::felt_from_bytes(felt.try_into().unwrap()))
+            .collect();
+        let Some(felts) = felts else {
+            return ERR_INVALID_FELT;
+        };
+
+        // The products of the first i + 1 felts
+        let mut products: Vec<Felt> = Vec::with_capacity(count);
+        for felt in &felts {
+            let product = match products.last() {
+                Some(last) => backend::felt_mul(last, felt),
+                None => backend::felt_mul(&felt_one(), felt),
+            };
+            products.push(product);
+        }
+        let Some(mut inverse) = backend::felt_inverse(&products[count - 1]) else {
+            return ERR_DIVISION_BY_ZERO;
+        };
+        // Walking back, the inverse of the first i + 1 felts times the product of the first i is
+        // the inverse of felt i, and the inverse of the first felt is what remains
+        let mut write = |i: usize, felt: &Felt| {
+            bytes[32 * i..32 * (i + 1)].copy_from_slice(&backend::bytes_from_felt(felt));
+        };
+        for i in (1..count).rev() {
+            write(i, &backend::felt_mul(&inverse, &products[i - 1]));
+            inverse = backend::felt_mul(&inverse, &felts[i]);
+        }
+        write(0, &inverse);
+        SUCCESS
+    })
+}
+
+/// Raises the first felt to the power of a 256 bit big endian integer, writing the result into the
+/// last argument
+#[no_mangle]
+pub extern "C" fn felt_pow(base_bytes: Bytes, exponent_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(base_bytes, exponent_bytes, result_bytes);
+        let Some(base) = try_felt_from_bytes(base_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        // The exponent is any 256 bits big endian integer, not necessarily a felt
+        let exponent = array_from_bytes(exponent_bytes);
+        bytes_from_felt(&backend::felt_pow(&base, &exponent), result_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes the smallest square root of a felt into the last argument, or returns
+/// ERR_NOT_QUADRATIC_RESIDUE
+#[no_mangle]
+pub extern "C" fn felt_sqrt(a_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, result_bytes);
+        let Some(a) = try_felt_from_bytes(a_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        // Both roots are valid, the backend picks the smallest one like cairo-lang's `sqrt`
+        let Some(root) = backend::felt_sqrt(&a) else {
+            return ERR_NOT_QUADRATIC_RESIDUE;
+        };
+        bytes_from_felt(&root, result_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes whether the felt is a square, zero included, into the second argument
+#[no_mangle]
+pub extern "C" fn felt_is_quadratic_residue(a_bytes: Bytes, result: *mut bool) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, result);
+        let Some(a) = try_felt_from_bytes(a_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        // Zero is considered a quadratic residue, as in the `is_quad_residue` hint
+        unsafe { *result = backend::felt_sqrt(&a).is_some() };
+        SUCCESS
+    })
+}
+
+/// Splits a felt into its 128 low bits and the remaining high bits, writing both as felts into the
+/// last two arguments, like the `split_felt` hint: value = high * 2^128 + low with low < 2^128
+#[no_mangle]
+pub extern "C" fn felt_split_128(value_bytes: Bytes, low_bytes: Bytes, high_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(value_bytes, low_bytes, high_bytes);
+        let Some(value) = try_felt_from_bytes(value_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        // Split the canonical representative, never the bytes as given
+        let value = backend::bytes_from_felt(&value);
+        let (mut low, mut high) = ([0u8; 32], [0u8; 32]);
+        low[16..].copy_from_slice(&value[16..]);
+        high[16..].copy_from_slice(&value[..16]);
+        bytes_from_array(&low, low_bytes);
+        bytes_from_array(&high, high_bytes);
+        SUCCESS
+    })
+}
+
+/// Interprets a felt as a signed integer of [-(p - 1) / 2, (p - 1) / 2], like the `as_int` of the
+/// hints, writing its absolute value and whether it is negative into the last two arguments. The
+/// felts up to (p - 1) / 2 are nonnegative and the greater ones stand for felt - p
+#[no_mangle]
+pub extern "C" fn felt_as_signed(value_bytes: Bytes, abs_bytes: Bytes, negative: *mut bool) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(value_bytes, abs_bytes, negative);
+        let Some(value) = try_felt_from_bytes(value_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        let is_negative = backend::bytes_from_felt(&value) > HALF_MODULUS_BYTES;
+        let abs = if is_negative {
+            backend::felt_sub(&felt_zero(), &value)
+        } else {
+            value
+        };
+        bytes_from_felt(&abs, abs_bytes);
+        unsafe { *negative = is_negative };
+        SUCCESS
+    })
+}
+
+/// Writes the felt of a signed integer given by its absolute value, at most (p - 1) / 2, and
+/// whether it is negative into the last argument, the inverse of felt_as_signed. Returns
+/// ERR_INVALID_FELT when the absolute value is greater than (p - 1) / 2
+#[no_mangle]
+pub extern "C" fn felt_from_signed(abs_bytes: Bytes, negative: bool, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(abs_bytes, result_bytes);
+        let Some(abs) = try_felt_from_bytes(abs_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        if backend::bytes_from_felt(&abs) > HALF_MODULUS_BYTES {
+            return ERR_INVALID_FELT;
+        }
+        let value = if negative {
+            backend::felt_sub(&felt_zero(), &abs)
+        } else {
+            abs
+        };
+        bytes_from_felt(&value, result_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes the felt of a 64 bits integer into the last argument
+#[no_mangle]
+pub extern "C" fn felt_from_u64(value: u64, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result_bytes);
+        let mut result = [0u8; 32];
+        result[24..].copy_from_slice(&value.to_be_bytes());
+        bytes_from_array(&result, result_bytes);
+        SUCCESS
+    })
+}
+
+/// Writes the felt of a 128 bits integer, high * 2^64 + low, into the last argument
+#[no_mangle]
+pub extern "C" fn felt_from_u128(high: u64, low: u64, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result_bytes);
+        let mut result = [0u8; 32];
+        result[16..24].copy_from_slice(&high.to_be_bytes());
+        result[24..].copy_from_slice(&low.to_be_bytes());
+        bytes_from_array(&result, result_bytes);
+        SUCCESS
+    })
+}
+
+// The canonical bytes of a felt whose `len` leading bytes are zeros, ERR_OUT_OF_RANGE otherwise.
+fn bytes_in_range(value_bytes: Bytes, len: usize) -> Result<[u8; 32], i32> {
+    let value = try_felt_from_bytes(value_bytes).ok_or(ERR_INVALID_FELT)?;
+    let value = backend::bytes_from_felt(&value);
+    if value[..len].iter().any(|&byte| byte != 0) {
+        return Err(ERR_OUT_OF_RANGE);
+    }
+    Ok(value)
+}
+
+/// Writes the value of a felt lower than 2^64 into the last argument. Returns ERR_OUT_OF_RANGE, and
+/// leaves the argument unchanged, for a greater felt
+#[no_mangle]
+pub extern "C" fn felt_to_u64_checked(value_bytes: Bytes, result: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(value_bytes, result);
+        match bytes_in_range(value_bytes, 24) {
+            Ok(value) => {
+                unsafe { *result = u64::from_be_bytes(value[24..].try_into().unwrap()) };
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Writes the value of a felt lower than 2^128 into the last two arguments, value = high * 2^64 +
+/// low. Returns ERR_OUT_OF_RANGE, and leaves the arguments unchanged, for a greater felt
+#[no_mangle]
+pub extern "C" fn felt_to_u128_checked(value_bytes: Bytes, high: *mut u64, low: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(value_bytes, high, low);
+        match bytes_in_range(value_bytes, 16) {
+            Ok(value) => {
+                unsafe {
+                    *high = u64::from_be_bytes(value[16..24].try_into().unwrap());
+                    *low = u64::from_be_bytes(value[24..].try_into().unwrap());
+                }
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Compares two felts as integers of [0, p), writing -1, 0 or 1 into the last argument when the
+/// first one is lower than, equal to or greater than the second one
+#[no_mangle]
+pub extern "C" fn felt_cmp(a_bytes: Bytes, b_bytes: Bytes, result: *mut i32) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result);
+        let Some((a, b)) = operands_from_bytes(a_bytes, b_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        // The big endian encodings of the canonical representatives order like the integers
+        let ordering = backend::bytes_from_felt(&a).cmp(&backend::bytes_from_felt(&b));
+        unsafe { *result = ordering as i32 };
+        SUCCESS
+    })
+}
+
+/// Writes whether the felt is zero into the second argument
+#[no_mangle]
+pub extern "C" fn felt_is_zero(a_bytes: Bytes, result: *mut bool) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, result);
+        let Some(a) = try_felt_from_bytes(a_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        unsafe { *result = backend::bytes_from_felt(&a) == [0u8; 32] };
+        SUCCESS
+    })
+}
+
+/// Writes whether the two felts are equal into the last argument, in constant time. Compares the
+/// 32 bytes encodings as is, without checking that they are lower than the STARK prime
+#[no_mangle]
+pub extern "C" fn felt_ct_eq(a_bytes: Bytes, b_bytes: Bytes, result: *mut bool) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result);
+        // Compare in place rather than copying the possibly secret felts
+        let (a, b) = unsafe {
+            (
+                core::slice::from_raw_parts(a_bytes, 32),
+                core::slice::from_raw_parts(b_bytes, 32),
+            )
+        };
+        unsafe { *result = a.ct_eq(b).into() };
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_types_core::felt::Felt;
+
+    #[test]
+    fn test_felt_check_bytes() {
+        let mut max = Felt::MAX.to_bytes_be();
+        assert_eq!(felt_check_bytes(max.as_mut_ptr()), SUCCESS);
+        let mut zero = [0u8; 32];
+        assert_eq!(felt_check_bytes(zero.as_mut_ptr()), SUCCESS);
+
+        let mut p = MODULUS_BYTES;
+        assert_eq!(felt_check_bytes(p.as_mut_ptr()), ERR_INVALID_FELT);
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(felt_check_bytes(not_a_felt.as_mut_ptr()), ERR_INVALID_FELT);
+    }
+
+    #[test]
+    fn test_felt_from_bytes_wide() {
+        let mut result = [0u8; 32];
+        // 2^256 mod P, 2^384 - 1 mod P and 2^512 - 1 mod P, from the field arithmetic
+        let two_128 = Felt::from(u128::MAX) + Felt::ONE;
+        let cases = [
+            ([&[1u8][..], &[0u8; 32]].concat(), two_128 * two_128),
+            (vec![0xffu8; 48], two_128 * two_128 * two_128 - Felt::ONE),
+            (
+                vec![0xffu8; 64],
+                two_128 * two_128 * two_128 * two_128 - Felt::ONE,
+            ),
+        ];
+        for (bytes, expected) in cases {
+            assert_eq!(
+                felt_from_bytes_wide(bytes.as_ptr(), bytes.len(), result.as_mut_ptr()),
+                SUCCESS
+            );
+            assert_eq!(result, expected.to_bytes_be());
+        }
+        // The 32 bytes felts come back unchanged
+        let max = Felt::MAX.to_bytes_be();
+        assert_eq!(
+            felt_from_bytes_wide(max.as_ptr(), 32, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, max);
+
+        assert_eq!(
+            felt_from_bytes_wide([0u8; 65].as_ptr(), 65, result.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_felt_add_sub_mul() {
+        let mut max = Felt::MAX.to_bytes_be();
+        let mut one = Felt::ONE.to_bytes_be();
+        let mut two = Felt::TWO.to_bytes_be();
+        let mut result = [0u8; 32];
+
+        // (P - 1) + 1 = 0
+        assert_eq!(
+            felt_add(max.as_mut_ptr(), one.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, [0u8; 32]);
+
+        // 1 - 2 = P - 1
+        assert_eq!(
+            felt_sub(one.as_mut_ptr(), two.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, max);
+
+        // (P - 1) * 2 = P - 2
+        assert_eq!(
+            felt_mul(max.as_mut_ptr(), two.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, (Felt::MAX - Felt::ONE).to_bytes_be());
+    }
+
+    #[test]
+    fn test_felt_invalid_operands() {
+        // P itself is not a felt
+        let mut p = Felt::MAX.to_bytes_be();
+        p[31] += 1;
+        let mut one = Felt::ONE.to_bytes_be();
+        let mut result = [0u8; 32];
+        assert_eq!(
+            felt_add(p.as_mut_ptr(), one.as_mut_ptr(), result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(
+            felt_mul(one.as_mut_ptr(), p.as_mut_ptr(), result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(
+            felt_inverse(p.as_mut_ptr(), result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        let mut is_residue = false;
+        assert_eq!(
+            felt_is_quadratic_residue(p.as_mut_ptr(), &mut is_residue),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_felt_inverse() {
+        let mut two = Felt::TWO.to_bytes_be();
+        let mut inverse = [0u8; 32];
+        assert_eq!(
+            felt_inverse(two.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        let inverse = Felt::from_bytes_be(&inverse);
+        assert_eq!(inverse * Felt::TWO, Felt::ONE);
+
+        let mut zero = [0u8; 32];
+        let mut result = [0u8; 32];
+        assert_eq!(
+            felt_inverse(zero.as_mut_ptr(), result.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+    }
+
+    #[test]
+    fn test_felt_batch_inverse() {
+        let felts: Vec<Felt> = (1..=5u64).map(|i| Felt::from(i * 7)).collect();
+        let mut values: Vec<u8> = felts.iter().flat_map(|felt| felt.to_bytes_be()).collect();
+        assert_eq!(felt_batch_inverse(values.as_mut_ptr(), 5), SUCCESS);
+        for (felt, inverse) in felts.iter().zip(values.chunks_exact(32)) {
+            let inverse = Felt::from_bytes_be(inverse.try_into().unwrap());
+            assert_eq!(*felt * inverse, Felt::ONE);
+        }
+        assert_eq!(felt_batch_inverse(core::ptr::null_mut(), 0), SUCCESS);
+
+        let mut values = [Felt::TWO, Felt::ZERO]
+            .iter()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect::<Vec<u8>>();
+        let expected = values.clone();
+        assert_eq!(
+            felt_batch_inverse(values.as_mut_ptr(), 2),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_felt_sqrt() {
+        let mut four = Felt::from(4u8).to_bytes_be();
+        let mut root = [0u8; 32];
+        assert_eq!(felt_sqrt(four.as_mut_ptr(), root.as_mut_ptr()), SUCCESS);
+        assert_eq!(root, Felt::TWO.to_bytes_be());
+
+        // 3 is not a square modulo the STARK prime
+        let mut three = Felt::THREE.to_bytes_be();
+        assert_eq!(
+            felt_sqrt(three.as_mut_ptr(), root.as_mut_ptr()),
+            ERR_NOT_QUADRATIC_RESIDUE
+        );
+    }
+
+    #[test]
+    fn test_felt_is_quadratic_residue() {
+        let zero = [0u8; 32];
+        let four = Felt::from(4u8).to_bytes_be();
+        let three = Felt::THREE.to_bytes_be();
+        let mut is_residue = false;
+        for (mut felt, expected) in [(zero, true), (four, true), (three, false)] {
+            assert_eq!(
+                felt_is_quadratic_residue(felt.as_mut_ptr(), &mut is_residue),
+                SUCCESS
+            );
+            assert_eq!(is_residue, expected);
+        }
+    }
+
+    #[test]
+    fn test_felt_pow() {
+        let mut three = Felt::THREE.to_bytes_be();
+        let mut result = [0u8; 32];
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            felt_pow(three.as_mut_ptr(), zero.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, Felt::ONE.to_bytes_be());
+
+        let mut five = Felt::from(5u8).to_bytes_be();
+        assert_eq!(
+            felt_pow(three.as_mut_ptr(), five.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, Felt::from(243u8).to_bytes_be());
+
+        // Fermat's little theorem: 3^(P - 1) = 1
+        let mut p_minus_one = Felt::MAX.to_bytes_be();
+        assert_eq!(
+            felt_pow(
+                three.as_mut_ptr(),
+                p_minus_one.as_mut_ptr(),
+                result.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(result, Felt::ONE.to_bytes_be());
+
+        // Exponents don't need to be felts
+        let mut max_exponent = [0xffu8; 32];
+        assert_eq!(
+            felt_pow(
+                three.as_mut_ptr(),
+                max_exponent.as_mut_ptr(),
+                result.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        let mut expected = Felt::ONE;
+        for _ in 0..256 {
+            expected = expected * expected * Felt::THREE;
+        }
+        assert_eq!(result, expected.to_bytes_be());
+    }
+
+    #[test]
+    fn test_felt_split_128() {
+        let split = |value: &mut [u8; 32]| {
+            let (mut low, mut high) = ([0u8; 32], [0u8; 32]);
+            let status = felt_split_128(value.as_mut_ptr(), low.as_mut_ptr(), high.as_mut_ptr());
+            (status, low, high)
+        };
+        let value = Felt::from_hex("0x12340000000000000000000000000000abcd").unwrap();
+        let (status, low, high) = split(&mut value.to_bytes_be());
+        assert_eq!(status, SUCCESS);
+        assert_eq!(low, Felt::from(0xabcdu64).to_bytes_be());
+        assert_eq!(high, Felt::from(0x1234u64).to_bytes_be());
+
+        // p - 1 = 2^251 + 17 * 2^192, whose high bits are 2^123 + 17 * 2^64
+        let (status, low, high) = split(&mut Felt::MAX.to_bytes_be());
+        assert_eq!(status, SUCCESS);
+        assert_eq!(low, [0u8; 32]);
+        let expected = Felt::from((1u128 << 123) + (17u128 << 64));
+        assert_eq!(high, expected.to_bytes_be());
+
+        assert_eq!(split(&mut { MODULUS_BYTES }).0, ERR_INVALID_FELT);
+    }
+
+    #[test]
+    fn test_felt_machine_integers() {
+        let mut felt = [0u8; 32];
+        assert_eq!(felt_from_u64(u64::MAX, felt.as_mut_ptr()), SUCCESS);
+        assert_eq!(felt, Felt::from(u64::MAX).to_bytes_be());
+        let mut value = 0;
+        assert_eq!(felt_to_u64_checked(felt.as_mut_ptr(), &mut value), SUCCESS);
+        assert_eq!(value, u64::MAX);
+
+        let (high, low) = (0x0123_4567_89ab_cdefu64, 0xfedc_ba98_7654_3210u64);
+        assert_eq!(felt_from_u128(high, low, felt.as_mut_ptr()), SUCCESS);
+        let expected = ((high as u128) << 64) | low as u128;
+        assert_eq!(felt, Felt::from(expected).to_bytes_be());
+        let (mut high_result, mut low_result) = (0, 0);
+        assert_eq!(
+            felt_to_u128_checked(felt.as_mut_ptr(), &mut high_result, &mut low_result),
+            SUCCESS
+        );
+        assert_eq!((high_result, low_result), (high, low));
+
+        // 2^128 - 1 doesn't fit in 64 bits, 2^128 doesn't fit in 128 bits either
+        assert_eq!(
+            felt_to_u64_checked(felt.as_mut_ptr(), &mut value),
+            ERR_OUT_OF_RANGE
+        );
+        assert_eq!(value, u64::MAX);
+        let mut two_128 = (Felt::from(u128::MAX) + Felt::ONE).to_bytes_be();
+        assert_eq!(
+            felt_to_u128_checked(two_128.as_mut_ptr(), &mut high_result, &mut low_result),
+            ERR_OUT_OF_RANGE
+        );
+        assert_eq!((high_result, low_result), (high, low));
+        assert_eq!(
+            felt_to_u64_checked({ MODULUS_BYTES }.as_mut_ptr(), &mut value),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_felt_signed() {
+        let as_signed = |value: Felt| {
+            let (mut value, mut abs, mut negative) = (value.to_bytes_be(), [0u8; 32], false);
+            let status = felt_as_signed(value.as_mut_ptr(), abs.as_mut_ptr(), &mut negative);
+            assert_eq!(status, SUCCESS);
+            (Felt::from_bytes_be(&abs), negative)
+        };
+        let from_signed = |abs: Felt, negative: bool| {
+            let (mut abs, mut result) = (abs.to_bytes_be(), [0u8; 32]);
+            let status = felt_from_signed(abs.as_mut_ptr(), negative, result.as_mut_ptr());
+            (status, Felt::from_bytes_be(&result))
+        };
+        let half = Felt::from_bytes_be(&HALF_MODULUS_BYTES);
+        let one = Felt::ONE;
+        assert_eq!(half + half, Felt::MAX);
+        assert_eq!(as_signed(Felt::ZERO), (Felt::ZERO, false));
+        assert_eq!(as_signed(Felt::MAX), (one, true));
+        // (p - 1) / 2 is the greatest nonnegative integer and (p + 1) / 2 stands for -(p - 1) / 2
+        assert_eq!(as_signed(half), (half, false));
+        assert_eq!(as_signed(half + one), (half, true));
+
+        for (abs, negative) in [(one, true), (half, true), (half, false), (Felt::ZERO, true)] {
+            let (status, value) = from_signed(abs, negative);
+            assert_eq!(status, SUCCESS);
+            assert_eq!(as_signed(value), (abs, negative && abs != Felt::ZERO));
+        }
+        assert_eq!(from_signed(Felt::ZERO, true).1, Felt::ZERO);
+        assert_eq!(from_signed(half + one, false).0, ERR_INVALID_FELT);
+    }
+
+    #[test]
+    fn test_felt_cmp() {
+        let cmp = |a: Felt, b: Felt| {
+            let (mut a, mut b, mut result) = (a.to_bytes_be(), b.to_bytes_be(), 2);
+            assert_eq!(
+                felt_cmp(a.as_mut_ptr(), b.as_mut_ptr(), &mut result),
+                SUCCESS
+            );
+            result
+        };
+        let one = Felt::ONE;
+        assert_eq!(cmp(one, Felt::MAX), -1);
+        assert_eq!(cmp(Felt::MAX, one), 1);
+        assert_eq!(cmp(one, one), 0);
+        // The order of the integers, not of the 64 bit limbs
+        assert_eq!(cmp(Felt::from(1u128 << 64), Felt::from(2u64)), 1);
+
+        let (mut a, mut p, mut result) = ([0u8; 32], MODULUS_BYTES, 0);
+        assert_eq!(
+            felt_cmp(a.as_mut_ptr(), p.as_mut_ptr(), &mut result),
+            ERR_INVALID_FELT
+        );
+
+        let mut is_zero = false;
+        assert_eq!(felt_is_zero(a.as_mut_ptr(), &mut is_zero), SUCCESS);
+        assert!(is_zero);
+        let mut max = Felt::MAX.to_bytes_be();
+        assert_eq!(felt_is_zero(max.as_mut_ptr(), &mut is_zero), SUCCESS);
+        assert!(!is_zero);
+        assert_eq!(felt_is_zero(p.as_mut_ptr(), &mut is_zero), ERR_INVALID_FELT);
+    }
+
+    #[test]
+    fn test_felt_ct_eq() {
+        let mut a = Felt::from_hex("0x1234").unwrap().to_bytes_be();
+        let mut b = a;
+        let mut result = false;
+        assert_eq!(
+            felt_ct_eq(a.as_mut_ptr(), b.as_mut_ptr(), &mut result),
+            SUCCESS
+        );
+        assert!(result);
+
+        b[0] = 1;
+        assert_eq!(
+            felt_ct_eq(a.as_mut_ptr(), b.as_mut_ptr(), &mut result),
+            SUCCESS
+        );
+        assert!(!result);
+    }
+}
@@ -0,0 +1,47 @@
+// The table of every exported function, for the fuzz targets to call all of them with arguments of
+// their choosing rather than declaring each one again.
+//
+// build.rs generates the table from the signatures of the exports, under their cfg attributes, so
+// that it follows the functions and features of the library as they are added. Each argument is
+// drawn from an `Arguments` implementation of the fuzz targets, by its type: the buffers of `Bytes`
+// arguments and the other arrays, of the length the `_sized` functions expect or not, the lengths
+// and other integers, the handles of the allocating functions, and NULL for the callbacks.
+use alloc::vec::Vec;
+
+use crate::Bytes;
+
+/// The arguments of the calls of the table, drawn by the fuzz targets
+pub trait Arguments {
+    /// A buffer of `len` bytes behind a `Bytes` argument
+    fn bytes(&mut self, len: usize) -> Bytes;
+    /// A buffer behind a `Bytes` argument of a `_sized` function, which expects `len` bytes, and
+    /// the length passed after it
+    fn sized_bytes(&mut self, len: usize) -> (Bytes, usize);
+    /// An array of any length, for the pointers to other types than `Bytes`, aligned for `T`
+    fn array<T>(&mut self) -> *mut T;
+    /// An array behind a pointer to other types than `Bytes` of a `_sized` function, which expects
+    /// `len` elements, and the count of elements passed after it
+    fn sized_array<T>(&mut self, len: usize) -> (*mut T, usize);
+    /// An array of pointers to arrays, for the columns of the batch layouts
+    fn pointers(&mut self) -> *mut *mut u8;
+    /// A `usize`, the length or the count of elements of an array, which must fit in the arrays
+    fn length(&mut self) -> usize;
+    /// Any other integer, also cast to the narrower types and to bool. The caller sizes some
+    /// buffers from them, like the 2^cap_height roots of merkle_cap, so they must be small
+    fn integer(&mut self) -> u64;
+    /// A handle allocated by an earlier call, or NULL
+    fn handle<T: 'static>(&mut self) -> *mut T;
+    /// Where the call writes the handle it allocates, owned by the fuzz targets from then on
+    fn handle_slot<T: 'static>(&mut self) -> *mut *mut T;
+    /// A handle allocated by an earlier call, or NULL, released by the call
+    fn free_handle<T: 'static>(&mut self) -> *mut T;
+}
+
+/// An exported function, with the status it returns, or `None` for the functions that return
+/// something else
+pub struct Export<A> {
+    pub name: &'static str,
+    pub call: fn(&mut A) -> Option<i32>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/fuzzing.rs"));
@@ -0,0 +1,169 @@
+// Multiplications of the generator of the STARK curve with a precomputed table.
+//
+// The table holds d * 16^j * G for the 63 windows j of 4 bits of a scalar below 2^252 and the 15
+// non zero digits d, as affine points, so that a product is the sum of one point per non zero
+// digit of the scalar, without doublings. It is built by starknet_crypto_init, or else on first
+// use, about a thousand additions and conversions to affine coordinates, and then shared by the
+// threads. get_public_key, the ECDSA signatures and ec_mul of the generator all go through it.
+use std::sync::OnceLock;
+
+#[cfg(feature = "signing")]
+use elliptic_curve::bigint::{
+    modular::runtime_mod::{DynResidue, DynResidueParams},
+    Encoding,
+};
+#[cfg(feature = "signing")]
+use starknet_crypto_rs::{ExtendedSignature, SignError};
+use starknet_curve::curve_params::GENERATOR;
+use starknet_types_core::{
+    curve::{AffinePoint, ProjectivePoint},
+    felt::Felt,
+};
+#[cfg(feature = "signing")]
+use zeroize::Zeroizing;
+
+#[cfg(feature = "signing")]
+use crate::schnorr::{reduce, ORDER};
+
+const WINDOWS: usize = 63;
+
+static TABLE: OnceLock<Vec<[AffinePoint; 15]>> = OnceLock::new();
+
+fn table() -> &'static [[AffinePoint; 15]] {
+    TABLE.get_or_init(|| {
+        let mut base = ProjectivePoint::from_affine_unchecked(GENERATOR.x(), GENERATOR.y());
+        (0..WINDOWS)
+            .map(|_| {
+                let mut multiple = base.clone();
+                let row = core::array::from_fn(|_| {
+                    let point = multiple.to_affine().unwrap();
+                    multiple += &base;
+                    point
+                });
+                // After the last addition the multiple is 16 * base, the base of the next window
+                base = multiple;
+                row
+            })
+            .collect()
+    })
+}
+
+// Builds the table now rather than on first use.
+pub(crate) fn init() {
+    table();
+}
+
+// The product of G and a big endian scalar below 2^252, None for the point at infinity.
+pub(crate) fn mul(scalar: &[u8; 32]) -> Option<AffinePoint> {
+    let mut product = ProjectivePoint::identity();
+    for (window, row) in table().iter().enumerate() {
+        let digit = (scalar[31 - window / 2] >> (4 * (window % 2))) & 0xf;
+        if digit != 0 {
+            product += &row[digit as usize - 1];
+        }
+    }
+    product.to_affine().ok()
+}
+
+// The x coordinate of scalar * G, like get_public_key of starknet-crypto, zero for the point at
+// infinity.
+pub(crate) fn public_key(scalar: &Felt) -> Felt {
+    mul(&scalar.to_bytes_be()).map_or(Felt::ZERO, |point| point.x())
+}
+
+// An ECDSA signature like sign of starknet-crypto, with the same checks of the message hash and k.
+#[cfg(feature = "signing")]
+pub(crate) fn sign(
+    private_key: &Felt,
+    message: &Felt,
+    k: &Felt,
+) -> Result<ExtendedSignature, SignError> {
+    // The message hash, r and s must be lower than 2^251
+    let in_range = |felt: &Felt| felt.to_bytes_be()[0] < 0x08;
+    if !in_range(message) {
+        return Err(SignError::InvalidMessageHash);
+    }
+    let k = Zeroizing::new(reduce(k).to_be_bytes());
+    let point = mul(&k).ok_or(SignError::InvalidK)?;
+    let r = point.x();
+    if r == Felt::ZERO || !in_range(&r) {
+        return Err(SignError::InvalidK);
+    }
+
+    let params = DynResidueParams::new(&ORDER);
+    let residue = |felt: &Felt| DynResidue::new(&reduce(felt), params);
+    let (k_inverse, _) = residue(&Felt::from_bytes_be(&k)).invert();
+    let s = ((residue(&r) * residue(private_key) + residue(message)) * k_inverse).retrieve();
+    let s = Felt::from_bytes_be(&s.to_be_bytes());
+    if s == Felt::ZERO || !in_range(&s) {
+        return Err(SignError::InvalidK);
+    }
+    Ok(ExtendedSignature {
+        r,
+        s,
+        v: Felt::from(point.y().to_bytes_be()[31] & 1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecdsa::get_public_key;
+    use starknet_curve::curve_params::EC_ORDER;
+
+    #[test]
+    fn test_init() {
+        assert_eq!(crate::starknet_crypto_init(), crate::SUCCESS);
+        assert_eq!(TABLE.get().map(Vec::len), Some(WINDOWS));
+    }
+
+    #[test]
+    fn test_public_key() {
+        let scalars = [
+            Felt::ZERO,
+            Felt::ONE,
+            Felt::from(0xffffu64),
+            EC_ORDER - Felt::ONE,
+            EC_ORDER,
+            Felt::from_hex("0x3c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc")
+                .unwrap(),
+        ];
+        for scalar in scalars {
+            assert_eq!(public_key(&scalar), get_public_key(&scalar));
+        }
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_sign() {
+        let private_key =
+            Felt::from_hex("0x3c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc")
+                .unwrap();
+        let message = Felt::from(0x1234u64);
+        // Nonces below and above the curve order sign like starknet-crypto
+        for k in [Felt::THREE, EC_ORDER - Felt::TWO, EC_ORDER + Felt::TWO] {
+            let (signature, expected) = (
+                sign(&private_key, &message, &k).unwrap(),
+                starknet_crypto_rs::sign(&private_key, &message, &k).unwrap(),
+            );
+            assert_eq!(
+                (signature.r, signature.s, signature.v),
+                (expected.r, expected.s, expected.v)
+            );
+        }
+
+        let two_251 =
+            Felt::from_hex("0x800000000000000000000000000000000000000000000000000000000000000")
+                .unwrap();
+        assert!(matches!(
+            sign(&private_key, &two_251, &Felt::THREE),
+            Err(SignError::InvalidMessageHash)
+        ));
+        for k in [Felt::ZERO, EC_ORDER] {
+            assert!(matches!(
+                sign(&private_key, &message, &k),
+                Err(SignError::InvalidK)
+            ));
+        }
+    }
+}
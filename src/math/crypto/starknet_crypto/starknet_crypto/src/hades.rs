@@ -0,0 +1,171 @@
+// The Hades permutation of Poseidon, over the compressed round constants of starknet-crypto but
+// with the linear layer computed by additions only and the partial rounds unrolled four at a time.
+// With the `hades` feature it replaces the permutation of starknet-crypto in every Poseidon hash of
+// the library, and the modules import the Poseidon functions from here whichever one is selected.
+//
+// The MDS matrix ((3, 1, 1), (1, -1, 1), (1, 1, 2)) maps the state (s0, s1, s2) to t + 2 * s0,
+// t - 2 * s1 and t - 3 * s2, with t = s0 + s1 + s2. starknet-crypto multiplies by the constants 2 and
+// 3, three field multiplications per round on top of the two of a partial round's S-box, which
+// this permutation does with doublings.
+#![cfg_attr(not(feature = "hades"), allow(dead_code))]
+
+use starknet_types_core::felt::Felt;
+
+use crate::constants::generated::POSEIDON_COMP_CONSTS;
+
+#[cfg(feature = "hades")]
+pub(crate) use self::{
+    hash as poseidon_hash, hash_many as poseidon_hash_many, hash_single as poseidon_hash_single,
+    permute as poseidon_permute_comp, Hasher as PoseidonHasher,
+};
+#[cfg(not(feature = "hades"))]
+pub(crate) use starknet_crypto_rs::{
+    poseidon_hash, poseidon_hash_many, poseidon_hash_single, poseidon_permute_comp, PoseidonHasher,
+};
+
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 83;
+
+#[inline(always)]
+fn mix(state: &mut [Felt; 3]) {
+    let [s0, s1, s2] = *state;
+    let t = s0 + s1 + s2;
+    state[0] = t + s0 + s0;
+    state[1] = t - (s1 + s1);
+    state[2] = t - (s2 + s2 + s2);
+}
+
+#[inline(always)]
+fn cube(x: Felt) -> Felt {
+    x * x * x
+}
+
+#[inline(always)]
+fn full_round(state: &mut [Felt; 3], constants: &[Felt]) {
+    for (felt, constant) in state.iter_mut().zip(constants) {
+        *felt = cube(*felt + *constant);
+    }
+    mix(state);
+}
+
+// The compressed constants of the partial rounds only add to the last felt of the state.
+#[inline(always)]
+fn partial_round(state: &mut [Felt; 3], constant: Felt) {
+    state[2] = cube(state[2] + constant);
+    mix(state);
+}
+
+// The permutation of poseidon_permute_comp.
+pub(crate) fn permute(state: &mut [Felt; 3]) {
+    let (first, rest) = POSEIDON_COMP_CONSTS.split_at(3 * FULL_ROUNDS / 2);
+    let (partial, last) = rest.split_at(PARTIAL_ROUNDS);
+    for constants in first.chunks_exact(3) {
+        full_round(state, constants);
+    }
+    let mut chunks = partial.chunks_exact(4);
+    for constants in chunks.by_ref() {
+        partial_round(state, constants[0]);
+        partial_round(state, constants[1]);
+        partial_round(state, constants[2]);
+        partial_round(state, constants[3]);
+    }
+    for &constant in chunks.remainder() {
+        partial_round(state, constant);
+    }
+    for constants in last.chunks_exact(3) {
+        full_round(state, constants);
+    }
+}
+
+pub(crate) fn hash(x: Felt, y: Felt) -> Felt {
+    let mut state = [x, y, Felt::TWO];
+    permute(&mut state);
+    state[0]
+}
+
+pub(crate) fn hash_single(x: Felt) -> Felt {
+    let mut state = [x, Felt::ZERO, Felt::ONE];
+    permute(&mut state);
+    state[0]
+}
+
+pub(crate) fn hash_many<'a, I: IntoIterator<Item = &'a Felt>>(felts: I) -> Felt {
+    let mut hasher = Hasher::default();
+    for &felt in felts {
+        hasher.update(felt);
+    }
+    hasher.finalize()
+}
+
+// The sponge of hash_many, absorbing the felts two at a time.
+#[derive(Default)]
+pub(crate) struct Hasher {
+    state: [Felt; 3],
+    buffer: Option<Felt>,
+}
+
+impl Hasher {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn update(&mut self, felt: Felt) {
+        match self.buffer.take() {
+            Some(previous) => {
+                self.state[0] += previous;
+                self.state[1] += felt;
+                permute(&mut self.state);
+            }
+            None => self.buffer = Some(felt),
+        }
+    }
+
+    // Pads the felts with a one, like poseidon_hash_many.
+    pub(crate) fn finalize(mut self) -> Felt {
+        match self.buffer.take() {
+            Some(last) => {
+                self.state[0] += last;
+                self.state[1] += Felt::ONE;
+            }
+            None => self.state[0] += Felt::ONE,
+        }
+        permute(&mut self.state);
+        self.state[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hades_matches_starknet_crypto() {
+        assert_eq!(POSEIDON_COMP_CONSTS.len(), 3 * FULL_ROUNDS + PARTIAL_ROUNDS);
+        let felts: Vec<Felt> = [0u64, 1, 2, 42, u64::MAX]
+            .map(Felt::from)
+            .into_iter()
+            .chain([Felt::MAX, -Felt::TWO])
+            .collect();
+        for window in felts.windows(3) {
+            let mut state = [window[0], window[1], window[2]];
+            let mut expected = state;
+            permute(&mut state);
+            starknet_crypto_rs::poseidon_permute_comp(&mut expected);
+            assert_eq!(state, expected);
+            assert_eq!(
+                hash(window[0], window[1]),
+                starknet_crypto_rs::poseidon_hash(window[0], window[1])
+            );
+            assert_eq!(
+                hash_single(window[2]),
+                starknet_crypto_rs::poseidon_hash_single(window[2])
+            );
+        }
+        for len in 0..felts.len() {
+            assert_eq!(
+                hash_many(&felts[..len]),
+                starknet_crypto_rs::poseidon_hash_many(&felts[..len])
+            );
+        }
+    }
+}
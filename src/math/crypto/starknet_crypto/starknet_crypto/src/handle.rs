@@ -0,0 +1,203 @@
+// Felts kept in their native representation between calls, so that long chains of field
+// operations and hashes don't convert to bytes and back at every step.
+//
+// The handles are opaque to C. Every function producing a felt allocates a new handle, which must
+// be released with felt_free, and the operands are left untouched.
+use alloc::boxed::Box;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    batch::pedersen_hash_pair, bytes_from_field_element, ffi_guard, hades::poseidon_hash,
+    try_field_element_from_bytes, Bytes, ERR_DIVISION_BY_ZERO, ERR_INVALID_FELT, SUCCESS,
+};
+
+/// A felt owned by the caller between calls
+pub struct FeltHandle(Felt);
+
+fn new_handle(felt: Felt, handle: *mut *mut FeltHandle) -> i32 {
+    unsafe { *handle = Box::into_raw(Box::new(FeltHandle(felt))) };
+    SUCCESS
+}
+
+fn binary_op(
+    a: *const FeltHandle,
+    b: *const FeltHandle,
+    result: *mut *mut FeltHandle,
+    op: impl FnOnce(&Felt, &Felt) -> Felt,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result);
+        let (a, b) = unsafe { (&(*a).0, &(*b).0) };
+        new_handle(op(a, b), result)
+    })
+}
+
+/// Allocates a handle holding the felt of the first argument, writing it into the last argument. It
+/// must be released with felt_free
+#[no_mangle]
+pub extern "C" fn felt_new_from_bytes(bytes: Bytes, handle: *mut *mut FeltHandle) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(bytes, handle);
+        let Some(felt) = try_field_element_from_bytes(bytes) else {
+            return ERR_INVALID_FELT;
+        };
+        new_handle(felt, handle)
+    })
+}
+
+/// Writes the felt of a handle into the last argument
+#[no_mangle]
+pub extern "C" fn felt_read_bytes(handle: *const FeltHandle, bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(handle, bytes);
+        bytes_from_field_element(unsafe { (*handle).0 }, bytes);
+        SUCCESS
+    })
+}
+
+/// Allocates a handle holding the sum of two handles, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn felt_op_add(
+    a: *const FeltHandle,
+    b: *const FeltHandle,
+    result: *mut *mut FeltHandle,
+) -> i32 {
+    binary_op(a, b, result, |a, b| *a + *b)
+}
+
+/// Allocates a handle holding the difference of two handles, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn felt_op_sub(
+    a: *const FeltHandle,
+    b: *const FeltHandle,
+    result: *mut *mut FeltHandle,
+) -> i32 {
+    binary_op(a, b, result, |a, b| *a - *b)
+}
+
+/// Allocates a handle holding the product of two handles, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn felt_op_mul(
+    a: *const FeltHandle,
+    b: *const FeltHandle,
+    result: *mut *mut FeltHandle,
+) -> i32 {
+    binary_op(a, b, result, |a, b| *a * *b)
+}
+
+/// Allocates a handle holding the inverse of a handle, writing it into the last argument. Returns
+/// ERR_DIVISION_BY_ZERO for zero
+#[no_mangle]
+pub extern "C" fn felt_op_inverse(a: *const FeltHandle, result: *mut *mut FeltHandle) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, result);
+        let Some(inverse) = unsafe { (*a).0 }.inverse() else {
+            return ERR_DIVISION_BY_ZERO;
+        };
+        new_handle(inverse, result)
+    })
+}
+
+/// Allocates a handle holding the pedersen hash of two handles, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn felt_hash_pedersen(
+    a: *const FeltHandle,
+    b: *const FeltHandle,
+    result: *mut *mut FeltHandle,
+) -> i32 {
+    binary_op(a, b, result, pedersen_hash_pair)
+}
+
+/// Allocates a handle holding the poseidon hash of two handles, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn felt_hash_poseidon(
+    a: *const FeltHandle,
+    b: *const FeltHandle,
+    result: *mut *mut FeltHandle,
+) -> i32 {
+    binary_op(a, b, result, |a, b| poseidon_hash(*a, *b))
+}
+
+/// Releases a handle allocated by the felt_new_from_bytes, felt_op_ and felt_hash_ functions. Does
+/// nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn felt_free(handle: *mut FeltHandle) -> i32 {
+    ffi_guard(|| {
+        if !handle.is_null() {
+            drop(unsafe { Box::from_raw(handle) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_crypto_rs::pedersen_hash;
+
+    fn handle(value: u64) -> *mut FeltHandle {
+        let mut handle = core::ptr::null_mut();
+        assert_eq!(
+            felt_new_from_bytes(Felt::from(value).to_bytes_be().as_mut_ptr(), &mut handle),
+            SUCCESS
+        );
+        handle
+    }
+
+    fn read(handle: *const FeltHandle) -> Felt {
+        let mut bytes = [0u8; 32];
+        assert_eq!(felt_read_bytes(handle, bytes.as_mut_ptr()), SUCCESS);
+        Felt::from_bytes_be(&bytes)
+    }
+
+    fn op(
+        f: extern "C" fn(*const FeltHandle, *const FeltHandle, *mut *mut FeltHandle) -> i32,
+        a: *const FeltHandle,
+        b: *const FeltHandle,
+    ) -> *mut FeltHandle {
+        let mut result = core::ptr::null_mut();
+        assert_eq!(f(a, b, &mut result), SUCCESS);
+        result
+    }
+
+    #[test]
+    fn test_felt_handles() {
+        let (a, b) = (handle(7), handle(3));
+        let sum = op(felt_op_add, a, b);
+        let difference = op(felt_op_sub, b, a);
+        let product = op(felt_op_mul, sum, difference);
+        // (7 + 3) * (3 - 7) = -40
+        assert_eq!(read(product), -Felt::from(40u8));
+        let pedersen = op(felt_hash_pedersen, a, b);
+        assert_eq!(
+            read(pedersen),
+            pedersen_hash(&Felt::from(7u8), &Felt::THREE)
+        );
+        let poseidon = op(felt_hash_poseidon, a, b);
+        assert_eq!(read(poseidon), poseidon_hash(Felt::from(7u8), Felt::THREE));
+
+        let mut inverse = core::ptr::null_mut();
+        assert_eq!(felt_op_inverse(b, &mut inverse), SUCCESS);
+        assert_eq!(read(inverse) * Felt::THREE, Felt::ONE);
+        // The operands are left untouched
+        assert_eq!(read(a), Felt::from(7u8));
+
+        for handle in [a, b, sum, difference, product, pedersen, poseidon, inverse] {
+            assert_eq!(felt_free(handle), SUCCESS);
+        }
+        assert_eq!(felt_free(core::ptr::null_mut()), SUCCESS);
+    }
+
+    #[test]
+    fn test_felt_handle_errors() {
+        let mut result = core::ptr::null_mut();
+        assert_eq!(
+            felt_new_from_bytes([0xffu8; 32].as_mut_ptr(), &mut result),
+            ERR_INVALID_FELT
+        );
+        let zero = handle(0);
+        assert_eq!(felt_op_inverse(zero, &mut result), ERR_DIVISION_BY_ZERO);
+        assert_eq!(felt_free(zero), SUCCESS);
+    }
+}
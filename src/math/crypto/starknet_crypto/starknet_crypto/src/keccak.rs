@@ -0,0 +1,210 @@
+// Keccak primitives used by the keccak builtin and the Ethereum compatible hints.
+use alloc::vec::Vec;
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    bytes_from_array, differential, ffi_guard,
+    profiling::{Primitive, Profile},
+    slice_from_ptr, Bytes, DIFF_KECCAK256, DIFF_KECCAK_F1600, DIFF_STARKNET_KECCAK,
+    ERR_INVALID_LENGTH, SUCCESS,
+};
+
+// Number of 64 bits lanes in a Keccak-f[1600] state.
+const KECCAK_STATE_LANES: usize = 25;
+// Number of 64 bits lanes in a block of keccak256, its 1088 bits rate.
+const KECCAK256_RATE_LANES: usize = 17;
+
+// The keccak256 hash truncated to its 250 low bits, so that it is a felt.
+pub(crate) fn sn_keccak(data: &[u8]) -> [u8; 32] {
+    let mut hash: [u8; 32] = Keccak256::digest(data).into();
+    hash[0] &= 0x03;
+    hash
+}
+
+/// Applies the Keccak-f[1600] permutation in place over a state of 25 64 bit lanes
+#[no_mangle]
+pub extern "C" fn keccak_f1600(state: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(state);
+        let _profile = Profile::start(Primitive::Keccak, 1);
+        // The state is 25 little endian lanes, permuted in place
+        let state = unsafe { &mut *(state as *mut [u64; KECCAK_STATE_LANES]) };
+        let lane_bytes = |state: &[u64]| -> Vec<u8> {
+            state.iter().flat_map(|lane| lane.to_le_bytes()).collect()
+        };
+        let input = differential::enabled().then(|| lane_bytes(state));
+        keccak::f1600(state);
+        if let Some(input) = input {
+            differential::report_bytes(DIFF_KECCAK_F1600, &input, &lane_bytes(state));
+        }
+        SUCCESS
+    })
+}
+
+/// Absorbs `num_blocks` contiguous blocks of 17 64 bit lanes into a zero Keccak-f[1600] state,
+/// permuting after each, and writes the 4 lanes squeezed out of the state into the last argument,
+/// like the keccak builtin over a whole segment. The input must be padded by the caller, so the
+/// output is the keccak256 digest of the unpadded input as little endian lanes. Returns
+/// ERR_INVALID_LENGTH when there is no block
+#[no_mangle]
+pub extern "C" fn keccak_u256_blocks(
+    input: *const u64,
+    num_blocks: usize,
+    output: *mut u64,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(output);
+        if num_blocks == 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let Some(len) = num_blocks.checked_mul(KECCAK256_RATE_LANES) else {
+            return ERR_INVALID_LENGTH;
+        };
+        let _profile = Profile::start(Primitive::Keccak, 8 * len);
+        let input = match slice_from_ptr(input, len) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let mut state = [0u64; KECCAK_STATE_LANES];
+        for block in input.chunks_exact(KECCAK256_RATE_LANES) {
+            for (lane, value) in state.iter_mut().zip(block) {
+                *lane ^= value;
+            }
+            keccak::f1600(&mut state);
+        }
+        let output = unsafe { &mut *(output as *mut [u64; 4]) };
+        output.copy_from_slice(&state[..4]);
+        SUCCESS
+    })
+}
+
+/// Computes the keccak256 hash of `len` bytes, writing the 32 bytes digest into the last argument
+#[no_mangle]
+pub extern "C" fn keccak256(data: *const u8, len: usize, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let _profile = Profile::start(Primitive::Keccak, len);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let hash: [u8; 32] = Keccak256::digest(data).into();
+        differential::report_bytes(DIFF_KECCAK256, data, &hash);
+        bytes_from_array(&hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the keccak256 hash of `len` bytes truncated to its 250 low bits, writing it into the
+/// last argument
+#[no_mangle]
+pub extern "C" fn starknet_keccak(data: *const u8, len: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let _profile = Profile::start(Primitive::Keccak, len);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let hash = sn_keccak(data);
+        differential::report_bytes(DIFF_STARKNET_KECCAK, data, &hash);
+        bytes_from_array(&hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex_bytes;
+
+    #[test]
+    fn test_keccak_f1600_zero_state() {
+        let mut state = [0u64; KECCAK_STATE_LANES];
+        assert_eq!(keccak_f1600(state.as_mut_ptr()), SUCCESS);
+        assert_eq!(state[0], 0xf1258f7940e1dde7);
+        assert_eq!(state[1], 0x84d5ccf933c0478a);
+        assert_eq!(state[24], 0xeaf1ff7b5ceca249);
+    }
+
+    // The keccak256 padding of the data into whole blocks of lanes.
+    fn padded_blocks(data: &[u8]) -> Vec<u64> {
+        let rate = 8 * KECCAK256_RATE_LANES;
+        let mut padded = data.to_vec();
+        padded.resize((data.len() / rate + 1) * rate, 0);
+        padded[data.len()] ^= 0x01;
+        *padded.last_mut().unwrap() ^= 0x80;
+        padded
+            .chunks_exact(8)
+            .map(|lane| u64::from_le_bytes(lane.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_keccak_u256_blocks() {
+        for len in [0, 135, 136, 300] {
+            let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let blocks = padded_blocks(&data);
+            let mut output = [0u64; 4];
+            assert_eq!(
+                keccak_u256_blocks(
+                    blocks.as_ptr(),
+                    blocks.len() / KECCAK256_RATE_LANES,
+                    output.as_mut_ptr()
+                ),
+                SUCCESS
+            );
+            let digest: Vec<u8> = output.iter().flat_map(|lane| lane.to_le_bytes()).collect();
+            assert_eq!(digest[..], Keccak256::digest(&data)[..]);
+        }
+
+        let mut output = [0u64; 4];
+        assert_eq!(
+            keccak_u256_blocks(core::ptr::null(), 0, output.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_keccak256() {
+        let mut result = [0u8; 32];
+        assert_eq!(
+            keccak256(core::ptr::null(), 0, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result.to_vec(),
+            hex_bytes("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+
+        let data = b"hello world";
+        assert_eq!(
+            keccak256(data.as_ptr(), data.len(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result.to_vec(),
+            hex_bytes("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad")
+        );
+    }
+
+    #[test]
+    fn test_starknet_keccak() {
+        // Selector of the `transfer` entry point
+        let name = b"transfer";
+        let mut result = [0u8; 32];
+        assert_eq!(
+            starknet_keccak(name.as_ptr(), name.len(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            starknet_types_core::felt::Felt::from_hex(
+                "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+            )
+            .unwrap()
+            .to_bytes_be()
+        );
+    }
+}
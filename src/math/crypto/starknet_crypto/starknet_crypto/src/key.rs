@@ -0,0 +1,234 @@
+// Derivation of Stark private keys, along EIP-2645 paths followed by the StarkEx key grinding.
+//
+// An EIP-2645 path is m/2645'/layer'/application'/eth_address_1'/eth_address_2'/index, where the
+// layer and the application are the 31 low bits of the sha256 of their names, and eth_address_1
+// and eth_address_2 the bits 0 to 30 and 31 to 61 of the Ethereum address. The secp256k1 private
+// key BIP32 derives along the path is then ground into a Stark private key.
+use core::ffi::{c_char, CStr};
+
+use elliptic_curve::{
+    bigint::{Encoding, NonZero, U256},
+    sec1::ToEncodedPoint,
+    PrimeField,
+};
+use hmac::{Hmac, Mac};
+use k256::{FieldBytes, ProjectivePoint, Scalar};
+use sha2::{Digest, Sha256, Sha512};
+use starknet_curve::curve_params::EC_ORDER;
+use starknet_types_core::felt::Felt;
+use zeroize::Zeroizing;
+
+use crate::{
+    ffi_guard,
+    secret::{bytes_from_secret, SecretFelt},
+    slice_from_ptr, Bytes, ERR_INVALID_K, SUCCESS,
+};
+
+const HARDENED: u32 = 1 << 31;
+
+// Hashes the seed followed by an increasing index, as its shortest big endian bytes, until the
+// sha256 digest is lower than the largest multiple of the curve order below 2^256, so that the
+// digest reduced modulo the order is uniform.
+fn grind(seed: &[u8]) -> Zeroizing<SecretFelt> {
+    let order = NonZero::new(U256::from_be_bytes(EC_ORDER.to_bytes_be())).unwrap();
+    let limit = U256::MAX.wrapping_sub(&U256::MAX.rem(&order));
+    let mut index = 0u64;
+    loop {
+        let index_bytes = index.to_be_bytes();
+        let skipped = (index.leading_zeros() as usize / 8).min(7);
+        let digest = Zeroizing::new(
+            Sha256::new()
+                .chain_update(seed)
+                .chain_update(&index_bytes[skipped..])
+                .finalize(),
+        );
+        let key = U256::from_be_slice(&digest);
+        if key < limit {
+            let key = Zeroizing::new(key.rem(&order).to_be_bytes());
+            let key = Felt::from_bytes_be(&key);
+            return Zeroizing::new(SecretFelt(key));
+        }
+        index += 1;
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).unwrap();
+    for data in data {
+        mac.update(data);
+    }
+    Zeroizing::new(mac.finalize().into_bytes().into())
+}
+
+// Adds the first half of a BIP32 node to the parent key. Returns None for the invalid keys.
+fn child_key(parent: &Scalar, node: &[u8; 64]) -> Option<Zeroizing<Scalar>> {
+    let tweak: Option<Scalar> = Scalar::from_repr(*FieldBytes::from_slice(&node[..32])).into();
+    let key = Zeroizing::new(tweak? + parent);
+    (!bool::from(key.is_zero())).then_some(key)
+}
+
+// Derives the BIP32 private key at a path from a seed. Returns None when the path goes through an
+// invalid key, which BIP32 would skip.
+fn bip32_derive(seed: &[u8], path: &[u32]) -> Option<Zeroizing<Scalar>> {
+    // A node is the tweak of the key followed by the chain code
+    let mut node = hmac_sha512(b"Bitcoin seed", &[seed]);
+    let mut key = child_key(&Scalar::ZERO, &node)?;
+    for &index in path {
+        let chain_code = &node[32..];
+        node = if index >= HARDENED {
+            let key_bytes = Zeroizing::new(key.to_bytes());
+            hmac_sha512(chain_code, &[&[0], &key_bytes, &index.to_be_bytes()])
+        } else {
+            let public_key = (ProjectivePoint::GENERATOR * *key)
+                .to_affine()
+                .to_encoded_point(true);
+            hmac_sha512(chain_code, &[public_key.as_bytes(), &index.to_be_bytes()])
+        };
+        key = child_key(&key, &node)?;
+    }
+    Some(key)
+}
+
+/// Grinds the `seed_len` bytes of a seed into a Stark private key with the StarkEx algorithm,
+/// writing it into the last argument. The seed of the key of an Ethereum account is the 32 bytes r
+/// of its secp256k1 signature of the key derivation message
+#[no_mangle]
+pub extern "C" fn grind_key(seed: *const u8, seed_len: usize, private_key: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key);
+        let seed = match slice_from_ptr(seed, seed_len) {
+            Ok(seed) => seed,
+            Err(status) => return status,
+        };
+        bytes_from_secret(&grind(seed), private_key);
+        SUCCESS
+    })
+}
+
+// The 31 low bits of the sha256 of a name.
+fn path_index_from_name(name: &[u8]) -> u32 {
+    let digest = Sha256::digest(name);
+    u32::from_be_bytes(digest[28..].try_into().unwrap()) & (HARDENED - 1)
+}
+
+/// Derives a Stark private key along the EIP-2645 path of the given layer and application, NUL
+/// terminated names such as `starkex` and `starkdeployement`, 20 bytes Ethereum address and index,
+/// from the `seed_len` bytes of a BIP32 seed, writing it into the last argument. Returns
+/// ERR_INVALID_K in the negligible case where the path goes through an invalid BIP32 key
+#[no_mangle]
+pub extern "C" fn eip2645_derive(
+    seed: *const u8,
+    seed_len: usize,
+    layer: *const c_char,
+    application: *const c_char,
+    eth_address: *const u8,
+    index: u32,
+    private_key: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(layer, application, eth_address, private_key);
+        let seed = match slice_from_ptr(seed, seed_len) {
+            Ok(seed) => seed,
+            Err(status) => return status,
+        };
+        let (layer, application) = unsafe { (CStr::from_ptr(layer), CStr::from_ptr(application)) };
+        // The 62 low bits of the address are in its last 8 bytes
+        let eth_address = unsafe { core::slice::from_raw_parts(eth_address, 20) };
+        let eth_address = u64::from_be_bytes(eth_address[12..].try_into().unwrap());
+
+        let path = [
+            HARDENED | 2645,
+            HARDENED | path_index_from_name(layer.to_bytes()),
+            HARDENED | path_index_from_name(application.to_bytes()),
+            HARDENED | (eth_address as u32 & (HARDENED - 1)),
+            HARDENED | ((eth_address >> 31) as u32 & (HARDENED - 1)),
+            index,
+        ];
+        let Some(key) = bip32_derive(seed, &path) else {
+            return ERR_INVALID_K;
+        };
+        bytes_from_secret(&grind(&key.to_bytes()), private_key);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hex_bytes, ERR_INVALID_LENGTH, ERR_NULL_POINTER};
+
+    #[test]
+    fn test_grind_key() {
+        // The grindKey vector of the StarkEx key derivation tests
+        let seed = hex_bytes("86f3e7293141f20a8baff320e8ee4accb9d4a4bf2b4d295e8cee784db46e0519");
+        let expected =
+            Felt::from_hex("0x5c8c8683596c732541a59e03007b2d30dbbbb873556fe65b5fb63c16688f941")
+                .unwrap();
+        let mut private_key = [0u8; 32];
+        assert_eq!(
+            grind_key(seed.as_ptr(), seed.len(), private_key.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(private_key, expected.to_bytes_be());
+
+        assert_eq!(
+            grind_key(core::ptr::null(), 1, private_key.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            grind_key(seed.as_ptr(), usize::MAX, private_key.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            grind_key(seed.as_ptr(), seed.len(), core::ptr::null_mut()),
+            ERR_NULL_POINTER
+        );
+        // The key was left alone by the failed calls
+        assert_eq!(private_key, expected.to_bytes_be());
+
+        // An empty seed may be NULL
+        assert_eq!(
+            grind_key(core::ptr::null(), 0, private_key.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(private_key, grind(&[]).to_bytes_be());
+    }
+
+    #[test]
+    fn test_eip2645_derive() {
+        // The seed of the mnemonic "range mountain blast problem vibrant void vivid doctor cluster
+        // enough melody salt layer language laptop boat major space monkey unit glimpse pause
+        // change vibrant", from the StarkEx key derivation test vectors
+        let seed = hex_bytes(concat!(
+            "ed43abe2785ca9dfe74a2a8b05feaf2cdd4eda6847914674f88f5f114c3b694d",
+            "4ae930dd3808088ce40143430157ed79a66d7ff8d4919bd833d31d4a0f16f441",
+        ));
+        let eth_address = hex_bytes("a4864d977b944315389d1765ffa7e66f74ee8cd7");
+
+        let mut private_key = [0u8; 32];
+        for (index, expected) in [
+            (
+                0,
+                "0x6cf0a8bf113352eb863157a45c5e5567abb34f8d32cddafd2c22aa803f4892c",
+            ),
+            (
+                7,
+                "0x341751bdc42841da35ab74d13a1372c1f0250617e8a2ef96034d9f46e6847af",
+            ),
+        ] {
+            assert_eq!(
+                eip2645_derive(
+                    seed.as_ptr(),
+                    seed.len(),
+                    c"starkex".as_ptr(),
+                    c"starkdeployement".as_ptr(),
+                    eth_address.as_ptr(),
+                    index,
+                    private_key.as_mut_ptr(),
+                ),
+                SUCCESS
+            );
+            assert_eq!(private_key, Felt::from_hex(expected).unwrap().to_bytes_be());
+        }
+    }
+}
@@ -0,0 +1,351 @@
+// KZG commitments and proofs over BLS12-381 as specified by EIP-4844, for the data availability
+// hints of the Starknet OS.
+//
+// Blobs are 4096 elements of the BLS12-381 scalar field in evaluation form, 32 bytes big endian
+// each. G1 points are 48 bytes and G2 points 96 bytes, compressed as in the Zcash serialization.
+// The trusted setup of the EIP-4844 ceremony is not embedded: the embedder loads it once with
+// kzg_load_trusted_setup before committing or verifying.
+use std::sync::RwLock;
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{
+            curves::bls12_381::{
+                curve::BLS12381Curve,
+                default_types::{FrConfig, FrElement},
+                pairing::BLS12381AtePairing,
+                twist::BLS12381TwistCurve,
+            },
+            point::ShortWeierstrassProjectivePoint,
+            traits::Compress,
+        },
+        traits::{IsEllipticCurve, IsPairing},
+    },
+    field::{element::FieldElement, fields::montgomery_backed_prime_fields::IsModulus},
+    msm::pippenger::msm,
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+
+use crate::{
+    array_from_bytes, bytes_from_array, ffi_guard, logger::log, slice_from_ptr, Bytes,
+    ERR_INVALID_FELT, ERR_INVALID_LENGTH, ERR_INVALID_POINT, ERR_INVALID_PROOF,
+    ERR_NO_TRUSTED_SETUP, LOG_DEBUG, SUCCESS,
+};
+
+const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+type G1Point = ShortWeierstrassProjectivePoint<BLS12381Curve>;
+type G2Point = ShortWeierstrassProjectivePoint<BLS12381TwistCurve>;
+
+struct TrustedSetup {
+    // The Lagrange basis over the roots of unity in bit reversed order, in G1
+    g1_lagrange: Vec<G1Point>,
+    // The secret of the ceremony times the G2 generator
+    tau_g2: G2Point,
+}
+
+static TRUSTED_SETUP: RwLock<Option<TrustedSetup>> = RwLock::new(None);
+
+// Reads an element of the BLS12-381 scalar field from 32 bytes big endian.
+fn scalar_from_bytes(bytes: &[u8]) -> Option<FrElement> {
+    let value = U256::from_bytes_be(bytes).ok()?;
+    (value < FrConfig::MODULUS).then(|| FrElement::new(value))
+}
+
+fn g1_from_bytes(bytes: &[u8]) -> Option<G1Point> {
+    BLS12381Curve::decompress_g1_point(&mut bytes.to_vec()).ok()
+}
+
+fn g2_from_bytes(bytes: &[u8]) -> Option<G2Point> {
+    BLS12381Curve::decompress_g2_point(&mut bytes.to_vec()).ok()
+}
+
+/// Loads the EIP-4844 trusted setup, the `g1_count` G1 points of the Lagrange basis in bit reversed
+/// order followed by nothing else, and the `g2_count` G2 points of the monomial basis, replacing
+/// the setup loaded before. The G1 count is 4096 and there are at least two G2 points
+#[no_mangle]
+pub extern "C" fn kzg_load_trusted_setup(
+    g1_lagrange: *const u8,
+    g1_count: usize,
+    g2_monomial: *const u8,
+    g2_count: usize,
+) -> i32 {
+    ffi_guard(|| {
+        if g1_count != FIELD_ELEMENTS_PER_BLOB || g2_count < 2 {
+            return ERR_INVALID_LENGTH;
+        }
+        let (g1_lagrange, g2_monomial) = match (
+            slice_from_ptr(g1_lagrange, 48 * g1_count),
+            slice_from_ptr(g2_monomial, 96 * g2_count),
+        ) {
+            (Ok(g1_lagrange), Ok(g2_monomial)) => (g1_lagrange, g2_monomial),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+        let g1_lagrange: Option<Vec<G1Point>> =
+            g1_lagrange.chunks_exact(48).map(g1_from_bytes).collect();
+        let (Some(g1_lagrange), Some(tau_g2)) = (g1_lagrange, g2_from_bytes(&g2_monomial[96..192]))
+        else {
+            return ERR_INVALID_POINT;
+        };
+        log(
+            LOG_DEBUG,
+            format_args!("loaded a KZG trusted setup of {g1_count} G1 points"),
+        );
+        *TRUSTED_SETUP.write().unwrap() = Some(TrustedSetup {
+            g1_lagrange,
+            tau_g2,
+        });
+        SUCCESS
+    })
+}
+
+/// Computes the KZG commitment of a blob of 4096 scalars, 131072 bytes, writing the 48 bytes G1
+/// point into the last argument. Returns ERR_INVALID_FELT for a scalar that is not lower than the
+/// BLS12-381 scalar field modulus and ERR_NO_TRUSTED_SETUP before kzg_load_trusted_setup
+#[no_mangle]
+pub extern "C" fn kzg_commit(blob: *const u8, commitment: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(blob, commitment);
+        let blob = unsafe { core::slice::from_raw_parts(blob, 32 * FIELD_ELEMENTS_PER_BLOB) };
+        let scalars: Option<Vec<U256>> = blob
+            .chunks_exact(32)
+            .map(|bytes| Some(scalar_from_bytes(bytes)?.representative()))
+            .collect();
+        let Some(scalars) = scalars else {
+            return ERR_INVALID_FELT;
+        };
+        let setup = TRUSTED_SETUP.read().unwrap();
+        let Some(setup) = setup.as_ref() else {
+            return ERR_NO_TRUSTED_SETUP;
+        };
+        let point = msm(&scalars, &setup.g1_lagrange).unwrap();
+        bytes_from_array(&BLS12381Curve::compress_g1_point(&point), commitment);
+        SUCCESS
+    })
+}
+
+/// Verifies a KZG proof, a 48 bytes G1 point, that the polynomial of a commitment evaluates to `y`
+/// at `z`, two 32 bytes scalars. Returns SUCCESS for a valid proof, ERR_INVALID_PROOF for an
+/// invalid one and another error code for malformed inputs or before kzg_load_trusted_setup
+#[no_mangle]
+pub extern "C" fn kzg_verify_proof(
+    commitment: *const u8,
+    z: Bytes,
+    y: Bytes,
+    proof: *const u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(commitment, z, y, proof);
+        let (commitment, proof) = unsafe {
+            (
+                core::slice::from_raw_parts(commitment, 48),
+                core::slice::from_raw_parts(proof, 48),
+            )
+        };
+        let (Some(z), Some(y)) = (
+            scalar_from_bytes(&array_from_bytes(z)),
+            scalar_from_bytes(&array_from_bytes(y)),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let (Some(commitment), Some(proof)) = (g1_from_bytes(commitment), g1_from_bytes(proof))
+        else {
+            return ERR_INVALID_POINT;
+        };
+        let setup = TRUSTED_SETUP.read().unwrap();
+        let Some(setup) = setup.as_ref() else {
+            return ERR_NO_TRUSTED_SETUP;
+        };
+
+        // e(commitment - [y]G1, G2) == e(proof, [tau - z]G2)
+        let g2 = BLS12381TwistCurve::generator();
+        let lhs = commitment.operate_with(
+            &BLS12381Curve::generator()
+                .operate_with_self(y.representative())
+                .neg(),
+        );
+        let rhs = setup
+            .tau_g2
+            .operate_with(&g2.operate_with_self(z.representative()).neg());
+        match BLS12381AtePairing::compute_batch(&[(&lhs, &g2.neg()), (&proof, &rhs)]) {
+            Ok(product) if product == FieldElement::one() => SUCCESS,
+            _ => ERR_INVALID_PROOF,
+        }
+    })
+}
+
+/// Evaluates the polynomial of `count` contiguous coefficients over the BLS12-381 scalar field,
+/// lowest degree first, at a point, writing the value into the last argument. The coefficients and
+/// the point are 32 bytes scalars lower than the field modulus
+#[no_mangle]
+pub extern "C" fn bls_poly_eval(
+    coefficients: *const u8,
+    count: usize,
+    point: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(point, result);
+        let coefficients = match count.checked_mul(32).ok_or(ERR_INVALID_LENGTH) {
+            Ok(len) => match slice_from_ptr(coefficients, len) {
+                Ok(coefficients) => coefficients,
+                Err(status) => return status,
+            },
+            Err(status) => return status,
+        };
+        let Some(point) = scalar_from_bytes(&array_from_bytes(point)) else {
+            return ERR_INVALID_FELT;
+        };
+        // Horner's rule from the highest degree down
+        let mut value = FrElement::zero();
+        for coefficient in coefficients.chunks_exact(32).rev() {
+            let Some(coefficient) = scalar_from_bytes(coefficient) else {
+                return ERR_INVALID_FELT;
+            };
+            value = value * &point + coefficient;
+        }
+        bytes_from_array(&value.to_bytes_be(), result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar_bytes(scalar: &FrElement) -> [u8; 32] {
+        scalar.to_bytes_be().try_into().unwrap()
+    }
+
+    // Loads, once for all the tests, a setup for the secret 7 whose Lagrange basis is replaced with
+    // [i + 1]G1 to check the commitment as a plain multi scalar multiplication.
+    fn load_test_setup() {
+        static LOADED: std::sync::Once = std::sync::Once::new();
+        LOADED.call_once(load_setup);
+    }
+
+    fn load_setup() {
+        let g1 = BLS12381Curve::generator();
+        let mut point = g1.clone();
+        let mut g1_lagrange = Vec::with_capacity(48 * FIELD_ELEMENTS_PER_BLOB);
+        for _ in 0..FIELD_ELEMENTS_PER_BLOB {
+            g1_lagrange.extend(BLS12381Curve::compress_g1_point(&point));
+            point = point.operate_with(&g1);
+        }
+        let g2 = BLS12381TwistCurve::generator();
+        let g2_monomial: Vec<u8> = [g2.clone(), g2.operate_with_self(7u64)]
+            .iter()
+            .flat_map(BLS12381Curve::compress_g2_point)
+            .collect();
+        assert_eq!(
+            kzg_load_trusted_setup(
+                g1_lagrange.as_ptr(),
+                FIELD_ELEMENTS_PER_BLOB,
+                g2_monomial.as_ptr(),
+                2
+            ),
+            SUCCESS
+        );
+    }
+
+    #[test]
+    fn test_kzg_commit() {
+        load_test_setup();
+        let mut blob = vec![0u8; 32 * FIELD_ELEMENTS_PER_BLOB];
+        // The scalars 1, 2 and 3 at the indices 0, 1 and 4095
+        blob[31] = 1;
+        blob[63] = 2;
+        let last = blob.len() - 1;
+        blob[last] = 3;
+        let mut commitment = [0u8; 48];
+        assert_eq!(kzg_commit(blob.as_ptr(), commitment.as_mut_ptr()), SUCCESS);
+
+        let expected = BLS12381Curve::generator().operate_with_self(1u64 + 2 * 2 + 3 * 4096);
+        assert_eq!(commitment, BLS12381Curve::compress_g1_point(&expected));
+
+        blob[..32].copy_from_slice(&FrConfig::MODULUS.to_bytes_be());
+        assert_eq!(
+            kzg_commit(blob.as_ptr(), commitment.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_kzg_verify_proof() {
+        load_test_setup();
+        // p(X) = 5 + 3X commits to p(7) G1, and the quotient (p(X) - p(z)) / (X - z) is 3
+        let g1 = BLS12381Curve::generator();
+        let commitment = BLS12381Curve::compress_g1_point(&g1.operate_with_self(5u64 + 3 * 7));
+        let proof = BLS12381Curve::compress_g1_point(&g1.operate_with_self(3u64));
+        let z = FrElement::from(11);
+        let mut z_bytes = scalar_bytes(&z);
+
+        let mut y = scalar_bytes(&(FrElement::from(5) + FrElement::from(3) * z));
+        assert_eq!(
+            kzg_verify_proof(
+                commitment.as_ptr(),
+                z_bytes.as_mut_ptr(),
+                y.as_mut_ptr(),
+                proof.as_ptr()
+            ),
+            SUCCESS
+        );
+
+        let mut wrong_y = scalar_bytes(&FrElement::from(5));
+        assert_eq!(
+            kzg_verify_proof(
+                commitment.as_ptr(),
+                z_bytes.as_mut_ptr(),
+                wrong_y.as_mut_ptr(),
+                proof.as_ptr()
+            ),
+            ERR_INVALID_PROOF
+        );
+
+        // Without the compression flag
+        let not_a_point = [0u8; 48];
+        assert_eq!(
+            kzg_verify_proof(
+                not_a_point.as_ptr(),
+                z_bytes.as_mut_ptr(),
+                y.as_mut_ptr(),
+                proof.as_ptr()
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_bls_poly_eval() {
+        // 1 + 2X + 3X^2 at X = -1 is 2
+        let coefficients: Vec<u8> = [1u64, 2, 3]
+            .iter()
+            .flat_map(|&c| scalar_bytes(&FrElement::from(c)))
+            .collect();
+        let mut point = scalar_bytes(&-FrElement::one());
+        let mut result = [0u8; 32];
+        assert_eq!(
+            bls_poly_eval(
+                coefficients.as_ptr(),
+                3,
+                point.as_mut_ptr(),
+                result.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(result, scalar_bytes(&FrElement::from(2)));
+
+        let mut modulus = FrConfig::MODULUS.to_bytes_be();
+        assert_eq!(
+            bls_poly_eval(
+                coefficients.as_ptr(),
+                3,
+                modulus.as_mut_ptr(),
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+}
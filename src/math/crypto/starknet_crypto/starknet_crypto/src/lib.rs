@@ -1,77 +1,2198 @@
-use starknet_crypto::{
-    pedersen_hash as starknet_crypto_pedersen_hash, poseidon_permute_comp, verify, FieldElement,
+// Without the `std` feature the crate only needs `core` and `alloc`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+// The exported functions are called from C, which is responsible for the validity of the pointers.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+extern crate alloc;
+
+#[cfg(any(not(feature = "std"), test))]
+use ecdsa::get_public_key as starknet_crypto_get_public_key;
+use ecdsa::{recover as starknet_crypto_recover, verify};
+use starknet_crypto_rs::{
+    pedersen_hash as starknet_crypto_pedersen_hash, RecoverError, VerifyError,
 };
-extern crate libc;
+#[cfg(feature = "signing")]
+use starknet_crypto_rs::{rfc6979_generate_k as starknet_crypto_rfc6979_generate_k, SignError};
+use starknet_types_core::felt::Felt;
+// The signatures go through the table of the generator when there is a thread safe place for it
+#[cfg(all(feature = "signing", not(feature = "std")))]
+use ecdsa::sign;
+#[cfg(all(feature = "signing", feature = "std"))]
+use generator::sign;
 
-// C representation of a bit array: a raw pointer to a mutable unsigned 8 bits integer.
-type Bytes = *mut u8;
+use alloc::vec::Vec;
+use elliptic_curve::bigint::{Encoding, U256};
+use hades::poseidon_permute_comp;
+use profiling::{Primitive, Profile};
+use secret::try_secret_from_bytes;
+#[cfg(feature = "signing")]
+use secret::{bytes_from_secret, SecretFelt};
+use starknet_curve::curve_params::EC_ORDER;
+#[cfg(feature = "std")]
+use std::{cell::RefCell, panic::AssertUnwindSafe};
+#[cfg(feature = "signing")]
+use zeroize::Zeroizing;
 
-fn field_element_from_bytes(bytes: Bytes) -> FieldElement {
-    let array = unsafe {
-        let slice: &mut [u8] = std::slice::from_raw_parts_mut(bytes, 32);
-        let array: [u8; 32] = slice.try_into().unwrap();
-        array
+// Returns ERR_NULL_POINTER from the calling function when one of the pointers to fixed size
+// arguments is NULL and, with the `check-alignment` cargo feature, ERR_MISALIGNED_POINTER when one
+// is not aligned for its type. Defined before the modules so that they can use it.
+macro_rules! check_pointers {
+    ($($ptr:expr),+ $(,)?) => {
+        $(
+            if let Err(status) = $crate::Pointer::check($ptr) {
+                return status;
+            }
+        )+
     };
-    FieldElement::from_bytes_be(&array).unwrap()
 }
 
-fn bytes_from_field_element(felt: FieldElement, bytes: Bytes) {
-    let byte_array = felt.to_bytes_be();
-    for i in 0..32 {
-        unsafe {
-            *bytes.offset(i) = byte_array[i as usize];
+mod address;
+#[cfg(feature = "std")]
+mod allocator;
+mod backend;
+mod batch;
+#[cfg(feature = "std")]
+mod bench;
+mod bigint;
+mod blake2s;
+mod block;
+#[cfg(feature = "kzg")]
+mod bls12_381;
+mod byte_array;
+mod class;
+mod commitment;
+mod constants;
+mod curve;
+mod differential;
+mod drbg;
+mod ecdsa;
+#[cfg(feature = "signing")]
+mod ecies;
+mod felt;
+/// The table of the exported functions called by the fuzz targets
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+#[cfg(feature = "std")]
+mod generator;
+mod hades;
+mod handle;
+mod keccak;
+#[cfg(feature = "signing")]
+mod key;
+#[cfg(feature = "kzg")]
+mod kzg;
+mod le;
+mod limbs;
+mod logger;
+mod merkle;
+mod messaging;
+mod mont;
+mod ntt;
+#[cfg(feature = "std")]
+mod patricia;
+#[cfg(feature = "std")]
+mod pedersen;
+mod pedersen_left;
+mod profiling;
+mod program;
+#[cfg(feature = "random")]
+mod random;
+mod schnorr;
+#[cfg(feature = "secp")]
+mod secp;
+#[cfg(feature = "secp")]
+mod secp256k1;
+#[cfg(feature = "secp256r1")]
+mod secp256r1;
+mod secret;
+mod sha256;
+/// The `_sized` counterparts of the functions taking `Bytes`, also called by the fuzz targets
+pub mod sized;
+mod stream;
+mod string;
+mod transaction;
+mod transcript;
+mod typed_data;
+mod uint256;
+mod vectors;
+mod vrf;
+
+/// Version of the ABI described by this header. The major version changes on breaking changes to
+/// existing functions, the minor version when functions are added.
+pub const ABI_MAJOR: u32 = 1;
+pub const ABI_MINOR: u32 = 102;
+/// starknet_crypto_abi_version must return STARKNET_CRYPTO_ABI_VERSION, otherwise the linked
+/// library doesn't match the header
+pub const STARKNET_CRYPTO_ABI_VERSION: u32 = (ABI_MAJOR << 16) | ABI_MINOR;
+
+/// C representation of a bit array: a raw pointer to a mutable unsigned 8 bits integer.
+pub type Bytes = *mut u8;
+
+/// Status codes returned by every function
+pub const SUCCESS: i32 = 0;
+/// The bytes do not encode a felt lower than the STARK prime
+pub const ERR_INVALID_FELT: i32 = 1;
+pub const ERR_INVALID_MESSAGE_HASH: i32 = 2;
+pub const ERR_INVALID_K: i32 = 3;
+/// The coordinates do not describe a point of the STARK curve
+pub const ERR_INVALID_POINT: i32 = 4;
+/// The result is the point at infinity, which has no affine coordinates
+pub const ERR_POINT_AT_INFINITY: i32 = 5;
+pub const ERR_INVALID_R: i32 = 6;
+pub const ERR_INVALID_S: i32 = 7;
+pub const ERR_INVALID_V: i32 = 8;
+pub const ERR_DIVISION_BY_ZERO: i32 = 9;
+/// The signature is well formed but doesn't match the message and public key
+pub const ERR_INVALID_SIGNATURE: i32 = 10;
+pub const ERR_NOT_QUADRATIC_RESIDUE: i32 = 11;
+/// The thread pool of the batch functions could not be created
+pub const ERR_THREAD_POOL: i32 = 12;
+/// The length of an array overflows, or a non empty array is a NULL pointer
+pub const ERR_INVALID_LENGTH: i32 = 13;
+/// Alias of ERR_INVALID_LENGTH returned by the _sized functions for a buffer of the wrong length
+pub const ERR_BAD_LENGTH: i32 = ERR_INVALID_LENGTH;
+/// The function panicked, the message can be retrieved with starknet_crypto_last_panic_message
+/// or starknet_crypto_last_error
+pub const ERR_PANIC: i32 = 14;
+/// The hash kind is neither HASH_PEDERSEN nor HASH_POSEIDON
+pub const ERR_INVALID_HASH_KIND: i32 = 15;
+/// The Merkle or storage proof is well formed but doesn't lead to the root, or the VRF proof is
+/// invalid
+pub const ERR_INVALID_PROOF: i32 = 16;
+/// The root is not one of the Patricia trie roots computed by the library in this process, or its
+/// trie was evicted from the node store or cleared
+pub const ERR_UNKNOWN_ROOT: i32 = 17;
+/// The SNIP-12 revision is neither 0 nor 1
+pub const ERR_INVALID_REVISION: i32 = 18;
+/// The operating system's random number generator failed
+pub const ERR_RNG: i32 = 19;
+/// The string is not a well formed number or short string
+pub const ERR_INVALID_STRING: i32 = 20;
+/// No KZG trusted setup was loaded
+pub const ERR_NO_TRUSTED_SETUP: i32 = 21;
+/// A known answer test of starknet_crypto_selftest failed
+pub const ERR_SELF_TEST_FAILED: i32 = 22;
+/// The primitive is not one of the VECTORS_ kinds of starknet_crypto_test_vectors
+pub const ERR_INVALID_PRIMITIVE: i32 = 23;
+/// The allocator functions are NULL, or were set after the library allocated
+pub const ERR_ALLOCATOR: i32 = 24;
+/// A pointer to a fixed size argument is NULL
+pub const ERR_NULL_POINTER: i32 = 25;
+/// A pointer is not aligned for its type, only checked with the `check-alignment` cargo feature
+pub const ERR_MISALIGNED_POINTER: i32 = 26;
+/// The layout is neither LAYOUT_INTERLEAVED nor LAYOUT_COLUMNS
+pub const ERR_INVALID_LAYOUT: i32 = 27;
+/// A limb of a uint256 is not lower than 2^128
+pub const ERR_INVALID_UINT256: i32 = 28;
+/// The Pedersen commitment doesn't open to the value and blinding factor
+pub const ERR_INVALID_OPENING: i32 = 29;
+/// The partial sum and the doubled point of ec_op share their x coordinate at some step, so that
+/// the next addition would be a doubling or reach the point at infinity
+pub const ERR_EC_OP_SAME_X: i32 = 30;
+/// The tag of a stark_encrypt message doesn't authenticate it under the private key of
+/// stark_decrypt, because of a modified message or another recipient
+pub const ERR_DECRYPTION_FAILED: i32 = 31;
+/// The felt doesn't fit in the machine integer of the conversion
+pub const ERR_OUT_OF_RANGE: i32 = 32;
+
+/// The number of bytes a stark_encrypt message adds to its plaintext: the ephemeral public key and
+/// the tag
+pub const ENCRYPTION_OVERHEAD: usize = 64;
+
+/// Levels of the events received by the starknet_crypto_set_logger callback
+pub const LOG_WARNING: u32 = 0;
+pub const LOG_DEBUG: u32 = 1;
+
+/// Hash kinds selecting the node hash of the Merkle functions
+pub const HASH_PEDERSEN: u32 = 0;
+pub const HASH_POSEIDON: u32 = 1;
+
+/// Layouts of the operands of the batch functions: one array of the interleaved records, or one
+/// array per operand
+pub const LAYOUT_INTERLEAVED: u32 = 0;
+pub const LAYOUT_COLUMNS: u32 = 1;
+
+/// Primitives of the test vectors of starknet_crypto_test_vectors
+pub const VECTORS_PEDERSEN: u32 = 0;
+pub const VECTORS_POSEIDON: u32 = 1;
+pub const VECTORS_POSEIDON_PERMUTE: u32 = 2;
+pub const VECTORS_PUBLIC_KEY: u32 = 3;
+
+/// Primitives timed by starknet_crypto_bench
+pub const BENCH_PEDERSEN: u32 = 0;
+pub const BENCH_POSEIDON: u32 = 1;
+pub const BENCH_POSEIDON_PERMUTE: u32 = 2;
+pub const BENCH_ECDSA_VERIFY: u32 = 3;
+pub const BENCH_KECCAK: u32 = 4;
+pub const BENCH_FELT_MUL: u32 = 5;
+/// The Poseidon hash of 16 felts by starknet-crypto's poseidon_hash_many
+pub const BENCH_POSEIDON_MANY: u32 = 6;
+/// The Poseidon hash of the same 16 felts by the Hades permutation of the `hades` feature
+pub const BENCH_HADES_POSEIDON_MANY: u32 = 7;
+
+/// Primitives reported to the starknet_crypto_set_differential callback, with their inputs and
+/// output: the felts of the arguments, and for DIFF_ECDSA_VERIFY the felt 1 for a valid signature
+/// and 0 for an invalid one, for DIFF_EC_OP the height as a felt after m, for DIFF_KECCAK_F1600 the
+/// 200 bytes state before and after, and for DIFF_KECCAK256 and DIFF_STARKNET_KECCAK the hashed
+/// bytes and the digest
+pub const DIFF_PEDERSEN: u32 = 0;
+pub const DIFF_POSEIDON: u32 = 1;
+pub const DIFF_POSEIDON_SINGLE: u32 = 2;
+pub const DIFF_POSEIDON_MANY: u32 = 3;
+pub const DIFF_POSEIDON_PERMUTE: u32 = 4;
+pub const DIFF_PEDERSEN_ON_ELEMENTS: u32 = 5;
+pub const DIFF_ECDSA_VERIFY: u32 = 6;
+pub const DIFF_KECCAK_F1600: u32 = 7;
+pub const DIFF_KECCAK256: u32 = 8;
+pub const DIFF_STARKNET_KECCAK: u32 = 9;
+pub const DIFF_EC_OP: u32 = 10;
+
+#[cfg(feature = "std")]
+thread_local! {
+    // Message of the last panic caught on this thread.
+    static LAST_PANIC_MESSAGE: RefCell<String> = const { RefCell::new(String::new()) };
+    // Message of the last error status returned on this thread.
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+// The human-readable description of an error status.
+#[cfg(feature = "std")]
+fn error_message(status: i32) -> &'static str {
+    match status {
+        ERR_INVALID_FELT => "the bytes do not encode a felt lower than the STARK prime",
+        ERR_INVALID_MESSAGE_HASH => "the message hash is out of range",
+        ERR_INVALID_K => "the nonce k is out of range",
+        ERR_INVALID_POINT => "the coordinates do not describe a point of the curve",
+        ERR_POINT_AT_INFINITY => "the result is the point at infinity",
+        ERR_INVALID_R => "the signature component r is out of range",
+        ERR_INVALID_S => "the signature component s is out of range",
+        ERR_INVALID_V => "the recovery id v is out of range",
+        ERR_DIVISION_BY_ZERO => "division by zero",
+        ERR_INVALID_SIGNATURE => "the signature doesn't match the message and public key",
+        ERR_NOT_QUADRATIC_RESIDUE => "the felt is not a quadratic residue",
+        ERR_THREAD_POOL => "the thread pool could not be created",
+        ERR_INVALID_LENGTH => "the length is invalid, or a non empty array is a NULL pointer",
+        ERR_INVALID_HASH_KIND => "the hash kind is neither HASH_PEDERSEN nor HASH_POSEIDON",
+        ERR_INVALID_PROOF => "the proof is invalid",
+        ERR_UNKNOWN_ROOT => "the root is not one of the Patricia trie roots",
+        ERR_INVALID_REVISION => "the SNIP-12 revision is neither 0 nor 1",
+        ERR_RNG => "the operating system's random number generator failed",
+        ERR_INVALID_STRING => "the string is not a well formed number or short string",
+        ERR_NO_TRUSTED_SETUP => "no KZG trusted setup was loaded",
+        ERR_SELF_TEST_FAILED => "a known answer test failed",
+        ERR_INVALID_PRIMITIVE => "the primitive is not one of the VECTORS_ kinds",
+        ERR_ALLOCATOR => {
+            "the allocator functions are NULL, or were set after the library allocated"
+        }
+        ERR_NULL_POINTER => "a pointer to a fixed size argument is NULL",
+        ERR_MISALIGNED_POINTER => "a pointer is not aligned for its type",
+        ERR_INVALID_LAYOUT => "the layout is neither LAYOUT_INTERLEAVED nor LAYOUT_COLUMNS",
+        ERR_INVALID_UINT256 => "a limb of a uint256 is not lower than 2^128",
+        ERR_INVALID_OPENING => "the commitment doesn't open to the value and blinding factor",
+        ERR_EC_OP_SAME_X => "the partial sum and the doubled point share their x coordinate",
+        ERR_DECRYPTION_FAILED => "the message doesn't authenticate under the private key",
+        ERR_OUT_OF_RANGE => "the felt doesn't fit in the machine integer",
+        _ => "unknown error",
+    }
+}
+
+// Runs the body of an FFI function, turning a panic into ERR_PANIC since unwinding into the
+// caller is undefined behavior, and recording the message of any error status it returns.
+#[cfg(feature = "std")]
+fn ffi_guard(body: impl FnOnce() -> i32) -> i32 {
+    // The body only touches the caller's buffers, which are not to be trusted after an error
+    let status = std::panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => String::from("unknown panic"),
+            },
+        };
+        LAST_ERROR.with(|last_error| {
+            let mut last_error = last_error.borrow_mut();
+            last_error.clear();
+            last_error.push_str("panicked: ");
+            last_error.push_str(&message);
+        });
+        logger::log(LOG_WARNING, format_args!("caught a panic: {message}"));
+        LAST_PANIC_MESSAGE.with(|last_message| *last_message.borrow_mut() = message);
+        ERR_PANIC
+    });
+    if status != SUCCESS && status != ERR_PANIC {
+        LAST_ERROR.with(|last_error| {
+            let mut last_error = last_error.borrow_mut();
+            last_error.clear();
+            last_error.push_str(error_message(status));
+        });
+    }
+    status
+}
+
+// Without `std` panics can't be caught, they end up in the panic handler which never returns.
+#[cfg(not(feature = "std"))]
+fn ffi_guard(body: impl FnOnce() -> i32) -> i32 {
+    body()
+}
+
+// `no_std` targets have no way to abort the process: hang like most embedded panic handlers.
+#[cfg(not(any(feature = "std", test)))]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+// Without `std` there is no system allocator, the embedder provides one through these functions.
+#[cfg(not(any(feature = "std", test)))]
+mod embedder_allocator {
+    use core::alloc::{GlobalAlloc, Layout};
+
+    extern "C" {
+        /// Provided by the embedder when the library is built without the `std` cargo feature.
+        /// Returns a block of `size` bytes aligned to `align`, or NULL when out of memory
+        fn starknet_crypto_alloc(size: usize, align: usize) -> *mut u8;
+        /// Provided by the embedder when the library is built without the `std` cargo feature.
+        /// Frees a block returned by `starknet_crypto_alloc` for the same `size` and `align`
+        fn starknet_crypto_dealloc(ptr: *mut u8, size: usize, align: usize);
+    }
+
+    struct EmbedderAllocator;
+
+    unsafe impl GlobalAlloc for EmbedderAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            starknet_crypto_alloc(layout.size(), layout.align())
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            starknet_crypto_dealloc(ptr, layout.size(), layout.align())
         }
     }
+
+    #[global_allocator]
+    static ALLOCATOR: EmbedderAllocator = EmbedderAllocator;
+}
+
+// The raw pointers of the exported functions, checked by check_pointers.
+trait Pointer {
+    fn check(self) -> Result<(), i32>;
 }
 
+impl<T> Pointer for *const T {
+    fn check(self) -> Result<(), i32> {
+        if self.is_null() {
+            return Err(ERR_NULL_POINTER);
+        }
+        #[cfg(feature = "check-alignment")]
+        if !self.is_aligned() {
+            return Err(ERR_MISALIGNED_POINTER);
+        }
+        Ok(())
+    }
+}
+
+impl<T> Pointer for *mut T {
+    fn check(self) -> Result<(), i32> {
+        self.cast_const().check()
+    }
+}
+
+fn array_from_bytes(bytes: *const u8) -> [u8; 32] {
+    let mut array = [0u8; 32];
+    unsafe { core::ptr::copy_nonoverlapping(bytes, array.as_mut_ptr(), 32) };
+    array
+}
+
+fn bytes_from_array(array: &[u8], bytes: *mut u8) {
+    unsafe { core::ptr::copy_nonoverlapping(array.as_ptr(), bytes, array.len()) };
+}
+
+// The largest felt, p - 1, as 32 bytes big endian.
+const FELT_MAX_BYTES: [u8; 32] = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x08;
+    bytes[7] = 0x11;
+    bytes
+};
+
+// Returns `None` when the big endian value is not lower than the STARK prime, which
+// `Felt::from_bytes_be` reduces instead.
+fn try_felt_from_array(array: &[u8; 32]) -> Option<Felt> {
+    (*array <= FELT_MAX_BYTES).then(|| Felt::from_bytes_be(array))
+}
+
+// Returns `None` when the 32 bytes big endian value is not lower than the STARK prime.
+fn try_field_element_from_bytes(bytes: Bytes) -> Option<Felt> {
+    try_felt_from_array(&array_from_bytes(bytes))
+}
+
+// Reads a C array of `len` elements. The pointer may be null when the array is empty.
+fn slice_from_ptr<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], i32> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() || len > isize::MAX as usize / core::mem::size_of::<T>() {
+        return Err(ERR_INVALID_LENGTH);
+    }
+    Ok(unsafe { core::slice::from_raw_parts(ptr, len) })
+}
+
+// Reads `count` contiguous 32 bytes big endian felts from a C array.
+fn field_elements_from_bytes(bytes: *const u8, count: usize) -> Result<Vec<Felt>, i32> {
+    let len = count.checked_mul(32).ok_or(ERR_INVALID_LENGTH)?;
+    slice_from_ptr(bytes, len)?
+        .chunks_exact(32)
+        .map(|chunk| try_felt_from_array(chunk.try_into().unwrap()).ok_or(ERR_INVALID_FELT))
+        .collect()
+}
+
+fn bytes_from_field_element(felt: Felt, bytes: Bytes) {
+    bytes_from_array(&felt.to_bytes_be(), bytes);
+}
+
+// Chains the elements with the Pedersen hash starting from zero, and terminates the chain with its
+// length.
+fn hash_on_elements(elements: &[Felt]) -> Felt {
+    let chain = elements.iter().fold(Felt::ZERO, |acc, element| {
+        starknet_crypto_pedersen_hash(&acc, element)
+    });
+    starknet_crypto_pedersen_hash(&chain, &Felt::from(elements.len()))
+}
+
+fn status_from_verify_error(error: VerifyError) -> i32 {
+    match error {
+        VerifyError::InvalidPublicKey => ERR_INVALID_POINT,
+        VerifyError::InvalidMessageHash => ERR_INVALID_MESSAGE_HASH,
+        VerifyError::InvalidR => ERR_INVALID_R,
+        VerifyError::InvalidS => ERR_INVALID_S,
+    }
+}
+
+// Maps the outcome of a signature verification to a status code.
+fn status_from_verification(verification: Result<bool, VerifyError>) -> i32 {
+    match verification {
+        Ok(true) => SUCCESS,
+        Ok(false) => ERR_INVALID_SIGNATURE,
+        Err(error) => status_from_verify_error(error),
+    }
+}
+
+/// Returns the ABI version of the library, the major version in the high 16 bits and the minor one
+/// in the low 16 bits
 #[no_mangle]
-extern "C" fn poseidon_permute(
+pub extern "C" fn starknet_crypto_abi_version() -> u32 {
+    STARKNET_CRYPTO_ABI_VERSION
+}
+
+/// Returns whether every function of the library may be called from several threads at once,
+/// which is always the case. The global state, the Pedersen tables, the table of multiples of the
+/// curve generator, the thread pool, the Patricia node store, the trusted setup, the deterministic
+/// generator and the hooks, is behind OnceLock, locks and atomics, and the last error and panic
+/// messages are kept per thread. A context, like a transcript or a generator, must still not be
+/// used by two threads at once
+#[no_mangle]
+pub extern "C" fn starknet_crypto_is_threadsafe() -> bool {
+    true
+}
+
+// The cargo features of the build, in the order of the manifest.
+const FEATURES: [(&str, bool); 12] = [
+    ("std", cfg!(feature = "std")),
+    ("signing", cfg!(feature = "signing")),
+    ("random", cfg!(feature = "random")),
+    ("secp", cfg!(feature = "secp")),
+    ("secp256r1", cfg!(feature = "secp256r1")),
+    ("parallel", cfg!(feature = "parallel")),
+    ("backend-lambdaworks", cfg!(feature = "backend-lambdaworks")),
+    ("kzg", cfg!(feature = "kzg")),
+    ("hades", cfg!(feature = "hades")),
+    ("profiling", cfg!(feature = "profiling")),
+    ("check-alignment", cfg!(feature = "check-alignment")),
+    ("verify-only", cfg!(feature = "verify-only")),
+];
+
+/// Copies at most `len` bytes of the UTF-8 description of the build into the first argument, and
+/// writes its full length into the last argument. The description is made of space separated
+/// `key=value` pairs: the `version` of this library, the version of its `starknet-crypto`
+/// dependency, the comma separated cargo `features` and the field arithmetic `backend`
+#[no_mangle]
+pub extern "C" fn starknet_crypto_build_info(
+    buffer: *mut u8,
+    len: usize,
+    info_len: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(info_len);
+        if buffer.is_null() && len > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let features: Vec<&str> = FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect();
+        let backend = if cfg!(feature = "backend-lambdaworks") {
+            "lambdaworks"
+        } else {
+            "starknet-types-core"
+        };
+        let info = alloc::format!(
+            "version={} starknet-crypto={} features={} backend={backend}",
+            env!("CARGO_PKG_VERSION"),
+            env!("STARKNET_CRYPTO_RS_VERSION"),
+            features.join(","),
+        );
+        let copied = info.len().min(len);
+        bytes_from_array(&info.as_bytes()[..copied], buffer);
+        unsafe { *info_len = info.len() };
+        SUCCESS
+    })
+}
+
+/// Performs the one-time initialization up front, so that the first calls are not slower than the
+/// next ones: builds the Pedersen tables of starknet_crypto_precompute_pedersen and the table of
+/// the multiples of the generator of get_public_key, ecdsa_sign and ec_mul. The Poseidon round
+/// constants are compiled in and starknet_crypto_set_threads spawns the threads of its pool right
+/// away, so nothing else is initialized lazily. Only the first call does any work
+#[no_mangle]
+pub extern "C" fn starknet_crypto_init() -> i32 {
+    ffi_guard(|| {
+        #[cfg(feature = "std")]
+        generator::init();
+        #[cfg(feature = "std")]
+        return pedersen::starknet_crypto_precompute_pedersen();
+        #[cfg(not(feature = "std"))]
+        SUCCESS
+    })
+}
+
+/// Runs known answer tests of the Pedersen hash, the Poseidon hash and the ECDSA verification
+/// through the exported functions, to catch a miscompiled library or one linked against the wrong
+/// header before using it. Returns SUCCESS when they all pass, ERR_SELF_TEST_FAILED otherwise
+#[no_mangle]
+pub extern "C" fn starknet_crypto_selftest() -> i32 {
+    ffi_guard(|| {
+        let felt = |hex: &str| Felt::from_hex(hex).unwrap().to_bytes_be();
+        let mut result = [0u8; 32];
+
+        // From the starknet-crypto tests
+        let pedersen_passes = pedersen_hash(
+            felt("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb").as_mut_ptr(),
+            felt("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a").as_mut_ptr(),
+            result.as_mut_ptr(),
+        ) == SUCCESS
+            && result == felt("0x30e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662");
+
+        // From cairo-lang v0.11.0
+        let poseidon_passes = poseidon_hash(
+            felt("0xb662f9017fa7956fd70e26129b1833e10ad000fd37b4d9f4e0ce6884b7bbe").as_mut_ptr(),
+            felt("0x1fe356bf76102cdae1bfbdc173602ead228b12904c00dad9cf16e035468bea").as_mut_ptr(),
+            result.as_mut_ptr(),
+        ) == SUCCESS
+            && result == felt("0x75540825a6ecc5dc7d7c2f5f868164182742227f1367d66c43ee51ec7937a81");
+
+        // A valid signature of the message 2, which must fail for the message 3
+        let mut public_key =
+            felt("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca");
+        let mut r = felt("0x411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20");
+        let mut s = felt("0x405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b");
+        let mut verify = |message: &str| {
+            verify_signature(
+                public_key.as_mut_ptr(),
+                felt(message).as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            )
+        };
+        let ecdsa_passes = verify("0x2") == SUCCESS && verify("0x3") == ERR_INVALID_SIGNATURE;
+
+        if pedersen_passes && poseidon_passes && ecdsa_passes {
+            SUCCESS
+        } else {
+            ERR_SELF_TEST_FAILED
+        }
+    })
+}
+
+/// Copies at most `len` bytes of the UTF-8 message of the last panic caught on the calling thread
+/// into the first argument, and writes its full length (zero when nothing panicked) into the last
+/// argument. Only available with the `std` cargo feature (enabled by default)
+#[cfg(feature = "std")]
+#[no_mangle]
+pub extern "C" fn starknet_crypto_last_panic_message(
+    buffer: *mut u8,
+    len: usize,
+    message_len: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(message_len);
+        if buffer.is_null() && len > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        // Copies at most `len` bytes of the UTF-8 message and reports its full length, which is
+        // zero when nothing panicked on this thread
+        LAST_PANIC_MESSAGE.with(|last_message| {
+            let last_message = last_message.borrow();
+            let copied = last_message.len().min(len);
+            bytes_from_array(&last_message.as_bytes()[..copied], buffer);
+            unsafe { *message_len = last_message.len() };
+        });
+        SUCCESS
+    })
+}
+
+/// Copies at most `len` bytes of the UTF-8 message of the last error status returned on the calling
+/// thread, including the payload of a panic, into the first argument, and writes its full length
+/// (zero when nothing failed) into the last argument. The message is kept until the next error, a
+/// successful call doesn't clear it. Only available with the `std` cargo feature (enabled by
+/// default)
+#[cfg(feature = "std")]
+#[no_mangle]
+pub extern "C" fn starknet_crypto_last_error(
+    buffer: *mut u8,
+    len: usize,
+    message_len: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(message_len);
+        if buffer.is_null() && len > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        LAST_ERROR.with(|last_error| {
+            let last_error = last_error.borrow();
+            let copied = last_error.len().min(len);
+            bytes_from_array(&last_error.as_bytes()[..copied], buffer);
+            unsafe { *message_len = last_error.len() };
+        });
+        SUCCESS
+    })
+}
+
+/// Computes the poseidon hash permutation over a state of three felts
+#[no_mangle]
+pub extern "C" fn poseidon_permute(
     first_state_felt: Bytes,
     second_state_felt: Bytes,
     third_state_felt: Bytes,
-) {
-    // Convert state from C representation to FieldElement
-    let mut state_array: [FieldElement; 3] = [
-        field_element_from_bytes(first_state_felt),
-        field_element_from_bytes(second_state_felt),
-        field_element_from_bytes(third_state_felt),
-    ];
-    // Call poseidon permute comp
-    poseidon_permute_comp(&mut state_array);
-    // Convert state from FieldElement back to C representation
-    bytes_from_field_element(state_array[0], first_state_felt);
-    bytes_from_field_element(state_array[1], second_state_felt);
-    bytes_from_field_element(state_array[2], third_state_felt);
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(first_state_felt, second_state_felt, third_state_felt);
+        let _profile = Profile::start(Primitive::Poseidon, 1);
+        // Convert state from C representation to Felt
+        let (Some(first), Some(second), Some(third)) = (
+            try_field_element_from_bytes(first_state_felt),
+            try_field_element_from_bytes(second_state_felt),
+            try_field_element_from_bytes(third_state_felt),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let mut state_array: [Felt; 3] = [first, second, third];
+        // Call poseidon permute comp
+        poseidon_permute_comp(&mut state_array);
+        differential::report(DIFF_POSEIDON_PERMUTE, &[first, second, third], &state_array);
+        // Convert state from Felt back to C representation
+        bytes_from_field_element(state_array[0], first_state_felt);
+        bytes_from_field_element(state_array[1], second_state_felt);
+        bytes_from_field_element(state_array[2], third_state_felt);
+        SUCCESS
+    })
+}
+
+/// Computes in place the poseidon hash permutation over a contiguous 96 bytes state of three felts
+#[no_mangle]
+pub extern "C" fn poseidon_permute_state(state: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(state);
+        let _profile = Profile::start(Primitive::Poseidon, 1);
+        let Ok(elements) = field_elements_from_bytes(state, 3) else {
+            return ERR_INVALID_FELT;
+        };
+        let mut state_array: [Felt; 3] = [elements[0], elements[1], elements[2]];
+        poseidon_permute_comp(&mut state_array);
+        differential::report(DIFF_POSEIDON_PERMUTE, &elements, &state_array);
+        for (i, felt) in state_array.into_iter().enumerate() {
+            bytes_from_field_element(felt, unsafe { state.add(32 * i) });
+        }
+        SUCCESS
+    })
+}
+
+/// Computes the poseidon hash of two felts, writing the result into the third argument
+#[no_mangle]
+pub extern "C" fn poseidon_hash(x: Bytes, y: Bytes, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x, y, result);
+        let _profile = Profile::start(Primitive::Poseidon, 1);
+        // Convert Felts from C representation to Felt
+        let (Some(x), Some(y)) = (
+            try_field_element_from_bytes(x),
+            try_field_element_from_bytes(y),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        // Call the poseidon_hash of starknet-crypto, or of the `hades` permutation
+        let hash_in_felt = hades::poseidon_hash(x, y);
+        differential::report(DIFF_POSEIDON, &[x, y], &[hash_in_felt]);
+        bytes_from_field_element(hash_in_felt, result);
+        SUCCESS
+    })
+}
+
+/// Computes the poseidon hash of a single felt, writing the result into the second argument
+#[no_mangle]
+pub extern "C" fn poseidon_hash_single(x: Bytes, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x, result);
+        let _profile = Profile::start(Primitive::Poseidon, 1);
+        // Convert Felt from C representation to Felt
+        let Some(x) = try_field_element_from_bytes(x) else {
+            return ERR_INVALID_FELT;
+        };
+
+        // Call the poseidon_hash_single of starknet-crypto, or of the `hades` permutation
+        let hash_in_felt = hades::poseidon_hash_single(x);
+        differential::report(DIFF_POSEIDON_SINGLE, &[x], &[hash_in_felt]);
+        bytes_from_field_element(hash_in_felt, result);
+        SUCCESS
+    })
+}
+
+/// Computes the poseidon hash of a contiguous array of `count` felts, writing the result into the
+/// last argument
+#[no_mangle]
+pub extern "C" fn poseidon_hash_many(felts: *const u8, count: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let _profile = Profile::start(Primitive::Poseidon, count);
+        // Convert the felt array from C representation to Felt
+        let elements = match field_elements_from_bytes(felts, count) {
+            Ok(elements) => elements,
+            Err(status) => return status,
+        };
+
+        // Call the poseidon_hash_many of starknet-crypto, or of the `hades` permutation
+        let hash_in_felt = hades::poseidon_hash_many(&elements);
+        differential::report(DIFF_POSEIDON_MANY, &elements, &[hash_in_felt]);
+        bytes_from_field_element(hash_in_felt, result);
+        SUCCESS
+    })
+}
+
+/// Computes the poseidon hash of a domain tag felt followed by a contiguous array of `count` felts,
+/// writing the result into the last argument. The result is the poseidon_hash_many of the tag and
+/// the felts, with the same padding, so that hashes of different tags never collide on the same
+/// felts
+#[no_mangle]
+pub extern "C" fn poseidon_hash_many_tagged(
+    tag: Bytes,
+    felts: *const u8,
+    count: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(tag, result);
+        let _profile = Profile::start(Primitive::Poseidon, count);
+        let Some(tag) = try_field_element_from_bytes(tag) else {
+            return ERR_INVALID_FELT;
+        };
+        let elements = match field_elements_from_bytes(felts, count) {
+            Ok(elements) => elements,
+            Err(status) => return status,
+        };
+
+        let mut tagged = Vec::with_capacity(count + 1);
+        tagged.push(tag);
+        tagged.extend(elements);
+        let hash_in_felt = hades::poseidon_hash_many(&tagged);
+        differential::report(DIFF_POSEIDON_MANY, &tagged, &[hash_in_felt]);
+        bytes_from_field_element(hash_in_felt, result);
+        SUCCESS
+    })
+}
+
+/// Computes the pedersen hash of two felts, writing the result into the third argument
+#[no_mangle]
+pub extern "C" fn pedersen_hash(felt_1: Bytes, felt_2: Bytes, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(felt_1, felt_2, result);
+        let _profile = Profile::start(Primitive::Pedersen, 1);
+        // Convert Felts from C representation to Felt
+        let (Some(f1), Some(f2)) = (
+            try_field_element_from_bytes(felt_1),
+            try_field_element_from_bytes(felt_2),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        // Call starknet_crypto_rs::pedersen_hash
+        let hash_in_felt = starknet_crypto_pedersen_hash(&f1, &f2);
+        differential::report(DIFF_PEDERSEN, &[f1, f2], &[hash_in_felt]);
+        bytes_from_field_element(hash_in_felt, result);
+        SUCCESS
+    })
+}
+
+/// Computes the pedersen hash of two integers, writing the result into the last argument
+#[no_mangle]
+pub extern "C" fn pedersen_hash_u64(a: u64, b: u64, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let _profile = Profile::start(Primitive::Pedersen, 1);
+        let (a, b) = (Felt::from(a), Felt::from(b));
+        let hash = starknet_crypto_pedersen_hash(&a, &b);
+        differential::report(DIFF_PEDERSEN, &[a, b], &[hash]);
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the pedersen hash of a felt and an integer, writing the result into the last argument
+#[no_mangle]
+pub extern "C" fn pedersen_hash_felt_u64(a: Bytes, b: u64, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, result);
+        let _profile = Profile::start(Primitive::Pedersen, 1);
+        let Some(a) = try_field_element_from_bytes(a) else {
+            return ERR_INVALID_FELT;
+        };
+        let b = Felt::from(b);
+        let hash = starknet_crypto_pedersen_hash(&a, &b);
+        differential::report(DIFF_PEDERSEN, &[a, b], &[hash]);
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the pedersen hash of the accumulator and the next felt, writing the result back into
+/// the accumulator, which is left untouched on error
+#[no_mangle]
+pub extern "C" fn pedersen_hash_acc(acc: Bytes, next: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(acc, next);
+        let _profile = Profile::start(Primitive::Pedersen, 1);
+        let (Some(a), Some(b)) = (
+            try_field_element_from_bytes(acc),
+            try_field_element_from_bytes(next),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let hash = starknet_crypto_pedersen_hash(&a, &b);
+        differential::report(DIFF_PEDERSEN, &[a, b], &[hash]);
+        bytes_from_field_element(hash, acc);
+        SUCCESS
+    })
 }
 
+/// Computes the length terminated pedersen hash chain H(...H(H(0, a0), a1)..., n) over a contiguous
+/// array of `count` felts
 #[no_mangle]
-extern "C" fn pedersen_hash(felt_1: Bytes, felt_2: Bytes, result: Bytes) {
-    // Convert Felts from C representation to FieldElement
-    let f1 = field_element_from_bytes(felt_1);
-    let f2 = field_element_from_bytes(felt_2);
+pub extern "C" fn pedersen_hash_on_elements(
+    elements: *const u8,
+    count: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let _profile = Profile::start(Primitive::Pedersen, count);
+        // Convert the felt array from C representation to Felt
+        let elements = match field_elements_from_bytes(elements, count) {
+            Ok(elements) => elements,
+            Err(status) => return status,
+        };
 
-    // Call starknet_crypto::pedersen_hash
-    let hash_in_felt = starknet_crypto_pedersen_hash(&f1, &f2);
-    bytes_from_field_element(hash_in_felt, result);
+        let hash = hash_on_elements(&elements);
+        differential::report(DIFF_PEDERSEN_ON_ELEMENTS, &elements, &[hash]);
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
 }
 
+/// Verifies a signature (r, s) of a message hash against a public key. Returns SUCCESS for a valid
+/// signature, ERR_INVALID_SIGNATURE for an invalid one and another error code for malformed inputs
 #[no_mangle]
-extern "C" fn verify_signature(
+pub extern "C" fn verify_signature(
     public_key_bytes: Bytes,
     message_bytes: Bytes,
     r_bytes: Bytes,
     s_bytes: Bytes,
-) -> bool {
-    let public_key = field_element_from_bytes(public_key_bytes);
-    let message = field_element_from_bytes(message_bytes);
-    let r = field_element_from_bytes(r_bytes);
-    let s = field_element_from_bytes(s_bytes);
-    let verification_result = verify(&public_key, &message, &r, &s);
-
-    // An error on the verification is an invalid signature
-    // That shouldn't verify
-    match verification_result {
-        Ok(verifies) => verifies,
-        Err(_) => false,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(public_key_bytes, message_bytes, r_bytes, s_bytes);
+        let _profile = Profile::start(Primitive::EcdsaVerify, 1);
+        let (Some(public_key), Some(message), Some(r), Some(s)) = (
+            try_field_element_from_bytes(public_key_bytes),
+            try_field_element_from_bytes(message_bytes),
+            try_field_element_from_bytes(r_bytes),
+            try_field_element_from_bytes(s_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let status = status_from_verification(verify(&public_key, &message, &r, &s));
+        differential::report_verification(&[public_key, message, r, s], status);
+        status
+    })
+}
+
+/// Verifies a signature (r, s) of a message hash against the x coordinate of a public key, like
+/// the ECDSA builtin, which only keeps that coordinate: the signature is valid when it is valid
+/// for either of the two points of that x coordinate. Returns the codes of verify_signature,
+/// which follows the same semantics
+#[no_mangle]
+pub extern "C" fn verify_signature_xonly(
+    public_key_x: Bytes,
+    message_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    // The nonce point R = zw * G + rw * Q of (x, y) is zw * G - rw * Q of (x, -y), and the
+    // verification checks r against the x coordinate of both
+    verify_signature(public_key_x, message_bytes, r_bytes, s_bytes)
+}
+
+/// Like verify_signature, and also rejects r and s that are not lower than the curve order and
+/// the malleable s above half the order, since (r, n - s) is valid whenever (r, s) is. Each
+/// malformed input has its own error code, ERR_INVALID_R or ERR_INVALID_S for out of range
+/// components
+#[no_mangle]
+pub extern "C" fn verify_signature_strict(
+    public_key_bytes: Bytes,
+    message_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(public_key_bytes, message_bytes, r_bytes, s_bytes);
+        let order = U256::from_be_bytes(EC_ORDER.to_bytes_be());
+        if U256::from_be_bytes(array_from_bytes(r_bytes)) >= order {
+            return ERR_INVALID_R;
+        }
+        if U256::from_be_bytes(array_from_bytes(s_bytes)) > order.shr_vartime(1) {
+            return ERR_INVALID_S;
+        }
+        verify_signature(public_key_bytes, message_bytes, r_bytes, s_bytes)
+    })
+}
+
+/// Like verify_signature, over the message hash of `count` contiguous felts: their hash on
+/// elements with HASH_PEDERSEN and their Poseidon hash many with HASH_POSEIDON
+#[no_mangle]
+pub extern "C" fn verify_signature_over_elements(
+    public_key_bytes: Bytes,
+    elements: *const u8,
+    count: usize,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+    hash_kind: u32,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(public_key_bytes, r_bytes, s_bytes);
+        let (Some(public_key), Some(r), Some(s)) = (
+            try_field_element_from_bytes(public_key_bytes),
+            try_field_element_from_bytes(r_bytes),
+            try_field_element_from_bytes(s_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let elements = match field_elements_from_bytes(elements, count) {
+            Ok(elements) => elements,
+            Err(status) => return status,
+        };
+        let message = match hash_kind {
+            HASH_PEDERSEN => hash_on_elements(&elements),
+            HASH_POSEIDON => hades::poseidon_hash_many(&elements),
+            _ => return ERR_INVALID_HASH_KIND,
+        };
+        let _profile = Profile::start(Primitive::EcdsaVerify, 1);
+        let status = status_from_verification(verify(&public_key, &message, &r, &s));
+        differential::report_verification(&[public_key, message, r, s], status);
+        status
+    })
+}
+
+// The components of a signature are non-zero and lower than 2^251, like the message hash.
+fn signature_component_in_range(bytes: &[u8; 32]) -> bool {
+    *bytes != [0; 32] && bytes[0] < 0x08
+}
+
+/// Encodes a signature (r, s) in the 64 bytes compact format, r followed by s in big endian.
+/// Returns ERR_INVALID_R or ERR_INVALID_S for a component that is zero or not lower than 2^251
+#[no_mangle]
+pub extern "C" fn signature_to_compact(r_bytes: Bytes, s_bytes: Bytes, compact: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(r_bytes, s_bytes, compact);
+        let (r, s) = (array_from_bytes(r_bytes), array_from_bytes(s_bytes));
+        if !signature_component_in_range(&r) {
+            return ERR_INVALID_R;
+        }
+        if !signature_component_in_range(&s) {
+            return ERR_INVALID_S;
+        }
+        let compact = unsafe { core::slice::from_raw_parts_mut(compact, 64) };
+        compact[..32].copy_from_slice(&r);
+        compact[32..].copy_from_slice(&s);
+        SUCCESS
+    })
+}
+
+/// Decodes a signature in the 64 bytes compact format into r and s. Returns ERR_INVALID_R or
+/// ERR_INVALID_S for a component that is zero or not lower than 2^251, leaving both unchanged
+#[no_mangle]
+pub extern "C" fn signature_from_compact(
+    compact: *const u8,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(compact, r_bytes, s_bytes);
+        let (r, s) = (
+            array_from_bytes(compact),
+            array_from_bytes(compact.wrapping_add(32)),
+        );
+        if !signature_component_in_range(&r) {
+            return ERR_INVALID_R;
+        }
+        if !signature_component_in_range(&s) {
+            return ERR_INVALID_S;
+        }
+        bytes_from_array(&r, r_bytes);
+        bytes_from_array(&s, s_bytes);
+        SUCCESS
+    })
+}
+
+/// Derives the public key (x coordinate) of a private key, writing it into the second argument.
+/// Not constant time: the scalar multiplication branches on the bits of the private key
+#[no_mangle]
+pub extern "C" fn get_public_key(private_key_bytes: Bytes, public_key_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key_bytes, public_key_bytes);
+        let Some(private_key) = try_secret_from_bytes(private_key_bytes) else {
+            return ERR_INVALID_FELT;
+        };
+
+        #[cfg(feature = "std")]
+        let public_key = generator::public_key(&private_key);
+        #[cfg(not(feature = "std"))]
+        let public_key = starknet_crypto_get_public_key(&private_key);
+        bytes_from_field_element(public_key, public_key_bytes);
+        SUCCESS
+    })
+}
+
+/// Deterministically generates the k of a signature from a message hash, a private key and an
+/// optional seed (may be NULL) as per RFC 6979, writing it into the last argument. Not guaranteed
+/// to run in constant time
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn rfc6979_generate_k(
+    message_bytes: Bytes,
+    private_key_bytes: Bytes,
+    seed_bytes: Bytes,
+    k_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(message_bytes, private_key_bytes, k_bytes);
+        let (Some(message), Some(private_key)) = (
+            try_field_element_from_bytes(message_bytes),
+            try_secret_from_bytes(private_key_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        // The seed is optional extra entropy, a null pointer means no seed
+        let seed = if seed_bytes.is_null() {
+            None
+        } else {
+            let Some(seed) = try_field_element_from_bytes(seed_bytes) else {
+                return ERR_INVALID_FELT;
+            };
+            Some(seed)
+        };
+
+        let k = Zeroizing::new(SecretFelt(starknet_crypto_rfc6979_generate_k(
+            &message,
+            &private_key,
+            seed.as_ref(),
+        )));
+        bytes_from_secret(&k, k_bytes);
+        SUCCESS
+    })
+}
+
+/// Signs a message hash with a private key and a k supplied by the caller, writing r and s into the
+/// last two arguments. The k must be secret and never reused, ecdsa_sign_seeded derives it
+/// deterministically as per RFC 6979 instead. Not constant time: the scalar multiplication and the
+/// inversion of k branch on the secrets
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn ecdsa_sign(
+    private_key_bytes: Bytes,
+    message_bytes: Bytes,
+    k_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key_bytes, message_bytes, k_bytes, r_bytes, s_bytes);
+        let _profile = Profile::start(Primitive::EcdsaSign, 1);
+        let (Some(private_key), Some(message), Some(k)) = (
+            try_secret_from_bytes(private_key_bytes),
+            try_field_element_from_bytes(message_bytes),
+            try_secret_from_bytes(k_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        match sign(&private_key, &message, &k) {
+            Ok(signature) => {
+                bytes_from_field_element(signature.r, r_bytes);
+                bytes_from_field_element(signature.s, s_bytes);
+                SUCCESS
+            }
+            Err(SignError::InvalidMessageHash) => ERR_INVALID_MESSAGE_HASH,
+            Err(SignError::InvalidK) => ERR_INVALID_K,
+        }
+    })
+}
+
+/// Signs a message hash with a private key and the k generated by rfc6979_generate_k with a felt
+/// seed as extra entropy, writing r and s into the last two arguments. The signatures are
+/// deterministic for a seed and distinct across seeds. In the rare event where the k is not a valid
+/// nonce, the next seed is tried, so an invalid k only surfaces for a zero message hash signed with
+/// a private key that is a multiple of the curve order, for which none is. Not constant time
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn ecdsa_sign_seeded(
+    private_key_bytes: Bytes,
+    message_bytes: Bytes,
+    seed_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            private_key_bytes,
+            message_bytes,
+            seed_bytes,
+            r_bytes,
+            s_bytes
+        );
+        let _profile = Profile::start(Primitive::EcdsaSign, 1);
+        let (Some(private_key), Some(message), Some(seed)) = (
+            try_secret_from_bytes(private_key_bytes),
+            try_field_element_from_bytes(message_bytes),
+            try_field_element_from_bytes(seed_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        match sign_rfc6979(&private_key, &message, Some(seed)) {
+            Ok((r, s)) => {
+                bytes_from_field_element(r, r_bytes);
+                bytes_from_field_element(s, s_bytes);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+// Signs with the k generated by rfc6979_generate_k with the seed as extra entropy, moving on to
+// the next seed, from one without a seed, in the rare event where the k is not a valid nonce.
+#[cfg(feature = "signing")]
+pub(crate) fn sign_rfc6979(
+    private_key: &Felt,
+    message: &Felt,
+    mut seed: Option<Felt>,
+) -> Result<(Felt, Felt), i32> {
+    // s = (message + r * private_key) / k is then zero whatever the k
+    if *message == Felt::ZERO && [Felt::ZERO, EC_ORDER].contains(private_key) {
+        return Err(ERR_INVALID_K);
+    }
+    loop {
+        let k = Zeroizing::new(SecretFelt(starknet_crypto_rfc6979_generate_k(
+            message,
+            private_key,
+            seed.as_ref(),
+        )));
+        match sign(private_key, message, &k) {
+            Ok(signature) => return Ok((signature.r, signature.s)),
+            Err(SignError::InvalidMessageHash) => return Err(ERR_INVALID_MESSAGE_HASH),
+            Err(SignError::InvalidK) => {
+                seed = Some(seed.map_or(Felt::ONE, |seed| seed + Felt::ONE))
+            }
+        }
+    }
+}
+
+/// Recovers the public key from a message hash and a signature (r, s, v), writing it into the last
+/// argument
+#[no_mangle]
+pub extern "C" fn recover(
+    message_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+    v_bytes: Bytes,
+    public_key_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(message_bytes, r_bytes, s_bytes, v_bytes, public_key_bytes);
+        let (Some(message), Some(r), Some(s), Some(v)) = (
+            try_field_element_from_bytes(message_bytes),
+            try_field_element_from_bytes(r_bytes),
+            try_field_element_from_bytes(s_bytes),
+            try_field_element_from_bytes(v_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        match starknet_crypto_recover(&message, &r, &s, &v) {
+            Ok(public_key) => {
+                bytes_from_field_element(public_key, public_key_bytes);
+                SUCCESS
+            }
+            Err(RecoverError::InvalidMessageHash) => ERR_INVALID_MESSAGE_HASH,
+            Err(RecoverError::InvalidR) => ERR_INVALID_R,
+            Err(RecoverError::InvalidS) => ERR_INVALID_S,
+            Err(RecoverError::InvalidV) => ERR_INVALID_V,
+        }
+    })
+}
+
+/// The bytes of a hex string without prefix, for the test vectors of the modules
+#[cfg(test)]
+pub(crate) fn hex_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_crypto_rs::sign;
+
+    fn felt_bytes(hex: &str) -> [u8; 32] {
+        Felt::from_hex(hex).unwrap().to_bytes_be()
+    }
+
+    #[test]
+    fn test_poseidon_hash() {
+        // Test data generated from `cairo-lang` v0.11.0
+        let mut x = felt_bytes("0xb662f9017fa7956fd70e26129b1833e10ad000fd37b4d9f4e0ce6884b7bbe");
+        let mut y = felt_bytes("0x1fe356bf76102cdae1bfbdc173602ead228b12904c00dad9cf16e035468bea");
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash(x.as_mut_ptr(), y.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            felt_bytes("0x75540825a6ecc5dc7d7c2f5f868164182742227f1367d66c43ee51ec7937a81")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_permute_state() {
+        let mut first = felt_bytes("0x1");
+        let mut second = felt_bytes("0x2");
+        let mut third = felt_bytes("0x3");
+        let mut state = [first, second, third].concat();
+        assert_eq!(
+            poseidon_permute(first.as_mut_ptr(), second.as_mut_ptr(), third.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(poseidon_permute_state(state.as_mut_ptr()), SUCCESS);
+        assert_eq!(state, [first, second, third].concat());
+
+        // The state is left untouched when one of its felts is invalid
+        let mut invalid = [felt_bytes("0x1"), [0xff; 32], felt_bytes("0x3")].concat();
+        let expected = invalid.clone();
+        assert_eq!(
+            poseidon_permute_state(invalid.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(invalid, expected);
+    }
+
+    #[test]
+    fn test_poseidon_hash_single() {
+        // Test data generated from `cairo-lang` v0.11.0
+        let mut x = felt_bytes("0x9dad5d6f502ccbcb6d34ede04f0337df3b98936aaf782f4cc07d147e3a4fd6");
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_single(x.as_mut_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            felt_bytes("0x11222854783f17f1c580ff64671bc3868de034c236f956216e8ed4ab7533455")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_hash_many() {
+        // Test data generated from `cairo-lang` v0.11.0
+        let felts = [
+            felt_bytes("0x9bf52404586087391c5fbb42538692e7ca2149bac13c145ae4230a51a6fc47"),
+            felt_bytes("0x40304159ee9d2d611120fbd7c7fb8020cc8f7a599bfa108e0e085222b862c0"),
+            felt_bytes("0x46286e4f3c450761d960d6a151a9c0988f9e16f8a48d4c0a85817c009f806a"),
+        ]
+        .concat();
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_many(felts.as_ptr(), 3, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            felt_bytes("0x1ec38b38dc88bac7b0ed6ff6326f975a06a59ac601b417745fd412a5d38e4f7")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_hash_many_tagged() {
+        let mut tag = Felt::from_bytes_be_slice(b"tag").to_bytes_be();
+        let felts = [felt_bytes("0x1"), felt_bytes("0x2")].concat();
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_many_tagged(tag.as_mut_ptr(), felts.as_ptr(), 2, result.as_mut_ptr()),
+            SUCCESS
+        );
+        let tag_felt = Felt::from_bytes_be_slice(b"tag");
+        let expected = hades::poseidon_hash_many(&[tag_felt, Felt::ONE, Felt::TWO]);
+        assert_eq!(result, expected.to_bytes_be());
+
+        assert_eq!(
+            poseidon_hash_many_tagged(tag.as_mut_ptr(), core::ptr::null(), 0, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, hades::poseidon_hash_many(&[tag_felt]).to_bytes_be());
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            poseidon_hash_many_tagged(
+                not_a_felt.as_mut_ptr(),
+                felts.as_ptr(),
+                2,
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_pedersen_hash_u64() {
+        let expected = starknet_crypto_pedersen_hash(&Felt::from(7u8), &Felt::TWO);
+        let mut result = [0u8; 32];
+        assert_eq!(pedersen_hash_u64(7, 2, result.as_mut_ptr()), SUCCESS);
+        assert_eq!(result, expected.to_bytes_be());
+
+        let mut seven = felt_bytes("0x7");
+        let mut result = [0u8; 32];
+        assert_eq!(
+            pedersen_hash_felt_u64(seven.as_mut_ptr(), 2, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, expected.to_bytes_be());
+    }
+
+    #[test]
+    fn test_pedersen_hash_acc() {
+        let elements = [7u8, 2, 9].map(Felt::from);
+        let mut acc = [0u8; 32];
+        for element in elements {
+            assert_eq!(
+                pedersen_hash_acc(acc.as_mut_ptr(), element.to_bytes_be().as_mut_ptr()),
+                SUCCESS
+            );
+        }
+        let expected = elements.iter().fold(Felt::ZERO, |acc, element| {
+            starknet_crypto_pedersen_hash(&acc, element)
+        });
+        assert_eq!(acc, expected.to_bytes_be());
+
+        // The same buffer as both operands hashes the accumulator with itself
+        let mut both = acc;
+        assert_eq!(
+            pedersen_hash_acc(both.as_mut_ptr(), both.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            both,
+            starknet_crypto_pedersen_hash(&expected, &expected).to_bytes_be()
+        );
+
+        assert_eq!(
+            pedersen_hash_acc(acc.as_mut_ptr(), [0xffu8; 32].as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(acc, expected.to_bytes_be());
+    }
+
+    #[test]
+    fn test_pedersen_hash_on_elements() {
+        let a = Felt::from_hex("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb")
+            .unwrap();
+        let b = Felt::from_hex("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a")
+            .unwrap();
+        let elements = [a.to_bytes_be(), b.to_bytes_be()].concat();
+        let mut result = [0u8; 32];
+        assert_eq!(
+            pedersen_hash_on_elements(elements.as_ptr(), 2, result.as_mut_ptr()),
+            SUCCESS
+        );
+
+        let expected = starknet_crypto_pedersen_hash(
+            &starknet_crypto_pedersen_hash(&starknet_crypto_pedersen_hash(&Felt::ZERO, &a), &b),
+            &Felt::TWO,
+        );
+        assert_eq!(result, expected.to_bytes_be());
+    }
+
+    #[test]
+    fn test_pedersen_hash_on_elements_empty() {
+        // The empty chain is H(0, 0)
+        let mut result = [0u8; 32];
+        assert_eq!(
+            pedersen_hash_on_elements(core::ptr::null(), 0, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            felt_bytes("0x49ee3eba8c1600700ee1b87eb599f16716b0b1022947733551fde4050ca6804")
+        );
+    }
+
+    #[test]
+    fn test_get_public_key() {
+        let mut private_key =
+            felt_bytes("0x03c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc");
+        let mut public_key = [0u8; 32];
+        assert_eq!(
+            get_public_key(private_key.as_mut_ptr(), public_key.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            public_key,
+            felt_bytes("0x077a3b314db07c45076d11f62b6f9e748a39790441823307743cf00d6597ea43")
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_rfc6979_generate_k() {
+        // Test data generated from `cairo-lang`
+        let mut message =
+            felt_bytes("0x0080977da1148412a7976215729d396b72aec9e955498757a7b859281354b4b1");
+        let mut private_key =
+            felt_bytes("0x03fa56dcdbe2fb6769a83786469faf589a3d1e31c66db8b0432f741a38cdeed1");
+        let mut seed =
+            felt_bytes("0x0776cc1aa4c66417a4923768b9d4a7cfca731e862e4972ed930d8f2ad45d352b");
+        let mut k = [0u8; 32];
+        assert_eq!(
+            rfc6979_generate_k(
+                message.as_mut_ptr(),
+                private_key.as_mut_ptr(),
+                seed.as_mut_ptr(),
+                k.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            k,
+            felt_bytes("0x0013480c97bb5861404aa16e1f97a99411ba8f4039b2d54de839dea5c9f0af47")
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_rfc6979_generate_k_without_seed() {
+        let mut message = felt_bytes("0x1");
+        let mut private_key = felt_bytes("0x2");
+        let mut k = [0u8; 32];
+        assert_eq!(
+            rfc6979_generate_k(
+                message.as_mut_ptr(),
+                private_key.as_mut_ptr(),
+                core::ptr::null_mut(),
+                k.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        let expected = starknet_crypto_rfc6979_generate_k(&Felt::ONE, &Felt::TWO, None);
+        assert_eq!(k, expected.to_bytes_be());
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_ecdsa_sign() {
+        let mut private_key =
+            felt_bytes("0x1e2feb89414c343c1027c4d1c386bbc4cd613e30d8f16adf91b7584a2265b1f");
+        let mut message =
+            felt_bytes("0x1e542e2da71b3f5d7b4e9d329b4d30ac0b5d6f266ebef7364bf61c39aac35d0");
+        let mut k = felt_bytes("0x3");
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        assert_eq!(
+            ecdsa_sign(
+                private_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                k.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        let mut public_key = [0u8; 32];
+        assert_eq!(
+            get_public_key(private_key.as_mut_ptr(), public_key.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            verify_signature(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_ecdsa_sign_seeded() {
+        let mut private_key = felt_bytes("0x1");
+        let mut message = felt_bytes("0x2");
+        let mut public_key = [0u8; 32];
+        get_public_key(private_key.as_mut_ptr(), public_key.as_mut_ptr());
+        let mut sign_seeded = |seed: &str| {
+            let (mut seed, mut r, mut s) = (felt_bytes(seed), [0u8; 32], [0u8; 32]);
+            let status = ecdsa_sign_seeded(
+                private_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                seed.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            );
+            assert_eq!(status, SUCCESS);
+            (r, s)
+        };
+        let (mut r, mut s) = sign_seeded("0x1");
+        assert_eq!(sign_seeded("0x1"), (r, s));
+        assert_ne!(sign_seeded("0x2").0, r);
+        assert_eq!(
+            verify_signature(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        // The k is the one of rfc6979_generate_k
+        let expected = sign(
+            &Felt::ONE,
+            &Felt::TWO,
+            &starknet_crypto_rfc6979_generate_k(&Felt::TWO, &Felt::ONE, Some(&Felt::ONE)),
+        )
+        .unwrap();
+        assert_eq!(r, expected.r.to_bytes_be());
+
+        // No k is valid for a zero message hash and a private key multiple of the curve order
+        for mut private_key in [[0u8; 32], EC_ORDER.to_bytes_be()] {
+            let (mut zero, mut r, mut s) = ([0u8; 32], [0u8; 32], [0u8; 32]);
+            assert_eq!(
+                ecdsa_sign_seeded(
+                    private_key.as_mut_ptr(),
+                    zero.as_mut_ptr(),
+                    felt_bytes("0x1").as_mut_ptr(),
+                    r.as_mut_ptr(),
+                    s.as_mut_ptr(),
+                ),
+                ERR_INVALID_K
+            );
+        }
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_ecdsa_sign_errors() {
+        let mut private_key = felt_bytes("0x1");
+        let mut message = felt_bytes("0x1");
+        let mut zero = [0u8; 32];
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        assert_eq!(
+            ecdsa_sign(
+                private_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                zero.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            ERR_INVALID_K
+        );
+
+        // 2^251 is a valid felt but not a valid message hash
+        let mut message_too_large =
+            felt_bytes("0x800000000000000000000000000000000000000000000000000000000000000");
+        let mut k = felt_bytes("0x2");
+        assert_eq!(
+            ecdsa_sign(
+                private_key.as_mut_ptr(),
+                message_too_large.as_mut_ptr(),
+                k.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            ERR_INVALID_MESSAGE_HASH
+        );
+
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            ecdsa_sign(
+                private_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                not_a_felt.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_recover() {
+        let private_key = Felt::ONE;
+        let message = Felt::TWO;
+        let signature = sign(&private_key, &message, &Felt::THREE).unwrap();
+
+        let mut message = message.to_bytes_be();
+        let mut r = signature.r.to_bytes_be();
+        let mut s = signature.s.to_bytes_be();
+        let mut v = signature.v.to_bytes_be();
+        let mut public_key = [0u8; 32];
+        assert_eq!(
+            recover(
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+                v.as_mut_ptr(),
+                public_key.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            public_key,
+            starknet_crypto_get_public_key(&private_key).to_bytes_be()
+        );
+    }
+
+    #[test]
+    fn test_recover_invalid_r() {
+        let mut message = felt_bytes("0x2");
+        let mut r =
+            felt_bytes("0x03ee9bffffffffff26ffffffff60ffffffffffffffffffffffffffff004accff");
+        let mut s =
+            felt_bytes("0x0405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b");
+        let mut v = felt_bytes("0x0");
+        let mut public_key = [0u8; 32];
+        assert_eq!(
+            recover(
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+                v.as_mut_ptr(),
+                public_key.as_mut_ptr(),
+            ),
+            ERR_INVALID_R
+        );
+
+        let mut v = felt_bytes("0x2");
+        let mut r = felt_bytes("0x1");
+        assert_eq!(
+            recover(
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+                v.as_mut_ptr(),
+                public_key.as_mut_ptr(),
+            ),
+            ERR_INVALID_V
+        );
+    }
+
+    #[test]
+    fn test_poseidon_hash_many_empty() {
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_many(core::ptr::null(), 0, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, hades::poseidon_hash_many(&[]).to_bytes_be());
+    }
+
+    #[test]
+    fn test_invalid_felt() {
+        // P itself is not a felt
+        let mut p = felt_bytes("0x800000000000011000000000000000000000000000000000000000000000000");
+        p[31] = 1;
+        let mut one = felt_bytes("0x1");
+        let mut result = [0u8; 32];
+        assert_eq!(
+            pedersen_hash(p.as_mut_ptr(), one.as_mut_ptr(), result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(
+            poseidon_permute(one.as_mut_ptr(), one.as_mut_ptr(), p.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        let elements = [one, p].concat();
+        assert_eq!(
+            poseidon_hash_many(elements.as_ptr(), 2, result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        let mut result = [0u8; 32];
+        assert_eq!(
+            poseidon_hash_many(core::ptr::null(), 1, result.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+        let one = felt_bytes("0x1");
+        assert_eq!(
+            pedersen_hash_on_elements(one.as_ptr(), usize::MAX, result.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_errors() {
+        let mut public_key =
+            felt_bytes("0x077a3b314db07c45076d11f62b6f9e748a39790441823307743cf00d6597ea43");
+        let mut message = felt_bytes("0x2");
+        let mut zero = felt_bytes("0x0");
+        let mut one = felt_bytes("0x1");
+        assert_eq!(
+            verify_signature(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                zero.as_mut_ptr(),
+                one.as_mut_ptr(),
+            ),
+            ERR_INVALID_R
+        );
+        assert_eq!(
+            verify_signature(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                one.as_mut_ptr(),
+                zero.as_mut_ptr(),
+            ),
+            ERR_INVALID_S
+        );
+        assert_eq!(
+            verify_signature(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                one.as_mut_ptr(),
+                one.as_mut_ptr(),
+            ),
+            ERR_INVALID_SIGNATURE
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_verify_signature_xonly() {
+        // The private keys k and n - k share the x coordinate of their public keys, with opposite
+        // y coordinates
+        let private_key = Felt::from(0x1234u64);
+        let public_key = starknet_crypto_get_public_key(&private_key);
+        assert_eq!(
+            starknet_crypto_get_public_key(&(EC_ORDER - private_key)),
+            public_key
+        );
+        let message = Felt::from(0xabcu64);
+        for private_key in [private_key, EC_ORDER - private_key] {
+            let signature = sign(&private_key, &message, &Felt::THREE).unwrap();
+            let status = verify_signature_xonly(
+                public_key.to_bytes_be().as_mut_ptr(),
+                message.to_bytes_be().as_mut_ptr(),
+                signature.r.to_bytes_be().as_mut_ptr(),
+                signature.s.to_bytes_be().as_mut_ptr(),
+            );
+            assert_eq!(status, SUCCESS);
+            let status = verify_signature_xonly(
+                public_key.to_bytes_be().as_mut_ptr(),
+                (message + Felt::ONE).to_bytes_be().as_mut_ptr(),
+                signature.r.to_bytes_be().as_mut_ptr(),
+                signature.s.to_bytes_be().as_mut_ptr(),
+            );
+            assert_eq!(status, ERR_INVALID_SIGNATURE);
+        }
+        let off_curve = (1u64..)
+            .map(Felt::from)
+            .find(|x| starknet_types_core::curve::AffinePoint::new_from_x(x, false).is_none())
+            .unwrap();
+        assert_eq!(
+            verify_signature_xonly(
+                off_curve.to_bytes_be().as_mut_ptr(),
+                message.to_bytes_be().as_mut_ptr(),
+                Felt::ONE.to_bytes_be().as_mut_ptr(),
+                Felt::ONE.to_bytes_be().as_mut_ptr(),
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_strict() {
+        let mut public_key =
+            felt_bytes("0x01ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca");
+        let mut message = felt_bytes("0x2");
+        let mut r =
+            felt_bytes("0x0411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20");
+        let s =
+            Felt::from_hex("0x0405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b")
+                .unwrap();
+        // This s is above half the order, n - s below it, computed as felts since n is lower than
+        // the STARK prime
+        let mut high_s = s.to_bytes_be();
+        let mut low_s = (EC_ORDER - s).to_bytes_be();
+        let mut verify = |verify_fn: extern "C" fn(Bytes, Bytes, Bytes, Bytes) -> i32,
+                          r: &mut [u8; 32],
+                          s: &mut [u8; 32]| {
+            verify_fn(
+                public_key.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            )
+        };
+
+        assert_eq!(verify(verify_signature, &mut r, &mut low_s), SUCCESS);
+        assert_eq!(verify(verify_signature, &mut r, &mut high_s), SUCCESS);
+        assert_eq!(verify(verify_signature_strict, &mut r, &mut low_s), SUCCESS);
+        assert_eq!(
+            verify(verify_signature_strict, &mut r, &mut high_s),
+            ERR_INVALID_S
+        );
+
+        let mut order = EC_ORDER.to_bytes_be();
+        assert_eq!(
+            verify(verify_signature_strict, &mut order, &mut low_s),
+            ERR_INVALID_R
+        );
+        let mut one = felt_bytes("0x1");
+        assert_eq!(
+            verify(verify_signature_strict, &mut r, &mut one),
+            ERR_INVALID_SIGNATURE
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_over_elements() {
+        let private_key = Felt::from(42u8);
+        let mut public_key = starknet_crypto_get_public_key(&private_key).to_bytes_be();
+        let elements = [1u8, 2, 3].map(Felt::from);
+        let elements_bytes: Vec<u8> = elements.iter().flat_map(|e| e.to_bytes_be()).collect();
+        for (hash_kind, message) in [
+            (HASH_PEDERSEN, hash_on_elements(&elements)),
+            (HASH_POSEIDON, hades::poseidon_hash_many(&elements)),
+        ] {
+            let signature = sign(&private_key, &message, &Felt::THREE).unwrap();
+            let (mut r, mut s) = (signature.r.to_bytes_be(), signature.s.to_bytes_be());
+            let mut verify = |count, hash_kind| {
+                verify_signature_over_elements(
+                    public_key.as_mut_ptr(),
+                    elements_bytes.as_ptr(),
+                    count,
+                    r.as_mut_ptr(),
+                    s.as_mut_ptr(),
+                    hash_kind,
+                )
+            };
+            assert_eq!(verify(3, hash_kind), SUCCESS);
+            assert_eq!(verify(2, hash_kind), ERR_INVALID_SIGNATURE);
+            assert_eq!(verify(3, 1 - hash_kind), ERR_INVALID_SIGNATURE);
+            assert_eq!(verify(3, 2), ERR_INVALID_HASH_KIND);
+        }
+    }
+
+    #[test]
+    fn test_signature_compact() {
+        let mut r =
+            felt_bytes("0x0411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20");
+        let mut s =
+            felt_bytes("0x0405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b");
+        let mut compact = [0u8; 64];
+        assert_eq!(
+            signature_to_compact(r.as_mut_ptr(), s.as_mut_ptr(), compact.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(compact[..32], r);
+        assert_eq!(compact[32..], s);
+
+        let (mut decoded_r, mut decoded_s) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            signature_from_compact(
+                compact.as_ptr(),
+                decoded_r.as_mut_ptr(),
+                decoded_s.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!((decoded_r, decoded_s), (r, s));
+    }
+
+    #[test]
+    fn test_signature_compact_out_of_range() {
+        let mut valid = felt_bytes("0x1");
+        let mut zero = [0u8; 32];
+        // 2^251
+        let mut too_big =
+            felt_bytes("0x800000000000000000000000000000000000000000000000000000000000000");
+        let mut compact = [0u8; 64];
+        assert_eq!(
+            signature_to_compact(zero.as_mut_ptr(), valid.as_mut_ptr(), compact.as_mut_ptr()),
+            ERR_INVALID_R
+        );
+        assert_eq!(
+            signature_to_compact(
+                valid.as_mut_ptr(),
+                too_big.as_mut_ptr(),
+                compact.as_mut_ptr()
+            ),
+            ERR_INVALID_S
+        );
+
+        compact[..32].copy_from_slice(&valid);
+        compact[32..].copy_from_slice(&too_big);
+        let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            signature_from_compact(compact.as_ptr(), r.as_mut_ptr(), s.as_mut_ptr()),
+            ERR_INVALID_S
+        );
+        assert_eq!(r, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_abi_version() {
+        assert_eq!(starknet_crypto_abi_version() >> 16, ABI_MAJOR);
+        assert_eq!(starknet_crypto_abi_version() & 0xffff, ABI_MINOR);
+    }
+
+    #[test]
+    fn test_build_info() {
+        let mut info = [0u8; 256];
+        let mut info_len = 0;
+        assert_eq!(
+            starknet_crypto_build_info(info.as_mut_ptr(), info.len(), &mut info_len),
+            SUCCESS
+        );
+        let info = core::str::from_utf8(&info[..info_len]).unwrap();
+        assert!(info.starts_with(&alloc::format!(
+            "version={} starknet-crypto=0.8.",
+            env!("CARGO_PKG_VERSION")
+        )));
+        #[cfg(feature = "std")]
+        assert!(info.contains(" features=std"));
+        assert!(info.contains(" backend="));
+
+        // The full length is reported past a truncated copy
+        let mut truncated = [0u8; 8];
+        let mut truncated_len = 0;
+        assert_eq!(
+            starknet_crypto_build_info(truncated.as_mut_ptr(), 8, &mut truncated_len),
+            SUCCESS
+        );
+        assert_eq!(truncated_len, info_len);
+        assert_eq!(&truncated, b"version=");
+    }
+
+    #[test]
+    fn test_selftest() {
+        assert_eq!(starknet_crypto_selftest(), SUCCESS);
+    }
+
+    #[test]
+    fn test_init() {
+        assert_eq!(starknet_crypto_init(), SUCCESS);
+        assert_eq!(starknet_crypto_init(), SUCCESS);
+        #[cfg(feature = "std")]
+        assert!(pedersen::TABLES.get().is_some());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ffi_guard_catches_panics() {
+        assert_eq!(ffi_guard(|| panic!("boom {}", 42)), ERR_PANIC);
+
+        let mut message = [0u8; 16];
+        let mut message_len = 0;
+        assert_eq!(
+            starknet_crypto_last_panic_message(message.as_mut_ptr(), 16, &mut message_len),
+            SUCCESS
+        );
+        assert_eq!(&message[..message_len], b"boom 42");
+
+        // The message is truncated to the buffer, but its full length is reported
+        assert_eq!(ffi_guard(|| panic!("static message")), ERR_PANIC);
+        let mut message = [0u8; 6];
+        assert_eq!(
+            starknet_crypto_last_panic_message(message.as_mut_ptr(), 6, &mut message_len),
+            SUCCESS
+        );
+        assert_eq!(&message, b"static");
+        assert_eq!(message_len, 14);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_last_error() {
+        let mut message = [0u8; 64];
+        let mut message_len = 0;
+        let mut last_error = || {
+            assert_eq!(
+                starknet_crypto_last_error(message.as_mut_ptr(), 64, &mut message_len),
+                SUCCESS
+            );
+            String::from_utf8(message[..message_len].to_vec()).unwrap()
+        };
+        assert_eq!(last_error(), "");
+
+        assert_eq!(ffi_guard(|| ERR_INVALID_FELT), ERR_INVALID_FELT);
+        assert_eq!(
+            last_error(),
+            "the bytes do not encode a felt lower than the STARK prime"
+        );
+        // A success keeps the message of the last error
+        assert_eq!(ffi_guard(|| SUCCESS), SUCCESS);
+        assert_eq!(
+            last_error(),
+            "the bytes do not encode a felt lower than the STARK prime"
+        );
+
+        assert_eq!(ffi_guard(|| panic!("boom")), ERR_PANIC);
+        assert_eq!(last_error(), "panicked: boom");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_calls() {
+        let _store = patricia::tests::STORE.lock();
+        assert!(starknet_crypto_is_threadsafe());
+        // The threads race on the lazy initializations, the Patricia node store and the batches,
+        // and each one must only ever see its own last error
+        std::thread::scope(|scope| {
+            for thread in 0..8u64 {
+                scope.spawn(move || {
+                    let felt = Felt::from(thread);
+                    let status = [ERR_INVALID_FELT, ERR_INVALID_POINT][thread as usize % 2];
+                    for i in 0..10u64 {
+                        assert_eq!(starknet_crypto_init(), SUCCESS);
+                        assert_eq!(starknet_crypto_selftest(), SUCCESS);
+
+                        let other = Felt::from(i);
+                        let mut result = [0u8; 32];
+                        assert_eq!(
+                            pedersen_hash(
+                                felt.to_bytes_be().as_mut_ptr(),
+                                other.to_bytes_be().as_mut_ptr(),
+                                result.as_mut_ptr()
+                            ),
+                            SUCCESS
+                        );
+                        let expected = starknet_crypto_pedersen_hash(&felt, &other);
+                        assert_eq!(result, expected.to_bytes_be());
+                        let pairs = [felt.to_bytes_be(), other.to_bytes_be()].concat();
+                        assert_eq!(
+                            batch::pedersen_hash_batch(pairs.as_ptr(), 1, result.as_mut_ptr()),
+                            SUCCESS
+                        );
+                        assert_eq!(result, expected.to_bytes_be());
+
+                        let entries = [Felt::from(thread + 1), other + Felt::ONE]
+                            .map(|felt| felt.to_bytes_be())
+                            .concat();
+                        let mut root = [0u8; 32];
+                        assert_eq!(
+                            patricia::patricia_root(entries.as_ptr(), 1, root.as_mut_ptr()),
+                            SUCCESS
+                        );
+                        assert_eq!(
+                            patricia::patricia_update(
+                                root.as_mut_ptr(),
+                                entries.as_ptr(),
+                                1,
+                                result.as_mut_ptr()
+                            ),
+                            SUCCESS
+                        );
+                        assert_eq!(result, root);
+
+                        assert_eq!(ffi_guard(|| status), status);
+                        let mut message = [0u8; 64];
+                        let mut message_len = 0;
+                        assert_eq!(
+                            starknet_crypto_last_error(message.as_mut_ptr(), 64, &mut message_len),
+                            SUCCESS
+                        );
+                        assert_eq!(&message[..message_len], error_message(status).as_bytes());
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let null = core::ptr::null_mut();
+        let mut felt = [0u8; 32];
+        assert_eq!(
+            pedersen_hash(null, felt.as_mut_ptr(), null),
+            ERR_NULL_POINTER
+        );
+        assert_eq!(
+            verify_signature(
+                felt.as_mut_ptr(),
+                felt.as_mut_ptr(),
+                felt.as_mut_ptr(),
+                null
+            ),
+            ERR_NULL_POINTER
+        );
+        assert_eq!(
+            batch::pedersen_hash_batch([0u8; 64].as_ptr(), 1, null),
+            ERR_INVALID_LENGTH
+        );
+        // The seed of rfc6979_generate_k is optional
+        #[cfg(feature = "signing")]
+        {
+            let mut k = [0u8; 32];
+            felt[31] = 1;
+            assert_eq!(
+                rfc6979_generate_k(felt.as_mut_ptr(), felt.as_mut_ptr(), null, k.as_mut_ptr()),
+                SUCCESS
+            );
+        }
+    }
+
+    #[cfg(feature = "check-alignment")]
+    #[test]
+    fn test_misaligned_pointers() {
+        let mut state = [0u64; 26];
+        let misaligned = unsafe { (state.as_mut_ptr() as *mut u8).add(1) } as *mut u64;
+        assert_eq!(keccak::keccak_f1600(misaligned), ERR_MISALIGNED_POINTER);
+        assert_eq!(keccak::keccak_f1600(state.as_mut_ptr()), SUCCESS);
     }
 }
@@ -0,0 +1,304 @@
+// Variants of the hot FFI functions over plain integer limbs.
+//
+// A felt is four little endian 64 bits limbs holding its canonical (non Montgomery) value, which
+// is the natural integer representation on both sides and keeps the pointers `u64` aligned.
+// Whole arrays convert to and from the 32 bytes big endian felts of the other functions in one
+// call, for the program outputs and public inputs.
+use starknet_crypto_rs::pedersen_hash;
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    ecdsa::verify,
+    felt::MODULUS_BYTES,
+    ffi_guard,
+    hades::{poseidon_hash, poseidon_permute_comp},
+    slice_from_ptr, status_from_verification, try_felt_from_array, ERR_INVALID_FELT,
+    ERR_INVALID_LENGTH, SUCCESS,
+};
+
+// Returns `None` when the limbs value is not lower than the STARK prime.
+fn try_field_element_from_limbs(limbs: *const u64) -> Option<Felt> {
+    let limbs = unsafe { core::ptr::read_unaligned(limbs as *const [u64; 4]) };
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).rev().zip(limbs) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    try_felt_from_array(&bytes)
+}
+
+fn limbs_from_field_element(felt: Felt, limbs: *mut u64) {
+    let bytes = felt.to_bytes_be();
+    let mut array = [0u64; 4];
+    for (limb, chunk) in array.iter_mut().zip(bytes.chunks_exact(8).rev()) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    unsafe { core::ptr::write_unaligned(limbs as *mut [u64; 4], array) };
+}
+
+/// Like `pedersen_hash`, with felts as four little endian 64 bit limbs holding their canonical
+/// value
+#[no_mangle]
+pub extern "C" fn pedersen_hash_limbs(a: *const u64, b: *const u64, result: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result);
+        let (Some(a), Some(b)) = (
+            try_field_element_from_limbs(a),
+            try_field_element_from_limbs(b),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        limbs_from_field_element(pedersen_hash(&a, &b), result);
+        SUCCESS
+    })
+}
+
+/// Like `poseidon_hash`, with felts as four little endian 64 bit limbs holding their canonical
+/// value
+#[no_mangle]
+pub extern "C" fn poseidon_hash_limbs(x: *const u64, y: *const u64, result: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x, y, result);
+        let (Some(x), Some(y)) = (
+            try_field_element_from_limbs(x),
+            try_field_element_from_limbs(y),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        limbs_from_field_element(poseidon_hash(x, y), result);
+        SUCCESS
+    })
+}
+
+/// Like `poseidon_permute`, over a state of three contiguous felts as four little endian 64 bit
+/// limbs holding their canonical value (12 limbs)
+#[no_mangle]
+pub extern "C" fn poseidon_permute_limbs(state: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(state);
+        // The state is three contiguous felts, permuted in place
+        let (Some(first), Some(second), Some(third)) = (
+            try_field_element_from_limbs(state),
+            try_field_element_from_limbs(unsafe { state.add(4) }),
+            try_field_element_from_limbs(unsafe { state.add(8) }),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let mut state_array = [first, second, third];
+        poseidon_permute_comp(&mut state_array);
+        for (i, felt) in state_array.into_iter().enumerate() {
+            limbs_from_field_element(felt, unsafe { state.add(4 * i) });
+        }
+        SUCCESS
+    })
+}
+
+/// Like `verify_signature`, with felts as four little endian 64 bit limbs holding their canonical
+/// value
+#[no_mangle]
+pub extern "C" fn verify_signature_limbs(
+    public_key: *const u64,
+    message: *const u64,
+    r: *const u64,
+    s: *const u64,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(public_key, message, r, s);
+        let (Some(public_key), Some(message), Some(r), Some(s)) = (
+            try_field_element_from_limbs(public_key),
+            try_field_element_from_limbs(message),
+            try_field_element_from_limbs(r),
+            try_field_element_from_limbs(s),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        status_from_verification(verify(&public_key, &message, &r, &s))
+    })
+}
+
+// Converts `count` felts of `units.0` input units into felts of `units.1` output units, checking
+// the big endian bytes of each one returned by the conversion against the STARK prime.
+fn convert<T, U>(
+    input: *const T,
+    count: usize,
+    output: *mut U,
+    units: (usize, usize),
+    convert: impl Fn(&[T], &mut [U]) -> [u8; 32],
+) -> i32 {
+    let (Some(input_len), Some(output_len)) =
+        (count.checked_mul(units.0), count.checked_mul(units.1))
+    else {
+        return ERR_INVALID_LENGTH;
+    };
+    let input = match slice_from_ptr(input, input_len) {
+        Ok(input) => input,
+        Err(status) => return status,
+    };
+    if count == 0 {
+        return SUCCESS;
+    }
+    if output.is_null() || output_len > isize::MAX as usize / core::mem::size_of::<U>() {
+        return ERR_INVALID_LENGTH;
+    }
+    let output = unsafe { core::slice::from_raw_parts_mut(output, output_len) };
+    let mut valid = true;
+    for (felt, converted) in input
+        .chunks_exact(units.0)
+        .zip(output.chunks_exact_mut(units.1))
+    {
+        valid &= convert(felt, converted) < MODULUS_BYTES;
+    }
+    if valid {
+        SUCCESS
+    } else {
+        ERR_INVALID_FELT
+    }
+}
+
+/// Converts `count` contiguous felts as four little endian 64 bit limbs into contiguous 32 bytes
+/// big endian felts. Returns ERR_INVALID_FELT when one of them is not lower than the STARK prime,
+/// after converting all of them
+#[no_mangle]
+pub extern "C" fn felts_to_bytes_be(limbs: *const u64, count: usize, bytes: *mut u8) -> i32 {
+    ffi_guard(|| {
+        convert(limbs, count, bytes, (4, 32), |limbs, bytes| {
+            for (chunk, limb) in bytes.chunks_exact_mut(8).rev().zip(limbs) {
+                chunk.copy_from_slice(&limb.to_be_bytes());
+            }
+            bytes.try_into().unwrap()
+        })
+    })
+}
+
+/// Converts `count` contiguous 32 bytes big endian felts into contiguous felts as four little
+/// endian 64 bit limbs, like felts_to_bytes_be the other way around
+#[no_mangle]
+pub extern "C" fn felts_from_bytes_be(bytes: *const u8, count: usize, limbs: *mut u64) -> i32 {
+    ffi_guard(|| {
+        convert(bytes, count, limbs, (32, 4), |bytes, limbs| {
+            for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8).rev()) {
+                *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+            }
+            bytes.try_into().unwrap()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt_limbs(hex: &str) -> [u64; 4] {
+        let mut limbs = [0u64; 4];
+        limbs_from_field_element(Felt::from_hex(hex).unwrap(), limbs.as_mut_ptr());
+        limbs
+    }
+
+    #[test]
+    fn test_limbs_are_little_endian() {
+        let limbs = felt_limbs("0x300000000000000020000000000000001");
+        assert_eq!(limbs, [1, 2, 3, 0]);
+        assert_eq!(
+            try_field_element_from_limbs(limbs.as_ptr()).unwrap(),
+            Felt::from_hex("0x300000000000000020000000000000001").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pedersen_hash_limbs() {
+        let a = felt_limbs("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb");
+        let b = felt_limbs("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a");
+        let mut result = [0u64; 4];
+        assert_eq!(
+            pedersen_hash_limbs(a.as_ptr(), b.as_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            felt_limbs("0x30e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_hash_limbs() {
+        let x = felt_limbs("0x1");
+        let y = felt_limbs("0x2");
+        let mut result = [0u64; 4];
+        assert_eq!(
+            poseidon_hash_limbs(x.as_ptr(), y.as_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            try_field_element_from_limbs(result.as_ptr()).unwrap(),
+            poseidon_hash(Felt::ONE, Felt::TWO)
+        );
+    }
+
+    #[test]
+    fn test_poseidon_permute_limbs() {
+        let mut state = [0u64; 12];
+        state[4] = 1;
+        state[8] = 2;
+        assert_eq!(poseidon_permute_limbs(state.as_mut_ptr()), SUCCESS);
+
+        let mut expected = [Felt::ZERO, Felt::ONE, Felt::TWO];
+        poseidon_permute_comp(&mut expected);
+        for (i, felt) in expected.into_iter().enumerate() {
+            assert_eq!(
+                try_field_element_from_limbs(state[4 * i..].as_ptr()).unwrap(),
+                felt
+            );
+        }
+    }
+
+    #[test]
+    fn test_felt_arrays() {
+        let felts = [
+            Felt::ONE,
+            Felt::from_hex("0x300000000000000020000000000000001").unwrap(),
+            Felt::MAX,
+        ];
+        let limbs: Vec<u64> = felts
+            .iter()
+            .flat_map(|felt| {
+                let mut limbs = [0u64; 4];
+                limbs_from_field_element(*felt, limbs.as_mut_ptr());
+                limbs
+            })
+            .collect();
+        let expected: Vec<u8> = felts.iter().flat_map(|felt| felt.to_bytes_be()).collect();
+
+        let mut bytes = [0u8; 96];
+        assert_eq!(
+            felts_to_bytes_be(limbs.as_ptr(), 3, bytes.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(bytes[..], expected[..]);
+        let mut back = [0u64; 12];
+        assert_eq!(
+            felts_from_bytes_be(bytes.as_ptr(), 3, back.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(back[..], limbs[..]);
+        assert_eq!(
+            felts_to_bytes_be(core::ptr::null(), 0, core::ptr::null_mut()),
+            SUCCESS
+        );
+
+        // The STARK prime in the middle of the array, the felts around it are still converted
+        bytes[32..64].copy_from_slice(&MODULUS_BYTES);
+        let mut converted = [0u64; 12];
+        assert_eq!(
+            felts_from_bytes_be(bytes.as_ptr(), 3, converted.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(converted[8..], limbs[8..]);
+        assert_eq!(
+            felts_to_bytes_be(limbs.as_ptr(), 3, core::ptr::null_mut()),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            felts_to_bytes_be(limbs.as_ptr(), usize::MAX, bytes.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+    }
+}
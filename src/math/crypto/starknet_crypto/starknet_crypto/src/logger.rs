@@ -0,0 +1,83 @@
+// A logging callback set by the embedder, receiving the internal events of the library: the lazy
+// initializations, the fallbacks to slower paths and the scheduling of the batches.
+//
+// The events are only formatted when a logger is set, so they cost an atomic load otherwise. The
+// logger is global and called from whichever thread the event happens on, including the threads of
+// the pool of the batch functions.
+use alloc::string::String;
+use core::{
+    fmt::{Arguments, Write},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::{ffi_guard, SUCCESS};
+
+/// A logging callback, receiving a LOG_ level and a UTF-8 message which is not NUL terminated and
+/// only valid for the duration of the call. NULL for no logger
+pub type Logger = Option<extern "C" fn(level: u32, message: *const u8, len: usize)>;
+
+static LOGGER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Sends the internal events of the library to a logger, or stops logging them for NULL (the
+/// default). The logger must be callable from any thread
+#[no_mangle]
+pub extern "C" fn starknet_crypto_set_logger(logger: Logger) -> i32 {
+    ffi_guard(|| {
+        let logger = logger.map_or(core::ptr::null_mut(), |logger| logger as *mut ());
+        LOGGER.store(logger, Ordering::Release);
+        SUCCESS
+    })
+}
+
+// Sends an event to the logger when one is set.
+pub(crate) fn log(level: u32, args: Arguments) {
+    let logger = LOGGER.load(Ordering::Acquire);
+    if logger.is_null() {
+        return;
+    }
+    // Only starknet_crypto_set_logger stores into LOGGER, and always a non NULL Logger
+    let logger: extern "C" fn(u32, *const u8, usize) = unsafe { core::mem::transmute(logger) };
+    let mut message = String::new();
+    let _ = message.write_fmt(args);
+    logger(level, message.as_ptr(), message.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LOG_DEBUG, LOG_WARNING};
+    use std::sync::Mutex;
+
+    static EVENTS: Mutex<Vec<(u32, String)>> = Mutex::new(Vec::new());
+
+    extern "C" fn record(level: u32, message: *const u8, len: usize) {
+        let message = unsafe { core::slice::from_raw_parts(message, len) };
+        let message = String::from_utf8(message.to_vec()).unwrap();
+        EVENTS.lock().unwrap().push((level, message));
+    }
+
+    #[test]
+    fn test_logger() {
+        // Other tests may log concurrently, so only the events of this test are looked at
+        assert_eq!(starknet_crypto_set_logger(Some(record)), SUCCESS);
+        log(LOG_DEBUG, format_args!("logger test {}", 1));
+        log(LOG_WARNING, format_args!("logger test {}", 2));
+        assert_eq!(starknet_crypto_set_logger(None), SUCCESS);
+        log(LOG_DEBUG, format_args!("logger test {}", 3));
+
+        let events: Vec<_> = EVENTS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, message)| message.starts_with("logger test"))
+            .cloned()
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (LOG_DEBUG, String::from("logger test 1")),
+                (LOG_WARNING, String::from("logger test 2")),
+            ]
+        );
+    }
+}
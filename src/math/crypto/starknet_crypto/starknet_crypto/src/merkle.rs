@@ -0,0 +1,581 @@
+// Roots of binary Merkle trees over arrays of felts.
+//
+// The leaves are padded with zeros up to the next power of two, and each node is the hash of its
+// two children. The root of a single leaf is the leaf itself, and the root of no leaves is zero.
+// With a thread pool, each thread reduces a contiguous chunk of the leaves to the root of its
+// subtree before the calling thread combines the subtree roots. In a proof, bit i of the index of
+// the leaf (least significant bit first) tells whether the node at depth i from the leaves is a
+// right child. The cap of height h is the 2^h roots of the subtrees at depth h from the root, the
+// leaves being padded to at least 2^h, so that segments hashed apart combine into the root later.
+// A memory segment commits as the root of the tree whose leaves are the hashes of its (address,
+// value) pairs, so that both the pairs and the tree hash on the thread pool.
+use alloc::vec::Vec;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    batch::{map_batch, pedersen_hash_pair, thread_count},
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes,
+    hades::poseidon_hash,
+    logger::log,
+    slice_from_ptr, try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_HASH_KIND,
+    ERR_INVALID_LENGTH, ERR_INVALID_PROOF, HASH_PEDERSEN, HASH_POSEIDON, LOG_DEBUG, SUCCESS,
+};
+
+type NodeHash = fn(&Felt, &Felt) -> Felt;
+
+fn poseidon_node_hash(left: &Felt, right: &Felt) -> Felt {
+    poseidon_hash(*left, *right)
+}
+
+fn node_hash(hash_kind: u32) -> Result<NodeHash, i32> {
+    match hash_kind {
+        HASH_PEDERSEN => Ok(pedersen_hash_pair),
+        HASH_POSEIDON => Ok(poseidon_node_hash),
+        _ => Err(ERR_INVALID_HASH_KIND),
+    }
+}
+
+// Reduces a power of two number of nodes to the root of their subtree, level by level.
+fn subtree_root(nodes: &[Felt], hash: &impl Fn(&Felt, &Felt) -> Felt) -> Felt {
+    let mut level = nodes.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| hash(&pair[0], &pair[1]))
+            .collect();
+    }
+    level[0]
+}
+
+fn merkle_root(mut leaves: Vec<Felt>, hash: impl Fn(&Felt, &Felt) -> Felt + Sync + Send) -> Felt {
+    if leaves.is_empty() {
+        return Felt::ZERO;
+    }
+    leaves.resize(leaves.len().next_power_of_two(), Felt::ZERO);
+
+    // A power of two number of subtrees, one per thread, so that they all have the same height
+    let subtrees = thread_count().next_power_of_two().min(leaves.len());
+    let subtree_len = leaves.len() / subtrees;
+    log(
+        LOG_DEBUG,
+        format_args!(
+            "splitting a Merkle tree of {} leaves into {subtrees} subtrees",
+            leaves.len()
+        ),
+    );
+    let roots = map_batch(subtrees, |i| {
+        subtree_root(&leaves[i * subtree_len..(i + 1) * subtree_len], &hash)
+    });
+    subtree_root(&roots, &hash)
+}
+
+// The roots of the `cap_len` subtrees at the depth of the cap, in order.
+fn subtree_roots(
+    mut leaves: Vec<Felt>,
+    cap_len: usize,
+    hash: impl Fn(&Felt, &Felt) -> Felt + Sync + Send,
+) -> Vec<Felt> {
+    if leaves.is_empty() {
+        return alloc::vec![Felt::ZERO; cap_len];
+    }
+    leaves.resize(leaves.len().next_power_of_two().max(cap_len), Felt::ZERO);
+    let subtree_len = leaves.len() / cap_len;
+    map_batch(cap_len, |i| {
+        subtree_root(&leaves[i * subtree_len..(i + 1) * subtree_len], &hash)
+    })
+}
+
+/// Computes the root of the binary Merkle tree with Pedersen nodes over `count` contiguous leaves,
+/// padded with zeros up to the next power of two, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn pedersen_merkle_root(leaves: *const u8, count: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let leaves = match field_elements_from_bytes(leaves, count) {
+            Ok(leaves) => leaves,
+            Err(status) => return status,
+        };
+        bytes_from_field_element(merkle_root(leaves, pedersen_hash_pair), result);
+        SUCCESS
+    })
+}
+
+/// Computes the root of the binary Merkle tree with Poseidon nodes over `count` contiguous leaves,
+/// padded with zeros up to the next power of two, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn poseidon_merkle_root(leaves: *const u8, count: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let leaves = match field_elements_from_bytes(leaves, count) {
+            Ok(leaves) => leaves,
+            Err(status) => return status,
+        };
+        bytes_from_field_element(merkle_root(leaves, poseidon_node_hash), result);
+        SUCCESS
+    })
+}
+
+/// Computes the cap of height `cap_height` of the binary Merkle tree over `count` contiguous
+/// leaves, padded with zeros up to the next power of two and to at least 2^cap_height leaves: the
+/// 2^cap_height contiguous roots of its subtrees at depth cap_height, from the leftmost one, written
+/// into the last argument. The nodes hash with HASH_PEDERSEN or HASH_POSEIDON. The cap of height 0
+/// is the root, and the cap of no leaves is made of zeros
+#[no_mangle]
+pub extern "C" fn merkle_cap(
+    leaves: *const u8,
+    count: usize,
+    cap_height: u32,
+    hash_kind: u32,
+    cap: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        let hash = match node_hash(hash_kind) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let Some(cap_len) = 1usize
+            .checked_shl(cap_height)
+            .filter(|&len| len <= isize::MAX as usize / 32)
+        else {
+            return ERR_INVALID_LENGTH;
+        };
+        if cap.is_null() {
+            return ERR_INVALID_LENGTH;
+        }
+        let leaves = match field_elements_from_bytes(leaves, count) {
+            Ok(leaves) => leaves,
+            Err(status) => return status,
+        };
+        let cap = unsafe { core::slice::from_raw_parts_mut(cap, 32 * cap_len) };
+        for (bytes, root) in cap
+            .chunks_exact_mut(32)
+            .zip(subtree_roots(leaves, cap_len, hash))
+        {
+            bytes.copy_from_slice(&root.to_bytes_be());
+        }
+        SUCCESS
+    })
+}
+
+/// Computes the commitment to a memory segment of `count` contiguous (address, value) pairs of
+/// felts, writing it into the last argument: the root of the binary Merkle tree over the hashes of
+/// the pairs, with HASH_PEDERSEN or HASH_POSEIDON for both the pairs and the nodes. The pairs and
+/// the subtrees hash on the thread pool of the batch functions when there is one
+#[no_mangle]
+pub extern "C" fn hash_memory_segment(
+    pairs: *const u8,
+    count: usize,
+    hash_kind: u32,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let hash = match node_hash(hash_kind) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let pairs = match count
+            .checked_mul(2)
+            .ok_or(ERR_INVALID_LENGTH)
+            .and_then(|felt_count| field_elements_from_bytes(pairs, felt_count))
+        {
+            Ok(pairs) => pairs,
+            Err(status) => return status,
+        };
+        let leaves = map_batch(count, |i| hash(&pairs[2 * i], &pairs[2 * i + 1]));
+        bytes_from_field_element(merkle_root(leaves, hash), result);
+        SUCCESS
+    })
+}
+
+/// Verifies the proof that `leaf` is the leaf at `index` of the Merkle tree with the given root,
+/// from the `depth` contiguous siblings of the path starting at the leaf. The nodes hash with
+/// HASH_PEDERSEN or HASH_POSEIDON. Returns SUCCESS for a valid proof, ERR_INVALID_PROOF for an
+/// invalid one and another error code for malformed inputs
+#[no_mangle]
+pub extern "C" fn merkle_verify(
+    root: Bytes,
+    leaf: Bytes,
+    index: u64,
+    siblings: *const u8,
+    depth: usize,
+    hash_kind: u32,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(root, leaf);
+        let hash = match node_hash(hash_kind) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let (Some(root), Some(leaf)) = (
+            try_field_element_from_bytes(root),
+            try_field_element_from_bytes(leaf),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let siblings = match field_elements_from_bytes(siblings, depth) {
+            Ok(siblings) => siblings,
+            Err(status) => return status,
+        };
+        // The index must fit in a tree of the given depth
+        if depth < 64 && index >> depth != 0 {
+            return ERR_INVALID_PROOF;
+        }
+
+        let node = siblings
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (i, sibling)| {
+                if i < 64 && (index >> i) & 1 == 1 {
+                    hash(sibling, &node)
+                } else {
+                    hash(&node, sibling)
+                }
+            });
+        if node == root {
+            SUCCESS
+        } else {
+            ERR_INVALID_PROOF
+        }
+    })
+}
+
+/// Verifies that the `count` contiguous leaves are at the given strictly increasing indices of the
+/// Merkle tree of the given depth and root, from a multiproof of `proof_count` contiguous nodes. The
+/// multiproof lists the nodes needed on the way up, level by level from the leaves and by
+/// increasing index within a level, that are not computed from the leaves themselves. The nodes
+/// hash with HASH_PEDERSEN or HASH_POSEIDON. Returns SUCCESS for a valid multiproof,
+/// ERR_INVALID_PROOF for an invalid one and another error code for malformed inputs
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn merkle_multiproof_verify(
+    root: Bytes,
+    indices: *const u64,
+    leaves: *const u8,
+    count: usize,
+    proof: *const u8,
+    proof_count: usize,
+    depth: usize,
+    hash_kind: u32,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(root);
+        let hash = match node_hash(hash_kind) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let Some(root) = try_field_element_from_bytes(root) else {
+            return ERR_INVALID_FELT;
+        };
+        let (indices, leaves, proof) = match (
+            slice_from_ptr(indices, count),
+            field_elements_from_bytes(leaves, count),
+            field_elements_from_bytes(proof, proof_count),
+        ) {
+            (Ok(indices), Ok(leaves), Ok(proof)) => (indices, leaves, proof),
+            (Err(status), ..) | (_, Err(status), _) | (.., Err(status)) => return status,
+        };
+        // The indices must fit in a tree of the given depth, and there must be a leaf to prove
+        let in_tree = |&index: &u64| depth >= 64 || index >> depth == 0;
+        if count == 0
+            || !indices.windows(2).all(|pair| pair[0] < pair[1])
+            || !indices.iter().all(in_tree)
+        {
+            return ERR_INVALID_PROOF;
+        }
+
+        // The known nodes of the current level with their index, by increasing index
+        let mut level: Vec<(u64, Felt)> = indices.iter().copied().zip(leaves).collect();
+        let mut proof = proof.into_iter();
+        for _ in 0..depth {
+            let mut parents = Vec::with_capacity(level.len());
+            let mut nodes = level.into_iter().peekable();
+            while let Some((index, node)) = nodes.next() {
+                let parent = match nodes.peek() {
+                    // Both children are known, the sibling comes from the leaves
+                    Some(&(next_index, sibling)) if index & 1 == 0 && next_index == index + 1 => {
+                        nodes.next();
+                        hash(&node, &sibling)
+                    }
+                    _ => {
+                        let Some(sibling) = proof.next() else {
+                            return ERR_INVALID_PROOF;
+                        };
+                        if index & 1 == 0 {
+                            hash(&node, &sibling)
+                        } else {
+                            hash(&sibling, &node)
+                        }
+                    }
+                };
+                parents.push((index >> 1, parent));
+            }
+            level = parents;
+        }
+
+        // Every node of the multiproof must be used
+        if proof.next().is_none() && level == [(0, root)] {
+            SUCCESS
+        } else {
+            ERR_INVALID_PROOF
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_crypto_rs::pedersen_hash;
+
+    fn pedersen_root(leaves: &[Felt]) -> [u8; 32] {
+        let bytes: Vec<u8> = leaves.iter().flat_map(|leaf| leaf.to_bytes_be()).collect();
+        let mut root = [0u8; 32];
+        assert_eq!(
+            pedersen_merkle_root(bytes.as_ptr(), leaves.len(), root.as_mut_ptr()),
+            SUCCESS
+        );
+        root
+    }
+
+    #[test]
+    fn test_pedersen_merkle_root() {
+        let leaves: Vec<Felt> = (1..=3u8).map(Felt::from).collect();
+        let left = pedersen_hash(&leaves[0], &leaves[1]);
+        let right = pedersen_hash(&leaves[2], &Felt::ZERO);
+        assert_eq!(
+            pedersen_root(&leaves),
+            pedersen_hash(&left, &right).to_bytes_be()
+        );
+
+        assert_eq!(pedersen_root(&leaves[..1]), leaves[0].to_bytes_be());
+        assert_eq!(pedersen_root(&[]), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_poseidon_merkle_root() {
+        let leaves: Vec<Felt> = (1..=5u8).map(Felt::from).collect();
+        let bytes: Vec<u8> = leaves.iter().flat_map(|leaf| leaf.to_bytes_be()).collect();
+        let mut root = [0u8; 32];
+        assert_eq!(
+            poseidon_merkle_root(bytes.as_ptr(), leaves.len(), root.as_mut_ptr()),
+            SUCCESS
+        );
+
+        let zero = Felt::ZERO;
+        let level: Vec<Felt> = [leaves.as_slice(), &[zero; 3]]
+            .concat()
+            .chunks(2)
+            .map(|pair| poseidon_hash(pair[0], pair[1]))
+            .collect();
+        let left = poseidon_hash(level[0], level[1]);
+        let right = poseidon_hash(level[2], level[3]);
+        assert_eq!(root, poseidon_hash(left, right).to_bytes_be());
+    }
+
+    #[test]
+    fn test_merkle_cap() {
+        let leaves: Vec<Felt> = (1..=5u8).map(Felt::from).collect();
+        let bytes: Vec<u8> = leaves.iter().flat_map(|leaf| leaf.to_bytes_be()).collect();
+        let cap = |count, cap_height, hash_kind| {
+            let mut cap = alloc::vec![0u8; 32usize << cap_height];
+            let status = merkle_cap(
+                bytes.as_ptr(),
+                count,
+                cap_height,
+                hash_kind,
+                cap.as_mut_ptr(),
+            );
+            assert_eq!(status, SUCCESS);
+            cap.chunks_exact(32)
+                .map(|root| Felt::from_bytes_be(root.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        for (hash_kind, hash) in [
+            (HASH_PEDERSEN, pedersen_hash_pair as NodeHash),
+            (HASH_POSEIDON, poseidon_node_hash),
+        ] {
+            let root = merkle_root(leaves.clone(), hash);
+            assert_eq!(cap(5, 0, hash_kind), [root]);
+
+            // The 8 padded leaves split into two subtrees of 4, which hash into the root
+            let halves = cap(5, 1, hash_kind);
+            assert_eq!(halves[0], merkle_root(leaves[..4].to_vec(), hash));
+            let right = [leaves[4], Felt::ZERO, Felt::ZERO, Felt::ZERO];
+            assert_eq!(halves[1], merkle_root(right.to_vec(), hash));
+            assert_eq!(hash(&halves[0], &halves[1]), root);
+            assert_eq!(subtree_root(&cap(5, 2, hash_kind), &hash), root);
+
+            // A cap higher than the tree is the padded leaves
+            let padded = cap(5, 3, hash_kind);
+            assert_eq!(padded[..5], leaves[..]);
+            assert_eq!(padded[5..], [Felt::ZERO; 3]);
+            assert_eq!(cap(0, 2, hash_kind), [Felt::ZERO; 4]);
+        }
+
+        let mut root = [0u8; 32];
+        assert_eq!(
+            merkle_cap(bytes.as_ptr(), 5, 0, 2, root.as_mut_ptr()),
+            ERR_INVALID_HASH_KIND
+        );
+        assert_eq!(
+            merkle_cap(bytes.as_ptr(), 5, 64, HASH_POSEIDON, root.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            merkle_cap(bytes.as_ptr(), 5, 0, HASH_POSEIDON, core::ptr::null_mut()),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_hash_memory_segment() {
+        let pairs: Vec<Felt> = (1..=6u8).map(Felt::from).collect();
+        let bytes: Vec<u8> = pairs.iter().flat_map(|felt| felt.to_bytes_be()).collect();
+        for (hash_kind, hash) in [
+            (HASH_PEDERSEN, pedersen_hash_pair as NodeHash),
+            (HASH_POSEIDON, poseidon_node_hash),
+        ] {
+            let mut result = [0u8; 32];
+            assert_eq!(
+                hash_memory_segment(bytes.as_ptr(), 3, hash_kind, result.as_mut_ptr()),
+                SUCCESS
+            );
+            let leaves = pairs.chunks(2).map(|pair| hash(&pair[0], &pair[1]));
+            let expected = merkle_root(leaves.collect(), hash);
+            assert_eq!(Felt::from_bytes_be(&result), expected);
+        }
+
+        let mut result = [0u8; 32];
+        assert_eq!(
+            hash_memory_segment(core::ptr::null(), 0, HASH_POSEIDON, result.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(result, [0u8; 32]);
+        assert_eq!(
+            hash_memory_segment(bytes.as_ptr(), 3, 2, result.as_mut_ptr()),
+            ERR_INVALID_HASH_KIND
+        );
+        assert_eq!(
+            hash_memory_segment(
+                bytes.as_ptr(),
+                usize::MAX,
+                HASH_POSEIDON,
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_merkle_verify() {
+        let leaves: Vec<Felt> = (1..=4u8).map(Felt::from).collect();
+        for (hash_kind, hash) in [
+            (HASH_PEDERSEN, pedersen_hash_pair as NodeHash),
+            (HASH_POSEIDON, poseidon_node_hash),
+        ] {
+            let left = hash(&leaves[0], &leaves[1]);
+            let right = hash(&leaves[2], &leaves[3]);
+            let mut root = hash(&left, &right).to_bytes_be();
+
+            // The path of the leaf at index 2: its sibling 4, then the left subtree
+            let siblings = [leaves[3].to_bytes_be(), left.to_bytes_be()].concat();
+            let mut leaf = leaves[2].to_bytes_be();
+            let mut verify = |leaf: &mut [u8; 32], index, depth| {
+                merkle_verify(
+                    root.as_mut_ptr(),
+                    leaf.as_mut_ptr(),
+                    index,
+                    siblings.as_ptr(),
+                    depth,
+                    hash_kind,
+                )
+            };
+            assert_eq!(verify(&mut leaf, 2, 2), SUCCESS);
+            assert_eq!(verify(&mut leaf, 3, 2), ERR_INVALID_PROOF);
+            assert_eq!(verify(&mut leaf, 6, 2), ERR_INVALID_PROOF);
+            assert_eq!(verify(&mut leaf, 2, 1), ERR_INVALID_PROOF);
+            let mut other_leaf = leaves[0].to_bytes_be();
+            assert_eq!(verify(&mut other_leaf, 2, 2), ERR_INVALID_PROOF);
+        }
+
+        let mut felt = [0u8; 32];
+        assert_eq!(
+            merkle_verify(
+                felt.as_mut_ptr(),
+                felt.as_mut_ptr(),
+                0,
+                core::ptr::null(),
+                0,
+                2
+            ),
+            ERR_INVALID_HASH_KIND
+        );
+    }
+
+    #[test]
+    fn test_merkle_multiproof_verify() {
+        let leaves: Vec<Felt> = (1..=8u8).map(Felt::from).collect();
+        let hash = pedersen_hash_pair;
+        let level_1: Vec<Felt> = leaves
+            .chunks(2)
+            .map(|pair| hash(&pair[0], &pair[1]))
+            .collect();
+        let level_2: Vec<Felt> = level_1
+            .chunks(2)
+            .map(|pair| hash(&pair[0], &pair[1]))
+            .collect();
+        let mut root = hash(&level_2[0], &level_2[1]).to_bytes_be();
+
+        // Proving the leaves 0, 1 and 5 takes the leaf 4, then the nodes 1 and 3 of the next level.
+        // Both nodes of the level below the root are computed.
+        let to_bytes = |felts: &[Felt]| -> Vec<u8> {
+            felts.iter().flat_map(|felt| felt.to_bytes_be()).collect()
+        };
+        let proven = to_bytes(&[leaves[0], leaves[1], leaves[5]]);
+        let proof = to_bytes(&[leaves[4], level_1[1], level_1[3]]);
+        let mut verify = |indices: &[u64], proof: &[u8], depth| {
+            merkle_multiproof_verify(
+                root.as_mut_ptr(),
+                indices.as_ptr(),
+                proven.as_ptr(),
+                indices.len(),
+                proof.as_ptr(),
+                proof.len() / 32,
+                depth,
+                HASH_PEDERSEN,
+            )
+        };
+        assert_eq!(verify(&[0, 1, 5], &proof, 3), SUCCESS);
+        assert_eq!(verify(&[0, 1, 4], &proof, 3), ERR_INVALID_PROOF);
+        assert_eq!(verify(&[1, 0, 5], &proof, 3), ERR_INVALID_PROOF);
+        assert_eq!(verify(&[0, 1, 8], &proof, 3), ERR_INVALID_PROOF);
+        assert_eq!(verify(&[0, 1, 5], &proof[..32], 3), ERR_INVALID_PROOF);
+        let extra = [proof.as_slice(), &[0u8; 32]].concat();
+        assert_eq!(verify(&[0, 1, 5], &extra, 3), ERR_INVALID_PROOF);
+        assert_eq!(verify(&[], &proof, 3), ERR_INVALID_PROOF);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_merkle_root() {
+        let leaves: Vec<u8> = (0..100u8)
+            .flat_map(|i| Felt::from(i).to_bytes_be())
+            .collect();
+        let mut sequential = [0u8; 32];
+        assert_eq!(
+            poseidon_merkle_root(leaves.as_ptr(), 100, sequential.as_mut_ptr()),
+            SUCCESS
+        );
+
+        assert_eq!(crate::batch::starknet_crypto_set_threads(3), SUCCESS);
+        let mut parallel = [0u8; 32];
+        assert_eq!(
+            poseidon_merkle_root(leaves.as_ptr(), 100, parallel.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(crate::batch::starknet_crypto_set_threads(1), SUCCESS);
+
+        assert_eq!(sequential, parallel);
+    }
+}
@@ -0,0 +1,152 @@
+// Hashes of the messages sent from L1 to L2 through the Starknet core contract.
+//
+// The hash of a message consumed by an L1 handler is the keccak256 of the 32 bytes big endian words
+// of the sender's Ethereum address, the recipient contract address, the nonce, the selector of the
+// handler, the length of the payload and the felts of the payload, like
+// `StarknetMessaging.l1ToL2MessageHash`. It is a 256 bits integer rather than a felt.
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    bytes_from_array, ffi_guard, field_elements_from_bytes, try_field_element_from_bytes, Bytes,
+    ERR_INVALID_FELT, SUCCESS,
+};
+
+/// Computes the hash of an L1 to L2 message from the 20 bytes Ethereum address `from_address` to
+/// the contract `to_address`, with the selector of its L1 handler, `payload_len` contiguous felts
+/// of payload and the nonce of the message, writing the 32 bytes big endian keccak256 digest into
+/// the last argument
+#[no_mangle]
+pub extern "C" fn l1_to_l2_message_hash(
+    from_address: *const u8,
+    to_address: Bytes,
+    selector: Bytes,
+    payload: *const u8,
+    payload_len: usize,
+    nonce: Bytes,
+    result: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(from_address, to_address, selector, nonce, result);
+        let (Some(to_address), Some(selector), Some(nonce)) = (
+            try_field_element_from_bytes(to_address),
+            try_field_element_from_bytes(selector),
+            try_field_element_from_bytes(nonce),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let payload = match field_elements_from_bytes(payload, payload_len) {
+            Ok(payload) => payload,
+            Err(status) => return status,
+        };
+        let from_address = unsafe { core::slice::from_raw_parts(from_address, 20) };
+
+        let mut hasher = Keccak256::new();
+        hasher.update([0u8; 12]);
+        hasher.update(from_address);
+        for word in [to_address, nonce, selector, payload.len().into()] {
+            hasher.update(word.to_bytes_be());
+        }
+        for felt in payload {
+            hasher.update(felt.to_bytes_be());
+        }
+        let hash: [u8; 32] = hasher.finalize().into();
+        bytes_from_array(&hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hex_bytes, ERR_INVALID_LENGTH};
+    use starknet_types_core::felt::Felt;
+
+    fn felt(value: u64) -> [u8; 32] {
+        Felt::from(value).to_bytes_be()
+    }
+
+    fn hex_felt(hex: &str) -> [u8; 32] {
+        Felt::from_hex(hex).unwrap().to_bytes_be()
+    }
+
+    #[test]
+    fn test_l1_to_l2_message_hash_goerli() {
+        // The message of the Goerli L1 handler transaction
+        // 0x374286ae28f201e61ffbc5b022cc9701208640b405ea34ea9799f97d5d2d23c
+        let from_address = hex_bytes("c3511006c04ef1d78af4c8e0e74ec18a6e64ff9e");
+        let payload = [
+            hex_felt("0x689ead7d814e51ed93644bc145f0754839b8dcb340027ce0c30953f38f55d7"),
+            hex_felt("0x2c68af0bb140000"),
+            felt(0),
+        ]
+        .concat();
+        let mut result = [0u8; 32];
+        let status = l1_to_l2_message_hash(
+            from_address.as_ptr(),
+            hex_felt("0x73314940630fd6dcda0d772d4c972c4e0a9946bef9dabf4ef84eda8ef542b82")
+                .as_mut_ptr(),
+            hex_felt("0x2d757788a8d8d6f21d1cd40bce38a8222d70654214e96ff95d8086e684fbee5")
+                .as_mut_ptr(),
+            payload.as_ptr(),
+            3,
+            felt(775628).as_mut_ptr(),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(status, SUCCESS);
+        assert_eq!(
+            result[..],
+            hex_bytes("c51a543ef9563ad2545342b390b67edfcddf9886aa36846cf70382362fc5fab3")
+        );
+    }
+
+    #[test]
+    fn test_l1_to_l2_message_hash() {
+        let from_address = [0xc3u8; 20];
+        let payload = [felt(7), felt(8)].concat();
+        let mut result = [0u8; 32];
+        let status = l1_to_l2_message_hash(
+            from_address.as_ptr(),
+            felt(1).as_mut_ptr(),
+            felt(2).as_mut_ptr(),
+            payload.as_ptr(),
+            2,
+            felt(3).as_mut_ptr(),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(status, SUCCESS);
+
+        // abi.encodePacked of uint256 words, the nonce before the selector
+        let mut packed = [0u8; 12].to_vec();
+        packed.extend_from_slice(&from_address);
+        for word in [felt(1), felt(3), felt(2), felt(2), felt(7), felt(8)] {
+            packed.extend_from_slice(&word);
+        }
+        let expected: [u8; 32] = Keccak256::digest(&packed).into();
+        assert_eq!(result, expected);
+
+        assert_eq!(
+            l1_to_l2_message_hash(
+                from_address.as_ptr(),
+                [0xffu8; 32].as_mut_ptr(),
+                felt(2).as_mut_ptr(),
+                payload.as_ptr(),
+                2,
+                felt(3).as_mut_ptr(),
+                result.as_mut_ptr(),
+            ),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(
+            l1_to_l2_message_hash(
+                from_address.as_ptr(),
+                felt(1).as_mut_ptr(),
+                felt(2).as_mut_ptr(),
+                core::ptr::null(),
+                2,
+                felt(3).as_mut_ptr(),
+                result.as_mut_ptr(),
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+}
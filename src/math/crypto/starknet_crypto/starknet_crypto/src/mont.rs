@@ -0,0 +1,128 @@
+// Entry points over raw Montgomery limbs.
+//
+// The Zig `Felt252` and `Felt` share the same Montgomery representation: four little
+// endian 64 bits limbs with R = 2^256. Passing the limbs directly skips both the byte
+// serialization and the Montgomery conversion on each side of the FFI.
+use starknet_crypto_rs::pedersen_hash;
+use starknet_types_core::felt::Felt;
+
+use crate::{ffi_guard, hades::poseidon_permute_comp, ERR_INVALID_FELT, SUCCESS};
+
+// The STARK prime 2^251 + 17 * 2^192 + 1 as little endian limbs.
+const MODULUS: [u64; 4] = [1, 0, 0, 0x0800000000000011];
+
+// Returns `None` when the limbs do not encode a reduced Montgomery representation.
+fn try_field_element_from_mont(limbs: *const u64) -> Option<Felt> {
+    let mut limbs = unsafe { core::ptr::read_unaligned(limbs as *const [u64; 4]) };
+    // The raw limbs of a felt are the most significant first
+    limbs.reverse();
+    limbs
+        .iter()
+        .lt(MODULUS.iter().rev())
+        .then(|| Felt::from_raw(limbs))
+}
+
+fn mont_from_field_element(felt: Felt, limbs: *mut u64) {
+    unsafe { core::ptr::write_unaligned(limbs as *mut [u64; 4], felt.to_raw_reversed()) };
+}
+
+/// Computes the pedersen hash of two felts given as Montgomery limbs (four little endian 64 bit
+/// limbs with R = 2^256, the representation of the Zig Felt252), writing the result into the last
+/// argument. Unreduced limbs are reported as ERR_INVALID_FELT
+#[no_mangle]
+pub extern "C" fn pedersen_hash_mont(a: *const u64, b: *const u64, result: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result);
+        let (Some(a), Some(b)) = (
+            try_field_element_from_mont(a),
+            try_field_element_from_mont(b),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        mont_from_field_element(pedersen_hash(&a, &b), result);
+        SUCCESS
+    })
+}
+
+/// Computes the poseidon hash permutation in place over a state of three contiguous felts given as
+/// Montgomery limbs (12 limbs). Unreduced limbs are reported as ERR_INVALID_FELT
+#[no_mangle]
+pub extern "C" fn poseidon_permute_mont(state: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(state);
+        // The state is three contiguous felts, permuted in place
+        let (Some(first), Some(second), Some(third)) = (
+            try_field_element_from_mont(state),
+            try_field_element_from_mont(unsafe { state.add(4) }),
+            try_field_element_from_mont(unsafe { state.add(8) }),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let mut state_array = [first, second, third];
+        poseidon_permute_comp(&mut state_array);
+        for (i, felt) in state_array.into_iter().enumerate() {
+            mont_from_field_element(felt, unsafe { state.add(4 * i) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pedersen_hash_mont() {
+        let a = Felt::from_hex("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb")
+            .unwrap();
+        let b = Felt::from_hex("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a")
+            .unwrap();
+        let mut result = [0u64; 4];
+        assert_eq!(
+            pedersen_hash_mont(
+                a.to_raw_reversed().as_ptr(),
+                b.to_raw_reversed().as_ptr(),
+                result.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            result,
+            Felt::from_hex("0x30e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662")
+                .unwrap()
+                .to_raw_reversed()
+        );
+    }
+
+    #[test]
+    fn test_poseidon_permute_mont() {
+        let mut expected = [Felt::ZERO, Felt::ONE, Felt::TWO];
+        let mut state: Vec<u64> = expected
+            .iter()
+            .flat_map(|felt| felt.to_raw_reversed())
+            .collect();
+        assert_eq!(poseidon_permute_mont(state.as_mut_ptr()), SUCCESS);
+
+        poseidon_permute_comp(&mut expected);
+        for (i, felt) in expected.iter().enumerate() {
+            assert_eq!(state[4 * i..4 * i + 4], felt.to_raw_reversed());
+        }
+    }
+
+    #[test]
+    fn test_mont_rejects_unreduced_limbs() {
+        let one = Felt::ONE.to_raw_reversed();
+        let mut result = [0u64; 4];
+        assert_eq!(
+            pedersen_hash_mont(MODULUS.as_ptr(), one.as_ptr(), result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+
+        // P - 1 is the largest reduced value
+        let max = [0, 0, 0, 0x0800000000000011];
+        assert_eq!(
+            pedersen_hash_mont(max.as_ptr(), one.as_ptr(), result.as_mut_ptr()),
+            SUCCESS
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// Number theoretic transforms over the STARK prime field.
+//
+// The multiplicative group of the field has order p - 1 = 2^192 * (2^59 + 17), so it has roots of
+// unity of every power of two order up to 2^192: the powers of the generator 3 by (p - 1) / 2^k.
+// The transforms evaluate the polynomial of the coefficients at the powers of the root of unity of
+// the length, both in natural order.
+use alloc::vec::Vec;
+
+use elliptic_curve::bigint::{Encoding, U256};
+
+use crate::{
+    backend::{self, Felt},
+    felt::MODULUS_BYTES,
+    ffi_guard, ERR_INVALID_FELT, ERR_INVALID_LENGTH, SUCCESS,
+};
+
+fn felt_from_u8(value: u8) -> Felt {
+    let mut bytes = [0u8; 32];
+    bytes[31] = value;
+    backend::felt_from_bytes(&bytes).unwrap()
+}
+
+// The root of unity of order 2^log_len.
+fn root_of_unity(log_len: u32) -> Felt {
+    let order = U256::from_be_bytes(MODULUS_BYTES).wrapping_sub(&U256::ONE);
+    let exponent = order.shr_vartime(log_len as usize).to_be_bytes();
+    backend::felt_pow(&felt_from_u8(3), &exponent)
+}
+
+// The iterative Cooley-Tukey transform, after the bit reversal permutation of the values, with the
+// inverse roots of unity for the inverse transform.
+fn transform(values: &mut [Felt], inverse: bool) {
+    let len = values.len();
+    let log_len = len.trailing_zeros();
+    for i in 0..len {
+        let j = i
+            .reverse_bits()
+            .checked_shr(usize::BITS - log_len)
+            .unwrap_or(0);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    for log_half in 0..log_len {
+        let half = 1 << log_half;
+        let mut step = root_of_unity(log_half + 1);
+        if inverse {
+            step = backend::felt_inverse(&step).unwrap();
+        }
+        for chunk in values.chunks_exact_mut(2 * half) {
+            let (low, high) = chunk.split_at_mut(half);
+            let mut twiddle = felt_from_u8(1);
+            for (a, b) in low.iter_mut().zip(high) {
+                let t = backend::felt_mul(&twiddle, b);
+                *b = backend::felt_sub(a, &t);
+                *a = backend::felt_add(a, &t);
+                twiddle = backend::felt_mul(&twiddle, &step);
+            }
+        }
+    }
+}
+
+/// Transforms in place the `len` contiguous felts of a power of two length, the coefficients of a
+/// polynomial into its evaluations at the powers of the root of unity of the length, or back with a
+/// non-zero `inverse`
+#[no_mangle]
+pub extern "C" fn stark_ntt(values: *mut u8, len: usize, inverse: u8) -> i32 {
+    ffi_guard(|| {
+        if !len.is_power_of_two() || values.is_null() {
+            return ERR_INVALID_LENGTH;
+        }
+        let bytes = unsafe { core::slice::from_raw_parts_mut(values, 32 * len) };
+        let felts: Option<Vec<Felt>> = bytes
+            .chunks_exact(32)
+            .map(|felt| backend::felt_from_bytes(felt.try_into().unwrap()))
+            .collect();
+        let Some(mut felts) = felts else {
+            return ERR_INVALID_FELT;
+        };
+
+        transform(&mut felts, inverse != 0);
+        if inverse != 0 {
+            // Divide by the length
+            let mut len_inverse = felt_from_u8(1);
+            let half = backend::felt_inverse(&felt_from_u8(2)).unwrap();
+            for _ in 0..len.trailing_zeros() {
+                len_inverse = backend::felt_mul(&len_inverse, &half);
+            }
+            for felt in &mut felts {
+                *felt = backend::felt_mul(felt, &len_inverse);
+            }
+        }
+
+        for (chunk, felt) in bytes.chunks_exact_mut(32).zip(&felts) {
+            chunk.copy_from_slice(&backend::bytes_from_felt(felt));
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_types_core::felt::Felt;
+
+    fn felts_bytes(felts: &[Felt]) -> Vec<u8> {
+        felts.iter().flat_map(|felt| felt.to_bytes_be()).collect()
+    }
+
+    #[test]
+    fn test_stark_ntt() {
+        // The transform of X is the powers of the root of unity of order 4, 1, w, -1, -w
+        let mut values = felts_bytes(&[0u8, 1, 0, 0].map(Felt::from));
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 4, 0), SUCCESS);
+        let w = Felt::from_bytes_be(values[32..64].try_into().unwrap());
+        assert_eq!(w * w, -Felt::ONE);
+        assert_eq!(values, felts_bytes(&[Felt::ONE, w, -Felt::ONE, -w]));
+
+        let mut values = felts_bytes(&[Felt::TWO]);
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 1, 1), SUCCESS);
+        assert_eq!(values, felts_bytes(&[Felt::TWO]));
+
+        // The inverse transform gives the coefficients back
+        let coefficients: Vec<Felt> = (1u64..=16).map(|i| Felt::from(i * i)).collect();
+        let mut values = felts_bytes(&coefficients);
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 16, 0), SUCCESS);
+        assert_ne!(values, felts_bytes(&coefficients));
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 16, 1), SUCCESS);
+        assert_eq!(values, felts_bytes(&coefficients));
+    }
+
+    #[test]
+    fn test_stark_ntt_errors() {
+        let mut values = felts_bytes(&[Felt::ONE; 3]);
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 3, 0), ERR_INVALID_LENGTH);
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 0, 0), ERR_INVALID_LENGTH);
+
+        let mut values = [0xffu8; 64];
+        assert_eq!(stark_ntt(values.as_mut_ptr(), 2, 0), ERR_INVALID_FELT);
+    }
+}
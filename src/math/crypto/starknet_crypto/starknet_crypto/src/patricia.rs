@@ -0,0 +1,855 @@
+// The Starknet binary Merkle-Patricia trie of height 251, committing to the contract states and
+// storages.
+//
+// Empty subtrees hash to zero. A binary node hashes as H(left, right), and an edge node, which
+// goes down `len` levels along the bits of `path` to a non empty child, as H(child, path) + len,
+// where H is the Pedersen hash.
+//
+// The storage proofs of the `pathfinder_getProof` and `starknet_getStorageProof` methods of the
+// nodes are the nodes along the path from the root of the contracts trie to the contract, and of
+// its storage trie to the key. The leaf of a contract is the hash of its state,
+// H(H(H(class_hash, storage_root), nonce), 0), and the state root the Poseidon hash of
+// "STARKNET_STATE_V0", the contracts root and the classes root, or the contracts root alone while
+// the classes root is zero. A proof that leaves the path along an edge proves the key is absent.
+//
+// Every node hashed is kept in a process wide store, so that patricia_update can walk down the
+// tries of the roots computed before, like the Starknet OS hints walk through their preimages. The
+// store holds at most patricia_set_capacity nodes: once an update leaves more, only the trie of its
+// new root is kept, so that updating block after block keeps working while the tries of the older
+// roots are evicted. patricia_clear empties it. Nodes are only ever inserted, or the whole store
+// replaced, so a thread panicking while holding it leaves it consistent and the other threads keep
+// using it.
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, OnceLock, PoisonError,
+    },
+};
+
+use elliptic_curve::bigint::{Encoding, U256};
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    batch::pedersen_hash_pair, bytes_from_field_element, ffi_guard, field_elements_from_bytes,
+    hades::poseidon_hash_many, transaction::try_field_elements_from_ptrs,
+    try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_LENGTH, ERR_INVALID_PROOF,
+    ERR_UNKNOWN_ROOT, SUCCESS,
+};
+
+const HEIGHT: usize = 251;
+
+#[derive(Clone, Copy)]
+enum Node {
+    Binary(Felt, Felt),
+    Edge { path: U256, len: usize, child: Felt },
+}
+
+impl Node {
+    fn hash(&self) -> Felt {
+        match *self {
+            Node::Binary(left, right) => pedersen_hash_pair(&left, &right),
+            Node::Edge { path, len, child } => {
+                let path = Felt::from_bytes_be(&path.to_be_bytes());
+                pedersen_hash_pair(&child, &path) + Felt::from(len as u64)
+            }
+        }
+    }
+}
+
+type Nodes = HashMap<Felt, Node>;
+
+static NODES: OnceLock<Mutex<Nodes>> = OnceLock::new();
+
+const DEFAULT_CAPACITY: usize = 1 << 20;
+
+static CAPACITY: AtomicUsize = AtomicUsize::new(DEFAULT_CAPACITY);
+
+fn nodes() -> std::sync::MutexGuard<'static, Nodes> {
+    NODES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+// Keeps only the nodes of the trie of a root once the store holds more than its capacity.
+fn evict(nodes: &mut Nodes, root: Felt) {
+    if nodes.len() <= CAPACITY.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut kept = Nodes::new();
+    let mut pending = vec![root];
+    while let Some(hash) = pending.pop() {
+        let Some(&node) = nodes.get(&hash) else {
+            continue;
+        };
+        match node {
+            Node::Binary(left, right) => pending.extend([left, right]),
+            Node::Edge { child, .. } => pending.push(child),
+        }
+        kept.insert(hash, node);
+    }
+    *nodes = kept;
+}
+
+// A subtree being updated, only hashed once all the updates are applied.
+enum Subtree {
+    Empty,
+    // A stored node, or a leaf at height zero
+    Hashed(Felt),
+    Binary(Box<Subtree>, Box<Subtree>),
+    Edge {
+        path: U256,
+        len: usize,
+        child: Box<Subtree>,
+    },
+}
+
+impl Subtree {
+    fn expand(hash: Felt, nodes: &Nodes) -> Result<Self, i32> {
+        match nodes.get(&hash) {
+            Some(&Node::Binary(left, right)) => Ok(Subtree::Binary(
+                Box::new(Subtree::Hashed(left)),
+                Box::new(Subtree::Hashed(right)),
+            )),
+            Some(&Node::Edge { path, len, child }) => Ok(Subtree::Edge {
+                path,
+                len,
+                child: Box::new(Subtree::Hashed(child)),
+            }),
+            None => Err(ERR_UNKNOWN_ROOT),
+        }
+    }
+
+    // Splits a subtree above the leaves into the subtrees of its two children.
+    fn split(self, nodes: &Nodes) -> Result<(Self, Self), i32> {
+        match self {
+            Subtree::Empty => Ok((Subtree::Empty, Subtree::Empty)),
+            Subtree::Hashed(hash) => Subtree::expand(hash, nodes)?.split(nodes),
+            Subtree::Binary(left, right) => Ok((*left, *right)),
+            Subtree::Edge { path, len, child } => {
+                // The first bit of the path picks the child, the rest of the edge goes below it
+                let rest = if len == 1 {
+                    *child
+                } else {
+                    let mask = U256::ONE.shl_vartime(len - 1).wrapping_sub(&U256::ONE);
+                    Subtree::Edge {
+                        path: path.bitand(&mask),
+                        len: len - 1,
+                        child,
+                    }
+                };
+                if path.bit_vartime(len - 1) {
+                    Ok((Subtree::Empty, rest))
+                } else {
+                    Ok((rest, Subtree::Empty))
+                }
+            }
+        }
+    }
+
+    // Joins the subtrees of two children at `height - 1` into their parent, extending the edge of
+    // an only child.
+    fn join(left: Self, right: Self, height: usize, nodes: &Nodes) -> Result<Self, i32> {
+        let (bit, child) = match (left, right) {
+            (Subtree::Empty, Subtree::Empty) => return Ok(Subtree::Empty),
+            (child, Subtree::Empty) => (U256::ZERO, child),
+            (Subtree::Empty, child) => (U256::ONE, child),
+            (left, right) => return Ok(Subtree::Binary(Box::new(left), Box::new(right))),
+        };
+        // Stored edges are expanded to be extended, the leaves at height zero are not nodes
+        let child = match child {
+            Subtree::Hashed(hash)
+                if height > 1 && matches!(nodes.get(&hash), Some(Node::Edge { .. })) =>
+            {
+                Subtree::expand(hash, nodes)?
+            }
+            child => child,
+        };
+        Ok(match child {
+            Subtree::Edge { path, len, child } => Subtree::Edge {
+                path: path.bitor(&bit.shl_vartime(len)),
+                len: len + 1,
+                child,
+            },
+            child => Subtree::Edge {
+                path: bit,
+                len: 1,
+                child: Box::new(child),
+            },
+        })
+    }
+
+    // Applies updates sorted by key, without duplicates, to a subtree of the given height.
+    fn update(self, height: usize, updates: &[(U256, Felt)], nodes: &Nodes) -> Result<Self, i32> {
+        if updates.is_empty() {
+            return Ok(self);
+        }
+        if height == 0 {
+            let value = updates[0].1;
+            return Ok(if value == Felt::ZERO {
+                Subtree::Empty
+            } else {
+                Subtree::Hashed(value)
+            });
+        }
+
+        let (left, right) = self.split(nodes)?;
+        let middle = updates.partition_point(|(key, _)| !key.bit_vartime(height - 1));
+        let left = left.update(height - 1, &updates[..middle], nodes)?;
+        let right = right.update(height - 1, &updates[middle..], nodes)?;
+        Subtree::join(left, right, height, nodes)
+    }
+
+    // Hashes the subtree, storing the new nodes.
+    fn hash(self, nodes: &mut Nodes) -> Felt {
+        match self {
+            Subtree::Empty => Felt::ZERO,
+            Subtree::Hashed(hash) => hash,
+            Subtree::Binary(left, right) => {
+                let node = Node::Binary(left.hash(nodes), right.hash(nodes));
+                let hash = node.hash();
+                nodes.insert(hash, node);
+                hash
+            }
+            Subtree::Edge { path, len, child } => {
+                let node = Node::Edge {
+                    path,
+                    len,
+                    child: child.hash(nodes),
+                };
+                let hash = node.hash();
+                nodes.insert(hash, node);
+                hash
+            }
+        }
+    }
+}
+
+// Applies `count` contiguous (key, value) pairs to the trie of a root.
+fn apply(root: Felt, entries: *const u8, count: usize) -> Result<Felt, i32> {
+    let felt_count = count.checked_mul(2).ok_or(ERR_INVALID_LENGTH)?;
+    let felts = field_elements_from_bytes(entries, felt_count)?;
+    let mut updates: Vec<(U256, Felt)> = felts
+        .chunks_exact(2)
+        .map(|entry| (U256::from_be_bytes(entry[0].to_bytes_be()), entry[1]))
+        .collect();
+    if updates.iter().any(|(key, _)| key.bits_vartime() > HEIGHT) {
+        return Err(ERR_INVALID_FELT);
+    }
+    // The sort is stable, so that after reversing the first update of each key is its last one
+    updates.reverse();
+    updates.sort_by_key(|(key, _)| *key);
+    updates.dedup_by_key(|(key, _)| *key);
+
+    let mut nodes = nodes();
+    let subtree = if root == Felt::ZERO {
+        Subtree::Empty
+    } else if nodes.contains_key(&root) {
+        Subtree::Hashed(root)
+    } else {
+        return Err(ERR_UNKNOWN_ROOT);
+    };
+    let root = subtree.update(HEIGHT, &updates, &nodes)?.hash(&mut nodes);
+    evict(&mut nodes, root);
+    Ok(root)
+}
+
+// Reads `count` contiguous proof nodes of 3 felts: zero, the left and the right child of a binary
+// node, or the length, the path and the child of an edge node, keyed by their hash.
+fn proof_nodes(proof: *const u8, count: usize) -> Result<Nodes, i32> {
+    let felt_count = count.checked_mul(3).ok_or(ERR_INVALID_LENGTH)?;
+    let felts = field_elements_from_bytes(proof, felt_count)?;
+    felts
+        .chunks_exact(3)
+        .map(|node| {
+            let len = U256::from_be_bytes(node[0].to_bytes_be());
+            let node = if len == U256::ZERO {
+                Node::Binary(node[1], node[2])
+            } else {
+                // An edge goes down at most the height of the trie, along a path of its length
+                let path = U256::from_be_bytes(node[1].to_bytes_be());
+                if len > U256::from(HEIGHT as u64)
+                    || path.bits_vartime() > len.as_words()[0] as usize
+                {
+                    return Err(ERR_INVALID_PROOF);
+                }
+                let len = len.as_words()[0] as usize;
+                Node::Edge {
+                    path,
+                    len,
+                    child: node[2],
+                }
+            };
+            Ok((node.hash(), node))
+        })
+        .collect()
+}
+
+// The hash of a contract state, the leaf of the contracts trie.
+fn contract_state(class_hash: Felt, storage_root: Felt, nonce: Felt) -> Felt {
+    let hash = pedersen_hash_pair(&class_hash, &storage_root);
+    pedersen_hash_pair(&pedersen_hash_pair(&hash, &nonce), &Felt::ZERO)
+}
+
+// The global state root over the roots of the contracts and classes tries.
+fn state_commitment(contracts_root: Felt, classes_root: Felt) -> Felt {
+    if classes_root == Felt::ZERO {
+        return contracts_root;
+    }
+    let prefix = Felt::from_bytes_be_slice(b"STARKNET_STATE_V0");
+    poseidon_hash_many(&[prefix, contracts_root, classes_root])
+}
+
+// Whether the proof nodes lead from the root to `value` at `key`, a zero value being absent.
+fn verify_proof(root: Felt, key: Felt, value: Felt, nodes: &Nodes) -> bool {
+    let key = U256::from_be_bytes(key.to_bytes_be());
+    if key.bits_vartime() > HEIGHT {
+        return false;
+    }
+    let (mut hash, mut height) = (root, HEIGHT);
+    while height > 0 && hash != Felt::ZERO {
+        match nodes.get(&hash) {
+            Some(&Node::Binary(left, right)) => {
+                hash = if key.bit_vartime(height - 1) {
+                    right
+                } else {
+                    left
+                };
+                height -= 1;
+            }
+            Some(&Node::Edge { path, len, child }) if len <= height => {
+                let mask = U256::ONE.shl_vartime(len).wrapping_sub(&U256::ONE);
+                if key.shr_vartime(height - len).bitand(&mask) != path {
+                    return value == Felt::ZERO;
+                }
+                hash = child;
+                height -= len;
+            }
+            _ => return false,
+        }
+    }
+    hash == value
+}
+
+/// Computes the root of the Starknet Patricia trie of height 251 holding the `count` contiguous
+/// (key, value) pairs of felts, writing it into the last argument. The keys are lower than 2^251,
+/// a key given twice keeps its last value and keys with a zero value are left out
+#[no_mangle]
+pub extern "C" fn patricia_root(entries: *const u8, count: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        match apply(Felt::ZERO, entries, count) {
+            Ok(root) => {
+                bytes_from_field_element(root, result);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Applies `count` contiguous (key, value) updates to the Patricia trie of a root computed by
+/// patricia_root or patricia_update in this process, writing the new root into the last argument.
+/// A zero value deletes the key. Only the tries built in this process are known, so this returns
+/// ERR_UNKNOWN_ROOT for a root other than zero that was computed elsewhere, or whose trie was since
+/// evicted or cleared
+#[no_mangle]
+pub extern "C" fn patricia_update(
+    prev_root: Bytes,
+    updates: *const u8,
+    count: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(prev_root, result);
+        let Some(prev_root) = try_field_element_from_bytes(prev_root) else {
+            return ERR_INVALID_FELT;
+        };
+        match apply(prev_root, updates, count) {
+            Ok(root) => {
+                bytes_from_field_element(root, result);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Empties the store of the Patricia trie nodes, after which patricia_update returns
+/// ERR_UNKNOWN_ROOT for every root computed before
+#[no_mangle]
+pub extern "C" fn patricia_clear() -> i32 {
+    ffi_guard(|| {
+        *nodes() = Nodes::new();
+        SUCCESS
+    })
+}
+
+/// Sets the most nodes the store of the Patricia trie nodes holds, 2^20 by default. Once an update
+/// leaves more, only the trie of its new root is kept, and patricia_update returns
+/// ERR_UNKNOWN_ROOT for the roots computed before it
+#[no_mangle]
+pub extern "C" fn patricia_set_capacity(max_nodes: usize) -> i32 {
+    ffi_guard(|| {
+        CAPACITY.store(max_nodes, Ordering::Relaxed);
+        SUCCESS
+    })
+}
+
+/// Verifies the storage proof that the storage of the contract at `contract_address` holds `value`
+/// at `storage_key` in the state of `state_root`, a zero value proving the key is absent. The
+/// contract state is its class hash, nonce and storage root, all zeros for a contract that is not
+/// deployed, and `classes_root` is the root of the classes trie. The proofs are `contract_proof_len`
+/// and `storage_proof_len` contiguous nodes of 3 felts, in any order: zero, the left and the right
+/// child of a binary node, or the length, the path and the child of an edge node. Returns
+/// ERR_INVALID_PROOF when either proof doesn't lead to its root
+#[no_mangle]
+pub extern "C" fn verify_storage_proof(
+    state_root: Bytes,
+    classes_root: Bytes,
+    contract_address: Bytes,
+    class_hash: Bytes,
+    nonce: Bytes,
+    storage_root: Bytes,
+    storage_key: Bytes,
+    value: Bytes,
+    contract_proof: *const u8,
+    contract_proof_len: usize,
+    storage_proof: *const u8,
+    storage_proof_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            state_root,
+            classes_root,
+            contract_address,
+            class_hash,
+            nonce,
+            storage_root,
+            storage_key,
+            value
+        );
+        let (
+            Some([state_root, classes_root, contract_address]),
+            Some([class_hash, nonce, storage_root, storage_key, value]),
+        ) = (
+            try_field_elements_from_ptrs([state_root, classes_root, contract_address]),
+            try_field_elements_from_ptrs([class_hash, nonce, storage_root, storage_key, value]),
+        )
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let (contract_proof, storage_proof) = match (
+            proof_nodes(contract_proof, contract_proof_len),
+            proof_nodes(storage_proof, storage_proof_len),
+        ) {
+            (Ok(contract_proof), Ok(storage_proof)) => (contract_proof, storage_proof),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+
+        let contracts_root = if classes_root == Felt::ZERO {
+            state_root
+        } else {
+            // The contracts root is the node, or the empty trie, that hashes with the classes root
+            // into the state root
+            let mut roots = contract_proof.keys().copied().chain([Felt::ZERO]);
+            let Some(contracts_root) =
+                roots.find(|&root| state_commitment(root, classes_root) == state_root)
+            else {
+                return ERR_INVALID_PROOF;
+            };
+            contracts_root
+        };
+        let contract_state = if [class_hash, nonce, storage_root] == [Felt::ZERO; 3] {
+            Felt::ZERO
+        } else {
+            contract_state(class_hash, storage_root, nonce)
+        };
+        if verify_proof(
+            contracts_root,
+            contract_address,
+            contract_state,
+            &contract_proof,
+        ) && verify_proof(storage_root, storage_key, value, &storage_proof)
+        {
+            SUCCESS
+        } else {
+            ERR_INVALID_PROOF
+        }
+    })
+}
+
+/// Computes the hash of the state of a contract from its class hash, the root of its storage trie
+/// and its nonce, its leaf in the contracts trie, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn contract_state_hash(
+    class_hash: Bytes,
+    storage_root: Bytes,
+    nonce: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(class_hash, storage_root, nonce, result);
+        let Some([class_hash, storage_root, nonce]) =
+            try_field_elements_from_ptrs([class_hash, storage_root, nonce])
+        else {
+            return ERR_INVALID_FELT;
+        };
+        bytes_from_field_element(contract_state(class_hash, storage_root, nonce), result);
+        SUCCESS
+    })
+}
+
+/// Computes the global state root of a block from the roots of its contracts and classes tries,
+/// the Poseidon hash of "STARKNET_STATE_V0" and both roots, or the contracts root while the
+/// classes root is zero, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn global_state_root(
+    contracts_root: Bytes,
+    classes_root: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(contracts_root, classes_root, result);
+        let Some([contracts_root, classes_root]) =
+            try_field_elements_from_ptrs([contracts_root, classes_root])
+        else {
+            return ERR_INVALID_FELT;
+        };
+        bytes_from_field_element(state_commitment(contracts_root, classes_root), result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use starknet_crypto_rs::pedersen_hash;
+
+    // Held by the tests that expect the roots they compute to stay in the store
+    pub(crate) static STORE: Mutex<()> = Mutex::new(());
+
+    fn lock_store() -> std::sync::MutexGuard<'static, ()> {
+        STORE.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn entries_bytes(entries: &[(u64, u64)]) -> Vec<u8> {
+        entries
+            .iter()
+            .flat_map(|&(key, value)| [Felt::from(key), Felt::from(value)])
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect()
+    }
+
+    fn root(entries: &[(u64, u64)]) -> Felt {
+        let mut root = [0u8; 32];
+        let entries = entries_bytes(entries);
+        assert_eq!(
+            patricia_root(entries.as_ptr(), entries.len() / 64, root.as_mut_ptr()),
+            SUCCESS
+        );
+        Felt::from_bytes_be(&root)
+    }
+
+    fn update(prev_root: Felt, updates: &[(u64, u64)]) -> Result<Felt, i32> {
+        let mut prev_root = prev_root.to_bytes_be();
+        let mut root = [0u8; 32];
+        let updates = entries_bytes(updates);
+        match patricia_update(
+            prev_root.as_mut_ptr(),
+            updates.as_ptr(),
+            updates.len() / 64,
+            root.as_mut_ptr(),
+        ) {
+            SUCCESS => Ok(Felt::from_bytes_be(&root)),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_patricia_root() {
+        let felt = Felt::from;
+        assert_eq!(root(&[]), Felt::ZERO);
+        assert_eq!(root(&[(5, 0)]), Felt::ZERO);
+
+        // A single leaf is an edge from the root along all the bits of its key
+        let single = pedersen_hash(&felt(7u8), &felt(5u8)) + felt(251u8);
+        assert_eq!(root(&[(5, 7)]), single);
+        assert_eq!(root(&[(5, 1), (5, 7)]), single);
+
+        // The leaves 0 and 1 share the path of 250 zeros down to their parent
+        let parent = pedersen_hash(&felt(3u8), &felt(4u8));
+        let expected = pedersen_hash(&parent, &Felt::ZERO) + felt(250u8);
+        assert_eq!(root(&[(1, 4), (0, 3)]), expected);
+    }
+
+    #[test]
+    fn test_patricia_update() {
+        let _store = lock_store();
+        let entries = [(0, 1), (1, 2), (2, 3), (1 << 40, 4), (u64::MAX, 5)];
+        let full = root(&entries);
+        for split in 0..entries.len() {
+            let partial = root(&entries[..split]);
+            assert_eq!(update(partial, &entries[split..]), Ok(full));
+
+            // Deleting the other keys goes back to the partial trie
+            let deletions: Vec<(u64, u64)> =
+                entries[split..].iter().map(|&(key, _)| (key, 0)).collect();
+            assert_eq!(update(full, &deletions), Ok(partial));
+        }
+        assert_eq!(update(full, &[]), Ok(full));
+
+        assert_eq!(update(Felt::ONE, &[(0, 1)]), Err(ERR_UNKNOWN_ROOT));
+    }
+
+    // The encoded nodes of the stored trie of a root along the path to a key.
+    fn proof(root: Felt, key: u64) -> (Vec<u8>, usize) {
+        let nodes = nodes();
+        let key = U256::from(key);
+        let (mut encoded, mut count) = (Vec::new(), 0);
+        let (mut hash, mut height) = (root, HEIGHT);
+        while let Some(&node) = nodes.get(&hash).filter(|_| height > 0) {
+            let felts = match node {
+                Node::Binary(left, right) => {
+                    hash = if key.bit_vartime(height - 1) {
+                        right
+                    } else {
+                        left
+                    };
+                    height -= 1;
+                    [Felt::ZERO, left, right]
+                }
+                Node::Edge { path, len, child } => {
+                    (hash, height) = (child, height - len);
+                    let path = Felt::from_bytes_be(&path.to_be_bytes());
+                    [Felt::from(len as u64), path, child]
+                }
+            };
+            encoded.extend(felts.iter().flat_map(|felt| felt.to_bytes_be()));
+            count += 1;
+        }
+        (encoded, count)
+    }
+
+    #[test]
+    fn test_verify_storage_proof() {
+        let _store = lock_store();
+        let felt = |value: u64| Felt::from(value);
+        let storage_root = root(&[(1, 10), (2, 20), (1 << 50, 30)]);
+        let (address, class_hash, nonce) = (0x1234, felt(0xc1a55), felt(3));
+        let state = pedersen_hash(
+            &pedersen_hash(&pedersen_hash(&class_hash, &storage_root), &nonce),
+            &Felt::ZERO,
+        );
+        let contracts = [felt(address), state, felt(7), felt(1)];
+        let contracts: Vec<u8> = contracts
+            .iter()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect();
+        let mut contracts_root = [0u8; 32];
+        assert_eq!(
+            patricia_root(contracts.as_ptr(), 2, contracts_root.as_mut_ptr()),
+            SUCCESS
+        );
+        let contracts_root = Felt::from_bytes_be(&contracts_root);
+        let classes_root = felt(0xc0ffee);
+        let prefix = Felt::from_bytes_be_slice(b"STARKNET_STATE_V0");
+        let state_root = poseidon_hash_many(&[prefix, contracts_root, classes_root]);
+
+        let (contract_proof, contract_proof_len) = proof(contracts_root, address);
+        let verify = |state_root: Felt,
+                      classes_root: Felt,
+                      [class_hash, nonce, storage_root]: [Felt; 3],
+                      key: u64,
+                      value: u64| {
+            let (storage_proof, storage_proof_len) = proof(storage_root, key);
+            verify_storage_proof(
+                state_root.to_bytes_be().as_mut_ptr(),
+                classes_root.to_bytes_be().as_mut_ptr(),
+                felt(address).to_bytes_be().as_mut_ptr(),
+                class_hash.to_bytes_be().as_mut_ptr(),
+                nonce.to_bytes_be().as_mut_ptr(),
+                storage_root.to_bytes_be().as_mut_ptr(),
+                felt(key).to_bytes_be().as_mut_ptr(),
+                felt(value).to_bytes_be().as_mut_ptr(),
+                contract_proof.as_ptr(),
+                contract_proof_len,
+                storage_proof.as_ptr(),
+                storage_proof_len,
+            )
+        };
+        let contract = [class_hash, nonce, storage_root];
+        assert_eq!(verify(state_root, classes_root, contract, 2, 20), SUCCESS);
+        assert_eq!(
+            verify(state_root, classes_root, contract, 1 << 50, 30),
+            SUCCESS
+        );
+        // Without a classes trie, the state root is the contracts root
+        assert_eq!(verify(contracts_root, Felt::ZERO, contract, 1, 10), SUCCESS);
+
+        // An absent key proves a zero value, along an edge leaving its path or an empty subtree
+        assert_eq!(verify(state_root, classes_root, contract, 3, 0), SUCCESS);
+        assert_eq!(
+            verify(state_root, classes_root, contract, 1 << 60, 0),
+            SUCCESS
+        );
+        assert_eq!(
+            verify(state_root, classes_root, contract, 3, 1),
+            ERR_INVALID_PROOF
+        );
+        assert_eq!(
+            verify(state_root, classes_root, contract, 2, 21),
+            ERR_INVALID_PROOF
+        );
+        assert_eq!(
+            verify(state_root, felt(1), contract, 2, 20),
+            ERR_INVALID_PROOF
+        );
+        assert_eq!(
+            verify(
+                state_root,
+                classes_root,
+                [class_hash, felt(4), storage_root],
+                2,
+                20
+            ),
+            ERR_INVALID_PROOF
+        );
+
+        // The proof nodes are rejected when malformed or missing
+        let (storage_proof, _) = proof(storage_root, 2);
+        let mut edge = [felt(252), felt(1), felt(1)].map(|felt| felt.to_bytes_be());
+        let status = |contract_proof: *const u8, contract_proof_len| {
+            verify_storage_proof(
+                state_root.to_bytes_be().as_mut_ptr(),
+                classes_root.to_bytes_be().as_mut_ptr(),
+                felt(address).to_bytes_be().as_mut_ptr(),
+                class_hash.to_bytes_be().as_mut_ptr(),
+                nonce.to_bytes_be().as_mut_ptr(),
+                storage_root.to_bytes_be().as_mut_ptr(),
+                felt(2).to_bytes_be().as_mut_ptr(),
+                felt(20).to_bytes_be().as_mut_ptr(),
+                contract_proof,
+                contract_proof_len,
+                storage_proof.as_ptr(),
+                0,
+            )
+        };
+        assert_eq!(
+            status(contract_proof.as_ptr(), contract_proof_len),
+            ERR_INVALID_PROOF
+        );
+        assert_eq!(status(edge.concat().as_ptr(), 1), ERR_INVALID_PROOF);
+        edge[0] = felt(1).to_bytes_be();
+        edge[1] = felt(2).to_bytes_be();
+        assert_eq!(status(edge.concat().as_ptr(), 1), ERR_INVALID_PROOF);
+        assert_eq!(status(core::ptr::null(), 1), ERR_INVALID_LENGTH);
+
+        // A contract that is not deployed has no storage
+        let empty = [Felt::ZERO; 3];
+        assert_eq!(
+            verify(state_root, classes_root, empty, 2, 0),
+            ERR_INVALID_PROOF
+        );
+        let state_root = root(&[(7, 1)]);
+        let (contract_proof, contract_proof_len) = proof(state_root, address);
+        assert_eq!(
+            verify_storage_proof(
+                state_root.to_bytes_be().as_mut_ptr(),
+                [0u8; 32].as_mut_ptr(),
+                felt(address).to_bytes_be().as_mut_ptr(),
+                [0u8; 32].as_mut_ptr(),
+                [0u8; 32].as_mut_ptr(),
+                [0u8; 32].as_mut_ptr(),
+                felt(2).to_bytes_be().as_mut_ptr(),
+                [0u8; 32].as_mut_ptr(),
+                contract_proof.as_ptr(),
+                contract_proof_len,
+                core::ptr::null(),
+                0,
+            ),
+            SUCCESS
+        );
+    }
+
+    #[test]
+    fn test_global_state_root() {
+        let felt = |value: u64| Felt::from(value);
+        let mut result = [0u8; 32];
+        let status = contract_state_hash(
+            felt(1).to_bytes_be().as_mut_ptr(),
+            felt(2).to_bytes_be().as_mut_ptr(),
+            felt(3).to_bytes_be().as_mut_ptr(),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(status, SUCCESS);
+        let state = pedersen_hash(&pedersen_hash(&felt(1), &felt(2)), &felt(3));
+        assert_eq!(
+            Felt::from_bytes_be(&result),
+            pedersen_hash(&state, &Felt::ZERO)
+        );
+
+        let state_root = |contracts_root: u64, classes_root: u64| {
+            let mut result = [0u8; 32];
+            let status = global_state_root(
+                felt(contracts_root).to_bytes_be().as_mut_ptr(),
+                felt(classes_root).to_bytes_be().as_mut_ptr(),
+                result.as_mut_ptr(),
+            );
+            assert_eq!(status, SUCCESS);
+            Felt::from_bytes_be(&result)
+        };
+        let prefix = Felt::from_bytes_be_slice(b"STARKNET_STATE_V0");
+        assert_eq!(
+            state_root(5, 6),
+            poseidon_hash_many(&[prefix, felt(5), felt(6)])
+        );
+        assert_eq!(state_root(5, 0), felt(5));
+
+        assert_eq!(
+            global_state_root(
+                [0xffu8; 32].as_mut_ptr(),
+                felt(6).to_bytes_be().as_mut_ptr(),
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_patricia_invalid_key() {
+        let mut key = Felt::ONE.to_bytes_be();
+        key[0] = 0x08;
+        let entry = [key, Felt::ONE.to_bytes_be()].concat();
+        let mut root = [0u8; 32];
+        assert_eq!(
+            patricia_root(entry.as_ptr(), 1, root.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_patricia_clear() {
+        let _store = lock_store();
+        let first = root(&[(1, 2), (3, 4)]);
+        assert_eq!(patricia_clear(), SUCCESS);
+        assert_eq!(update(first, &[(5, 6)]), Err(ERR_UNKNOWN_ROOT));
+        assert_eq!(update(Felt::ZERO, &[(1, 2), (3, 4)]), Ok(first));
+        assert!(update(first, &[(5, 6)]).is_ok());
+    }
+
+    #[test]
+    fn test_patricia_capacity() {
+        let _store = lock_store();
+        assert_eq!(patricia_set_capacity(4), SUCCESS);
+        let first = root(&[(1, 2), (3, 4)]);
+        let second = update(first, &[(5, 6)]).unwrap();
+        let third = update(second, &[(7, 8)]).unwrap();
+        // Past the capacity only the trie of the latest root is kept, and updates along it go on
+        assert_eq!(update(first, &[(9, 10)]), Err(ERR_UNKNOWN_ROOT));
+        assert!(nodes().contains_key(&third));
+        assert_eq!(update(third, &[(7, 0), (5, 0)]), Ok(first));
+        assert_eq!(patricia_set_capacity(DEFAULT_CAPACITY), SUCCESS);
+    }
+}
@@ -0,0 +1,124 @@
+// Windowed lookup tables for the Pedersen hash, built on demand by
+// `starknet_crypto_precompute_pedersen`.
+//
+// starknet-crypto ships tables of 4 bits windows in the binary. These tables use 8 bits windows, so
+// hashing takes half the point additions for about 1 MB of memory. They only speed up the batch
+// functions, which amortize the lookup of the tables.
+use std::sync::OnceLock;
+
+use starknet_curve::curve_params::{
+    PEDERSEN_P0, PEDERSEN_P1, PEDERSEN_P2, PEDERSEN_P3, SHIFT_POINT,
+};
+use starknet_types_core::{
+    curve::{AffinePoint, ProjectivePoint},
+    felt::Felt,
+};
+
+use crate::{ffi_guard, logger::log, LOG_DEBUG, SUCCESS};
+
+// Each window holds the 255 non zero multiples of its base point by a byte of the scalar.
+const WINDOW_SIZE: usize = 255;
+
+pub(crate) static TABLES: OnceLock<PedersenTables> = OnceLock::new();
+
+// The multiples of one generator for `windows` consecutive bytes of the scalar.
+struct Table(Vec<AffinePoint>);
+
+impl Table {
+    fn new(generator: &AffinePoint, windows: usize) -> Self {
+        let mut points = Vec::with_capacity(windows * WINDOW_SIZE);
+        let mut base = ProjectivePoint::from_affine_unchecked(generator.x(), generator.y());
+        for _ in 0..windows {
+            let mut multiple = base.clone();
+            for _ in 0..WINDOW_SIZE {
+                points.push(multiple.to_affine().unwrap());
+                multiple += &base;
+            }
+            // After the last addition the multiple is 256 * base, the base of the next window
+            base = multiple;
+        }
+        Self(points)
+    }
+
+    // Adds the multiple of the generator by the little endian scalar bytes to the accumulator.
+    fn add(&self, acc: &mut ProjectivePoint, scalar: &[u8]) {
+        for (window, &byte) in scalar.iter().enumerate() {
+            if byte > 0 {
+                *acc += &self.0[window * WINDOW_SIZE + byte as usize - 1];
+            }
+        }
+    }
+}
+
+// The tables of the four generators: the low 248 bits of each input use P0 and P2, the high 4 bits
+// P1 and P3.
+pub(crate) struct PedersenTables([Table; 4]);
+
+impl PedersenTables {
+    fn new() -> Self {
+        Self([
+            Table::new(&PEDERSEN_P0, 31),
+            Table::new(&PEDERSEN_P1, 1),
+            Table::new(&PEDERSEN_P2, 31),
+            Table::new(&PEDERSEN_P3, 1),
+        ])
+    }
+
+    pub(crate) fn hash(&self, x: &Felt, y: &Felt) -> Felt {
+        let [p0, p1, p2, p3] = &self.0;
+        let mut x = x.to_bytes_be();
+        x.reverse();
+        let mut y = y.to_bytes_be();
+        y.reverse();
+
+        let mut acc = ProjectivePoint::from_affine_unchecked(SHIFT_POINT.x(), SHIFT_POINT.y());
+        p0.add(&mut acc, &x[..31]);
+        p1.add(&mut acc, &x[31..]);
+        p2.add(&mut acc, &y[..31]);
+        p3.add(&mut acc, &y[31..]);
+        acc.to_affine().unwrap().x()
+    }
+}
+
+/// Builds the lookup tables that speed up pedersen_hash_batch, taking about 1 MB of memory. Only
+/// the first call builds them, and it may be made from any thread
+#[no_mangle]
+pub extern "C" fn starknet_crypto_precompute_pedersen() -> i32 {
+    ffi_guard(|| {
+        TABLES.get_or_init(|| {
+            log(
+                LOG_DEBUG,
+                format_args!("building the Pedersen lookup tables"),
+            );
+            PedersenTables::new()
+        });
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_crypto_rs::pedersen_hash;
+
+    #[test]
+    fn test_precomputed_pedersen_hash() {
+        assert_eq!(starknet_crypto_precompute_pedersen(), SUCCESS);
+        let tables = TABLES.get().unwrap();
+
+        let max = Felt::ZERO - Felt::ONE;
+        let a = Felt::from_hex("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb")
+            .unwrap();
+        let b = Felt::from_hex("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a")
+            .unwrap();
+        for (x, y) in [
+            (a, b),
+            (b, a),
+            (Felt::ZERO, Felt::ZERO),
+            (max, max),
+            (max, Felt::ONE),
+        ] {
+            assert_eq!(tables.hash(&x, &y), pedersen_hash(&x, &y));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Pedersen hashes sharing their left operand, which contributes its multiples of the first two
+// generators to the sum of every hash: a handle keeps that partial sum, so that each hash only adds
+// the multiples of the last two generators by its right operand.
+//
+// The partial sums are opaque to C, allocated by pedersen_precompute_left and released with
+// pedersen_left_free.
+use alloc::boxed::Box;
+
+use starknet_curve::curve_params::SHIFT_POINT;
+use starknet_types_core::{
+    curve::{AffinePoint, ProjectivePoint},
+    felt::Felt,
+};
+
+use crate::{
+    bytes_from_field_element,
+    constants::generated::{
+        CURVE_CONSTS_BITS, CURVE_CONSTS_P0, CURVE_CONSTS_P1, CURVE_CONSTS_P2, CURVE_CONSTS_P3,
+    },
+    ffi_guard,
+    profiling::{Primitive, Profile},
+    try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, SUCCESS,
+};
+
+/// The partial Pedersen sum of a left operand
+pub struct PedersenLeft(ProjectivePoint);
+
+// Adds the multiples of the windows of a table by the chunks of the little endian bits, like the
+// pedersen_hash of starknet-crypto.
+fn add_points(acc: &mut ProjectivePoint, bits: &[bool], table: &[AffinePoint]) {
+    let window_size = (1 << CURVE_CONSTS_BITS) - 1;
+    for (window, chunk) in bits.chunks(CURVE_CONSTS_BITS).enumerate() {
+        let offset = chunk
+            .iter()
+            .rev()
+            .fold(0, |offset, &bit| (offset << 1) | bit as usize);
+        if offset > 0 {
+            *acc += &table[window * window_size + offset - 1];
+        }
+    }
+}
+
+/// Precomputes the partial Pedersen sum of a left operand, writing the handle into the last
+/// argument. It must be released with pedersen_left_free
+#[no_mangle]
+pub extern "C" fn pedersen_precompute_left(a: Bytes, left: *mut *mut PedersenLeft) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, left);
+        let Some(a) = try_field_element_from_bytes(a) else {
+            return ERR_INVALID_FELT;
+        };
+        let a = a.to_bits_le();
+        let mut acc = ProjectivePoint::from_affine_unchecked(SHIFT_POINT.x(), SHIFT_POINT.y());
+        add_points(&mut acc, &a[..248], &CURVE_CONSTS_P0);
+        add_points(&mut acc, &a[248..252], &CURVE_CONSTS_P1);
+        unsafe { *left = Box::into_raw(Box::new(PedersenLeft(acc))) };
+        SUCCESS
+    })
+}
+
+/// Computes the pedersen hash of the left operand of a handle and a felt, writing the result into
+/// the last argument
+#[no_mangle]
+pub extern "C" fn pedersen_hash_with_left(
+    left: *const PedersenLeft,
+    b: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(left, b, result);
+        let _profile = Profile::start(Primitive::Pedersen, 1);
+        let Some(b) = try_field_element_from_bytes(b) else {
+            return ERR_INVALID_FELT;
+        };
+        let b = b.to_bits_le();
+        let mut acc = unsafe { (*left).0.clone() };
+        add_points(&mut acc, &b[..248], &CURVE_CONSTS_P2);
+        add_points(&mut acc, &b[248..252], &CURVE_CONSTS_P3);
+        let hash: Felt = acc.to_affine().unwrap().x();
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Releases a handle allocated by pedersen_precompute_left. Does nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn pedersen_left_free(left: *mut PedersenLeft) -> i32 {
+    ffi_guard(|| {
+        if !left.is_null() {
+            drop(unsafe { Box::from_raw(left) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_crypto_rs::pedersen_hash;
+
+    #[test]
+    fn test_pedersen_hash_with_left() {
+        let a = Felt::from_hex("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb")
+            .unwrap();
+        let mut left = core::ptr::null_mut();
+        assert_eq!(
+            pedersen_precompute_left(a.to_bytes_be().as_mut_ptr(), &mut left),
+            SUCCESS
+        );
+        let max = Felt::ZERO - Felt::ONE;
+        for b in [Felt::ZERO, Felt::ONE, a, max] {
+            let mut result = [0u8; 32];
+            assert_eq!(
+                pedersen_hash_with_left(left, b.to_bytes_be().as_mut_ptr(), result.as_mut_ptr()),
+                SUCCESS
+            );
+            assert_eq!(result, pedersen_hash(&a, &b).to_bytes_be());
+        }
+        assert_eq!(pedersen_left_free(left), SUCCESS);
+        assert_eq!(pedersen_left_free(core::ptr::null_mut()), SUCCESS);
+    }
+}
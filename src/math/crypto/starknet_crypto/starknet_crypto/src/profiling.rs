@@ -0,0 +1,186 @@
+// Counters of the calls into the builtin primitives, telling how much time the VM spends in this
+// library without an external profiler.
+//
+// Each primitive counts its calls, the nanoseconds spent in them and the items they processed: one
+// per call for the single functions, and the number of pairs, states, signatures or input elements
+// for the batch and array functions. The counters are only collected with the `profiling` cargo
+// feature, otherwise a Profile does nothing and is optimized away.
+#[cfg(feature = "profiling")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "profiling")]
+use crate::{ffi_guard, SUCCESS};
+
+#[derive(Clone, Copy)]
+pub(crate) enum Primitive {
+    Pedersen,
+    Poseidon,
+    // Its counters stay at zero without the `signing` feature
+    #[cfg_attr(not(feature = "signing"), allow(dead_code))]
+    EcdsaSign,
+    EcdsaVerify,
+    Keccak,
+    EcOp,
+}
+
+/// The counters of one primitive
+#[cfg(feature = "profiling")]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrimitiveStats {
+    pub calls: u64,
+    pub nanoseconds: u64,
+    pub items: u64,
+}
+
+/// The counters of the builtin primitives, filled by starknet_crypto_stats
+#[cfg(feature = "profiling")]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub pedersen: PrimitiveStats,
+    pub poseidon: PrimitiveStats,
+    pub ecdsa_sign: PrimitiveStats,
+    pub ecdsa_verify: PrimitiveStats,
+    pub keccak: PrimitiveStats,
+    pub ec_op: PrimitiveStats,
+}
+
+#[cfg(feature = "profiling")]
+struct Counters {
+    calls: AtomicU64,
+    nanoseconds: AtomicU64,
+    items: AtomicU64,
+}
+
+#[cfg(feature = "profiling")]
+impl Counters {
+    const fn new() -> Self {
+        Self {
+            calls: AtomicU64::new(0),
+            nanoseconds: AtomicU64::new(0),
+            items: AtomicU64::new(0),
+        }
+    }
+
+    fn load(&self) -> PrimitiveStats {
+        PrimitiveStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            nanoseconds: self.nanoseconds.load(Ordering::Relaxed),
+            items: self.items.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.calls.store(0, Ordering::Relaxed);
+        self.nanoseconds.store(0, Ordering::Relaxed);
+        self.items.store(0, Ordering::Relaxed);
+    }
+}
+
+// The counters of each Primitive, in the order of the enum.
+#[cfg(feature = "profiling")]
+static COUNTERS: [Counters; 6] = [const { Counters::new() }; 6];
+
+// Times a call into a primitive from its creation until it is dropped, at the end of the call.
+pub(crate) struct Profile {
+    #[cfg(feature = "profiling")]
+    primitive: Primitive,
+    #[cfg(feature = "profiling")]
+    items: usize,
+    #[cfg(feature = "profiling")]
+    start: std::time::Instant,
+}
+
+impl Profile {
+    #[inline(always)]
+    pub(crate) fn start(primitive: Primitive, items: usize) -> Self {
+        #[cfg(not(feature = "profiling"))]
+        let _ = (primitive, items);
+        Self {
+            #[cfg(feature = "profiling")]
+            primitive,
+            #[cfg(feature = "profiling")]
+            items,
+            #[cfg(feature = "profiling")]
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl Drop for Profile {
+    fn drop(&mut self) {
+        let counters = &COUNTERS[self.primitive as usize];
+        let nanoseconds = u64::try_from(self.start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        counters.calls.fetch_add(1, Ordering::Relaxed);
+        counters
+            .nanoseconds
+            .fetch_add(nanoseconds, Ordering::Relaxed);
+        counters
+            .items
+            .fetch_add(self.items as u64, Ordering::Relaxed);
+    }
+}
+
+/// Writes the counters of the builtin primitives since the start or the last
+/// starknet_crypto_stats_reset into the argument. Only available with the `profiling` cargo feature
+#[cfg(feature = "profiling")]
+#[no_mangle]
+pub extern "C" fn starknet_crypto_stats(stats: *mut Stats) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(stats);
+        let [pedersen, poseidon, ecdsa_sign, ecdsa_verify, keccak, ec_op] =
+            COUNTERS.each_ref().map(Counters::load);
+        unsafe {
+            *stats = Stats {
+                pedersen,
+                poseidon,
+                ecdsa_sign,
+                ecdsa_verify,
+                keccak,
+                ec_op,
+            }
+        };
+        SUCCESS
+    })
+}
+
+/// Resets the counters of the builtin primitives to zero. Only available with the `profiling` cargo
+/// feature
+#[cfg(feature = "profiling")]
+#[no_mangle]
+pub extern "C" fn starknet_crypto_stats_reset() -> i32 {
+    ffi_guard(|| {
+        COUNTERS.iter().for_each(Counters::reset);
+        SUCCESS
+    })
+}
+
+#[cfg(all(test, feature = "profiling"))]
+mod tests {
+    use super::*;
+    use crate::batch::poseidon_permute_batch;
+
+    fn stats() -> Stats {
+        let mut stats = Stats::default();
+        assert_eq!(starknet_crypto_stats(&mut stats), SUCCESS);
+        stats
+    }
+
+    #[test]
+    fn test_stats() {
+        // Other tests call the primitives concurrently, so the counters only give lower bounds
+        let before = stats();
+        let mut states = vec![0u8; 96 * 1000];
+        assert_eq!(poseidon_permute_batch(states.as_mut_ptr(), 1000), SUCCESS);
+        let after = stats();
+        assert!(after.poseidon.calls > before.poseidon.calls);
+        assert!(after.poseidon.items >= before.poseidon.items + 1000);
+        assert!(after.poseidon.nanoseconds > before.poseidon.nanoseconds);
+
+        // The other tests process far fewer than 1000 states
+        assert_eq!(starknet_crypto_stats_reset(), SUCCESS);
+        assert!(stats().poseidon.items < 1000);
+    }
+}
@@ -0,0 +1,251 @@
+// Hashes of Cairo programs run by the Starknet bootloader.
+//
+// The bootloader commits to the program it loads with the Pedersen hash chain of its data, like
+// `compute_program_hash_chain` of cairo-lang: the bootloader version, the main offset, the number of
+// builtins and their names as short strings, then the bytecode. The chain of n felts prefixed by
+// n hashes as H(n, H(a_1, H(a_2, ... H(a_{n-1}, a_n)))).
+//
+// The public input of a proof of the run commits to its public memory, the (address, value) pairs
+// of the program segment, of the builtin pointers at the start of the execution segment and of the
+// output segment, hashed in that order into the main page hash. The fact SHARP registers for a run
+// whose output is a single page is the keccak256 of the program hash and of the output words.
+use alloc::vec::Vec;
+
+use sha3::{Digest, Keccak256};
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    batch::pedersen_hash_pair, bytes_from_array, bytes_from_field_element, ffi_guard,
+    field_elements_from_bytes, hades::poseidon_hash_many, hash_on_elements,
+    try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_HASH_KIND, HASH_PEDERSEN,
+    HASH_POSEIDON, SUCCESS,
+};
+
+// The Pedersen hash chain of elements, folded from the last one.
+fn hash_chain(elements: &[Felt]) -> Felt {
+    let (last, rest) = elements.split_last().unwrap();
+    rest.iter()
+        .rev()
+        .fold(*last, |acc, element| pedersen_hash_pair(element, &acc))
+}
+
+/// Computes the hash the Starknet bootloader commits to for a program, writing it into the last
+/// argument, from `count` contiguous felts of the program without the bootloader version: its main
+/// offset, the number of its builtins, their names as short strings and its bytecode. The hash is
+/// the Pedersen hash chain of the length and then the version followed by those felts
+#[no_mangle]
+pub extern "C" fn compute_program_hash(
+    program_felts: *const u8,
+    count: usize,
+    version: u32,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let program = match field_elements_from_bytes(program_felts, count) {
+            Ok(program) => program,
+            Err(status) => return status,
+        };
+        let mut chain = Vec::with_capacity(count + 2);
+        chain.extend([Felt::from(count + 1), Felt::from(version)]);
+        chain.extend(program);
+        bytes_from_field_element(hash_chain(&chain), result);
+        SUCCESS
+    })
+}
+
+/// Computes the main page hash of the public memory of a run, writing it into the last argument:
+/// the hash of the (address, value) pairs of the `program_len` felts of the program from
+/// `program_base`, the `pointers_len` builtin pointers from `execution_base` and the `output_len`
+/// felts of output from `output_base`, flattened in that order. HASH_PEDERSEN hashes them with the
+/// hash on elements, HASH_POSEIDON with the Poseidon hash many
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn public_memory_hash(
+    program: *const u8,
+    program_len: usize,
+    program_base: u64,
+    builtin_pointers: *const u8,
+    pointers_len: usize,
+    execution_base: u64,
+    output: *const u8,
+    output_len: usize,
+    output_base: u64,
+    hash_kind: u32,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let segments = [
+            (program, program_len, program_base),
+            (builtin_pointers, pointers_len, execution_base),
+            (output, output_len, output_base),
+        ];
+        let mut memory = Vec::new();
+        for (values, len, base) in segments {
+            let values = match field_elements_from_bytes(values, len) {
+                Ok(values) => values,
+                Err(status) => return status,
+            };
+            let addresses = (0u64..).map(|offset| Felt::from(base) + offset);
+            memory.extend(
+                addresses
+                    .zip(values)
+                    .flat_map(|(address, value)| [address, value]),
+            );
+        }
+        let hash = match hash_kind {
+            HASH_PEDERSEN => hash_on_elements(&memory),
+            HASH_POSEIDON => poseidon_hash_many(&memory),
+            _ => return ERR_INVALID_HASH_KIND,
+        };
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the fact SHARP registers for a run of the program of `program_hash` with `output_len`
+/// contiguous felts of output in a single page, the keccak256 of the program hash and of the
+/// output as 32 bytes big endian words, writing the 32 bytes digest into the last argument
+#[no_mangle]
+pub extern "C" fn program_fact(
+    program_hash: Bytes,
+    output: *const u8,
+    output_len: usize,
+    result: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(program_hash, result);
+        let Some(program_hash) = try_field_element_from_bytes(program_hash) else {
+            return ERR_INVALID_FELT;
+        };
+        let output = match field_elements_from_bytes(output, output_len) {
+            Ok(output) => output,
+            Err(status) => return status,
+        };
+        let mut hasher = Keccak256::new();
+        for felt in output {
+            hasher.update(felt.to_bytes_be());
+        }
+        let output_hash = hasher.finalize();
+        let fact: [u8; 32] = Keccak256::new()
+            .chain_update(program_hash.to_bytes_be())
+            .chain_update(output_hash)
+            .finalize()
+            .into();
+        bytes_from_array(&fact, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ERR_INVALID_LENGTH;
+    use starknet_crypto_rs::pedersen_hash;
+
+    fn program_hash(program: &[Felt], version: u32) -> Result<Felt, i32> {
+        let bytes = bytes(program);
+        let mut result = [0u8; 32];
+        match compute_program_hash(bytes.as_ptr(), program.len(), version, result.as_mut_ptr()) {
+            SUCCESS => Ok(Felt::from_bytes_be(&result)),
+            status => Err(status),
+        }
+    }
+
+    fn bytes(felts: &[Felt]) -> Vec<u8> {
+        felts.iter().flat_map(|felt| felt.to_bytes_be()).collect()
+    }
+
+    #[test]
+    fn test_public_memory_hash() {
+        let felt = |value: u64| Felt::from(value);
+        let (program, pointers, output) = (
+            bytes(&[felt(10), felt(11)]),
+            bytes(&[felt(100)]),
+            bytes(&[felt(7), felt(8)]),
+        );
+        let hash = |hash_kind| {
+            let mut result = [0u8; 32];
+            let status = public_memory_hash(
+                program.as_ptr(),
+                2,
+                1,
+                pointers.as_ptr(),
+                1,
+                50,
+                output.as_ptr(),
+                2,
+                100,
+                hash_kind,
+                result.as_mut_ptr(),
+            );
+            (status, Felt::from_bytes_be(&result))
+        };
+        let memory = [1, 10, 2, 11, 50, 100, 100, 7, 101, 8].map(felt);
+        assert_eq!(hash(HASH_PEDERSEN), (SUCCESS, hash_on_elements(&memory)));
+        assert_eq!(hash(HASH_POSEIDON), (SUCCESS, poseidon_hash_many(&memory)));
+        assert_eq!(hash(2).0, ERR_INVALID_HASH_KIND);
+    }
+
+    #[test]
+    fn test_program_fact() {
+        let program_hash = Felt::from(0x1234u64);
+        let output = bytes(&[Felt::ONE, Felt::TWO]);
+        let mut fact = [0u8; 32];
+        assert_eq!(
+            program_fact(
+                program_hash.to_bytes_be().as_mut_ptr(),
+                output.as_ptr(),
+                2,
+                fact.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        let output_hash = Keccak256::digest(&output);
+        let expected: [u8; 32] =
+            Keccak256::digest([&program_hash.to_bytes_be()[..], &output_hash].concat()).into();
+        assert_eq!(fact, expected);
+
+        assert_eq!(
+            program_fact(
+                program_hash.to_bytes_be().as_mut_ptr(),
+                core::ptr::null(),
+                2,
+                fact.as_mut_ptr()
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_compute_program_hash() {
+        let felt = |value: u64| Felt::from(value);
+        let output = Felt::from_bytes_be_slice(b"output");
+        // main = 0, the output builtin and the bytecode of `ret`
+        let program = [felt(0), felt(1), output, felt(0x208b7fff7fff7ffe)];
+        let expected = pedersen_hash(
+            &felt(5),
+            &pedersen_hash(
+                &felt(0),
+                &pedersen_hash(
+                    &felt(0),
+                    &pedersen_hash(&felt(1), &pedersen_hash(&output, &program[3])),
+                ),
+            ),
+        );
+        assert_eq!(program_hash(&program, 0), Ok(expected));
+        assert_ne!(program_hash(&program, 1), Ok(expected));
+        assert_eq!(program_hash(&[], 2), Ok(pedersen_hash(&felt(1), &felt(2))));
+
+        let mut result = [0u8; 32];
+        assert_eq!(
+            compute_program_hash(core::ptr::null(), 1, 0, result.as_mut_ptr()),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            compute_program_hash([0xffu8; 32].as_ptr(), 1, 0, result.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+    }
+}
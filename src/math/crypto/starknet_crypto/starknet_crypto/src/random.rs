@@ -0,0 +1,238 @@
+// Uniformly random felts and Stark private keys, from the operating system's random number
+// generator.
+//
+// Both rejection sample 252 bits integers, which fall below the STARK prime and the curve order
+// about half of the time. In the deterministic mode of starknet_crypto_set_deterministic, the
+// integers are the felts of a process wide generator of drbg.rs instead, seeded with the
+// starknet_keccak of the seed bytes, so that a test run draws the same felts and keys every time.
+// The signatures and VRF proofs already take their nonces from RFC 6979, so these and the ephemeral
+// keys of stark_encrypt are the only draws of randomness.
+use std::sync::{Mutex, PoisonError};
+
+use starknet_curve::curve_params::EC_ORDER;
+use starknet_types_core::felt::Felt;
+use zeroize::Zeroizing;
+
+use crate::{
+    bytes_from_field_element,
+    drbg::Drbg,
+    ffi_guard,
+    keccak::sn_keccak,
+    secret::{bytes_from_secret, SecretFelt},
+    slice_from_ptr, try_felt_from_array, Bytes, ERR_INVALID_LENGTH, ERR_RNG, SUCCESS,
+};
+
+static DETERMINISTIC: Mutex<Option<Drbg>> = Mutex::new(None);
+
+// Fills the bytes from the deterministic generator when one is set, from the operating system's
+// generator otherwise.
+fn fill(bytes: &mut [u8; 32]) -> Result<(), i32> {
+    match DETERMINISTIC
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+    {
+        Some(drbg) => {
+            *bytes = drbg.next_felt().to_bytes_be();
+            Ok(())
+        }
+        None => getrandom::getrandom(bytes).or(Err(ERR_RNG)),
+    }
+}
+
+// Draws 252 bits integers until one is accepted.
+fn sample(accept: impl Fn(&Felt) -> bool) -> Result<Zeroizing<SecretFelt>, i32> {
+    let mut bytes = Zeroizing::new([0u8; 32]);
+    loop {
+        fill(&mut bytes)?;
+        bytes[0] &= 0x0f;
+        if let Some(felt) = try_felt_from_array(&bytes) {
+            if accept(&felt) {
+                return Ok(Zeroizing::new(SecretFelt(felt)));
+            }
+        }
+    }
+}
+
+// A uniformly random Stark private key, between 1 and the curve order excluded.
+pub(crate) fn draw_private_key() -> Result<Zeroizing<SecretFelt>, i32> {
+    sample(|key| *key != Felt::ZERO && *key < EC_ORDER)
+}
+
+/// Makes felt_random and stark_private_key_random draw from a generator seeded with the `len`
+/// bytes of the seed, the same felts and keys in the same order for the same seed, until the next
+/// call. A NULL seed goes back to the operating system's random number generator. The generator is
+/// shared by the threads, so their draws only repeat when they happen in the same order
+#[no_mangle]
+pub extern "C" fn starknet_crypto_set_deterministic(seed: *const u8, len: usize) -> i32 {
+    ffi_guard(|| {
+        let drbg = if seed.is_null() {
+            None
+        } else {
+            let seed = match slice_from_ptr(seed, len) {
+                Ok(seed) => seed,
+                Err(status) => return status,
+            };
+            Some(Drbg::new(Felt::from_bytes_be(&sn_keccak(seed))))
+        };
+        *DETERMINISTIC.lock().unwrap_or_else(PoisonError::into_inner) = drbg;
+        SUCCESS
+    })
+}
+
+/// Writes a uniformly random felt into the argument, or the next one of the deterministic mode of
+/// starknet_crypto_set_deterministic. Returns ERR_RNG when the operating system's random number
+/// generator fails
+#[no_mangle]
+pub extern "C" fn felt_random(result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        match sample(|_| true) {
+            Ok(felt) => {
+                bytes_from_field_element(**felt, result);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Writes `count` contiguous uniformly random felts into the buffer, the next `count` felts of
+/// felt_random in the deterministic mode. Returns ERR_INVALID_LENGTH for a NULL buffer of a non
+/// zero count, and ERR_RNG when the operating system's random number generator fails, with the
+/// felts drawn before the failure written
+#[no_mangle]
+pub extern "C" fn felt_random_fill(buf: *mut u8, count: usize) -> i32 {
+    ffi_guard(|| {
+        if count > isize::MAX as usize / 32 || (buf.is_null() && count > 0) {
+            return ERR_INVALID_LENGTH;
+        }
+        for i in 0..count {
+            match sample(|_| true) {
+                Ok(felt) => bytes_from_field_element(**felt, unsafe { buf.add(32 * i) }),
+                Err(status) => return status,
+            }
+        }
+        SUCCESS
+    })
+}
+
+/// Writes a uniformly random Stark private key, between 1 and the curve order excluded, into the
+/// argument, or the next one of the deterministic mode. Returns ERR_RNG when the operating system's
+/// random number generator fails
+#[no_mangle]
+pub extern "C" fn stark_private_key_random(private_key: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key);
+        match draw_private_key() {
+            Ok(key) => {
+                bytes_from_secret(&key, private_key);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // The tests of the deterministic mode would otherwise change the draws of the others
+    pub(crate) static GENERATOR: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_felt_random() {
+        let _generator = GENERATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut felts = [[0u8; 32]; 2];
+        for felt in &mut felts {
+            assert_eq!(felt_random(felt.as_mut_ptr()), SUCCESS);
+            assert!(try_felt_from_array(felt).is_some());
+        }
+        assert_ne!(felts[0], felts[1]);
+    }
+
+    #[test]
+    fn test_felt_random_fill() {
+        let _generator = GENERATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut felts = [[0u8; 32]; 8];
+        assert_eq!(felt_random_fill(felts.as_mut_ptr().cast(), 8), SUCCESS);
+        for (i, felt) in felts.iter().enumerate() {
+            assert!(try_felt_from_array(felt).is_some());
+            assert!(!felts[..i].contains(felt));
+        }
+
+        // The felts of felt_random, in order
+        assert_eq!(
+            starknet_crypto_set_deterministic(b"fill".as_ptr(), 4),
+            SUCCESS
+        );
+        assert_eq!(felt_random_fill(felts.as_mut_ptr().cast(), 8), SUCCESS);
+        assert_eq!(
+            starknet_crypto_set_deterministic(b"fill".as_ptr(), 4),
+            SUCCESS
+        );
+        for felt in &felts {
+            let mut expected = [0u8; 32];
+            assert_eq!(felt_random(expected.as_mut_ptr()), SUCCESS);
+            assert_eq!(*felt, expected);
+        }
+        assert_eq!(
+            starknet_crypto_set_deterministic(core::ptr::null(), 0),
+            SUCCESS
+        );
+
+        assert_eq!(felt_random_fill(core::ptr::null_mut(), 0), SUCCESS);
+        assert_eq!(
+            felt_random_fill(core::ptr::null_mut(), 1),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            felt_random_fill(felts.as_mut_ptr().cast(), usize::MAX / 16),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_stark_private_key_random() {
+        let _generator = GENERATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut private_key = [0u8; 32];
+        for _ in 0..16 {
+            assert_eq!(stark_private_key_random(private_key.as_mut_ptr()), SUCCESS);
+            let key = Felt::from_bytes_be(&private_key);
+            assert!(key != Felt::ZERO && key < EC_ORDER);
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let _generator = GENERATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let draw = || {
+            let (mut felt, mut private_key) = ([0u8; 32], [0u8; 32]);
+            assert_eq!(felt_random(felt.as_mut_ptr()), SUCCESS);
+            assert_eq!(stark_private_key_random(private_key.as_mut_ptr()), SUCCESS);
+            (felt, private_key)
+        };
+        let seed = b"replay";
+        assert_eq!(starknet_crypto_set_deterministic(seed.as_ptr(), 6), SUCCESS);
+        let draws = [draw(), draw()];
+        assert_ne!(draws[0], draws[1]);
+
+        // The first felt is the first one of the generator of the seed's starknet_keccak
+        let mut drbg = Drbg::new(Felt::from_bytes_be(&sn_keccak(seed)));
+        assert_eq!(draws[0].0, drbg.next_felt().to_bytes_be());
+
+        assert_eq!(starknet_crypto_set_deterministic(seed.as_ptr(), 6), SUCCESS);
+        assert_eq!([draw(), draw()], draws);
+        assert_eq!(starknet_crypto_set_deterministic(seed.as_ptr(), 5), SUCCESS);
+        assert_ne!(draw(), draws[0]);
+        // The empty seed is a seed too
+        assert_eq!(starknet_crypto_set_deterministic([].as_ptr(), 0), SUCCESS);
+        let empty = draw();
+        assert_eq!(
+            starknet_crypto_set_deterministic(core::ptr::null(), 0),
+            SUCCESS
+        );
+        assert_ne!(draw(), empty);
+    }
+}
@@ -0,0 +1,223 @@
+// Schnorr signatures over the STARK curve, linear in the private keys and nonces so that they can be
+// aggregated by multi-signature schemes like MuSig, which plain ECDSA can't.
+//
+// The encoding follows BIP-340 with Poseidon as the challenge hash. Public keys and nonce points are
+// x coordinates, standing for the point with the smallest of the two y coordinates, so the signer
+// negates its private key and nonce when their point has the other one. A signature of the message
+// m by the private key d, with the public key P and the nonce k, is the pair of felts
+//
+//     r = (k * G).x
+//     s = k + e * d mod n, with e = poseidon_hash_many(r, P.x, m) mod n
+//
+// and it is valid when s * G - e * P is the point of x coordinate r and of the smallest y.
+#[cfg(feature = "signing")]
+use elliptic_curve::bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use elliptic_curve::bigint::{Encoding, NonZero, U256};
+use starknet_curve::curve_params::GENERATOR;
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    backend::{self, Point},
+    ffi_guard,
+    hades::poseidon_hash_many,
+    try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_POINT, ERR_INVALID_S,
+    ERR_INVALID_SIGNATURE, SUCCESS,
+};
+#[cfg(feature = "signing")]
+use crate::{bytes_from_field_element, secret::try_secret_from_bytes, ERR_INVALID_K};
+
+pub(crate) const ORDER: U256 =
+    U256::from_be_hex("0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f");
+
+pub(crate) fn felt_from_field_element(felt: &Felt) -> backend::Felt {
+    backend::felt_from_bytes(&felt.to_bytes_be()).unwrap()
+}
+
+pub(crate) fn generator() -> Point {
+    let (x, y) = (
+        felt_from_field_element(&GENERATOR.x()),
+        felt_from_field_element(&GENERATOR.y()),
+    );
+    backend::point_from_coordinates(&x, &y).unwrap()
+}
+
+pub(crate) fn reduce(felt: &Felt) -> U256 {
+    U256::from_be_bytes(felt.to_bytes_be()).rem(&NonZero::new(ORDER).unwrap())
+}
+
+// Whether y is the smallest of the two y coordinates of the points of x coordinate x.
+pub(crate) fn has_smallest_y(x: &backend::Felt, y: &backend::Felt) -> bool {
+    backend::point_y_from_x(x).is_some_and(|smallest| smallest == *y)
+}
+
+fn challenge(r: Felt, public_key: Felt, message: Felt) -> U256 {
+    reduce(&poseidon_hash_many(&[r, public_key, message]))
+}
+
+// The x coordinate of scalar * G for a nonzero scalar, with the scalar negated when its point
+// doesn't have the smallest y.
+#[cfg(feature = "signing")]
+pub(crate) fn normalized_point(scalar: U256) -> (U256, Felt) {
+    let (x, y) = backend::point_mul(&generator(), &scalar.to_be_bytes()).unwrap();
+    let x_felt = Felt::from_bytes_be(&backend::bytes_from_felt(&x));
+    if has_smallest_y(&x, &y) {
+        (scalar, x_felt)
+    } else {
+        (scalar.neg_mod(&ORDER), x_felt)
+    }
+}
+
+/// Signs a message with a private key and a nonce k, both felts nonzero modulo the curve order,
+/// writing the Schnorr signature (r, s) into the last two arguments. k must be uniformly random and
+/// never reused. Not constant time: the scalar multiplications branch on the secrets
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn schnorr_sign(
+    private_key_bytes: Bytes,
+    message_bytes: Bytes,
+    k_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key_bytes, message_bytes, k_bytes, r_bytes, s_bytes);
+        let (Some(private_key), Some(message), Some(k)) = (
+            try_secret_from_bytes(private_key_bytes),
+            try_field_element_from_bytes(message_bytes),
+            try_secret_from_bytes(k_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let (private_key, k) = (reduce(&private_key), reduce(&k));
+        if private_key == U256::ZERO {
+            return ERR_INVALID_FELT;
+        }
+        if k == U256::ZERO {
+            return ERR_INVALID_K;
+        }
+
+        let (private_key, public_key) = normalized_point(private_key);
+        let (k, r) = normalized_point(k);
+        let params = DynResidueParams::new(&ORDER);
+        let e = DynResidue::new(&challenge(r, public_key, message), params);
+        let s = (e * DynResidue::new(&private_key, params))
+            .retrieve()
+            .add_mod(&k, &ORDER);
+        bytes_from_field_element(r, r_bytes);
+        bytes_from_field_element(Felt::from_bytes_be(&s.to_be_bytes()), s_bytes);
+        SUCCESS
+    })
+}
+
+/// Verifies a Schnorr signature (r, s) of a message by a public key. Returns SUCCESS for a valid
+/// signature, ERR_INVALID_SIGNATURE for an invalid one, ERR_INVALID_S when s is not lower than the
+/// curve order, ERR_INVALID_POINT when the public key is not the x coordinate of a point and
+/// ERR_INVALID_FELT when an argument is not a felt
+#[no_mangle]
+pub extern "C" fn schnorr_verify(
+    public_key_bytes: Bytes,
+    message_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(public_key_bytes, message_bytes, r_bytes, s_bytes);
+        let (Some(public_key), Some(message), Some(r), Some(s)) = (
+            try_field_element_from_bytes(public_key_bytes),
+            try_field_element_from_bytes(message_bytes),
+            try_field_element_from_bytes(r_bytes),
+            try_field_element_from_bytes(s_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let s = U256::from_be_bytes(s.to_bytes_be());
+        if s >= ORDER {
+            return ERR_INVALID_S;
+        }
+        let public_key_x = felt_from_field_element(&public_key);
+        let Some(public_key_y) = backend::point_y_from_x(&public_key_x) else {
+            return ERR_INVALID_POINT;
+        };
+        let public_key_point =
+            backend::point_from_coordinates(&public_key_x, &public_key_y).unwrap();
+
+        let e = challenge(r, public_key, message);
+        let scalars = [s.to_be_bytes(), e.neg_mod(&ORDER).to_be_bytes()];
+        match backend::point_msm(&[generator(), public_key_point], &scalars) {
+            Some((x, y)) if x == felt_from_field_element(&r) && has_smallest_y(&x, &y) => SUCCESS,
+            _ => ERR_INVALID_SIGNATURE,
+        }
+    })
+}
+
+#[cfg(all(test, feature = "signing"))]
+mod tests {
+    use super::*;
+    use starknet_curve::curve_params::EC_ORDER;
+
+    fn felt(value: u64) -> [u8; 32] {
+        Felt::from(value).to_bytes_be()
+    }
+
+    fn sign(private_key: u64, message: u64, k: u64) -> ([u8; 32], [u8; 32]) {
+        let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+        let status = schnorr_sign(
+            felt(private_key).as_mut_ptr(),
+            felt(message).as_mut_ptr(),
+            felt(k).as_mut_ptr(),
+            r.as_mut_ptr(),
+            s.as_mut_ptr(),
+        );
+        assert_eq!(status, SUCCESS);
+        (r, s)
+    }
+
+    fn verify(public_key: &[u8; 32], message: u64, r: &[u8; 32], s: &[u8; 32]) -> i32 {
+        let (mut public_key, mut r, mut s) = (*public_key, *r, *s);
+        schnorr_verify(
+            public_key.as_mut_ptr(),
+            felt(message).as_mut_ptr(),
+            r.as_mut_ptr(),
+            s.as_mut_ptr(),
+        )
+    }
+
+    #[test]
+    fn test_schnorr() {
+        assert_eq!(ORDER.to_be_bytes(), EC_ORDER.to_bytes_be());
+        // Enough private keys and nonces for both y coordinates of their points
+        let keys = 1..=8u64;
+        assert!(keys
+            .clone()
+            .any(|key| normalized_point(U256::from(key)).0 != U256::from(key)));
+        assert!(keys
+            .clone()
+            .any(|key| normalized_point(U256::from(key)).0 == U256::from(key)));
+        for (private_key, k) in keys.clone().zip(keys.rev()) {
+            let public_key = starknet_crypto_rs::get_public_key(&private_key.into()).to_bytes_be();
+            let (r, s) = sign(private_key, 42, k);
+            assert_eq!(verify(&public_key, 42, &r, &s), SUCCESS);
+            assert_eq!(verify(&public_key, 43, &r, &s), ERR_INVALID_SIGNATURE);
+            assert_eq!(verify(&felt(7), 42, &r, &s), ERR_INVALID_SIGNATURE);
+            assert_eq!(verify(&public_key, 42, &s, &r), ERR_INVALID_SIGNATURE);
+        }
+
+        let public_key = starknet_crypto_rs::get_public_key(&Felt::ONE).to_bytes_be();
+        let (r, _) = sign(1, 42, 2);
+        assert_eq!(
+            verify(&public_key, 42, &r, &ORDER.to_be_bytes()),
+            ERR_INVALID_S
+        );
+        let (mut zero, mut r_out, mut s_out) = (felt(0), [0u8; 32], [0u8; 32]);
+        assert_eq!(
+            schnorr_sign(
+                felt(1).as_mut_ptr(),
+                felt(42).as_mut_ptr(),
+                zero.as_mut_ptr(),
+                r_out.as_mut_ptr(),
+                s_out.as_mut_ptr()
+            ),
+            ERR_INVALID_K
+        );
+    }
+}
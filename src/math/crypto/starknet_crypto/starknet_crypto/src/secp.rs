@@ -0,0 +1,206 @@
+// Curve agnostic implementation of the secp256k1 and secp256r1 FFI operations.
+//
+// Coordinates and scalars are 32 bytes big endian integers. Coordinates must be lower than the
+// field prime, while scalars are reduced modulo the curve order. The scalar and point arithmetic of
+// k256 and p256 runs in constant time.
+use elliptic_curve::{
+    group::Curve as _,
+    ops::{Invert, Reduce},
+    point::DecompressPoint,
+    sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    subtle::Choice,
+    AffinePoint, Curve, CurveArithmetic, FieldBytes, FieldBytesSize, ProjectivePoint, Scalar,
+};
+
+use crate::{
+    array_from_bytes, bytes_from_array, Bytes, ERR_DIVISION_BY_ZERO, ERR_INVALID_POINT,
+    ERR_POINT_AT_INFINITY, SUCCESS,
+};
+
+// The curves exposed over FFI: 256 bits short Weierstrass curves with SEC1 point encodings.
+pub(crate) trait SecpCurve:
+    CurveArithmetic<AffinePoint = Self::Affine> + Curve<FieldBytesSize = Self::Size>
+{
+    type Affine: FromEncodedPoint<Self> + ToEncodedPoint<Self> + DecompressPoint<Self>;
+    type Size: ModulusSize;
+}
+
+impl<C> SecpCurve for C
+where
+    C: CurveArithmetic,
+    C::AffinePoint: FromEncodedPoint<C> + ToEncodedPoint<C> + DecompressPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    type Affine = C::AffinePoint;
+    type Size = FieldBytesSize<C>;
+}
+
+fn field_bytes<C: SecpCurve>(bytes: Bytes) -> FieldBytes<C> {
+    FieldBytes::<C>::clone_from_slice(&array_from_bytes(bytes))
+}
+
+// Reads an affine point from its C representation, checking that it lies on the curve.
+pub(crate) fn point_from_bytes<C: SecpCurve>(
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> Result<AffinePoint<C>, i32> {
+    let encoded = EncodedPoint::<C>::from_affine_coordinates(
+        &field_bytes::<C>(x_bytes),
+        &field_bytes::<C>(y_bytes),
+        false,
+    );
+    Option::from(AffinePoint::<C>::from_encoded_point(&encoded)).ok_or(ERR_INVALID_POINT)
+}
+
+// Writes a point into its C representation. The point at infinity has no affine coordinates
+// and is reported as an error instead.
+fn bytes_from_point<C: SecpCurve>(
+    point: &ProjectivePoint<C>,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    let encoded = point.to_affine().to_encoded_point(false);
+    let (Some(x), Some(y)) = (encoded.x(), encoded.y()) else {
+        return ERR_POINT_AT_INFINITY;
+    };
+    bytes_from_array(x, x_bytes);
+    bytes_from_array(y, y_bytes);
+    SUCCESS
+}
+
+// Reads a 256 bits integer and reduces it modulo the curve order.
+fn scalar_from_bytes<C: SecpCurve>(bytes: Bytes) -> Scalar<C> {
+    <Scalar<C> as Reduce<C::Uint>>::reduce_bytes(&field_bytes::<C>(bytes))
+}
+
+fn bytes_from_scalar<C: SecpCurve>(scalar: Scalar<C>, bytes: Bytes) {
+    let scalar_bytes: FieldBytes<C> = scalar.into();
+    bytes_from_array(&scalar_bytes, bytes);
+}
+
+pub(crate) fn add<C: SecpCurve>(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    q_x_bytes: Bytes,
+    q_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    let p = match point_from_bytes::<C>(p_x_bytes, p_y_bytes) {
+        Ok(point) => point,
+        Err(status) => return status,
+    };
+    let q = match point_from_bytes::<C>(q_x_bytes, q_y_bytes) {
+        Ok(point) => point,
+        Err(status) => return status,
+    };
+
+    let sum = ProjectivePoint::<C>::from(p) + ProjectivePoint::<C>::from(q);
+    bytes_from_point::<C>(&sum, result_x_bytes, result_y_bytes)
+}
+
+pub(crate) fn mul<C: SecpCurve>(
+    scalar_bytes: Bytes,
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    let p = match point_from_bytes::<C>(p_x_bytes, p_y_bytes) {
+        Ok(point) => point,
+        Err(status) => return status,
+    };
+
+    let product = ProjectivePoint::<C>::from(p) * scalar_from_bytes::<C>(scalar_bytes);
+    bytes_from_point::<C>(&product, result_x_bytes, result_y_bytes)
+}
+
+pub(crate) fn get_point_from_x<C: SecpCurve>(
+    x_bytes: Bytes,
+    v_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    // Like the `get_point_from_x` hint, pick the root with the same parity as v
+    let y_is_odd = Choice::from(array_from_bytes(v_bytes)[31] & 1);
+    let point: Option<AffinePoint<C>> =
+        AffinePoint::<C>::decompress(&field_bytes::<C>(x_bytes), y_is_odd).into();
+    let Some(point) = point else {
+        return ERR_INVALID_POINT;
+    };
+
+    let encoded = point.to_encoded_point(false);
+    bytes_from_array(encoded.y().unwrap(), y_bytes);
+    SUCCESS
+}
+
+// Writes the 33 bytes SEC1 compressed encoding of a point, its x coordinate prefixed by 0x02 for an
+// even y and 0x03 for an odd one.
+pub(crate) fn point_compress<C: SecpCurve>(
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+    compressed: *mut u8,
+) -> i32 {
+    let point = match point_from_bytes::<C>(x_bytes, y_bytes) {
+        Ok(point) => point,
+        Err(status) => return status,
+    };
+    let encoded = point.to_encoded_point(true);
+    unsafe { core::ptr::copy_nonoverlapping(encoded.as_bytes().as_ptr(), compressed, 33) };
+    SUCCESS
+}
+
+pub(crate) fn point_decompress<C: SecpCurve>(
+    compressed: *const u8,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    let compressed = unsafe { core::slice::from_raw_parts(compressed, 33) };
+    // Only the compressed tags, not the identity or the uncompressed encodings of the same length
+    let Ok(encoded) = EncodedPoint::<C>::from_bytes(compressed) else {
+        return ERR_INVALID_POINT;
+    };
+    if !encoded.is_compressed() {
+        return ERR_INVALID_POINT;
+    }
+    let point: Option<AffinePoint<C>> = AffinePoint::<C>::from_encoded_point(&encoded).into();
+    let Some(point) = point else {
+        return ERR_INVALID_POINT;
+    };
+    bytes_from_point::<C>(&point.into(), x_bytes, y_bytes)
+}
+
+pub(crate) fn scalar_add<C: SecpCurve>(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    let sum = scalar_from_bytes::<C>(a_bytes) + scalar_from_bytes::<C>(b_bytes);
+    bytes_from_scalar::<C>(sum, result_bytes);
+    SUCCESS
+}
+
+pub(crate) fn scalar_mul<C: SecpCurve>(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    let product = scalar_from_bytes::<C>(a_bytes) * scalar_from_bytes::<C>(b_bytes);
+    bytes_from_scalar::<C>(product, result_bytes);
+    SUCCESS
+}
+
+pub(crate) fn scalar_inverse<C: SecpCurve>(a_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    let inverse: Option<Scalar<C>> = scalar_from_bytes::<C>(a_bytes).invert().into();
+    let Some(inverse) = inverse else {
+        return ERR_DIVISION_BY_ZERO;
+    };
+    bytes_from_scalar::<C>(inverse, result_bytes);
+    SUCCESS
+}
+
+pub(crate) fn scalar_div<C: SecpCurve>(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    // The `div_mod_n` hint: a / b modulo the curve order
+    let b_inverse: Option<Scalar<C>> = scalar_from_bytes::<C>(b_bytes).invert().into();
+    let Some(b_inverse) = b_inverse else {
+        return ERR_DIVISION_BY_ZERO;
+    };
+    bytes_from_scalar::<C>(scalar_from_bytes::<C>(a_bytes) * b_inverse, result_bytes);
+    SUCCESS
+}
+
+#[cfg(test)]
+pub(crate) fn hex_bytes(hex: &str) -> [u8; 32] {
+    crate::hex_bytes(hex).try_into().unwrap()
+}
@@ -0,0 +1,646 @@
+// Arithmetic over the secp256k1 curve, used by the `starknet_with_keccak` layout hints.
+use k256::{
+    ecdsa::{signature::hazmat::PrehashVerifier, RecoveryId, Signature, VerifyingKey},
+    elliptic_curve::{scalar::IsHigh, sec1::ToEncodedPoint},
+    NonZeroScalar, Secp256k1,
+};
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    array_from_bytes, bytes_from_array, ffi_guard, secp, Bytes, ERR_INVALID_POINT, ERR_INVALID_R,
+    ERR_INVALID_S, ERR_INVALID_SIGNATURE, ERR_INVALID_V, SUCCESS,
+};
+
+// Reads a non zero integer lower than the curve order.
+fn non_zero_scalar_from_bytes(bytes: Bytes) -> Option<NonZeroScalar> {
+    NonZeroScalar::from_repr(array_from_bytes(bytes).into()).into()
+}
+
+/// Adds the secp256k1 points (x, y) given by the first four arguments, writing the affine result
+/// into the last two
+#[no_mangle]
+pub extern "C" fn secp256k1_add(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    q_x_bytes: Bytes,
+    q_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        secp::add::<Secp256k1>(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            result_x_bytes,
+            result_y_bytes,
+        )
+    })
+}
+
+/// Multiplies the secp256k1 point given by the second and third arguments by a scalar, writing the
+/// affine result into the last two arguments. Constant time with respect to the scalar
+#[no_mangle]
+pub extern "C" fn secp256k1_mul(
+    scalar_bytes: Bytes,
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            scalar_bytes,
+            p_x_bytes,
+            p_y_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        secp::mul::<Secp256k1>(
+            scalar_bytes,
+            p_x_bytes,
+            p_y_bytes,
+            result_x_bytes,
+            result_y_bytes,
+        )
+    })
+}
+
+/// Recovers the y coordinate of the secp256k1 point with the given x coordinate and the same parity
+/// as v
+#[no_mangle]
+pub extern "C" fn secp256k1_get_point_from_x(
+    x_bytes: Bytes,
+    v_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, v_bytes, y_bytes);
+        secp::get_point_from_x::<Secp256k1>(x_bytes, v_bytes, y_bytes)
+    })
+}
+
+/// Writes the 33 bytes SEC1 compressed encoding of the secp256k1 point (x, y) into the last
+/// argument: 0x02 for an even y or 0x03 for an odd one, followed by x
+#[no_mangle]
+pub extern "C" fn secp256k1_point_compress(
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+    compressed: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes, compressed);
+        secp::point_compress::<Secp256k1>(x_bytes, y_bytes, compressed)
+    })
+}
+
+/// Decodes a 33 bytes SEC1 compressed secp256k1 point, writing its coordinates into the last two
+/// arguments. Returns ERR_INVALID_POINT for another prefix than 0x02 or 0x03, or when the x
+/// coordinate is not the one of a point
+#[no_mangle]
+pub extern "C" fn secp256k1_point_decompress(
+    compressed: *const u8,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(compressed, x_bytes, y_bytes);
+        secp::point_decompress::<Secp256k1>(compressed, x_bytes, y_bytes)
+    })
+}
+
+/// Adds two scalars modulo the secp256k1 curve order
+#[no_mangle]
+pub extern "C" fn secp256k1_scalar_add(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        secp::scalar_add::<Secp256k1>(a_bytes, b_bytes, result_bytes)
+    })
+}
+
+/// Multiplies two scalars modulo the secp256k1 curve order
+#[no_mangle]
+pub extern "C" fn secp256k1_scalar_mul(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        secp::scalar_mul::<Secp256k1>(a_bytes, b_bytes, result_bytes)
+    })
+}
+
+/// Inverts a scalar modulo the secp256k1 curve order, writing the result into the last argument.
+/// Returns ERR_DIVISION_BY_ZERO when the scalar is zero modulo the order
+#[no_mangle]
+pub extern "C" fn secp256k1_scalar_inverse(a_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, result_bytes);
+        secp::scalar_inverse::<Secp256k1>(a_bytes, result_bytes)
+    })
+}
+
+/// Divides the first scalar by the second modulo the secp256k1 curve order
+#[no_mangle]
+pub extern "C" fn secp256k1_scalar_div(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        secp::scalar_div::<Secp256k1>(a_bytes, b_bytes, result_bytes)
+    })
+}
+
+/// Recovers the Ethereum address that signed a 32 bytes message hash with the signature (r, s, v),
+/// writing the 20 bytes address into the last argument. v is either the recovery id (0 or 1) or its
+/// Ethereum encoding (27 or 28), and s must be in the lower half of the scalars
+#[no_mangle]
+pub extern "C" fn secp256k1_recover_eth_address(
+    message_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+    v: u32,
+    address_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(message_bytes, r_bytes, s_bytes, address_bytes);
+        let Some(r) = non_zero_scalar_from_bytes(r_bytes) else {
+            return ERR_INVALID_R;
+        };
+        // Reject the malleable (high) s, as Ethereum does since EIP-2
+        let Some(s) = non_zero_scalar_from_bytes(s_bytes) else {
+            return ERR_INVALID_S;
+        };
+        if bool::from(s.is_high()) {
+            return ERR_INVALID_S;
+        }
+        // Both the raw recovery id and its Ethereum encoding are accepted
+        let recovery_id = match v {
+            0 | 27 => RecoveryId::new(false, false),
+            1 | 28 => RecoveryId::new(true, false),
+            _ => return ERR_INVALID_V,
+        };
+
+        let Ok(signature) = Signature::from_scalars(r, s) else {
+            return ERR_INVALID_SIGNATURE;
+        };
+        let Ok(public_key) = VerifyingKey::recover_from_prehash(
+            &array_from_bytes(message_bytes),
+            &signature,
+            recovery_id,
+        ) else {
+            return ERR_INVALID_SIGNATURE;
+        };
+
+        // The address is the 20 last bytes of the keccak hash of the uncompressed public key
+        let public_key = public_key.as_affine().to_encoded_point(false);
+        let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+        bytes_from_array(&hash[12..], address_bytes);
+        SUCCESS
+    })
+}
+
+/// Verifies a signature (r, s) of a 32 bytes message hash against the public key (x, y) given by
+/// the first two arguments. Returns SUCCESS for a valid signature, ERR_INVALID_SIGNATURE for an
+/// invalid one and another error code for malformed inputs. s must be in the lower half of the
+/// scalars
+#[no_mangle]
+pub extern "C" fn secp256k1_verify(
+    public_key_x_bytes: Bytes,
+    public_key_y_bytes: Bytes,
+    message_bytes: Bytes,
+    r_bytes: Bytes,
+    s_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            public_key_x_bytes,
+            public_key_y_bytes,
+            message_bytes,
+            r_bytes,
+            s_bytes
+        );
+        let public_key =
+            match secp::point_from_bytes::<Secp256k1>(public_key_x_bytes, public_key_y_bytes) {
+                Ok(point) => point,
+                Err(status) => return status,
+            };
+        let Ok(public_key) = VerifyingKey::from_affine(public_key) else {
+            return ERR_INVALID_POINT;
+        };
+        let Some(r) = non_zero_scalar_from_bytes(r_bytes) else {
+            return ERR_INVALID_R;
+        };
+        // Like for recovery, the malleable (high) s is rejected
+        let Some(s) = non_zero_scalar_from_bytes(s_bytes) else {
+            return ERR_INVALID_S;
+        };
+        if bool::from(s.is_high()) {
+            return ERR_INVALID_S;
+        }
+
+        let Ok(signature) = Signature::from_scalars(r, s) else {
+            return ERR_INVALID_SIGNATURE;
+        };
+        match public_key.verify_prehash(&array_from_bytes(message_bytes), &signature) {
+            Ok(()) => SUCCESS,
+            Err(_) => ERR_INVALID_SIGNATURE,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{secp::hex_bytes, ERR_DIVISION_BY_ZERO, ERR_POINT_AT_INFINITY};
+
+    const G_X: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const G_Y: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    const G2_X: &str = "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+    const G2_Y: &str = "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a";
+
+    #[test]
+    fn test_secp256k1_add() {
+        let mut g_x = hex_bytes(G_X);
+        let mut g_y = hex_bytes(G_Y);
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            secp256k1_add(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(result_x, hex_bytes(G2_X));
+        assert_eq!(result_y, hex_bytes(G2_Y));
+
+        let mut not_on_curve = hex_bytes(G2_Y);
+        assert_eq!(
+            secp256k1_add(
+                g_x.as_mut_ptr(),
+                not_on_curve.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_mul() {
+        let mut g_x = hex_bytes(G_X);
+        let mut g_y = hex_bytes(G_Y);
+        let mut two = [0u8; 32];
+        two[31] = 2;
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            secp256k1_mul(
+                two.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(result_x, hex_bytes(G2_X));
+        assert_eq!(result_y, hex_bytes(G2_Y));
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            secp256k1_mul(
+                zero.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            ERR_POINT_AT_INFINITY
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_point_compression() {
+        let (mut g_x, mut g_y) = (hex_bytes(G_X), hex_bytes(G_Y));
+        let mut compressed = [0u8; 33];
+        assert_eq!(
+            secp256k1_point_compress(g_x.as_mut_ptr(), g_y.as_mut_ptr(), compressed.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(compressed[0], 2);
+        assert_eq!(compressed[1..], g_x);
+
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            secp256k1_point_decompress(compressed.as_ptr(), x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!((x, y), (g_x, g_y));
+
+        // The other prefix gives the opposite point
+        compressed[0] ^= 1;
+        assert_eq!(
+            secp256k1_point_decompress(compressed.as_ptr(), x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(x, g_x);
+        assert_ne!(y, g_y);
+
+        for prefix in [0x00, 0x04, 0x05] {
+            compressed[0] = prefix;
+            assert_eq!(
+                secp256k1_point_decompress(compressed.as_ptr(), x.as_mut_ptr(), y.as_mut_ptr()),
+                ERR_INVALID_POINT
+            );
+        }
+        let mut not_on_curve = hex_bytes(G2_X);
+        assert_eq!(
+            secp256k1_point_compress(
+                g_x.as_mut_ptr(),
+                not_on_curve.as_mut_ptr(),
+                compressed.as_mut_ptr()
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_get_point_from_x() {
+        let mut g_x = hex_bytes(G_X);
+        let mut even = [0u8; 32];
+        let mut odd = [0u8; 32];
+        odd[31] = 1;
+        let mut y = [0u8; 32];
+
+        assert_eq!(
+            secp256k1_get_point_from_x(g_x.as_mut_ptr(), even.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(y, hex_bytes(G_Y));
+
+        assert_eq!(
+            secp256k1_get_point_from_x(g_x.as_mut_ptr(), odd.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(y[31] & 1, 1);
+    }
+
+    #[test]
+    fn test_secp256k1_scalar_inverse() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut a = hex_bytes(G_X);
+        let mut inverse = [0u8; 32];
+        let mut product = [0u8; 32];
+        assert_eq!(
+            secp256k1_scalar_inverse(a.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256k1_scalar_mul(a.as_mut_ptr(), inverse.as_mut_ptr(), product.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(product, one);
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            secp256k1_scalar_inverse(zero.as_mut_ptr(), inverse.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            secp256k1_scalar_div(zero.as_mut_ptr(), zero.as_mut_ptr(), product.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+
+        // The scalars from n up are reduced: n is zero and n + 1 is one
+        let mut order =
+            hex_bytes("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        assert_eq!(
+            secp256k1_scalar_inverse(order.as_mut_ptr(), inverse.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            secp256k1_scalar_div(a.as_mut_ptr(), order.as_mut_ptr(), product.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        let mut order_plus_one = order;
+        order_plus_one[31] += 1;
+        assert_eq!(
+            secp256k1_scalar_inverse(order_plus_one.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(inverse, one);
+        assert_eq!(
+            secp256k1_scalar_div(
+                a.as_mut_ptr(),
+                order_plus_one.as_mut_ptr(),
+                product.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(product, a);
+
+        // 2^256 - 1 times its inverse, both reduced
+        let mut max = [0xffu8; 32];
+        assert_eq!(
+            secp256k1_scalar_inverse(max.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256k1_scalar_mul(max.as_mut_ptr(), inverse.as_mut_ptr(), product.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(product, one);
+    }
+
+    #[test]
+    fn test_secp256k1_scalar_ops() {
+        let mut a = hex_bytes(G_X);
+        let mut b = hex_bytes(G2_Y);
+        let mut product = [0u8; 32];
+        let mut quotient = [0u8; 32];
+        assert_eq!(
+            secp256k1_scalar_mul(a.as_mut_ptr(), b.as_mut_ptr(), product.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256k1_scalar_div(product.as_mut_ptr(), b.as_mut_ptr(), quotient.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(quotient, a);
+
+        // a * b * b^-1 = a
+        let mut inverse = [0u8; 32];
+        assert_eq!(
+            secp256k1_scalar_inverse(b.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256k1_scalar_mul(
+                product.as_mut_ptr(),
+                inverse.as_mut_ptr(),
+                quotient.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(quotient, a);
+
+        // n - 1 + 2 = 1 modulo n
+        let mut order_minus_one =
+            hex_bytes("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+        let mut two = [0u8; 32];
+        two[31] = 2;
+        let mut sum = [0u8; 32];
+        assert_eq!(
+            secp256k1_scalar_add(
+                order_minus_one.as_mut_ptr(),
+                two.as_mut_ptr(),
+                sum.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(sum, one);
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            secp256k1_scalar_div(a.as_mut_ptr(), zero.as_mut_ptr(), quotient.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            secp256k1_scalar_inverse(zero.as_mut_ptr(), inverse.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_recover_eth_address() {
+        let mut private_key = [0u8; 32];
+        private_key[31] = 1;
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(&private_key.into()).unwrap();
+        let mut message = [0x42u8; 32];
+        let (signature, recovery_id) = signing_key.sign_prehash_recoverable(&message).unwrap();
+        let (r, s) = signature.split_bytes();
+        let (mut r, mut s): ([u8; 32], [u8; 32]) = (r.into(), s.into());
+        let mut address = [0u8; 20];
+        assert_eq!(
+            secp256k1_recover_eth_address(
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+                27 + recovery_id.to_byte() as u32,
+                address.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        // Address of the private key 1
+        assert_eq!(
+            address.to_vec(),
+            hex_bytes("7e5f4552091a69125d5dfcb7b8c2659029395bdf000000000000000000000000")[..20]
+                .to_vec()
+        );
+
+        assert_eq!(
+            secp256k1_recover_eth_address(
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+                29,
+                address.as_mut_ptr(),
+            ),
+            ERR_INVALID_V
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_recover_eth_address_rejects_high_s() {
+        let mut message = [0x42u8; 32];
+        let mut r = hex_bytes(G_X);
+        // n - 1 is in the upper half of the scalars
+        let mut s = hex_bytes("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+        let mut address = [0u8; 20];
+        assert_eq!(
+            secp256k1_recover_eth_address(
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+                0,
+                address.as_mut_ptr(),
+            ),
+            ERR_INVALID_S
+        );
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            secp256k1_recover_eth_address(
+                message.as_mut_ptr(),
+                zero.as_mut_ptr(),
+                s.as_mut_ptr(),
+                0,
+                address.as_mut_ptr(),
+            ),
+            ERR_INVALID_R
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_verify() {
+        // The public key of the private key 1 is the generator
+        let mut private_key = [0u8; 32];
+        private_key[31] = 1;
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(&private_key.into()).unwrap();
+        let mut message = [0x42u8; 32];
+        let (signature, _) = signing_key.sign_prehash_recoverable(&message).unwrap();
+        let (r, s) = signature.split_bytes();
+        let (mut r, mut s): ([u8; 32], [u8; 32]) = (r.into(), s.into());
+        let mut g_x = hex_bytes(G_X);
+        let mut g_y = hex_bytes(G_Y);
+        assert_eq!(
+            secp256k1_verify(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+
+        message[0] ^= 1;
+        assert_eq!(
+            secp256k1_verify(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            ERR_INVALID_SIGNATURE
+        );
+
+        assert_eq!(
+            secp256k1_verify(
+                g_x.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                message.as_mut_ptr(),
+                r.as_mut_ptr(),
+                s.as_mut_ptr(),
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+}
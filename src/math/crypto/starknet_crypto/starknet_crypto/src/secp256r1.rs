@@ -0,0 +1,385 @@
+// Arithmetic over the secp256r1 (P-256) curve, used by the Starknet OS hints.
+use p256::NistP256;
+
+use crate::{ffi_guard, secp, Bytes};
+
+/// Adds the secp256r1 points (x, y) given by the first four arguments, writing the affine result
+/// into the last two
+#[no_mangle]
+pub extern "C" fn secp256r1_add(
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    q_x_bytes: Bytes,
+    q_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        secp::add::<NistP256>(
+            p_x_bytes,
+            p_y_bytes,
+            q_x_bytes,
+            q_y_bytes,
+            result_x_bytes,
+            result_y_bytes,
+        )
+    })
+}
+
+/// Multiplies the secp256r1 point given by the second and third arguments by a scalar, writing the
+/// affine result into the last two arguments. Constant time with respect to the scalar
+#[no_mangle]
+pub extern "C" fn secp256r1_mul(
+    scalar_bytes: Bytes,
+    p_x_bytes: Bytes,
+    p_y_bytes: Bytes,
+    result_x_bytes: Bytes,
+    result_y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            scalar_bytes,
+            p_x_bytes,
+            p_y_bytes,
+            result_x_bytes,
+            result_y_bytes
+        );
+        secp::mul::<NistP256>(
+            scalar_bytes,
+            p_x_bytes,
+            p_y_bytes,
+            result_x_bytes,
+            result_y_bytes,
+        )
+    })
+}
+
+/// Recovers the y coordinate of the secp256r1 point with the given x coordinate and the same parity
+/// as v
+#[no_mangle]
+pub extern "C" fn secp256r1_get_point_from_x(
+    x_bytes: Bytes,
+    v_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, v_bytes, y_bytes);
+        secp::get_point_from_x::<NistP256>(x_bytes, v_bytes, y_bytes)
+    })
+}
+
+/// Writes the 33 bytes SEC1 compressed encoding of the secp256r1 point (x, y) into the last
+/// argument: 0x02 for an even y or 0x03 for an odd one, followed by x
+#[no_mangle]
+pub extern "C" fn secp256r1_point_compress(
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+    compressed: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(x_bytes, y_bytes, compressed);
+        secp::point_compress::<NistP256>(x_bytes, y_bytes, compressed)
+    })
+}
+
+/// Decodes a 33 bytes SEC1 compressed secp256r1 point, writing its coordinates into the last two
+/// arguments. Returns ERR_INVALID_POINT for another prefix than 0x02 or 0x03, or when the x
+/// coordinate is not the one of a point
+#[no_mangle]
+pub extern "C" fn secp256r1_point_decompress(
+    compressed: *const u8,
+    x_bytes: Bytes,
+    y_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(compressed, x_bytes, y_bytes);
+        secp::point_decompress::<NistP256>(compressed, x_bytes, y_bytes)
+    })
+}
+
+/// Adds two scalars modulo the secp256r1 curve order
+#[no_mangle]
+pub extern "C" fn secp256r1_scalar_add(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        secp::scalar_add::<NistP256>(a_bytes, b_bytes, result_bytes)
+    })
+}
+
+/// Multiplies two scalars modulo the secp256r1 curve order
+#[no_mangle]
+pub extern "C" fn secp256r1_scalar_mul(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        secp::scalar_mul::<NistP256>(a_bytes, b_bytes, result_bytes)
+    })
+}
+
+/// Inverts a scalar modulo the secp256r1 curve order, writing the result into the last argument.
+/// Returns ERR_DIVISION_BY_ZERO when the scalar is zero modulo the order
+#[no_mangle]
+pub extern "C" fn secp256r1_scalar_inverse(a_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, result_bytes);
+        secp::scalar_inverse::<NistP256>(a_bytes, result_bytes)
+    })
+}
+
+/// Divides the first scalar by the second modulo the secp256r1 curve order
+#[no_mangle]
+pub extern "C" fn secp256r1_scalar_div(a_bytes: Bytes, b_bytes: Bytes, result_bytes: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a_bytes, b_bytes, result_bytes);
+        secp::scalar_div::<NistP256>(a_bytes, b_bytes, result_bytes)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{secp::hex_bytes, ERR_DIVISION_BY_ZERO, ERR_INVALID_POINT, SUCCESS};
+
+    const G_X: &str = "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+    const G_Y: &str = "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5";
+    const G2_X: &str = "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978";
+    const G2_Y: &str = "07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1";
+
+    #[test]
+    fn test_secp256r1_add_and_mul() {
+        let mut g_x = hex_bytes(G_X);
+        let mut g_y = hex_bytes(G_Y);
+        let mut result_x = [0u8; 32];
+        let mut result_y = [0u8; 32];
+        assert_eq!(
+            secp256r1_add(
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(result_x, hex_bytes(G2_X));
+        assert_eq!(result_y, hex_bytes(G2_Y));
+
+        let mut two = [0u8; 32];
+        two[31] = 2;
+        result_x = [0u8; 32];
+        result_y = [0u8; 32];
+        assert_eq!(
+            secp256r1_mul(
+                two.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            SUCCESS
+        );
+        assert_eq!(result_x, hex_bytes(G2_X));
+        assert_eq!(result_y, hex_bytes(G2_Y));
+
+        assert_eq!(
+            secp256r1_add(
+                g_x.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_x.as_mut_ptr(),
+                g_y.as_mut_ptr(),
+                result_x.as_mut_ptr(),
+                result_y.as_mut_ptr(),
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_secp256r1_point_compression() {
+        let (mut g_x, mut g_y) = (hex_bytes(G_X), hex_bytes(G_Y));
+        let mut compressed = [0u8; 33];
+        assert_eq!(
+            secp256r1_point_compress(g_x.as_mut_ptr(), g_y.as_mut_ptr(), compressed.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(compressed[0], 3);
+        assert_eq!(compressed[1..], g_x);
+
+        let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+        assert_eq!(
+            secp256r1_point_decompress(compressed.as_ptr(), x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!((x, y), (g_x, g_y));
+
+        // The other prefix gives the opposite point
+        compressed[0] ^= 1;
+        assert_eq!(
+            secp256r1_point_decompress(compressed.as_ptr(), x.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(x, g_x);
+        assert_ne!(y, g_y);
+
+        for prefix in [0x00, 0x04, 0x05] {
+            compressed[0] = prefix;
+            assert_eq!(
+                secp256r1_point_decompress(compressed.as_ptr(), x.as_mut_ptr(), y.as_mut_ptr()),
+                ERR_INVALID_POINT
+            );
+        }
+        let mut not_on_curve = hex_bytes(G2_X);
+        assert_eq!(
+            secp256r1_point_compress(
+                g_x.as_mut_ptr(),
+                not_on_curve.as_mut_ptr(),
+                compressed.as_mut_ptr()
+            ),
+            ERR_INVALID_POINT
+        );
+    }
+
+    #[test]
+    fn test_secp256r1_get_point_from_x() {
+        let mut g_x = hex_bytes(G_X);
+        let mut odd = [0u8; 32];
+        odd[31] = 1;
+        let mut y = [0u8; 32];
+        assert_eq!(
+            secp256r1_get_point_from_x(g_x.as_mut_ptr(), odd.as_mut_ptr(), y.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(y, hex_bytes(G_Y));
+    }
+
+    #[test]
+    fn test_secp256r1_scalar_inverse() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut a = hex_bytes(G_X);
+        let mut inverse = [0u8; 32];
+        let mut product = [0u8; 32];
+        assert_eq!(
+            secp256r1_scalar_inverse(a.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256r1_scalar_mul(a.as_mut_ptr(), inverse.as_mut_ptr(), product.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(product, one);
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            secp256r1_scalar_inverse(zero.as_mut_ptr(), inverse.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            secp256r1_scalar_div(zero.as_mut_ptr(), zero.as_mut_ptr(), product.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+
+        // The scalars from n up are reduced: n is zero and n + 1 is one
+        let mut order =
+            hex_bytes("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+        assert_eq!(
+            secp256r1_scalar_inverse(order.as_mut_ptr(), inverse.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            secp256r1_scalar_div(a.as_mut_ptr(), order.as_mut_ptr(), product.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        let mut order_plus_one = order;
+        order_plus_one[31] += 1;
+        assert_eq!(
+            secp256r1_scalar_inverse(order_plus_one.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(inverse, one);
+        assert_eq!(
+            secp256r1_scalar_div(
+                a.as_mut_ptr(),
+                order_plus_one.as_mut_ptr(),
+                product.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(product, a);
+
+        // 2^256 - 1 times its inverse, both reduced
+        let mut max = [0xffu8; 32];
+        assert_eq!(
+            secp256r1_scalar_inverse(max.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256r1_scalar_mul(max.as_mut_ptr(), inverse.as_mut_ptr(), product.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(product, one);
+    }
+
+    #[test]
+    fn test_secp256r1_scalar_ops() {
+        let mut a = hex_bytes(G_X);
+        let mut b = hex_bytes(G2_Y);
+        let mut product = [0u8; 32];
+        let mut quotient = [0u8; 32];
+        assert_eq!(
+            secp256r1_scalar_mul(a.as_mut_ptr(), b.as_mut_ptr(), product.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256r1_scalar_div(product.as_mut_ptr(), b.as_mut_ptr(), quotient.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(quotient, a);
+
+        // a * b * b^-1 = a
+        let mut inverse = [0u8; 32];
+        assert_eq!(
+            secp256r1_scalar_inverse(b.as_mut_ptr(), inverse.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(
+            secp256r1_scalar_mul(
+                product.as_mut_ptr(),
+                inverse.as_mut_ptr(),
+                quotient.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(quotient, a);
+
+        // n + 1 = 1 modulo n
+        let mut order =
+            hex_bytes("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut sum = [0u8; 32];
+        assert_eq!(
+            secp256r1_scalar_add(order.as_mut_ptr(), one.as_mut_ptr(), sum.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(sum, one);
+
+        let mut zero = [0u8; 32];
+        assert_eq!(
+            secp256r1_scalar_div(a.as_mut_ptr(), zero.as_mut_ptr(), quotient.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            secp256r1_scalar_inverse(zero.as_mut_ptr(), inverse.as_mut_ptr()),
+            ERR_DIVISION_BY_ZERO
+        );
+    }
+}
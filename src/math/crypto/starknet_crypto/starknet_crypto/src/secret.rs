@@ -0,0 +1,89 @@
+// Wiping of the secret material, private keys and nonces, going through the signing functions.
+//
+// Secrets are copied out of the caller's buffers into `Zeroizing` wrappers, which overwrite them
+// with zeroes when dropped. The copies made by starknet-crypto while signing are out of reach.
+use core::ops::Deref;
+
+use starknet_types_core::felt::Felt;
+use zeroize::{DefaultIsZeroes, Zeroize, Zeroizing};
+
+use crate::{ffi_guard, try_felt_from_array, Bytes, ERR_INVALID_LENGTH, SUCCESS};
+
+// A felt that is wiped on drop once wrapped in `Zeroizing`.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SecretFelt(pub(crate) Felt);
+
+impl DefaultIsZeroes for SecretFelt {}
+
+impl Deref for SecretFelt {
+    type Target = Felt;
+
+    fn deref(&self) -> &Felt {
+        &self.0
+    }
+}
+
+// Reads a secret felt from its C representation without leaving copies of it behind.
+pub(crate) fn try_secret_from_bytes(bytes: Bytes) -> Option<Zeroizing<SecretFelt>> {
+    let mut array = Zeroizing::new([0u8; 32]);
+    unsafe { core::ptr::copy_nonoverlapping(bytes, array.as_mut_ptr(), 32) };
+    let felt = try_felt_from_array(&array)?;
+    Some(Zeroizing::new(SecretFelt(felt)))
+}
+
+// Writes a secret felt into its C representation without leaving copies of it behind.
+#[cfg(feature = "signing")]
+pub(crate) fn bytes_from_secret(secret: &SecretFelt, bytes: Bytes) {
+    let array = Zeroizing::new(secret.to_bytes_be());
+    unsafe { core::ptr::copy_nonoverlapping(array.as_ptr(), bytes, 32) };
+}
+
+/// Overwrites `len` bytes with zeroes in a way the compiler can't optimize away, to wipe the
+/// caller's own copies of private keys and nonces. The pointer may be NULL when `len` is zero
+#[no_mangle]
+pub extern "C" fn starknet_crypto_zeroize(bytes: *mut u8, len: usize) -> i32 {
+    ffi_guard(|| {
+        if len == 0 {
+            return SUCCESS;
+        }
+        if bytes.is_null() || len > isize::MAX as usize {
+            return ERR_INVALID_LENGTH;
+        }
+        unsafe { core::slice::from_raw_parts_mut(bytes, len) }.zeroize();
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zeroize() {
+        let mut secret = [0xabu8; 40];
+        assert_eq!(starknet_crypto_zeroize(secret.as_mut_ptr(), 32), SUCCESS);
+        assert_eq!(secret[..32], [0u8; 32]);
+        assert_eq!(secret[32..], [0xabu8; 8]);
+
+        assert_eq!(starknet_crypto_zeroize(core::ptr::null_mut(), 0), SUCCESS);
+        assert_eq!(
+            starknet_crypto_zeroize(core::ptr::null_mut(), 32),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_secret_round_trip() {
+        let mut bytes = Felt::from_hex("0x1234").unwrap().to_bytes_be();
+        let secret = try_secret_from_bytes(bytes.as_mut_ptr()).unwrap();
+        assert_eq!(**secret, Felt::from_hex("0x1234").unwrap());
+
+        let mut written = [0u8; 32];
+        bytes_from_secret(&secret, written.as_mut_ptr());
+        assert_eq!(written, bytes);
+
+        let mut not_a_felt = [0xffu8; 32];
+        assert!(try_secret_from_bytes(not_a_felt.as_mut_ptr()).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+// SHA-256 primitives used by the sha256 hint family.
+use sha2::{digest::generic_array::GenericArray, Digest, Sha256};
+
+use crate::{bytes_from_array, ffi_guard, slice_from_ptr, SUCCESS};
+
+/// Applies the sha256 compression function in place over a state of 8 words, given a 64 bytes
+/// message block
+#[no_mangle]
+pub extern "C" fn sha256_compress(state: *mut u32, block: *const u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(state, block);
+        // The state is 8 words updated in place, the block is 64 bytes
+        let state = unsafe { &mut *(state as *mut [u32; 8]) };
+        let block = unsafe { core::slice::from_raw_parts(block, 64) };
+        sha2::compress256(state, &[*GenericArray::from_slice(block)]);
+        SUCCESS
+    })
+}
+
+/// Computes the sha256 hash of `len` bytes with the standard padding, writing the 32 bytes digest
+/// into the last argument
+#[no_mangle]
+pub extern "C" fn sha256(data: *const u8, len: usize, result: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let hash: [u8; 32] = Sha256::digest(data).into();
+        bytes_from_array(&hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IV: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    #[test]
+    fn test_sha256_compress_abc() {
+        // Single block sha256 hash of "abc", padded by hand
+        let mut block = [0u8; 64];
+        block[..3].copy_from_slice(b"abc");
+        block[3] = 0x80;
+        block[63] = 24;
+
+        let mut state = IV;
+        assert_eq!(sha256_compress(state.as_mut_ptr(), block.as_ptr()), SUCCESS);
+        assert_eq!(
+            state,
+            [
+                0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+                0xf20015ad,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sha256() {
+        let mut result = [0u8; 32];
+        assert_eq!(sha256(b"abc".as_ptr(), 3, result.as_mut_ptr()), SUCCESS);
+        let expected: Vec<u8> = [
+            0xba7816bfu32,
+            0x8f01cfea,
+            0x414140de,
+            0x5dae2223,
+            0xb00361a3,
+            0x96177a9c,
+            0xb410ff61,
+            0xf20015ad,
+        ]
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .collect();
+        assert_eq!(result[..], expected[..]);
+
+        // The empty message, and one whose padding spills into a second block
+        assert_eq!(sha256(core::ptr::null(), 0, result.as_mut_ptr()), SUCCESS);
+        assert_eq!(result[..4], [0xe3, 0xb0, 0xc4, 0x42]);
+        let data = [b'a'; 56];
+        assert_eq!(sha256(data.as_ptr(), 56, result.as_mut_ptr()), SUCCESS);
+        assert_eq!(result[..], Sha256::digest(data)[..]);
+    }
+}
@@ -0,0 +1,159 @@
+// Length checked counterparts of every function taking a buffer of fixed length, for the callers
+// holding slices rather than arrays.
+//
+// The functions of ABI_MAJOR 1 read and write 32 bytes behind every `Bytes` pointer, 20 for the
+// address of secp256k1_recover_eth_address, and a fixed count of elements behind the other
+// pointers to arrays not sized by another argument, like the 25 lanes of keccak_f1600 or the
+// 33 bytes of a compressed secp256k1 point, so a shorter buffer is read or written out of bounds.
+// The `_sized` functions take the length of each buffer after its pointer, in elements of its
+// type, and return ERR_BAD_LENGTH, before touching any buffer, when one isn't that length, then
+// behave like the function they wrap. Their arguments can come straight from a fuzzer.
+//
+// build.rs generates them from the signatures of the exports and its table of the lengths, so a
+// new function taking `Bytes` gets its `_sized` counterpart without anything to write here.
+use crate::{ffi_guard, ERR_BAD_LENGTH};
+
+// Runs the wrapped function when every buffer has its expected length, given as (actual, expected).
+pub(crate) fn with_lengths(lengths: &[(usize, usize)], function: impl FnOnce() -> i32) -> i32 {
+    ffi_guard(|| {
+        if lengths.iter().any(|&(len, expected)| len != expected) {
+            return ERR_BAD_LENGTH;
+        }
+        function()
+    })
+}
+
+include!(concat!(env!("OUT_DIR"), "/sized.rs"));
+
+#[cfg(test)]
+mod tests {
+    use starknet_types_core::felt::Felt;
+
+    use super::*;
+    use crate::{pedersen_hash, ERR_NULL_POINTER, SUCCESS};
+
+    #[test]
+    fn test_pedersen_hash_sized() {
+        let (mut a, mut b) = (Felt::ONE.to_bytes_be(), Felt::TWO.to_bytes_be());
+        let mut expected = [0u8; 32];
+        assert_eq!(
+            pedersen_hash(a.as_mut_ptr(), b.as_mut_ptr(), expected.as_mut_ptr()),
+            SUCCESS
+        );
+        let mut result = [0u8; 32];
+        assert_eq!(
+            pedersen_hash_sized(
+                a.as_mut_ptr(),
+                32,
+                b.as_mut_ptr(),
+                32,
+                result.as_mut_ptr(),
+                32
+            ),
+            SUCCESS
+        );
+        assert_eq!(result, expected);
+
+        // A 31 bytes buffer is never read or written
+        let mut short = [0xffu8; 31];
+        assert_eq!(
+            pedersen_hash_sized(
+                short.as_mut_ptr(),
+                31,
+                b.as_mut_ptr(),
+                32,
+                result.as_mut_ptr(),
+                32
+            ),
+            ERR_BAD_LENGTH
+        );
+        assert_eq!(
+            pedersen_hash_sized(
+                a.as_mut_ptr(),
+                32,
+                b.as_mut_ptr(),
+                32,
+                short.as_mut_ptr(),
+                31
+            ),
+            ERR_BAD_LENGTH
+        );
+        assert_eq!(short, [0xffu8; 31]);
+        assert_eq!(
+            pedersen_hash_sized(
+                core::ptr::null_mut(),
+                32,
+                b.as_mut_ptr(),
+                32,
+                result.as_mut_ptr(),
+                32
+            ),
+            ERR_NULL_POINTER
+        );
+    }
+
+    #[test]
+    fn test_keccak_f1600_sized() {
+        let mut expected = [0u64; 25];
+        assert_eq!(crate::keccak::keccak_f1600(expected.as_mut_ptr()), SUCCESS);
+        let mut state = [0u64; 25];
+        assert_eq!(keccak_f1600_sized(state.as_mut_ptr(), 25), SUCCESS);
+        assert_eq!(state, expected);
+
+        // The lengths of the other pointers count their elements, lanes here
+        let mut short = [0u64; 24];
+        assert_eq!(keccak_f1600_sized(short.as_mut_ptr(), 24), ERR_BAD_LENGTH);
+        assert_eq!(
+            keccak_f1600_sized(short.as_mut_ptr(), 25 * 8),
+            ERR_BAD_LENGTH
+        );
+        assert_eq!(short, [0u64; 24]);
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_verify_signature_sized() {
+        let private_key = Felt::from(0xb0bu64);
+        let message = Felt::from(0x1234u64);
+        let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+        let mut public_key = [0u8; 32];
+        assert_eq!(
+            get_public_key_sized(
+                private_key.to_bytes_be().as_mut_ptr(),
+                32,
+                public_key.as_mut_ptr(),
+                32
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            ecdsa_sign_sized(
+                private_key.to_bytes_be().as_mut_ptr(),
+                32,
+                message.to_bytes_be().as_mut_ptr(),
+                32,
+                Felt::THREE.to_bytes_be().as_mut_ptr(),
+                32,
+                r.as_mut_ptr(),
+                32,
+                s.as_mut_ptr(),
+                32
+            ),
+            SUCCESS
+        );
+        let mut verify = |public_key: &mut [u8; 32], public_key_len| {
+            verify_signature_sized(
+                public_key.as_mut_ptr(),
+                public_key_len,
+                message.to_bytes_be().as_mut_ptr(),
+                32,
+                r.as_mut_ptr(),
+                32,
+                s.as_mut_ptr(),
+                32,
+            )
+        };
+        assert_eq!(verify(&mut public_key, 32), SUCCESS);
+        assert_eq!(verify(&mut public_key, 33), ERR_BAD_LENGTH);
+    }
+}
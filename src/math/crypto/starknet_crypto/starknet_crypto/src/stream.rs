@@ -0,0 +1,183 @@
+// Incremental hashing of streams of felts whose length isn't known up front, through contexts
+// owned by the caller between calls.
+//
+// The contexts are opaque to C: the `_new` functions allocate them and the `_free` functions
+// release them.
+use alloc::boxed::Box;
+
+use starknet_crypto_rs::pedersen_hash;
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, hades::PoseidonHasher, try_field_element_from_bytes,
+    Bytes, ERR_INVALID_FELT, SUCCESS,
+};
+
+/// A Poseidon sponge absorbing felts one at a time
+pub struct PoseidonContext(PoseidonHasher);
+
+/// Allocates an empty Poseidon sponge, writing it into the argument. It must be released with
+/// poseidon_ctx_free
+#[no_mangle]
+pub extern "C" fn poseidon_ctx_new(ctx: *mut *mut PoseidonContext) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx);
+        unsafe { *ctx = Box::into_raw(Box::new(PoseidonContext(PoseidonHasher::new()))) };
+        SUCCESS
+    })
+}
+
+/// Absorbs a felt into the sponge
+#[no_mangle]
+pub extern "C" fn poseidon_ctx_absorb(ctx: *mut PoseidonContext, felt: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx, felt);
+        let Some(felt) = try_field_element_from_bytes(felt) else {
+            return ERR_INVALID_FELT;
+        };
+        unsafe { (*ctx).0.update(felt) };
+        SUCCESS
+    })
+}
+
+/// Writes the poseidon_hash_many of the felts absorbed so far into the last argument, and empties
+/// the sponge so that it may hash another stream
+#[no_mangle]
+pub extern "C" fn poseidon_ctx_squeeze(ctx: *mut PoseidonContext, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx, result);
+        let hasher = core::mem::take(unsafe { &mut (*ctx).0 });
+        bytes_from_field_element(hasher.finalize(), result);
+        SUCCESS
+    })
+}
+
+/// Releases a sponge allocated by poseidon_ctx_new. Does nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn poseidon_ctx_free(ctx: *mut PoseidonContext) -> i32 {
+    ffi_guard(|| {
+        if !ctx.is_null() {
+            drop(unsafe { Box::from_raw(ctx) });
+        }
+        SUCCESS
+    })
+}
+
+/// A Pedersen hash chain updated one felt at a time
+#[derive(Default)]
+pub struct PedersenChain {
+    hash: Felt,
+    len: u64,
+}
+
+/// Allocates an empty Pedersen hash chain, writing it into the argument. It must be released with
+/// pedersen_chain_free
+#[no_mangle]
+pub extern "C" fn pedersen_chain_new(ctx: *mut *mut PedersenChain) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx);
+        unsafe { *ctx = Box::into_raw(Box::default()) };
+        SUCCESS
+    })
+}
+
+/// Appends a felt to the chain
+#[no_mangle]
+pub extern "C" fn pedersen_chain_update(ctx: *mut PedersenChain, felt: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx, felt);
+        let Some(felt) = try_field_element_from_bytes(felt) else {
+            return ERR_INVALID_FELT;
+        };
+        let chain = unsafe { &mut *ctx };
+        chain.hash = pedersen_hash(&chain.hash, &felt);
+        chain.len += 1;
+        SUCCESS
+    })
+}
+
+/// Writes the pedersen_hash_on_elements of the felts appended so far, H(...H(H(0, x1), x2)..., n),
+/// into the last argument, and empties the chain so that it may hash another stream
+#[no_mangle]
+pub extern "C" fn pedersen_chain_finalize(ctx: *mut PedersenChain, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(ctx, result);
+        let chain = core::mem::take(unsafe { &mut *ctx });
+        let hash = pedersen_hash(&chain.hash, &Felt::from(chain.len));
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Releases a chain allocated by pedersen_chain_new. Does nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn pedersen_chain_free(ctx: *mut PedersenChain) -> i32 {
+    ffi_guard(|| {
+        if !ctx.is_null() {
+            drop(unsafe { Box::from_raw(ctx) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pedersen_hash_on_elements;
+    use starknet_crypto_rs::poseidon_hash_many;
+
+    #[test]
+    fn test_poseidon_ctx() {
+        let mut ctx = core::ptr::null_mut();
+        assert_eq!(poseidon_ctx_new(&mut ctx), SUCCESS);
+
+        let felts: Vec<Felt> = (1..=5u8).map(Felt::from).collect();
+        let mut result = [0u8; 32];
+        // Both an odd and an even number of felts, the second stream reusing the squeezed sponge
+        for count in [5, 4] {
+            for felt in &felts[..count] {
+                let mut bytes = felt.to_bytes_be();
+                assert_eq!(poseidon_ctx_absorb(ctx, bytes.as_mut_ptr()), SUCCESS);
+            }
+            assert_eq!(poseidon_ctx_squeeze(ctx, result.as_mut_ptr()), SUCCESS);
+            assert_eq!(result, poseidon_hash_many(&felts[..count]).to_bytes_be());
+        }
+
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            poseidon_ctx_absorb(ctx, not_a_felt.as_mut_ptr()),
+            ERR_INVALID_FELT
+        );
+
+        assert_eq!(poseidon_ctx_free(ctx), SUCCESS);
+        assert_eq!(poseidon_ctx_free(core::ptr::null_mut()), SUCCESS);
+    }
+
+    #[test]
+    fn test_pedersen_chain() {
+        let mut ctx = core::ptr::null_mut();
+        assert_eq!(pedersen_chain_new(&mut ctx), SUCCESS);
+
+        let felts: Vec<u8> = (1..=3u8)
+            .flat_map(|i| Felt::from(i).to_bytes_be())
+            .collect();
+        let mut result = [0u8; 32];
+        let mut expected = [0u8; 32];
+        // A stream of three felts, then an empty one reusing the finalized chain
+        for count in [3, 0] {
+            for felt in felts[..32 * count].chunks(32) {
+                let mut bytes: [u8; 32] = felt.try_into().unwrap();
+                assert_eq!(pedersen_chain_update(ctx, bytes.as_mut_ptr()), SUCCESS);
+            }
+            assert_eq!(pedersen_chain_finalize(ctx, result.as_mut_ptr()), SUCCESS);
+            assert_eq!(
+                pedersen_hash_on_elements(felts.as_ptr(), count, expected.as_mut_ptr()),
+                SUCCESS
+            );
+            assert_eq!(result, expected);
+        }
+
+        assert_eq!(pedersen_chain_free(ctx), SUCCESS);
+        assert_eq!(pedersen_chain_free(core::ptr::null_mut()), SUCCESS);
+    }
+}
@@ -0,0 +1,408 @@
+// Hex and decimal strings of felts, and Cairo short strings.
+//
+// Strings are ASCII without a NUL terminator on input. On output they are NUL terminated, written
+// into buffers provided by the caller.
+//
+// A short string is the felt of the big endian value of its at most 31 bytes, so the leading NUL
+// bytes of a string don't change its felt and are dropped when the felt goes back to a string,
+// "\0ab" and "ab" being the same short string. The empty string is zero.
+use crate::{
+    array_from_bytes, bytes_from_array, felt::MODULUS_BYTES, ffi_guard, slice_from_ptr, Bytes,
+    ERR_INVALID_FELT, ERR_INVALID_LENGTH, ERR_INVALID_STRING, SUCCESS,
+};
+
+// Reads the digits of a number in the given radix into 32 bytes big endian, with as many leading
+// zeros as the string has.
+fn parse_digits(digits: &[u8], radix: u32) -> Result<[u8; 32], i32> {
+    if digits.is_empty() {
+        return Err(ERR_INVALID_STRING);
+    }
+    let mut number = [0u8; 32];
+    for &digit in digits {
+        let digit = char::from(digit)
+            .to_digit(radix)
+            .ok_or(ERR_INVALID_STRING)?;
+        // number = number * radix + digit, from the least significant byte up
+        let mut carry = digit;
+        for byte in number.iter_mut().rev() {
+            let value = u32::from(*byte) * radix + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(ERR_INVALID_FELT);
+        }
+    }
+    Ok(number)
+}
+
+// Reads a felt from its digits, rejecting the values that are not lower than the STARK prime.
+fn felt_from_digits(digits: &[u8], radix: u32) -> Result<[u8; 32], i32> {
+    let number = parse_digits(digits, radix)?;
+    if number >= MODULUS_BYTES {
+        return Err(ERR_INVALID_FELT);
+    }
+    Ok(number)
+}
+
+// Writes a string and its NUL terminator into a buffer of `buf_len` bytes.
+fn write_string(string: &[u8], buf: *mut u8, buf_len: usize) -> i32 {
+    if buf.is_null() || buf_len <= string.len() {
+        return ERR_INVALID_LENGTH;
+    }
+    bytes_from_array(string, buf);
+    unsafe { *buf.add(string.len()) = 0 };
+    SUCCESS
+}
+
+// Divides a 32 bytes big endian number by a small divisor in place, returning the remainder.
+fn div_rem(number: &mut [u8; 32], divisor: u32) -> u32 {
+    let mut remainder = 0;
+    for byte in number.iter_mut() {
+        let value = (remainder << 8) | u32::from(*byte);
+        *byte = (value / divisor) as u8;
+        remainder = value % divisor;
+    }
+    remainder
+}
+
+/// Parses the `len` bytes of a hex string, with or without a `0x` prefix, into a felt written into
+/// the last argument. Leading zeros are allowed and the digits may be upper or lower case. Returns
+/// ERR_INVALID_STRING for a malformed string and ERR_INVALID_FELT for a value that is not lower
+/// than the STARK prime
+#[no_mangle]
+pub extern "C" fn felt_from_hex(string: *const u8, len: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let string = match slice_from_ptr(string, len) {
+            Ok(string) => string,
+            Err(status) => return status,
+        };
+        let digits = string
+            .strip_prefix(b"0x")
+            .or_else(|| string.strip_prefix(b"0X"))
+            .unwrap_or(string);
+        match felt_from_digits(digits, 16) {
+            Ok(felt) => {
+                bytes_from_array(&felt, result);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Formats a felt as a NUL terminated `0x` prefixed lower case hex string without leading zeros,
+/// `0x0` for zero, into a buffer of `buf_len` bytes. 66 bytes always suffice, a smaller buffer may
+/// return ERR_INVALID_LENGTH
+#[no_mangle]
+pub extern "C" fn felt_to_hex(felt: Bytes, buf: *mut u8, buf_len: usize) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(felt);
+        let felt = array_from_bytes(felt);
+        if felt >= MODULUS_BYTES {
+            return ERR_INVALID_FELT;
+        }
+        let mut string = [0u8; 66];
+        let mut len = 2;
+        string[..2].copy_from_slice(b"0x");
+        for nibble in felt.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]) {
+            // Skip the leading zeros, but keep the last digit of zero
+            if nibble != 0 || len > 2 {
+                string[len] = b"0123456789abcdef"[usize::from(nibble)];
+                len += 1;
+            }
+        }
+        if len == 2 {
+            string[len] = b'0';
+            len += 1;
+        }
+        write_string(&string[..len], buf, buf_len)
+    })
+}
+
+/// Parses the `len` bytes of a decimal string into a felt written into the last argument. Leading
+/// zeros are allowed. Returns ERR_INVALID_STRING for a malformed string and ERR_INVALID_FELT for a
+/// value that is not lower than the STARK prime
+#[no_mangle]
+pub extern "C" fn felt_from_dec(string: *const u8, len: usize, result: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let digits = match slice_from_ptr(string, len) {
+            Ok(digits) => digits,
+            Err(status) => return status,
+        };
+        match felt_from_digits(digits, 10) {
+            Ok(felt) => {
+                bytes_from_array(&felt, result);
+                SUCCESS
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Formats a felt as a NUL terminated decimal string without leading zeros, `0` for zero, into a
+/// buffer of `buf_len` bytes. 77 bytes always suffice, a smaller buffer may return
+/// ERR_INVALID_LENGTH
+#[no_mangle]
+pub extern "C" fn felt_to_dec(felt: Bytes, buf: *mut u8, buf_len: usize) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(felt);
+        let mut felt = array_from_bytes(felt);
+        if felt >= MODULUS_BYTES {
+            return ERR_INVALID_FELT;
+        }
+        // The digits come least significant first, from the end of the string
+        let mut string = [0u8; 76];
+        let mut start = string.len();
+        loop {
+            start -= 1;
+            string[start] = b'0' + div_rem(&mut felt, 10) as u8;
+            if felt == [0u8; 32] {
+                break;
+            }
+        }
+        write_string(&string[start..], buf, buf_len)
+    })
+}
+
+/// Encodes the `len` bytes of a string of at most 31 bytes as a Cairo short string, writing the
+/// felt into the last argument. With `ascii_only` the bytes must be ASCII. Returns
+/// ERR_INVALID_LENGTH for a longer string and ERR_INVALID_STRING for a non ASCII byte
+#[no_mangle]
+pub extern "C" fn short_string_to_felt(
+    string: *const u8,
+    len: usize,
+    ascii_only: bool,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let string = match slice_from_ptr(string, len) {
+            Ok(string) => string,
+            Err(status) => return status,
+        };
+        if string.len() > 31 {
+            return ERR_INVALID_LENGTH;
+        }
+        if ascii_only && !string.is_ascii() {
+            return ERR_INVALID_STRING;
+        }
+        let mut felt = [0u8; 32];
+        felt[32 - string.len()..].copy_from_slice(string);
+        bytes_from_array(&felt, result);
+        SUCCESS
+    })
+}
+
+/// Decodes a felt as a Cairo short string without its leading NUL bytes, writing it NUL terminated
+/// into a buffer of `buf_len` bytes and its length without the terminator into the last argument.
+/// 32 bytes always suffice. Returns ERR_INVALID_STRING for a felt of more than 31 bytes
+#[no_mangle]
+pub extern "C" fn felt_to_short_string(
+    felt: Bytes,
+    buf: *mut u8,
+    buf_len: usize,
+    string_len: *mut usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(felt, string_len);
+        let felt = array_from_bytes(felt);
+        if felt[0] != 0 {
+            return ERR_INVALID_STRING;
+        }
+        let start = felt.iter().position(|&byte| byte != 0).unwrap_or(32);
+        let status = write_string(&felt[start..], buf, buf_len);
+        if status == SUCCESS {
+            unsafe { *string_len = 32 - start };
+        }
+        status
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_types_core::felt::Felt;
+
+    fn from_hex(string: &str) -> Result<Felt, i32> {
+        let mut result = [0u8; 32];
+        match felt_from_hex(string.as_ptr(), string.len(), result.as_mut_ptr()) {
+            SUCCESS => Ok(Felt::from_bytes_be(&result)),
+            status => Err(status),
+        }
+    }
+
+    fn to_hex(felt: Felt) -> String {
+        let mut buf = [0xffu8; 66];
+        assert_eq!(
+            felt_to_hex(felt.to_bytes_be().as_mut_ptr(), buf.as_mut_ptr(), buf.len()),
+            SUCCESS
+        );
+        let len = buf.iter().position(|&byte| byte == 0).unwrap();
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_felt_from_hex() {
+        let felt = Felt::from(0xabcdu16);
+        for string in ["0xabcd", "0XABCD", "abcd", "0x000000AbCd"] {
+            assert_eq!(from_hex(string), Ok(felt));
+        }
+        assert_eq!(from_hex("0"), Ok(Felt::ZERO));
+        let max = "0x800000000000011000000000000000000000000000000000000000000000000";
+        assert_eq!(from_hex(max), Ok(Felt::MAX));
+
+        for string in ["", "0x", "0xg", "0x 1", "-1"] {
+            assert_eq!(from_hex(string), Err(ERR_INVALID_STRING));
+        }
+        // The STARK prime and a value overflowing 256 bits
+        let prime = "0x800000000000011000000000000000000000000000000000000000000000001";
+        assert_eq!(from_hex(prime), Err(ERR_INVALID_FELT));
+        assert_eq!(from_hex(&"f".repeat(65)), Err(ERR_INVALID_FELT));
+    }
+
+    #[test]
+    fn test_felt_to_hex() {
+        assert_eq!(to_hex(Felt::ZERO), "0x0");
+        assert_eq!(to_hex(Felt::from(0xabcdu16)), "0xabcd");
+        assert_eq!(
+            to_hex(Felt::MAX),
+            "0x800000000000011000000000000000000000000000000000000000000000000"
+        );
+
+        // Room for "0xabcd" but not for its terminator
+        let mut buf = [0u8; 6];
+        let mut felt = Felt::from(0xabcdu16).to_bytes_be();
+        assert_eq!(
+            felt_to_hex(felt.as_mut_ptr(), buf.as_mut_ptr(), buf.len()),
+            ERR_INVALID_LENGTH
+        );
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            felt_to_hex(not_a_felt.as_mut_ptr(), buf.as_mut_ptr(), buf.len()),
+            ERR_INVALID_FELT
+        );
+    }
+
+    fn from_dec(string: &str) -> Result<Felt, i32> {
+        let mut result = [0u8; 32];
+        match felt_from_dec(string.as_ptr(), string.len(), result.as_mut_ptr()) {
+            SUCCESS => Ok(Felt::from_bytes_be(&result)),
+            status => Err(status),
+        }
+    }
+
+    fn to_dec(felt: Felt) -> String {
+        let mut buf = [0xffu8; 77];
+        assert_eq!(
+            felt_to_dec(felt.to_bytes_be().as_mut_ptr(), buf.as_mut_ptr(), buf.len()),
+            SUCCESS
+        );
+        let len = buf.iter().position(|&byte| byte == 0).unwrap();
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    // The STARK prime minus one, the largest felt.
+    const MAX_DEC: &str =
+        "3618502788666131213697322783095070105623107215331596699973092056135872020480";
+
+    #[test]
+    fn test_felt_from_dec() {
+        assert_eq!(from_dec("0"), Ok(Felt::ZERO));
+        assert_eq!(from_dec("00042"), Ok(Felt::from(42u8)));
+        assert_eq!(from_dec(MAX_DEC), Ok(Felt::MAX));
+
+        for string in ["", "0x1", "-1", "1.0", " 1"] {
+            assert_eq!(from_dec(string), Err(ERR_INVALID_STRING));
+        }
+        let prime = "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+        assert_eq!(from_dec(prime), Err(ERR_INVALID_FELT));
+        assert_eq!(from_dec(&"9".repeat(78)), Err(ERR_INVALID_FELT));
+    }
+
+    #[test]
+    fn test_felt_to_dec() {
+        assert_eq!(to_dec(Felt::ZERO), "0");
+        assert_eq!(to_dec(Felt::from(1234567890u32)), "1234567890");
+        assert_eq!(to_dec(Felt::MAX), MAX_DEC);
+
+        let mut buf = [0u8; 76];
+        assert_eq!(
+            felt_to_dec(
+                Felt::MAX.to_bytes_be().as_mut_ptr(),
+                buf.as_mut_ptr(),
+                buf.len()
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_short_strings() {
+        let to_felt = |string: &[u8], ascii_only| {
+            let mut result = [0u8; 32];
+            match short_string_to_felt(
+                string.as_ptr(),
+                string.len(),
+                ascii_only,
+                result.as_mut_ptr(),
+            ) {
+                SUCCESS => Ok(Felt::from_bytes_be(&result)),
+                status => Err(status),
+            }
+        };
+        let to_string = |felt: Felt| {
+            let mut buf = [0xffu8; 32];
+            let mut len = 0;
+            match felt_to_short_string(
+                felt.to_bytes_be().as_mut_ptr(),
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut len,
+            ) {
+                SUCCESS => {
+                    assert_eq!(buf[len], 0);
+                    Ok(buf[..len].to_vec())
+                }
+                status => Err(status),
+            }
+        };
+
+        let hello = Felt::from_hex("0x68656c6c6f").unwrap();
+        assert_eq!(to_felt(b"hello", true), Ok(hello));
+        assert_eq!(to_string(hello), Ok(b"hello".to_vec()));
+        assert_eq!(to_felt(b"", true), Ok(Felt::ZERO));
+        assert_eq!(to_string(Felt::ZERO), Ok(Vec::new()));
+        // The leading NUL bytes are dropped, the inner ones are kept
+        assert_eq!(to_felt(b"\0\0hello", true), Ok(hello));
+        assert_eq!(
+            to_string(to_felt(b"a\0b", true).unwrap()),
+            Ok(b"a\0b".to_vec())
+        );
+
+        let longest = [b'x'; 31];
+        assert_eq!(
+            to_string(to_felt(&longest, true).unwrap()),
+            Ok(longest.to_vec())
+        );
+        assert_eq!(to_felt(&[b'x'; 32], true), Err(ERR_INVALID_LENGTH));
+        assert_eq!(to_felt("é".as_bytes(), true), Err(ERR_INVALID_STRING));
+        assert_eq!(to_felt("é".as_bytes(), false), Ok(Felt::from(0xc3a9u16)));
+        assert_eq!(to_string(Felt::MAX), Err(ERR_INVALID_STRING));
+
+        // Room for "hello" but not for its terminator
+        let mut buf = [0u8; 5];
+        let mut len = 0;
+        assert_eq!(
+            felt_to_short_string(
+                hello.to_bytes_be().as_mut_ptr(),
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut len
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+}
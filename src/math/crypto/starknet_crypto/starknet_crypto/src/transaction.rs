@@ -0,0 +1,928 @@
+// Hashes of the Starknet invoke, declare and deploy_account transactions, from their fields already
+// encoded as felts.
+//
+// A v1 hash is the Pedersen hash on elements of the transaction type prefix, the version, the
+// sender address (the deployed address for deploy_account), a zero entry point selector, the hash
+// on elements of the calldata, the max fee, the chain id and the nonce.
+//
+// A v3 hash is the Poseidon hash of the prefix, the version, the sender address, the hash of the
+// tip followed by the resource bounds, the hash of the paymaster data, the chain id, the nonce and
+// the data availability modes, followed by the fields of the transaction type. Each resource bound
+// is encoded as its resource name << 192 | max amount << 128 | max price per unit, and the data
+// availability modes as the nonce mode << 32 | the fee mode.
+//
+// The calldata hash is the hash on elements of the calldata in v1 transactions, which ends with its
+// length, and its Poseidon hash many in v3 transactions. The hash of an event of a receipt is, since
+// Starknet v0.13.2, the Poseidon hash of the emitting address, the transaction hash, the length and
+// felts of the keys and then the length and felts of the data. Before, it was the hash on elements
+// of the address, the hash on elements of the keys and the hash on elements of the data.
+use alloc::vec::Vec;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes, hades::poseidon_hash_many,
+    hash_on_elements, try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_HASH_KIND,
+    HASH_PEDERSEN, HASH_POSEIDON, SUCCESS,
+};
+
+// Reads the felts behind each pointer.
+pub(crate) fn try_field_elements_from_ptrs<const N: usize>(ptrs: [Bytes; N]) -> Option<[Felt; N]> {
+    let mut felts = [Felt::ZERO; N];
+    for (felt, ptr) in felts.iter_mut().zip(ptrs) {
+        *felt = try_field_element_from_bytes(ptr)?;
+    }
+    Some(felts)
+}
+
+fn prefix(transaction_type: &[u8]) -> Felt {
+    Felt::from_bytes_be_slice(transaction_type)
+}
+
+fn v1_hash(
+    transaction_type: &[u8],
+    [address, max_fee, chain_id, nonce]: [Felt; 4],
+    calldata: &[Felt],
+) -> Felt {
+    hash_on_elements(&[
+        prefix(transaction_type),
+        Felt::ONE,
+        address,
+        Felt::ZERO,
+        hash_on_elements(calldata),
+        max_fee,
+        chain_id,
+        nonce,
+    ])
+}
+
+fn v3_hash(
+    transaction_type: &[u8],
+    [address, tip, chain_id, nonce, data_availability_modes]: [Felt; 5],
+    resource_bounds: &[Felt],
+    paymaster_data: &[Felt],
+    fields: &[Felt],
+) -> Felt {
+    let tip_and_bounds: Vec<Felt> = core::iter::once(tip)
+        .chain(resource_bounds.iter().copied())
+        .collect();
+    let mut elements = Vec::from([
+        prefix(transaction_type),
+        Felt::THREE,
+        address,
+        poseidon_hash_many(&tip_and_bounds),
+        poseidon_hash_many(paymaster_data),
+        chain_id,
+        nonce,
+        data_availability_modes,
+    ]);
+    elements.extend_from_slice(fields);
+    poseidon_hash_many(&elements)
+}
+
+/// Computes the hash of a v1 invoke transaction with `calldata_len` contiguous felts of calldata,
+/// writing it into the last argument
+#[no_mangle]
+pub extern "C" fn invoke_v1_transaction_hash(
+    sender_address: Bytes,
+    calldata: *const u8,
+    calldata_len: usize,
+    max_fee: Bytes,
+    chain_id: Bytes,
+    nonce: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(sender_address, max_fee, chain_id, nonce, result);
+        let Some(fields) = try_field_elements_from_ptrs([sender_address, max_fee, chain_id, nonce])
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let calldata = match field_elements_from_bytes(calldata, calldata_len) {
+            Ok(calldata) => calldata,
+            Err(status) => return status,
+        };
+        bytes_from_field_element(v1_hash(b"invoke", fields, &calldata), result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of a v1 declare transaction, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn declare_v1_transaction_hash(
+    sender_address: Bytes,
+    class_hash: Bytes,
+    max_fee: Bytes,
+    chain_id: Bytes,
+    nonce: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(sender_address, class_hash, max_fee, chain_id, nonce, result);
+        let Some([sender_address, class_hash, max_fee, chain_id, nonce]) =
+            try_field_elements_from_ptrs([sender_address, class_hash, max_fee, chain_id, nonce])
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let hash = v1_hash(
+            b"declare",
+            [sender_address, max_fee, chain_id, nonce],
+            &[class_hash],
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of a v1 deploy_account transaction deploying a class at `contract_address`
+/// with a salt and `calldata_len` contiguous felts of constructor calldata, writing it into the
+/// last argument
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn deploy_account_v1_transaction_hash(
+    contract_address: Bytes,
+    class_hash: Bytes,
+    salt: Bytes,
+    calldata: *const u8,
+    calldata_len: usize,
+    max_fee: Bytes,
+    chain_id: Bytes,
+    nonce: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            contract_address,
+            class_hash,
+            salt,
+            max_fee,
+            chain_id,
+            nonce,
+            result
+        );
+        let Some([contract_address, class_hash, salt, max_fee, chain_id, nonce]) =
+            try_field_elements_from_ptrs([
+                contract_address,
+                class_hash,
+                salt,
+                max_fee,
+                chain_id,
+                nonce,
+            ])
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let calldata = match field_elements_from_bytes(calldata, calldata_len) {
+            Ok(calldata) => calldata,
+            Err(status) => return status,
+        };
+        let calldata: Vec<Felt> = [class_hash, salt].into_iter().chain(calldata).collect();
+        let hash = v1_hash(
+            b"deploy_account",
+            [contract_address, max_fee, chain_id, nonce],
+            &calldata,
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of a v3 invoke transaction, writing it into the last argument. The
+/// resource bounds, paymaster data, account deployment data and calldata are contiguous felts
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn invoke_v3_transaction_hash(
+    sender_address: Bytes,
+    tip: Bytes,
+    resource_bounds: *const u8,
+    resource_bounds_len: usize,
+    paymaster_data: *const u8,
+    paymaster_data_len: usize,
+    chain_id: Bytes,
+    nonce: Bytes,
+    data_availability_modes: Bytes,
+    account_deployment_data: *const u8,
+    account_deployment_data_len: usize,
+    calldata: *const u8,
+    calldata_len: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            sender_address,
+            tip,
+            chain_id,
+            nonce,
+            data_availability_modes,
+            result
+        );
+        let Some(fields) = try_field_elements_from_ptrs([
+            sender_address,
+            tip,
+            chain_id,
+            nonce,
+            data_availability_modes,
+        ]) else {
+            return ERR_INVALID_FELT;
+        };
+        let (resource_bounds, paymaster_data, account_deployment_data, calldata) = match (
+            field_elements_from_bytes(resource_bounds, resource_bounds_len),
+            field_elements_from_bytes(paymaster_data, paymaster_data_len),
+            field_elements_from_bytes(account_deployment_data, account_deployment_data_len),
+            field_elements_from_bytes(calldata, calldata_len),
+        ) {
+            (Ok(a), Ok(b), Ok(c), Ok(d)) => (a, b, c, d),
+            (Err(status), ..) | (_, Err(status), ..) | (.., Err(status), _) | (.., Err(status)) => {
+                return status
+            }
+        };
+        let hash = v3_hash(
+            b"invoke",
+            fields,
+            &resource_bounds,
+            &paymaster_data,
+            &[
+                poseidon_hash_many(&account_deployment_data),
+                poseidon_hash_many(&calldata),
+            ],
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of a v3 declare transaction, writing it into the last argument. The resource
+/// bounds, paymaster data and account deployment data are contiguous felts
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn declare_v3_transaction_hash(
+    sender_address: Bytes,
+    tip: Bytes,
+    resource_bounds: *const u8,
+    resource_bounds_len: usize,
+    paymaster_data: *const u8,
+    paymaster_data_len: usize,
+    chain_id: Bytes,
+    nonce: Bytes,
+    data_availability_modes: Bytes,
+    account_deployment_data: *const u8,
+    account_deployment_data_len: usize,
+    class_hash: Bytes,
+    compiled_class_hash: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            sender_address,
+            tip,
+            chain_id,
+            nonce,
+            data_availability_modes,
+            class_hash,
+            compiled_class_hash,
+            result
+        );
+        let (Some(fields), Some([class_hash, compiled_class_hash])) = (
+            try_field_elements_from_ptrs([
+                sender_address,
+                tip,
+                chain_id,
+                nonce,
+                data_availability_modes,
+            ]),
+            try_field_elements_from_ptrs([class_hash, compiled_class_hash]),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let (resource_bounds, paymaster_data, account_deployment_data) = match (
+            field_elements_from_bytes(resource_bounds, resource_bounds_len),
+            field_elements_from_bytes(paymaster_data, paymaster_data_len),
+            field_elements_from_bytes(account_deployment_data, account_deployment_data_len),
+        ) {
+            (Ok(a), Ok(b), Ok(c)) => (a, b, c),
+            (Err(status), ..) | (_, Err(status), _) | (.., Err(status)) => return status,
+        };
+        let hash = v3_hash(
+            b"declare",
+            fields,
+            &resource_bounds,
+            &paymaster_data,
+            &[
+                poseidon_hash_many(&account_deployment_data),
+                class_hash,
+                compiled_class_hash,
+            ],
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of a v3 deploy_account transaction deploying a class at `contract_address`
+/// with a salt, writing it into the last argument. The resource bounds, paymaster data and
+/// constructor calldata are contiguous felts
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn deploy_account_v3_transaction_hash(
+    contract_address: Bytes,
+    tip: Bytes,
+    resource_bounds: *const u8,
+    resource_bounds_len: usize,
+    paymaster_data: *const u8,
+    paymaster_data_len: usize,
+    chain_id: Bytes,
+    nonce: Bytes,
+    data_availability_modes: Bytes,
+    calldata: *const u8,
+    calldata_len: usize,
+    class_hash: Bytes,
+    salt: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            contract_address,
+            tip,
+            chain_id,
+            nonce,
+            data_availability_modes,
+            class_hash,
+            salt,
+            result
+        );
+        let (Some(fields), Some([class_hash, salt])) = (
+            try_field_elements_from_ptrs([
+                contract_address,
+                tip,
+                chain_id,
+                nonce,
+                data_availability_modes,
+            ]),
+            try_field_elements_from_ptrs([class_hash, salt]),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let (resource_bounds, paymaster_data, calldata) = match (
+            field_elements_from_bytes(resource_bounds, resource_bounds_len),
+            field_elements_from_bytes(paymaster_data, paymaster_data_len),
+            field_elements_from_bytes(calldata, calldata_len),
+        ) {
+            (Ok(a), Ok(b), Ok(c)) => (a, b, c),
+            (Err(status), ..) | (_, Err(status), _) | (.., Err(status)) => return status,
+        };
+        let hash = v3_hash(
+            b"deploy_account",
+            fields,
+            &resource_bounds,
+            &paymaster_data,
+            &[poseidon_hash_many(&calldata), class_hash, salt],
+        );
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of `calldata_len` contiguous felts of calldata, writing it into the last
+/// argument: the hash on elements of a v1 transaction with HASH_PEDERSEN, the Poseidon hash many of
+/// a v3 transaction with HASH_POSEIDON
+#[no_mangle]
+pub extern "C" fn calldata_hash(
+    calldata: *const u8,
+    calldata_len: usize,
+    hash_kind: u32,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let calldata = match field_elements_from_bytes(calldata, calldata_len) {
+            Ok(calldata) => calldata,
+            Err(status) => return status,
+        };
+        let hash = match hash_kind {
+            HASH_PEDERSEN => hash_on_elements(&calldata),
+            HASH_POSEIDON => poseidon_hash_many(&calldata),
+            _ => return ERR_INVALID_HASH_KIND,
+        };
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of an event emitted by `from_address` in a transaction, with `keys_len` and
+/// `data_len` contiguous felts of keys and data, writing it into the last argument. HASH_POSEIDON
+/// is the hash of the receipts since Starknet v0.13.2 and HASH_PEDERSEN the one before, which
+/// ignores the transaction hash
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn event_hash(
+    from_address: Bytes,
+    transaction_hash: Bytes,
+    keys: *const u8,
+    keys_len: usize,
+    data: *const u8,
+    data_len: usize,
+    hash_kind: u32,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(from_address, transaction_hash, result);
+        let Some([from_address, transaction_hash]) =
+            try_field_elements_from_ptrs([from_address, transaction_hash])
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let (keys, data) = match (
+            field_elements_from_bytes(keys, keys_len),
+            field_elements_from_bytes(data, data_len),
+        ) {
+            (Ok(keys), Ok(data)) => (keys, data),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+        let hash = match hash_kind {
+            HASH_PEDERSEN => hash_on_elements(&[
+                from_address,
+                hash_on_elements(&keys),
+                hash_on_elements(&data),
+            ]),
+            HASH_POSEIDON => {
+                let mut elements = Vec::from([from_address, transaction_hash, keys.len().into()]);
+                elements.extend_from_slice(&keys);
+                elements.push(data.len().into());
+                elements.extend_from_slice(&data);
+                poseidon_hash_many(&elements)
+            }
+            _ => return ERR_INVALID_HASH_KIND,
+        };
+        bytes_from_field_element(hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ERR_INVALID_LENGTH;
+
+    fn felt(value: u64) -> Felt {
+        Felt::from(value)
+    }
+
+    // Leaks the felt, so that the pointer outlives the call
+    fn ptr(value: u64) -> Bytes {
+        Box::leak(Box::new(felt(value).to_bytes_be())).as_mut_ptr()
+    }
+
+    fn felts_bytes(felts: &[Felt]) -> Vec<u8> {
+        felts.iter().flat_map(|felt| felt.to_bytes_be()).collect()
+    }
+
+    fn hash(status: i32, result: [u8; 32]) -> Felt {
+        assert_eq!(status, SUCCESS);
+        Felt::from_bytes_be(&result)
+    }
+
+    #[test]
+    fn test_v1_transaction_hashes() {
+        let calldata = [felt(10), felt(11)];
+        let calldata_bytes = felts_bytes(&calldata);
+        let mut result = [0u8; 32];
+        let tail = |prefix: &[u8], address, calldata_hash| {
+            hash_on_elements(&[
+                Felt::from_bytes_be_slice(prefix),
+                felt(1),
+                address,
+                felt(0),
+                calldata_hash,
+                felt(5),
+                felt(6),
+                felt(7),
+            ])
+        };
+
+        let status = invoke_v1_transaction_hash(
+            ptr(1),
+            calldata_bytes.as_ptr(),
+            calldata.len(),
+            ptr(5),
+            ptr(6),
+            ptr(7),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            tail(b"invoke", felt(1), hash_on_elements(&calldata))
+        );
+
+        let status = declare_v1_transaction_hash(
+            ptr(1),
+            ptr(2),
+            ptr(5),
+            ptr(6),
+            ptr(7),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            tail(b"declare", felt(1), hash_on_elements(&[felt(2)]))
+        );
+
+        let status = deploy_account_v1_transaction_hash(
+            ptr(1),
+            ptr(2),
+            ptr(3),
+            calldata_bytes.as_ptr(),
+            calldata.len(),
+            ptr(5),
+            ptr(6),
+            ptr(7),
+            result.as_mut_ptr(),
+        );
+        let deploy_calldata = [felt(2), felt(3), felt(10), felt(11)];
+        assert_eq!(
+            hash(status, result),
+            tail(
+                b"deploy_account",
+                felt(1),
+                hash_on_elements(&deploy_calldata)
+            )
+        );
+
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            declare_v1_transaction_hash(
+                ptr(1),
+                not_a_felt.as_mut_ptr(),
+                ptr(5),
+                ptr(6),
+                ptr(7),
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_v3_transaction_hashes() {
+        let bounds = [felt(20), felt(21)];
+        let paymaster_data = [felt(30)];
+        let data = [felt(40), felt(41)];
+        let (bounds_bytes, paymaster_bytes, data_bytes) = (
+            felts_bytes(&bounds),
+            felts_bytes(&paymaster_data),
+            felts_bytes(&data),
+        );
+        let mut result = [0u8; 32];
+        let hash_with = |prefix: &[u8], fields: &[Felt]| {
+            let mut elements = Vec::from([
+                Felt::from_bytes_be_slice(prefix),
+                felt(3),
+                felt(1),
+                poseidon_hash_many(&[felt(2), felt(20), felt(21)]),
+                poseidon_hash_many(&paymaster_data),
+                felt(6),
+                felt(7),
+                felt(8),
+            ]);
+            elements.extend_from_slice(fields);
+            poseidon_hash_many(&elements)
+        };
+
+        let status = invoke_v3_transaction_hash(
+            ptr(1),
+            ptr(2),
+            bounds_bytes.as_ptr(),
+            bounds.len(),
+            paymaster_bytes.as_ptr(),
+            paymaster_data.len(),
+            ptr(6),
+            ptr(7),
+            ptr(8),
+            core::ptr::null(),
+            0,
+            data_bytes.as_ptr(),
+            data.len(),
+            result.as_mut_ptr(),
+        );
+        let expected = hash_with(
+            b"invoke",
+            &[poseidon_hash_many(&[]), poseidon_hash_many(&data)],
+        );
+        assert_eq!(hash(status, result), expected);
+
+        let status = declare_v3_transaction_hash(
+            ptr(1),
+            ptr(2),
+            bounds_bytes.as_ptr(),
+            bounds.len(),
+            paymaster_bytes.as_ptr(),
+            paymaster_data.len(),
+            ptr(6),
+            ptr(7),
+            ptr(8),
+            data_bytes.as_ptr(),
+            data.len(),
+            ptr(50),
+            ptr(51),
+            result.as_mut_ptr(),
+        );
+        let expected = hash_with(b"declare", &[poseidon_hash_many(&data), felt(50), felt(51)]);
+        assert_eq!(hash(status, result), expected);
+
+        let status = deploy_account_v3_transaction_hash(
+            ptr(1),
+            ptr(2),
+            bounds_bytes.as_ptr(),
+            bounds.len(),
+            paymaster_bytes.as_ptr(),
+            paymaster_data.len(),
+            ptr(6),
+            ptr(7),
+            ptr(8),
+            data_bytes.as_ptr(),
+            data.len(),
+            ptr(50),
+            ptr(51),
+            result.as_mut_ptr(),
+        );
+        let expected = hash_with(
+            b"deploy_account",
+            &[poseidon_hash_many(&data), felt(50), felt(51)],
+        );
+        assert_eq!(hash(status, result), expected);
+
+        assert_eq!(
+            invoke_v3_transaction_hash(
+                ptr(1),
+                ptr(2),
+                core::ptr::null(),
+                1,
+                paymaster_bytes.as_ptr(),
+                paymaster_data.len(),
+                ptr(6),
+                ptr(7),
+                ptr(8),
+                core::ptr::null(),
+                0,
+                data_bytes.as_ptr(),
+                data.len(),
+                result.as_mut_ptr(),
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+
+    fn hex(hex: &str) -> Felt {
+        Felt::from_hex(hex).unwrap()
+    }
+
+    fn hexes(hexes: &[&str]) -> Vec<u8> {
+        felts_bytes(&hexes.iter().map(|value| hex(value)).collect::<Vec<_>>())
+    }
+
+    // The resource bounds of the v3 transactions before Starknet v0.13.4, the L1 and L2 gas each
+    // packed as its name, max amount and max price per unit.
+    fn l1_gas_bounds(max_amount: &str, max_price_per_unit: &str) -> Vec<u8> {
+        let max_amount = u128::from_str_radix(max_amount, 16).unwrap();
+        let max_price_per_unit = u128::from_str_radix(max_price_per_unit, 16).unwrap();
+        let pack = |name: &[u8], max_amount: u128, max_price_per_unit: u128| {
+            let mut bytes = [0u8; 32];
+            bytes[8 - name.len()..8].copy_from_slice(name);
+            bytes[8..16].copy_from_slice(&(max_amount as u64).to_be_bytes());
+            bytes[16..].copy_from_slice(&max_price_per_unit.to_be_bytes());
+            bytes
+        };
+        [
+            pack(b"L1_GAS", max_amount, max_price_per_unit),
+            pack(b"L2_GAS", 0, 0),
+        ]
+        .concat()
+    }
+
+    // Hashes of Starknet mainnet transactions, from the transaction_hash.json fixtures of
+    // starknet_api. The v1 invoke and deploy_account and the v3 ones are in block 636864, the v1
+    // declare in block 346864 and the v3 one in block 630721.
+    #[test]
+    fn test_mainnet_transaction_hashes() {
+        let chain_id = || hex("0x534e5f4d41494e").to_bytes_be();
+        let ptr = |value: &str| Box::leak(Box::new(hex(value).to_bytes_be())).as_mut_ptr();
+        let mut result = [0u8; 32];
+
+        let calldata = hexes(&[
+            "0x2",
+            "0x68f5c6a61780768455de69077e07e89787839bf8166decfbf92b645209c0fb8",
+            "0x219209e083275171774dab1df80982e9df2096516f06319c5c6d71ae0a8480c",
+            "0x3",
+            "0x1114c7103e12c2b2ecbd3a2472ba9c48ddcbf702b1c242dd570057e26212111",
+            "0xb67495",
+            "0x0",
+            "0x1114c7103e12c2b2ecbd3a2472ba9c48ddcbf702b1c242dd570057e26212111",
+            "0x15543c3708653cda9d418b4ccd3be11368e40636c10c44b18cfe756b6d88b29",
+            "0x7",
+            "0x30baaaf1b243f6e74c656f98dcb24b98687dcbe783d25f35854148c4c602d41",
+            "0x0",
+            "0xb67495",
+            "0x0",
+            "0x1",
+            "0x3a1045717884ca9abbc2e",
+            "0x0",
+        ]);
+        let status = invoke_v1_transaction_hash(
+            ptr("0x6f7afd58d20aedbdb694ff539d3280ae497c1a510caddcc6a06c97eebd001dc"),
+            calldata.as_ptr(),
+            calldata.len() / 32,
+            ptr("0x7f49b0d6d7c"),
+            chain_id().as_mut_ptr(),
+            ptr("0x62"),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            hex("0x215b2e7efdedc5d9c056dd6a691b8117f292997d2cc8c15a9cebfa90620e35")
+        );
+
+        let status = declare_v1_transaction_hash(
+            ptr("0x3e2a6434cebce4475cdf8843859f2137259918171eba8d462e3d34c5c4fd110"),
+            ptr("0x4dae654c7b6707667a178729b512d61494fe590ab4accc46923d6409b97e617"),
+            ptr("0x1f04d0693837"),
+            chain_id().as_mut_ptr(),
+            ptr("0x15"),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            hex("0x70fb3ea4b786b752817a32d4835eaba19da495392df90f7f827cd9b60b2c925")
+        );
+
+        let calldata =
+            hexes(&["0x54c617a2e91df5344958e0eb2c30c58a1134b3f8e59e88deba60a24f95c0a2c"]);
+        let status = deploy_account_v1_transaction_hash(
+            ptr("0x2aaedc4cc67a5ad0134868689f49b9c8838db640a5c84a13e6cccc828c21aa"),
+            ptr("0x13bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6"),
+            ptr("0x54c617a2e91df5344958e0eb2c30c58a1134b3f8e59e88deba60a24f95c0a2c"),
+            calldata.as_ptr(),
+            1,
+            ptr("0x3a23c71d8b9"),
+            chain_id().as_mut_ptr(),
+            ptr("0x0"),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            hex("0x40e7ac7efc374f3d1241c6f991de2ea534d84e8be307420658353527226c5e4")
+        );
+
+        let calldata = hexes(&[
+            "0x1",
+            "0x4c0a5193d58f74fbace4b74dcf65481e734ed1714121bdc571da345540efa05",
+            "0x3943907ef0ef6f9d2e2408b05e520a66daaf74293dbf665e5a20b117676170e",
+            "0x2",
+            "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
+            "0x16345785d8a0000",
+        ]);
+        let bounds = l1_gas_bounds("a9e", "7f2a1ad4f2f1");
+        let status = invoke_v3_transaction_hash(
+            ptr("0x69c0f9bcd79697bdceaf7748e3ff8f34aa39e4063ce44896af664c0c96f6c10"),
+            ptr("0x0"),
+            bounds.as_ptr(),
+            2,
+            core::ptr::null(),
+            0,
+            chain_id().as_mut_ptr(),
+            ptr("0x9d"),
+            ptr("0x0"),
+            core::ptr::null(),
+            0,
+            calldata.as_ptr(),
+            calldata.len() / 32,
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            hex("0x1d4735f4ba73a67be2f648d9b21cab3783383b8c229566b46b027c46012219")
+        );
+
+        let bounds = l1_gas_bounds("e38c2", "24e2649cc098");
+        let status = declare_v3_transaction_hash(
+            ptr("0x46d3a562c606077c14c3479946e4145b7a372538875eb4e635e758fcd1d2c80"),
+            ptr("0x0"),
+            bounds.as_ptr(),
+            2,
+            core::ptr::null(),
+            0,
+            chain_id().as_mut_ptr(),
+            ptr("0x3"),
+            ptr("0x0"),
+            core::ptr::null(),
+            0,
+            ptr("0x7a9d1cd5dcf3d47b10e638eb1330d101f7d471f08eff9664b42dfa90f6973bf"),
+            ptr("0x4fc6444f59a2fb0b2c67e1e22caba0edc3b48727e8a9cc19e59e5c3e5dc8270"),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            hex("0x50ca961fd1b5f7f1ea1e8620ac980cc3c3fb4e434f0661c3a2d50893082b9fb")
+        );
+
+        let calldata =
+            hexes(&["0x1f218cfa725d1679ff028c53d094a765dfd1de632787cab37ffe10f3915dcc7"]);
+        let bounds = l1_gas_bounds("4c", "8ab967606cb9");
+        let status = deploy_account_v3_transaction_hash(
+            ptr("0x2ef67bd3f91cb3c6f5bdaf8468752a40fb3bdeb880779d36ec3c43736002158"),
+            ptr("0x0"),
+            bounds.as_ptr(),
+            2,
+            core::ptr::null(),
+            0,
+            chain_id().as_mut_ptr(),
+            ptr("0x0"),
+            ptr("0x0"),
+            calldata.as_ptr(),
+            1,
+            ptr("0x13bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6"),
+            ptr("0x1f218cfa725d1679ff028c53d094a765dfd1de632787cab37ffe10f3915dcc7"),
+            result.as_mut_ptr(),
+        );
+        assert_eq!(
+            hash(status, result),
+            hex("0x25cbbc1e197e1e6dd8886b4b359c2dbe26245a282547624d7e54e7c3e2cf15")
+        );
+    }
+
+    #[test]
+    fn test_calldata_and_event_hashes() {
+        let calldata = [felt(10), felt(11), felt(12)];
+        let calldata_bytes = felts_bytes(&calldata);
+        let mut result = [0u8; 32];
+        let status = calldata_hash(
+            calldata_bytes.as_ptr(),
+            3,
+            HASH_PEDERSEN,
+            result.as_mut_ptr(),
+        );
+        assert_eq!(hash(status, result), hash_on_elements(&calldata));
+        let status = calldata_hash(
+            calldata_bytes.as_ptr(),
+            3,
+            HASH_POSEIDON,
+            result.as_mut_ptr(),
+        );
+        assert_eq!(hash(status, result), poseidon_hash_many(&calldata));
+        let status = calldata_hash(core::ptr::null(), 0, HASH_POSEIDON, result.as_mut_ptr());
+        assert_eq!(hash(status, result), poseidon_hash_many(&[]));
+        assert_eq!(
+            calldata_hash(calldata_bytes.as_ptr(), 3, 2, result.as_mut_ptr()),
+            ERR_INVALID_HASH_KIND
+        );
+
+        // One key and two felts of data
+        let event = |hash_kind, transaction_hash| {
+            let mut result = [0u8; 32];
+            let status = event_hash(
+                ptr(1),
+                ptr(transaction_hash),
+                calldata_bytes.as_ptr(),
+                1,
+                calldata_bytes[32..].as_ptr(),
+                2,
+                hash_kind,
+                result.as_mut_ptr(),
+            );
+            hash(status, result)
+        };
+        assert_eq!(
+            event(HASH_POSEIDON, 2),
+            poseidon_hash_many(&[
+                felt(1),
+                felt(2),
+                felt(1),
+                felt(10),
+                felt(2),
+                felt(11),
+                felt(12)
+            ])
+        );
+        assert_ne!(event(HASH_POSEIDON, 3), event(HASH_POSEIDON, 2));
+        assert_eq!(
+            event(HASH_PEDERSEN, 2),
+            hash_on_elements(&[
+                felt(1),
+                hash_on_elements(&[felt(10)]),
+                hash_on_elements(&[felt(11), felt(12)])
+            ])
+        );
+        assert_eq!(event(HASH_PEDERSEN, 3), event(HASH_PEDERSEN, 2));
+        assert_eq!(
+            event_hash(
+                ptr(1),
+                ptr(2),
+                core::ptr::null(),
+                1,
+                core::ptr::null(),
+                0,
+                HASH_POSEIDON,
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_LENGTH
+        );
+    }
+}
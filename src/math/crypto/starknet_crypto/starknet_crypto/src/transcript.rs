@@ -0,0 +1,225 @@
+// Fiat-Shamir transcripts over a Poseidon duplex sponge, deriving the challenges of interactive
+// protocols from everything absorbed before them.
+//
+// The sponge has the rate 2 and the capacity 1 of the Poseidon hash. Felts are added into the rate
+// one at a time, with a permutation whenever it is full, and a squeeze pads the rate like
+// poseidon_hash_many, permutes and outputs the first felt. The first challenge of a transcript thus
+// is the poseidon_hash_many of the domain tag followed by the absorbed felts. Like the stream
+// contexts, transcripts are opaque to C and released with transcript_free.
+use alloc::boxed::Box;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes, hades::poseidon_permute_comp,
+    slice_from_ptr, try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, SUCCESS,
+};
+
+/// A Fiat-Shamir transcript
+pub struct Transcript {
+    state: [Felt; 3],
+    // The number of felts in the rate since the last permutation
+    absorbed: usize,
+}
+
+impl Transcript {
+    fn absorb(&mut self, felt: Felt) {
+        self.state[self.absorbed] += felt;
+        self.absorbed += 1;
+        if self.absorbed == 2 {
+            poseidon_permute_comp(&mut self.state);
+            self.absorbed = 0;
+        }
+    }
+
+    fn squeeze(&mut self) -> Felt {
+        self.state[self.absorbed] += Felt::ONE;
+        poseidon_permute_comp(&mut self.state);
+        self.absorbed = 0;
+        self.state[0]
+    }
+}
+
+/// Allocates a transcript separated from the others by a domain tag felt, writing it into the last
+/// argument. It must be released with transcript_free
+#[no_mangle]
+pub extern "C" fn transcript_new(domain_tag: Bytes, transcript: *mut *mut Transcript) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(domain_tag, transcript);
+        let Some(domain_tag) = try_field_element_from_bytes(domain_tag) else {
+            return ERR_INVALID_FELT;
+        };
+        let mut new = Box::new(Transcript {
+            state: [Felt::ZERO; 3],
+            absorbed: 0,
+        });
+        new.absorb(domain_tag);
+        unsafe { *transcript = Box::into_raw(new) };
+        SUCCESS
+    })
+}
+
+/// Absorbs `count` contiguous felts into the transcript. Nothing is absorbed when one of them is
+/// not a felt
+#[no_mangle]
+pub extern "C" fn transcript_absorb_felts(
+    transcript: *mut Transcript,
+    felts: *const u8,
+    count: usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(transcript);
+        let felts = match field_elements_from_bytes(felts, count) {
+            Ok(felts) => felts,
+            Err(status) => return status,
+        };
+        let transcript = unsafe { &mut *transcript };
+        for felt in felts {
+            transcript.absorb(felt);
+        }
+        SUCCESS
+    })
+}
+
+/// Absorbs `len` bytes into the transcript, as their length followed by their 31 bytes big endian
+/// chunks, the last one possibly shorter
+#[no_mangle]
+pub extern "C" fn transcript_absorb_bytes(
+    transcript: *mut Transcript,
+    data: *const u8,
+    len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(transcript);
+        let data = match slice_from_ptr(data, len) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let transcript = unsafe { &mut *transcript };
+        transcript.absorb(Felt::from(len));
+        for chunk in data.chunks(31) {
+            transcript.absorb(Felt::from_bytes_be_slice(chunk));
+        }
+        SUCCESS
+    })
+}
+
+/// Squeezes a challenge felt out of the transcript, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn transcript_squeeze_felt(transcript: *mut Transcript, challenge: Bytes) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(transcript, challenge);
+        let felt = unsafe { (*transcript).squeeze() };
+        bytes_from_field_element(felt, challenge);
+        SUCCESS
+    })
+}
+
+/// Squeezes a challenge felt out of the transcript, writing its 64 low bits into the last argument
+#[no_mangle]
+pub extern "C" fn transcript_squeeze_u64(transcript: *mut Transcript, challenge: *mut u64) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(transcript, challenge);
+        let bytes = unsafe { (*transcript).squeeze() }.to_bytes_be();
+        unsafe { *challenge = u64::from_be_bytes(bytes[24..].try_into().unwrap()) };
+        SUCCESS
+    })
+}
+
+/// Releases a transcript allocated by transcript_new. Does nothing for a NULL pointer
+#[no_mangle]
+pub extern "C" fn transcript_free(transcript: *mut Transcript) -> i32 {
+    ffi_guard(|| {
+        if !transcript.is_null() {
+            drop(unsafe { Box::from_raw(transcript) });
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_crypto_rs::poseidon_hash_many;
+
+    fn felt(value: u64) -> Felt {
+        Felt::from(value)
+    }
+
+    fn new_transcript(tag: u64) -> *mut Transcript {
+        let mut transcript = core::ptr::null_mut();
+        assert_eq!(
+            transcript_new(felt(tag).to_bytes_be().as_mut_ptr(), &mut transcript),
+            SUCCESS
+        );
+        transcript
+    }
+
+    fn squeeze_felt(transcript: *mut Transcript) -> Felt {
+        let mut challenge = [0u8; 32];
+        assert_eq!(
+            transcript_squeeze_felt(transcript, challenge.as_mut_ptr()),
+            SUCCESS
+        );
+        Felt::from_bytes_be(&challenge)
+    }
+
+    #[test]
+    fn test_transcript() {
+        let transcript = new_transcript(7);
+        let felts: Vec<u8> = [1, 2, 3]
+            .iter()
+            .flat_map(|&v| felt(v).to_bytes_be())
+            .collect();
+        assert_eq!(
+            transcript_absorb_felts(transcript, felts.as_ptr(), 3),
+            SUCCESS
+        );
+        let first = squeeze_felt(transcript);
+        assert_eq!(
+            first,
+            poseidon_hash_many(&[felt(7), felt(1), felt(2), felt(3)])
+        );
+
+        // The next challenges differ and depend on everything before them
+        let second = squeeze_felt(transcript);
+        assert_ne!(second, first);
+        let mut challenge = 0u64;
+        assert_eq!(transcript_squeeze_u64(transcript, &mut challenge), SUCCESS);
+        let third = squeeze_felt(transcript);
+        assert_ne!(third, second);
+        assert_eq!(transcript_free(transcript), SUCCESS);
+
+        let other = new_transcript(8);
+        assert_eq!(transcript_absorb_felts(other, felts.as_ptr(), 3), SUCCESS);
+        assert_ne!(squeeze_felt(other), first);
+        assert_eq!(transcript_free(other), SUCCESS);
+        assert_eq!(transcript_free(core::ptr::null_mut()), SUCCESS);
+    }
+
+    #[test]
+    fn test_transcript_absorb_bytes() {
+        let data = [0xabu8; 40];
+        let transcript = new_transcript(1);
+        assert_eq!(
+            transcript_absorb_bytes(transcript, data.as_ptr(), data.len()),
+            SUCCESS
+        );
+        let expected = poseidon_hash_many(&[
+            felt(1),
+            felt(40),
+            Felt::from_bytes_be_slice(&data[..31]),
+            Felt::from_bytes_be_slice(&data[31..]),
+        ]);
+        assert_eq!(squeeze_felt(transcript), expected);
+        assert_eq!(transcript_free(transcript), SUCCESS);
+
+        let transcript = new_transcript(1);
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            transcript_absorb_felts(transcript, not_a_felt.as_mut_ptr(), 1),
+            ERR_INVALID_FELT
+        );
+        assert_eq!(transcript_free(transcript), SUCCESS);
+    }
+}
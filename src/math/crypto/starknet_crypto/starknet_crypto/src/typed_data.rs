@@ -0,0 +1,440 @@
+// SNIP-12 hashing of typed data, the Starknet counterpart of EIP-712.
+//
+// Revision 0 hashes with the Pedersen hash on elements and revision 1 with the Poseidon hash of
+// many felts. A struct hashes as the hash of its type hash, the starknet_keccak of its encoded
+// type, followed by its encoded members, where a member struct is encoded as its own struct hash.
+// The domain separator is the struct hash of the domain, and the message hash the hash of the
+// `StarkNet Message` prefix, the domain separator, the account address and the struct hash of the
+// message.
+//
+// The SNIP-9 outside executions, the calls an account executes on behalf of a caller such as a
+// paymaster, are typed data of the domain `Account.execute_from_outside`, of version 1 with
+// revision 0 and of version 2 with revision 1. Revision 0 encodes the lengths of the calls and
+// calldata before their hashes, revision 1 leaves them out.
+use alloc::vec::Vec;
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    bytes_from_field_element, ffi_guard, field_elements_from_bytes, hades::poseidon_hash_many,
+    hash_on_elements, keccak::sn_keccak, slice_from_ptr, try_field_element_from_bytes, Bytes,
+    ERR_INVALID_FELT, ERR_INVALID_LENGTH, ERR_INVALID_REVISION, SUCCESS,
+};
+
+type ElementsHash = fn(&[Felt]) -> Felt;
+
+fn elements_hash(revision: u32) -> Result<ElementsHash, i32> {
+    match revision {
+        0 => Ok(hash_on_elements),
+        1 => Ok(|felts| poseidon_hash_many(felts)),
+        _ => Err(ERR_INVALID_REVISION),
+    }
+}
+
+fn type_hash(encoded_type: &[u8]) -> Felt {
+    Felt::from_bytes_be(&sn_keccak(encoded_type))
+}
+
+fn message_hash(
+    hash: ElementsHash,
+    domain_separator: Felt,
+    account_address: Felt,
+    struct_hash: Felt,
+) -> Felt {
+    let prefix = Felt::from_bytes_be_slice(b"StarkNet Message");
+    hash(&[prefix, domain_separator, account_address, struct_hash])
+}
+
+// The encoded types of the domains, outside executions and calls of both revisions.
+const DOMAIN_TYPES: [&[u8]; 2] = [
+    b"StarkNetDomain(name:felt,version:felt,chainId:felt)",
+    b"\"StarknetDomain\"(\"name\":\"shortstring\",\"version\":\"shortstring\",\"chainId\":\"shortstring\",\"revision\":\"shortstring\")",
+];
+const OUTSIDE_EXECUTION_TYPES: [&[u8]; 2] = [
+    b"OutsideExecution(caller:felt,nonce:felt,execute_after:felt,execute_before:felt,calls_len:felt,calls:OutsideCall*)OutsideCall(to:felt,selector:felt,calldata_len:felt,calldata:felt*)",
+    b"\"OutsideExecution\"(\"Caller\":\"ContractAddress\",\"Nonce\":\"felt\",\"Execute After\":\"u128\",\"Execute Before\":\"u128\",\"Calls\":\"Call*\")\"Call\"(\"To\":\"ContractAddress\",\"Selector\":\"selector\",\"Calldata\":\"felt*\")",
+];
+const CALL_TYPES: [&[u8]; 2] = [
+    b"OutsideCall(to:felt,selector:felt,calldata_len:felt,calldata:felt*)",
+    b"\"Call\"(\"To\":\"ContractAddress\",\"Selector\":\"selector\",\"Calldata\":\"felt*\")",
+];
+
+// Hashes the calls, each encoded as its contract address, its selector, the length of its calldata
+// and the calldata, returning their number and the hash of their struct hashes.
+fn calls_hash(revision: u32, mut encoded: &[Felt]) -> Result<(usize, Felt), i32> {
+    let hash = elements_hash(revision)?;
+    let call_type_hash = type_hash(CALL_TYPES[revision as usize]);
+    let mut call_hashes = Vec::new();
+    while let [to, selector, calldata_len, rest @ ..] = encoded {
+        let len = u64::try_from(*calldata_len)
+            .ok()
+            .and_then(|len| usize::try_from(len).ok())
+            .filter(|&len| len <= rest.len())
+            .ok_or(ERR_INVALID_LENGTH)?;
+        let (calldata, rest) = rest.split_at(len);
+        call_hashes.push(if revision == 0 {
+            hash(&[
+                call_type_hash,
+                *to,
+                *selector,
+                *calldata_len,
+                hash(calldata),
+            ])
+        } else {
+            hash(&[call_type_hash, *to, *selector, hash(calldata)])
+        });
+        encoded = rest;
+    }
+    if !encoded.is_empty() {
+        return Err(ERR_INVALID_LENGTH);
+    }
+    Ok((call_hashes.len(), hash(&call_hashes)))
+}
+
+/// Computes the struct hash of typed data of the given SNIP-12 revision, 0 or 1, from the
+/// `type_len` bytes of its encoded type and its `member_count` contiguous encoded members, writing
+/// it into the last argument. The domain separator is the struct hash of the domain
+#[no_mangle]
+pub extern "C" fn typed_data_struct_hash(
+    revision: u32,
+    encoded_type: *const u8,
+    type_len: usize,
+    members: *const u8,
+    member_count: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(result);
+        let hash = match elements_hash(revision) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let (encoded_type, members) = match (
+            slice_from_ptr(encoded_type, type_len),
+            field_elements_from_bytes(members, member_count),
+        ) {
+            (Ok(encoded_type), Ok(members)) => (encoded_type, members),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+
+        let elements: Vec<Felt> = core::iter::once(type_hash(encoded_type))
+            .chain(members)
+            .collect();
+        bytes_from_field_element(hash(&elements), result);
+        SUCCESS
+    })
+}
+
+/// Computes the hash of a message of the given SNIP-12 revision signed by `account_address`, from
+/// the domain separator and the struct hash of the message, writing it into the last argument
+#[no_mangle]
+pub extern "C" fn typed_data_message_hash(
+    revision: u32,
+    domain_separator: Bytes,
+    account_address: Bytes,
+    struct_hash: Bytes,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(domain_separator, account_address, struct_hash, result);
+        let hash = match elements_hash(revision) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let (Some(domain_separator), Some(account_address), Some(struct_hash)) = (
+            try_field_element_from_bytes(domain_separator),
+            try_field_element_from_bytes(account_address),
+            try_field_element_from_bytes(struct_hash),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+
+        let message_hash = message_hash(hash, domain_separator, account_address, struct_hash);
+        bytes_from_field_element(message_hash, result);
+        SUCCESS
+    })
+}
+
+/// Computes the SNIP-9 message hash of an outside execution of an account on a chain, for the
+/// given SNIP-12 revision, 0 for the outside executions of version 1 and 1 for those of version 2,
+/// writing it into the last argument. The caller is zero when anyone may submit the execution. The
+/// calls are `calls_len` contiguous felts: the contract address, the selector, the length of the
+/// calldata and the calldata of every call
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn outside_execution_hash(
+    revision: u32,
+    chain_id: Bytes,
+    account_address: Bytes,
+    caller: Bytes,
+    nonce: Bytes,
+    execute_after: Bytes,
+    execute_before: Bytes,
+    calls: *const u8,
+    calls_len: usize,
+    result: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(
+            chain_id,
+            account_address,
+            caller,
+            nonce,
+            execute_after,
+            execute_before,
+            result
+        );
+        let hash = match elements_hash(revision) {
+            Ok(hash) => hash,
+            Err(status) => return status,
+        };
+        let (
+            Some(chain_id),
+            Some(account_address),
+            Some(caller),
+            Some(nonce),
+            Some(execute_after),
+            Some(execute_before),
+        ) = (
+            try_field_element_from_bytes(chain_id),
+            try_field_element_from_bytes(account_address),
+            try_field_element_from_bytes(caller),
+            try_field_element_from_bytes(nonce),
+            try_field_element_from_bytes(execute_after),
+            try_field_element_from_bytes(execute_before),
+        )
+        else {
+            return ERR_INVALID_FELT;
+        };
+        let calls = match field_elements_from_bytes(calls, calls_len) {
+            Ok(calls) => calls,
+            Err(status) => return status,
+        };
+        let (call_count, calls_hash) = match calls_hash(revision, &calls) {
+            Ok(calls_hash) => calls_hash,
+            Err(status) => return status,
+        };
+
+        let revision_index = revision as usize;
+        let name = Felt::from_bytes_be_slice(b"Account.execute_from_outside");
+        let mut domain = Vec::from([
+            type_hash(DOMAIN_TYPES[revision_index]),
+            name,
+            Felt::from(revision + 1),
+            chain_id,
+        ]);
+        let mut execution = Vec::from([
+            type_hash(OUTSIDE_EXECUTION_TYPES[revision_index]),
+            caller,
+            nonce,
+            execute_after,
+            execute_before,
+        ]);
+        if revision == 0 {
+            execution.push(Felt::from(call_count));
+        } else {
+            domain.push(Felt::ONE);
+        }
+        execution.push(calls_hash);
+
+        let message_hash = message_hash(hash, hash(&domain), account_address, hash(&execution));
+        bytes_from_field_element(message_hash, result);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_data_struct_hash() {
+        let encoded_type = b"Mail(from:felt,to:felt)";
+        let members = [Felt::from(1u8), Felt::from(2u8)];
+        let members_bytes: Vec<u8> = members.iter().flat_map(|m| m.to_bytes_be()).collect();
+        let type_hash = Felt::from_bytes_be(&sn_keccak(encoded_type));
+
+        let mut result = [0u8; 32];
+        for (revision, hash) in [
+            (0, hash_on_elements as ElementsHash),
+            (1, |felts| poseidon_hash_many(felts)),
+        ] {
+            assert_eq!(
+                typed_data_struct_hash(
+                    revision,
+                    encoded_type.as_ptr(),
+                    encoded_type.len(),
+                    members_bytes.as_ptr(),
+                    members.len(),
+                    result.as_mut_ptr(),
+                ),
+                SUCCESS
+            );
+            let expected = hash(&[type_hash, members[0], members[1]]);
+            assert_eq!(result, expected.to_bytes_be());
+        }
+
+        assert_eq!(
+            typed_data_struct_hash(
+                2,
+                encoded_type.as_ptr(),
+                encoded_type.len(),
+                core::ptr::null(),
+                0,
+                result.as_mut_ptr()
+            ),
+            ERR_INVALID_REVISION
+        );
+    }
+
+    #[test]
+    fn test_typed_data_message_hash() {
+        let felt = |value: u8| Felt::from(value).to_bytes_be();
+        let prefix = Felt::from_hex("0x537461726b4e6574204d657373616765").unwrap();
+        let fields = [3u8, 4, 5].map(Felt::from);
+
+        let mut result = [0u8; 32];
+        for (revision, hash) in [
+            (0, hash_on_elements as ElementsHash),
+            (1, |felts| poseidon_hash_many(felts)),
+        ] {
+            assert_eq!(
+                typed_data_message_hash(
+                    revision,
+                    felt(3).as_mut_ptr(),
+                    felt(4).as_mut_ptr(),
+                    felt(5).as_mut_ptr(),
+                    result.as_mut_ptr(),
+                ),
+                SUCCESS
+            );
+            let expected = hash(&[prefix, fields[0], fields[1], fields[2]]);
+            assert_eq!(result, expected.to_bytes_be());
+        }
+
+        let mut not_a_felt = [0xffu8; 32];
+        assert_eq!(
+            typed_data_message_hash(
+                1,
+                not_a_felt.as_mut_ptr(),
+                felt(4).as_mut_ptr(),
+                felt(5).as_mut_ptr(),
+                result.as_mut_ptr(),
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+
+    #[test]
+    fn test_outside_execution_type_hashes() {
+        // From SNIP-9
+        let hex = |hex| Felt::from_hex(hex).unwrap();
+        assert_eq!(
+            type_hash(OUTSIDE_EXECUTION_TYPES[0]),
+            hex("0x11ff76fe3f640fa6f3d60bbd94a3b9d47141a2c96f87fdcfbeb2af1d03f7050")
+        );
+        assert_eq!(
+            type_hash(CALL_TYPES[0]),
+            hex("0xf00de1fccbb286f9a020ba8821ee936b1deea42a5c485c11ccdc82c8bebb3a")
+        );
+        assert_eq!(
+            type_hash(OUTSIDE_EXECUTION_TYPES[1]),
+            hex("0x312b56c05a7965066ddbda31c016d8d05afc305071c0ca3cdc2192c3c2f1f0f")
+        );
+        assert_eq!(
+            type_hash(CALL_TYPES[1]),
+            hex("0x3635c7f2a7ba93844c0d064e18e487f35ab90f7c39d00f186a781fc3f0c2ca9")
+        );
+        assert_eq!(
+            type_hash(DOMAIN_TYPES[1]),
+            hex("0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210")
+        );
+    }
+
+    #[test]
+    fn test_outside_execution_hash() {
+        let felt = |value: u64| Felt::from(value);
+        let felt_ptr = |value: u64| Box::leak(Box::new(felt(value).to_bytes_be())).as_mut_ptr();
+        // A transfer call and a call without calldata
+        let calls = [
+            felt(10),
+            felt(11),
+            felt(2),
+            felt(12),
+            felt(13),
+            felt(20),
+            felt(21),
+            felt(0),
+        ];
+        let calls_bytes: Vec<u8> = calls.iter().flat_map(|felt| felt.to_bytes_be()).collect();
+        let execution_hash = |revision, calls_len| {
+            let mut result = [0u8; 32];
+            let status = outside_execution_hash(
+                revision,
+                felt_ptr(1),
+                felt_ptr(2),
+                felt_ptr(3),
+                felt_ptr(4),
+                felt_ptr(5),
+                felt_ptr(6),
+                calls_bytes.as_ptr(),
+                calls_len,
+                result.as_mut_ptr(),
+            );
+            (status, Felt::from_bytes_be(&result))
+        };
+        let name = Felt::from_bytes_be_slice(b"Account.execute_from_outside");
+
+        let hash = hash_on_elements;
+        let call_type_hash = type_hash(CALL_TYPES[0]);
+        let call_hashes = [
+            hash(&[
+                call_type_hash,
+                felt(10),
+                felt(11),
+                felt(2),
+                hash(&calls[3..5]),
+            ]),
+            hash(&[call_type_hash, felt(20), felt(21), felt(0), hash(&[])]),
+        ];
+        let domain = hash(&[type_hash(DOMAIN_TYPES[0]), name, felt(1), felt(1)]);
+        let execution = hash(&[
+            type_hash(OUTSIDE_EXECUTION_TYPES[0]),
+            felt(3),
+            felt(4),
+            felt(5),
+            felt(6),
+            felt(2),
+            hash(&call_hashes),
+        ]);
+        assert_eq!(
+            execution_hash(0, calls.len()),
+            (SUCCESS, message_hash(hash, domain, felt(2), execution))
+        );
+
+        let hash: ElementsHash = |felts| poseidon_hash_many(felts);
+        let call_type_hash = type_hash(CALL_TYPES[1]);
+        let call_hashes = [
+            hash(&[call_type_hash, felt(10), felt(11), hash(&calls[3..5])]),
+            hash(&[call_type_hash, felt(20), felt(21), hash(&[])]),
+        ];
+        let domain = hash(&[type_hash(DOMAIN_TYPES[1]), name, felt(2), felt(1), felt(1)]);
+        let execution = hash(&[
+            type_hash(OUTSIDE_EXECUTION_TYPES[1]),
+            felt(3),
+            felt(4),
+            felt(5),
+            felt(6),
+            hash(&call_hashes),
+        ]);
+        assert_eq!(
+            execution_hash(1, calls.len()),
+            (SUCCESS, message_hash(hash, domain, felt(2), execution))
+        );
+
+        // A calldata running past the calls, and a truncated call
+        assert_eq!(execution_hash(1, 4).0, ERR_INVALID_LENGTH);
+        assert_eq!(execution_hash(1, 7).0, ERR_INVALID_LENGTH);
+        assert_eq!(execution_hash(2, calls.len()).0, ERR_INVALID_REVISION);
+    }
+}
@@ -0,0 +1,231 @@
+// The uint256 arithmetic of the `uint256` hints of the Cairo common library.
+//
+// A uint256 is passed as in Cairo memory: 64 bytes holding its low and its high 128 bits as two big
+// endian felts, in that order. The results are written the same way.
+use elliptic_curve::bigint::{Encoding, Limb, NonZero, U256};
+
+use crate::{bytes_from_array, ffi_guard, ERR_DIVISION_BY_ZERO, ERR_INVALID_UINT256, SUCCESS};
+
+// Reads a uint256 from its (low, high) felts, which must both be lower than 2^128.
+fn uint256_from_ptr(ptr: *const u8) -> Option<U256> {
+    let bytes = unsafe { &*(ptr as *const [u8; 64]) };
+    let (low, high) = bytes.split_at(32);
+    if low[..16] != [0; 16] || high[..16] != [0; 16] {
+        return None;
+    }
+    let mut value = [0u8; 32];
+    value[..16].copy_from_slice(&high[16..]);
+    value[16..].copy_from_slice(&low[16..]);
+    Some(U256::from_be_bytes(value))
+}
+
+fn uint256_to_ptr(value: &U256, ptr: *mut u8) {
+    let value = value.to_be_bytes();
+    let mut bytes = [0u8; 64];
+    bytes[16..32].copy_from_slice(&value[16..]);
+    bytes[48..].copy_from_slice(&value[..16]);
+    bytes_from_array(&bytes, ptr);
+}
+
+// Reads the two operands of a binary operation.
+fn operands_from_ptrs(a: *const u8, b: *const u8) -> Option<(U256, U256)> {
+    Some((uint256_from_ptr(a)?, uint256_from_ptr(b)?))
+}
+
+/// Adds two uint256 modulo 2^256, writing the sum into the third argument and whether it overflowed
+/// into the last one. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128
+#[no_mangle]
+pub extern "C" fn uint256_add(
+    a: *const u8,
+    b: *const u8,
+    result: *mut u8,
+    carry: *mut bool,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, result, carry);
+        let Some((a, b)) = operands_from_ptrs(a, b) else {
+            return ERR_INVALID_UINT256;
+        };
+        let (sum, overflow) = a.adc(&b, Limb::ZERO);
+        uint256_to_ptr(&sum, result);
+        unsafe { *carry = overflow != Limb::ZERO };
+        SUCCESS
+    })
+}
+
+/// Multiplies two uint256, writing the low and the high 256 bits of the product into the last two
+/// arguments. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128
+#[no_mangle]
+pub extern "C" fn uint256_mul(a: *const u8, b: *const u8, low: *mut u8, high: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, b, low, high);
+        let Some((a, b)) = operands_from_ptrs(a, b) else {
+            return ERR_INVALID_UINT256;
+        };
+        let (product_low, product_high) = a.mul_wide(&b);
+        uint256_to_ptr(&product_low, low);
+        uint256_to_ptr(&product_high, high);
+        SUCCESS
+    })
+}
+
+/// Divides the first uint256 by the second, writing the quotient and the remainder into the last
+/// two arguments. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128 and
+/// ERR_DIVISION_BY_ZERO for a zero divisor
+#[no_mangle]
+pub extern "C" fn uint256_unsigned_div_rem(
+    a: *const u8,
+    div: *const u8,
+    quotient: *mut u8,
+    remainder: *mut u8,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(a, div, quotient, remainder);
+        let Some((a, div)) = operands_from_ptrs(a, div) else {
+            return ERR_INVALID_UINT256;
+        };
+        let div = NonZero::new(div);
+        if bool::from(div.is_none()) {
+            return ERR_DIVISION_BY_ZERO;
+        }
+        let (q, r) = a.div_rem(&div.unwrap());
+        uint256_to_ptr(&q, quotient);
+        uint256_to_ptr(&r, remainder);
+        SUCCESS
+    })
+}
+
+/// Computes the integer square root of a uint256, rounded down, writing it into the last argument
+/// as a uint256. Returns ERR_INVALID_UINT256 when a limb is not lower than 2^128
+#[no_mangle]
+pub extern "C" fn uint256_sqrt(n: *const u8, root: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(n, root);
+        let Some(n) = uint256_from_ptr(n) else {
+            return ERR_INVALID_UINT256;
+        };
+        uint256_to_ptr(&n.sqrt_vartime(), root);
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uint256(low: u128, high: u128) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[16..32].copy_from_slice(&low.to_be_bytes());
+        bytes[48..].copy_from_slice(&high.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_uint256_add() {
+        let mut result = [0u8; 64];
+        let mut carry = true;
+        // The low limbs carry into the high ones
+        assert_eq!(
+            uint256_add(
+                uint256(u128::MAX, 1).as_ptr(),
+                uint256(2, 3).as_ptr(),
+                result.as_mut_ptr(),
+                &mut carry
+            ),
+            SUCCESS
+        );
+        assert_eq!(result, uint256(1, 5));
+        assert!(!carry);
+
+        assert_eq!(
+            uint256_add(
+                uint256(u128::MAX, u128::MAX).as_ptr(),
+                uint256(1, 0).as_ptr(),
+                result.as_mut_ptr(),
+                &mut carry
+            ),
+            SUCCESS
+        );
+        assert_eq!(result, uint256(0, 0));
+        assert!(carry);
+    }
+
+    #[test]
+    fn test_uint256_mul() {
+        let (mut low, mut high) = ([0u8; 64], [0u8; 64]);
+        // (2^256 - 1)^2 = 2^512 - 2^257 + 1
+        let max = uint256(u128::MAX, u128::MAX);
+        assert_eq!(
+            uint256_mul(
+                max.as_ptr(),
+                max.as_ptr(),
+                low.as_mut_ptr(),
+                high.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(low, uint256(1, 0));
+        assert_eq!(high, uint256(u128::MAX - 1, u128::MAX));
+    }
+
+    #[test]
+    fn test_uint256_unsigned_div_rem() {
+        let (mut quotient, mut remainder) = ([0u8; 64], [0u8; 64]);
+        // 2^128 + 7 = 3 * 113427455640312821154458202477256070487 + 2
+        assert_eq!(
+            uint256_unsigned_div_rem(
+                uint256(7, 1).as_ptr(),
+                uint256(3, 0).as_ptr(),
+                quotient.as_mut_ptr(),
+                remainder.as_mut_ptr()
+            ),
+            SUCCESS
+        );
+        assert_eq!(
+            quotient,
+            uint256(113427455640312821154458202477256070487, 0)
+        );
+        assert_eq!(remainder, uint256(2, 0));
+
+        assert_eq!(
+            uint256_unsigned_div_rem(
+                uint256(7, 1).as_ptr(),
+                uint256(0, 0).as_ptr(),
+                quotient.as_mut_ptr(),
+                remainder.as_mut_ptr()
+            ),
+            ERR_DIVISION_BY_ZERO
+        );
+    }
+
+    #[test]
+    fn test_uint256_sqrt() {
+        let mut root = [0u8; 64];
+        assert_eq!(
+            uint256_sqrt(uint256(0, 1).as_ptr(), root.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(root, uint256(1 << 64, 0));
+        assert_eq!(
+            uint256_sqrt(uint256(u128::MAX, u128::MAX).as_ptr(), root.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(root, uint256(u128::MAX, 0));
+        assert_eq!(
+            uint256_sqrt(uint256(8, 0).as_ptr(), root.as_mut_ptr()),
+            SUCCESS
+        );
+        assert_eq!(root, uint256(2, 0));
+    }
+
+    #[test]
+    fn test_uint256_invalid_limb() {
+        let mut invalid = uint256(0, 0);
+        invalid[15] = 1;
+        let mut root = [0u8; 64];
+        assert_eq!(
+            uint256_sqrt(invalid.as_ptr(), root.as_mut_ptr()),
+            ERR_INVALID_UINT256
+        );
+    }
+}
@@ -0,0 +1,152 @@
+// Deterministic test vectors of the primitives, for implementations checked against this library
+// without fixtures shared between languages.
+//
+// The inputs are felts lower than 2^251 drawn from a seed: the felt i of a seed is the sha256 of the
+// 8 bytes little endian seed followed by the 8 bytes little endian i, with its 5 high bits cleared.
+// The vectors of a primitive are contiguous, each made of its inputs and then of its outputs.
+use sha2::{Digest, Sha256};
+
+use crate::{
+    bytes_from_array, ffi_guard, get_public_key, pedersen_hash, poseidon_hash, poseidon_permute,
+    ERR_INVALID_LENGTH, ERR_INVALID_PRIMITIVE, SUCCESS, VECTORS_PEDERSEN, VECTORS_POSEIDON,
+    VECTORS_POSEIDON_PERMUTE, VECTORS_PUBLIC_KEY,
+};
+
+fn felt_from_seed(seed: u64, index: u64) -> [u8; 32] {
+    let mut felt: [u8; 32] = Sha256::new()
+        .chain_update(seed.to_le_bytes())
+        .chain_update(index.to_le_bytes())
+        .finalize()
+        .into();
+    felt[0] &= 0x07;
+    felt
+}
+
+// Computes the outputs of a vector into the felts following its inputs.
+type Compute = fn(&mut [[u8; 32]]) -> i32;
+
+// The numbers of input and output felts of a vector, and its computation.
+fn primitive(kind: u32) -> Option<(usize, usize, Compute)> {
+    match kind {
+        VECTORS_PEDERSEN => Some((2, 1, |felts| {
+            let [a, b, hash] = felts else { unreachable!() };
+            pedersen_hash(a.as_mut_ptr(), b.as_mut_ptr(), hash.as_mut_ptr())
+        })),
+        VECTORS_POSEIDON => Some((2, 1, |felts| {
+            let [a, b, hash] = felts else { unreachable!() };
+            poseidon_hash(a.as_mut_ptr(), b.as_mut_ptr(), hash.as_mut_ptr())
+        })),
+        VECTORS_POSEIDON_PERMUTE => Some((3, 3, |felts| {
+            let (input, output) = felts.split_at_mut(3);
+            output.copy_from_slice(input);
+            let [a, b, c] = output else { unreachable!() };
+            poseidon_permute(a.as_mut_ptr(), b.as_mut_ptr(), c.as_mut_ptr())
+        })),
+        VECTORS_PUBLIC_KEY => Some((1, 1, |felts| {
+            let [private_key, public_key] = felts else {
+                unreachable!()
+            };
+            get_public_key(private_key.as_mut_ptr(), public_key.as_mut_ptr())
+        })),
+        _ => None,
+    }
+}
+
+/// Writes `count` test vectors of a primitive drawn from a seed into `vectors`, `buf_len` bytes
+/// long. A vector of VECTORS_PEDERSEN or VECTORS_POSEIDON is two felts and their hash, of
+/// VECTORS_POSEIDON_PERMUTE three felts and their permutation, and of VECTORS_PUBLIC_KEY a private
+/// key and its public key. Returns ERR_INVALID_LENGTH when the vectors don't fit in the buffer
+#[no_mangle]
+pub extern "C" fn starknet_crypto_test_vectors(
+    kind: u32,
+    seed: u64,
+    count: usize,
+    vectors: *mut u8,
+    buf_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        let Some((inputs, outputs, compute)) = primitive(kind) else {
+            return ERR_INVALID_PRIMITIVE;
+        };
+        let felt_count = inputs + outputs;
+        match count.checked_mul(32 * felt_count) {
+            Some(len) if len <= buf_len => {}
+            _ => return ERR_INVALID_LENGTH,
+        }
+        if vectors.is_null() && count > 0 {
+            return ERR_INVALID_LENGTH;
+        }
+        let mut felts = [[0u8; 32]; 6];
+        let felts = &mut felts[..felt_count];
+        for vector in 0..count {
+            for (i, felt) in felts[..inputs].iter_mut().enumerate() {
+                *felt = felt_from_seed(seed, (vector * inputs + i) as u64);
+            }
+            let status = compute(felts);
+            if status != SUCCESS {
+                return status;
+            }
+            let offset = vector * felt_count * 32;
+            for (i, felt) in felts.iter().enumerate() {
+                bytes_from_array(felt, vectors.wrapping_add(offset + 32 * i));
+            }
+        }
+        SUCCESS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_types_core::felt::Felt;
+
+    fn vectors(kind: u32, seed: u64, count: usize, felts_per_vector: usize) -> Vec<[u8; 32]> {
+        let mut bytes = vec![0u8; 32 * felts_per_vector * count];
+        assert_eq!(
+            starknet_crypto_test_vectors(kind, seed, count, bytes.as_mut_ptr(), bytes.len()),
+            SUCCESS
+        );
+        bytes
+            .chunks_exact(32)
+            .map(|felt| felt.try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_starknet_crypto_test_vectors() {
+        let felt = |bytes: &[u8; 32]| Felt::from_bytes_be(bytes);
+        let pedersen = vectors(VECTORS_PEDERSEN, 42, 4, 3);
+        for vector in pedersen.chunks_exact(3) {
+            let hash = starknet_crypto_rs::pedersen_hash(&felt(&vector[0]), &felt(&vector[1]));
+            assert_eq!(vector[2], hash.to_bytes_be());
+        }
+        // Deterministic, and different for another seed
+        assert_eq!(vectors(VECTORS_PEDERSEN, 42, 4, 3), pedersen);
+        assert_ne!(vectors(VECTORS_PEDERSEN, 43, 4, 3), pedersen);
+        assert_eq!(pedersen[0], felt_from_seed(42, 0));
+        assert_eq!(pedersen[3], felt_from_seed(42, 2));
+
+        let permute = vectors(VECTORS_POSEIDON_PERMUTE, 1, 2, 6);
+        let mut state = [felt(&permute[0]), felt(&permute[1]), felt(&permute[2])];
+        starknet_crypto_rs::poseidon_permute_comp(&mut state);
+        assert_eq!(permute[3..6], state.map(|felt| felt.to_bytes_be()));
+
+        let public_keys = vectors(VECTORS_PUBLIC_KEY, 1, 2, 2);
+        let public_key = starknet_crypto_rs::get_public_key(&felt(&public_keys[0]));
+        assert_eq!(public_keys[1], public_key.to_bytes_be());
+        assert_eq!(vectors(VECTORS_POSEIDON, 7, 1, 3).len(), 3);
+    }
+
+    #[test]
+    fn test_starknet_crypto_test_vectors_errors() {
+        let mut bytes = [0u8; 95];
+        assert_eq!(
+            starknet_crypto_test_vectors(VECTORS_PEDERSEN, 0, 1, bytes.as_mut_ptr(), bytes.len()),
+            ERR_INVALID_LENGTH
+        );
+        assert_eq!(
+            starknet_crypto_test_vectors(4, 0, 1, bytes.as_mut_ptr(), bytes.len()),
+            ERR_INVALID_PRIMITIVE
+        );
+    }
+}
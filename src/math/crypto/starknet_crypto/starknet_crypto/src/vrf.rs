@@ -0,0 +1,247 @@
+// A verifiable random function over the STARK curve, so that the contracts consuming randomness
+// can be tested end to end against the VM with proofs made and checked by the same library.
+//
+// The construction is the ECVRF of RFC 9381 with the hash to curve of stark_hash_to_curve and
+// Poseidon as the challenge hash. Public keys are x coordinates standing for the point with the
+// smallest y, like the Schnorr public keys. With the private key d, the public key Y and H the point
+// hashed from Y.x || alpha, the proof of the felt alpha is the 4 felts (Gamma.x, Gamma.y, c, s) with
+//
+//     Gamma = d * H
+//     c = poseidon_hash_many(Y.x, H.x, H.y, Gamma.x, Gamma.y, U.x, U.y, V.x, V.y) mod n
+//     s = k + c * d mod n
+//
+// for U = k * G and V = k * H, with the nonce k generated by RFC 6979 from d and the hash
+// poseidon_hash(H.x, H.y). The proof is valid when c is the challenge of U = s * G - c * Y and
+// V = s * H - c * Gamma, and the output of the function is then beta = poseidon_hash(Gamma.x,
+// Gamma.y).
+#[cfg(feature = "signing")]
+use elliptic_curve::bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use elliptic_curve::bigint::{Encoding, U256};
+#[cfg(feature = "signing")]
+use starknet_crypto_rs::rfc6979_generate_k;
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    backend, bytes_from_field_element,
+    curve::{hash_to_curve, PointWithCoordinates},
+    ffi_guard,
+    hades::{poseidon_hash, poseidon_hash_many},
+    schnorr::{felt_from_field_element, generator, reduce, ORDER},
+    try_felt_from_array, try_field_element_from_bytes, Bytes, ERR_INVALID_FELT, ERR_INVALID_POINT,
+    ERR_INVALID_PROOF, SUCCESS,
+};
+#[cfg(feature = "signing")]
+use crate::{schnorr::normalized_point, secret::try_secret_from_bytes};
+
+// The domain separation tag of the points hashed to the curve.
+const SUITE: &[u8] = b"STARKNET_CRYPTO_ECVRF";
+// Gamma.x, Gamma.y, c and s.
+const PROOF_LEN: usize = 4 * 32;
+
+fn field_element_from_felt(felt: &backend::Felt) -> Felt {
+    Felt::from_bytes_be(&backend::bytes_from_felt(felt))
+}
+
+fn hash_point(public_key: &Felt, alpha: &Felt) -> PointWithCoordinates {
+    let msg = [public_key.to_bytes_be(), alpha.to_bytes_be()].concat();
+    hash_to_curve(&msg, SUITE.len() as u8, SUITE)
+}
+
+// The challenge of the public key and of the points H, Gamma, U and V.
+fn challenge(public_key: Felt, points: [&(backend::Felt, backend::Felt); 4]) -> U256 {
+    let mut inputs = [public_key; 9];
+    for (i, (x, y)) in points.into_iter().enumerate() {
+        inputs[1 + 2 * i] = field_element_from_felt(x);
+        inputs[2 + 2 * i] = field_element_from_felt(y);
+    }
+    reduce(&poseidon_hash_many(&inputs))
+}
+
+/// Proves the evaluation of the VRF over the felt alpha with a private key, a felt nonzero modulo
+/// the curve order, writing the 128 bytes proof (Gamma.x, Gamma.y, c, s) into the last argument.
+/// The proofs are deterministic. Not constant time: the scalar multiplications branch on the secrets
+#[cfg(feature = "signing")]
+#[no_mangle]
+pub extern "C" fn vrf_prove(private_key_bytes: Bytes, alpha_bytes: Bytes, proof: *mut u8) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(private_key_bytes, alpha_bytes, proof);
+        let (Some(private_key), Some(alpha)) = (
+            try_secret_from_bytes(private_key_bytes),
+            try_field_element_from_bytes(alpha_bytes),
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let private_key = reduce(&private_key);
+        if private_key == U256::ZERO {
+            return ERR_INVALID_FELT;
+        }
+
+        let (private_key, public_key) = normalized_point(private_key);
+        let (h, h_coordinates) = hash_point(&public_key, &alpha);
+        let gamma = backend::point_mul(&h, &private_key.to_be_bytes()).unwrap();
+        let nonce_hash = poseidon_hash(
+            field_element_from_felt(&h_coordinates.0),
+            field_element_from_felt(&h_coordinates.1),
+        );
+        let private_key_felt = Felt::from_bytes_be(&private_key.to_be_bytes());
+        let k = reduce(&rfc6979_generate_k(&nonce_hash, &private_key_felt, None));
+        let u = backend::point_mul(&generator(), &k.to_be_bytes()).unwrap();
+        let v = backend::point_mul(&h, &k.to_be_bytes()).unwrap();
+
+        let c = challenge(public_key, [&h_coordinates, &gamma, &u, &v]);
+        let params = DynResidueParams::new(&ORDER);
+        let s = (DynResidue::new(&c, params) * DynResidue::new(&private_key, params))
+            .retrieve()
+            .add_mod(&k, &ORDER);
+        let felts = [
+            backend::bytes_from_felt(&gamma.0),
+            backend::bytes_from_felt(&gamma.1),
+            c.to_be_bytes(),
+            s.to_be_bytes(),
+        ];
+        let proof = unsafe { core::slice::from_raw_parts_mut(proof, PROOF_LEN) };
+        proof.copy_from_slice(felts.as_flattened());
+        SUCCESS
+    })
+}
+
+/// Verifies a VRF proof of the felt alpha by a public key, writing the output beta of the function
+/// into the last argument. Returns SUCCESS for a valid proof, ERR_INVALID_PROOF for an invalid one,
+/// ERR_INVALID_POINT when the public key is not the x coordinate of a point and ERR_INVALID_FELT
+/// when an argument or one of the felts of the proof is not a felt
+#[no_mangle]
+pub extern "C" fn vrf_verify(
+    public_key_bytes: Bytes,
+    alpha_bytes: Bytes,
+    proof: *const u8,
+    beta_bytes: Bytes,
+) -> i32 {
+    ffi_guard(|| {
+        check_pointers!(public_key_bytes, alpha_bytes, proof, beta_bytes);
+        let proof = unsafe { core::slice::from_raw_parts(proof, PROOF_LEN) };
+        let felts: [Option<Felt>; 4] = core::array::from_fn(|i| {
+            try_felt_from_array(proof[32 * i..32 * (i + 1)].try_into().unwrap())
+        });
+        let (Some(public_key), Some(alpha), [Some(gamma_x), Some(gamma_y), Some(c), Some(s)]) = (
+            try_field_element_from_bytes(public_key_bytes),
+            try_field_element_from_bytes(alpha_bytes),
+            felts,
+        ) else {
+            return ERR_INVALID_FELT;
+        };
+        let public_key_x = felt_from_field_element(&public_key);
+        let Some(public_key_y) = backend::point_y_from_x(&public_key_x) else {
+            return ERR_INVALID_POINT;
+        };
+        let public_key_point =
+            backend::point_from_coordinates(&public_key_x, &public_key_y).unwrap();
+        let gamma = (
+            felt_from_field_element(&gamma_x),
+            felt_from_field_element(&gamma_y),
+        );
+        let Some(gamma_point) = backend::point_from_coordinates(&gamma.0, &gamma.1) else {
+            return ERR_INVALID_PROOF;
+        };
+        let (c, s) = (
+            U256::from_be_bytes(c.to_bytes_be()),
+            U256::from_be_bytes(s.to_bytes_be()),
+        );
+        if c >= ORDER || s >= ORDER {
+            return ERR_INVALID_PROOF;
+        }
+
+        let (h, h_coordinates) = hash_point(&public_key, &alpha);
+        let scalars = [s.to_be_bytes(), c.neg_mod(&ORDER).to_be_bytes()];
+        let (Some(u), Some(v)) = (
+            backend::point_msm(&[generator(), public_key_point], &scalars),
+            backend::point_msm(&[h, gamma_point], &scalars),
+        ) else {
+            return ERR_INVALID_PROOF;
+        };
+        if challenge(public_key, [&h_coordinates, &gamma, &u, &v]) != c {
+            return ERR_INVALID_PROOF;
+        }
+        bytes_from_field_element(poseidon_hash(gamma_x, gamma_y), beta_bytes);
+        SUCCESS
+    })
+}
+
+#[cfg(all(test, feature = "signing"))]
+mod tests {
+    use super::*;
+
+    fn felt(value: u64) -> [u8; 32] {
+        Felt::from(value).to_bytes_be()
+    }
+
+    fn prove(private_key: u64, alpha: u64) -> [u8; PROOF_LEN] {
+        let mut proof = [0u8; PROOF_LEN];
+        let status = vrf_prove(
+            felt(private_key).as_mut_ptr(),
+            felt(alpha).as_mut_ptr(),
+            proof.as_mut_ptr(),
+        );
+        assert_eq!(status, SUCCESS);
+        proof
+    }
+
+    fn verify(public_key: &[u8; 32], alpha: u64, proof: &[u8; PROOF_LEN]) -> (i32, [u8; 32]) {
+        let (mut public_key, mut beta) = (*public_key, [0u8; 32]);
+        let status = vrf_verify(
+            public_key.as_mut_ptr(),
+            felt(alpha).as_mut_ptr(),
+            proof.as_ptr(),
+            beta.as_mut_ptr(),
+        );
+        (status, beta)
+    }
+
+    #[test]
+    fn test_vrf() {
+        // Enough private keys for both y coordinates of their public keys
+        let mut betas = alloc::vec::Vec::new();
+        for private_key in 1..=4u64 {
+            let public_key = starknet_crypto_rs::get_public_key(&private_key.into()).to_bytes_be();
+            let proof = prove(private_key, 42);
+            assert_eq!(prove(private_key, 42), proof);
+            let (status, beta) = verify(&public_key, 42, &proof);
+            assert_eq!(status, SUCCESS);
+            let gamma: [Felt; 2] = core::array::from_fn(|i| {
+                Felt::from_bytes_be(&proof[32 * i..32 * (i + 1)].try_into().unwrap())
+            });
+            assert_eq!(beta, poseidon_hash(gamma[0], gamma[1]).to_bytes_be());
+            betas.push(beta);
+
+            assert_eq!(verify(&public_key, 43, &proof).0, ERR_INVALID_PROOF);
+            let other_key = starknet_crypto_rs::get_public_key(&(private_key + 1).into());
+            assert_eq!(
+                verify(&other_key.to_bytes_be(), 42, &proof).0,
+                ERR_INVALID_PROOF
+            );
+            let mut tampered = proof;
+            tampered[PROOF_LEN - 1] ^= 1;
+            assert_eq!(verify(&public_key, 42, &tampered).0, ERR_INVALID_PROOF);
+            let mut off_curve = proof;
+            off_curve[31] ^= 1;
+            assert_eq!(verify(&public_key, 42, &off_curve).0, ERR_INVALID_PROOF);
+        }
+        betas.dedup();
+        assert_eq!(betas.len(), 4);
+
+        let public_key = starknet_crypto_rs::get_public_key(&Felt::ONE).to_bytes_be();
+        let mut proof = prove(1, 42);
+        proof[96..].copy_from_slice(&ORDER.to_be_bytes());
+        assert_eq!(verify(&public_key, 42, &proof).0, ERR_INVALID_PROOF);
+        proof[96..].fill(0xff);
+        assert_eq!(verify(&public_key, 42, &proof).0, ERR_INVALID_FELT);
+        let mut zero_proof = [0u8; PROOF_LEN];
+        assert_eq!(
+            vrf_prove(
+                felt(0).as_mut_ptr(),
+                felt(42).as_mut_ptr(),
+                zero_proof.as_mut_ptr()
+            ),
+            ERR_INVALID_FELT
+        );
+    }
+}